@@ -41,3 +41,6 @@ pub use partition_value_calculator::*;
 /// Record batch partition splitter for partitioned tables
 pub mod record_batch_partition_splitter;
 pub use record_batch_partition_splitter::*;
+/// Record batch sorter for sorted tables
+pub mod record_batch_sorter;
+pub use record_batch_sorter::*;
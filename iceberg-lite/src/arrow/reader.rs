@@ -17,16 +17,19 @@
 
 //! Parquet file data reader
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::str::FromStr;
 use std::sync::Arc;
 
 use arrow_arith::boolean::{and, and_kleene, is_not_null, is_null, not, or, or_kleene};
-use arrow_array::{Array, ArrayRef, BooleanArray, Datum as ArrowDatum, RecordBatch, Scalar};
+use arrow_array::{
+    Array, ArrayRef, BooleanArray, Datum as ArrowDatum, Int64Array, RecordBatch,
+    RecordBatchOptions, Scalar,
+};
 use arrow_cast::cast::cast;
 use arrow_ord::cmp::{eq, gt, gt_eq, lt, lt_eq, neq};
 use arrow_schema::{
-    ArrowError, DataType, FieldRef, Schema as ArrowSchema, SchemaRef as ArrowSchemaRef,
+    ArrowError, DataType, Field, FieldRef, Schema as ArrowSchema, SchemaRef as ArrowSchemaRef,
 };
 use arrow_string::like::starts_with;
 use bytes::Bytes;
@@ -53,7 +56,9 @@ use crate::expr::visitors::page_index_evaluator::PageIndexEvaluator;
 use crate::expr::visitors::row_group_metrics_evaluator::RowGroupMetricsEvaluator;
 use crate::expr::{BoundPredicate, BoundReference};
 use crate::io::{FileIO, FileMetadata, FileRead};
-use crate::metadata_columns::{is_metadata_field, RESERVED_FIELD_ID_FILE};
+use crate::metadata_columns::{
+    is_metadata_field, pos_field, RESERVED_FIELD_ID_FILE, RESERVED_FIELD_ID_POS,
+};
 use crate::scan::{ArrowRecordBatchIterator, FileScanTask};
 use crate::spec::{Datum, NameMapping, NestedField, PrimitiveType, Schema, Type};
 use crate::utils::available_parallelism;
@@ -66,6 +71,7 @@ pub struct ArrowReaderBuilder {
     concurrency_limit_data_files: usize,
     row_group_filtering_enabled: bool,
     row_selection_enabled: bool,
+    row_filtering_enabled: bool,
 }
 
 impl ArrowReaderBuilder {
@@ -79,6 +85,7 @@ impl ArrowReaderBuilder {
             concurrency_limit_data_files: num_cpus,
             row_group_filtering_enabled: true,
             row_selection_enabled: false,
+            row_filtering_enabled: true,
         }
     }
 
@@ -107,6 +114,17 @@ impl ArrowReaderBuilder {
         self
     }
 
+    /// Determines whether rows not matching the scan predicate are filtered out.
+    ///
+    /// When disabled, the scan predicate only prunes row groups and the rows of the
+    /// selected row groups are returned unfiltered, which keeps their positions known
+    /// when reading the `_pos` column. Rows removed by equality deletes are always
+    /// filtered out.
+    pub fn with_row_filtering_enabled(mut self, row_filtering_enabled: bool) -> Self {
+        self.row_filtering_enabled = row_filtering_enabled;
+        self
+    }
+
     /// Build the ArrowReader.
     pub fn build(self) -> ArrowReader {
         ArrowReader {
@@ -119,6 +137,7 @@ impl ArrowReaderBuilder {
             concurrency_limit_data_files: self.concurrency_limit_data_files,
             row_group_filtering_enabled: self.row_group_filtering_enabled,
             row_selection_enabled: self.row_selection_enabled,
+            row_filtering_enabled: self.row_filtering_enabled,
         }
     }
 }
@@ -136,6 +155,7 @@ pub struct ArrowReader {
 
     row_group_filtering_enabled: bool,
     row_selection_enabled: bool,
+    row_filtering_enabled: bool,
 }
 
 impl ArrowReader {
@@ -146,6 +166,7 @@ impl ArrowReader {
         let batch_size = self.batch_size;
         let row_group_filtering_enabled = self.row_group_filtering_enabled;
         let row_selection_enabled = self.row_selection_enabled;
+        let row_filtering_enabled = self.row_filtering_enabled;
         let delete_file_loader = self.delete_file_loader;
 
         let iterator = tasks.into_iter().flat_map(move |task| {
@@ -159,6 +180,7 @@ impl ArrowReader {
                 delete_file_loader,
                 row_group_filtering_enabled,
                 row_selection_enabled,
+                row_filtering_enabled,
             ) {
                 Ok(iter) => iter,
                 Err(e) => {
@@ -180,7 +202,9 @@ impl ArrowReader {
         delete_file_loader: CachingDeleteFileLoader,
         row_group_filtering_enabled: bool,
         row_selection_enabled: bool,
+        row_filtering_enabled: bool,
     ) -> Result<ArrowRecordBatchIterator> {
+        let row_selection_enabled = row_selection_enabled && row_filtering_enabled;
         let should_load_page_index =
             (row_selection_enabled && task.predicate.is_some()) || !task.deletes.is_empty();
 
@@ -274,11 +298,24 @@ impl ArrowReader {
         record_batch_reader_builder =
             record_batch_reader_builder.with_projection(projection_mask.clone());
 
+        // The _pos column is not produced by the transformer, it is inserted at its
+        // projected index once the positions of the rows of a batch are known
+        let pos_column_index = task
+            .project_field_ids()
+            .iter()
+            .position(|&id| id == RESERVED_FIELD_ID_POS);
+        let transformer_field_ids: Vec<i32> = task
+            .project_field_ids()
+            .iter()
+            .filter(|&&id| id != RESERVED_FIELD_ID_POS)
+            .copied()
+            .collect();
+
         // RecordBatchTransformer performs any transformations required on the RecordBatches
         // that come back from the file, such as type promotion, default column insertion,
         // column re-ordering, partition constants, and virtual field addition (like _file)
         let mut record_batch_transformer_builder =
-            RecordBatchTransformerBuilder::new(task.schema_ref(), task.project_field_ids());
+            RecordBatchTransformerBuilder::new(task.schema_ref(), &transformer_field_ids);
 
         // Add the _file metadata column if it's in the projected fields
         if task.project_field_ids().contains(&RESERVED_FIELD_ID_FILE) {
@@ -306,14 +343,29 @@ impl ArrowReader {
         // we also have an optional predicate resulting from equality delete files.
         // If both are present, we logical-AND them together to form a single filter
         // predicate that we can pass to the `RecordBatchStreamBuilder`.
-        let final_predicate = match (&task.predicate, delete_predicate) {
+        let final_predicate = match (&task.predicate, &delete_predicate) {
             (None, None) => None,
             (Some(predicate), None) => Some(predicate.clone()),
-            (None, Some(ref predicate)) => Some(predicate.clone()),
+            (None, Some(predicate)) => Some(predicate.clone()),
             (Some(filter_predicate), Some(delete_predicate)) => {
-                Some(filter_predicate.clone().and(delete_predicate))
+                Some(filter_predicate.clone().and(delete_predicate.clone()))
             }
         };
+        // Without row filtering, the scan predicate only selects row groups
+        let row_filter_predicate = if row_filtering_enabled {
+            final_predicate.clone()
+        } else {
+            delete_predicate
+        };
+
+        // Row positions are derived from the selected row groups and the row selection,
+        // rows removed by a row filter while decoding cannot be accounted for
+        if pos_column_index.is_some() && row_filter_predicate.is_some() {
+            return Err(Error::new(
+                ErrorKind::FeatureUnsupported,
+                "Reading the _pos column with a filter predicate or equality deletes is not supported",
+            ));
+        }
 
         // There are three possible sources for potential lists of selected RowGroup indices,
         // and two for `RowSelection`s.
@@ -344,19 +396,26 @@ impl ArrowReader {
             selected_row_group_indices = Some(byte_range_filtered_row_groups);
         }
 
-        if let Some(predicate) = final_predicate {
+        if let Some(predicate) = &row_filter_predicate {
             let (iceberg_field_ids, field_id_map) = Self::build_field_id_set_and_map(
                 record_batch_reader_builder.parquet_schema(),
-                &predicate,
+                predicate,
             )?;
 
             let row_filter = Self::get_row_filter(
-                &predicate,
+                predicate,
                 record_batch_reader_builder.parquet_schema(),
                 &iceberg_field_ids,
                 &field_id_map,
             )?;
             record_batch_reader_builder = record_batch_reader_builder.with_row_filter(row_filter);
+        }
+
+        if let Some(predicate) = final_predicate {
+            let (_, field_id_map) = Self::build_field_id_set_and_map(
+                record_batch_reader_builder.parquet_schema(),
+                &predicate,
+            )?;
 
             if row_group_filtering_enabled {
                 let predicate_filtered_row_groups = Self::get_selected_row_group_indices(
@@ -415,6 +474,14 @@ impl ArrowReader {
             };
         }
 
+        let mut row_positions = pos_column_index.map(|_| {
+            RowPositions::new(
+                record_batch_reader_builder.metadata().row_groups(),
+                &selected_row_group_indices,
+                row_selection.as_ref(),
+            )
+        });
+
         if let Some(row_selection) = row_selection {
             record_batch_reader_builder =
                 record_batch_reader_builder.with_row_selection(row_selection);
@@ -433,7 +500,13 @@ impl ArrowReader {
         let iterator = record_batch_reader.map(move |batch| match batch {
             Ok(batch) => {
                 // Process the record batch (type promotion, column reordering, virtual fields, etc.)
-                record_batch_transformer.process_record_batch(batch)
+                let batch = record_batch_transformer.process_record_batch(batch)?;
+                match (pos_column_index, row_positions.as_mut()) {
+                    (Some(index), Some(row_positions)) => {
+                        row_positions.add_pos_column(batch, index)
+                    }
+                    _ => Ok(batch),
+                }
             }
             Err(err) => Err(err.into()),
         });
@@ -936,6 +1009,119 @@ impl ArrowReader {
     }
 }
 
+/// Positions in the data file of the rows returned by a record batch reader, used to
+/// fill the `_pos` metadata column.
+struct RowPositions {
+    /// Runs of rows in read order, as first position and row count.
+    runs: VecDeque<(u64, u64)>,
+}
+
+impl RowPositions {
+    fn new(
+        row_groups: &[RowGroupMetaData],
+        selected_row_groups: &Option<Vec<usize>>,
+        row_selection: Option<&RowSelection>,
+    ) -> Self {
+        let mut row_group_runs = Vec::with_capacity(row_groups.len());
+        let mut first_row = 0;
+        for row_group in row_groups {
+            let num_rows = row_group.num_rows() as u64;
+            row_group_runs.push((first_row, num_rows));
+            first_row += num_rows;
+        }
+
+        // Row groups are read in the order they are selected in
+        let read_runs: Vec<(u64, u64)> = match selected_row_groups {
+            Some(indices) => indices.iter().map(|&index| row_group_runs[index]).collect(),
+            None => row_group_runs,
+        };
+
+        let Some(row_selection) = row_selection else {
+            return Self {
+                runs: read_runs
+                    .into_iter()
+                    .filter(|&(_, count)| count > 0)
+                    .collect(),
+            };
+        };
+
+        // The row selection spans the rows of the row groups that are read
+        let mut runs = VecDeque::new();
+        let mut read_runs = read_runs.into_iter();
+        let mut current = read_runs.next();
+        for selector in Vec::<RowSelector>::from(row_selection.clone()) {
+            let mut remaining = selector.row_count as u64;
+            while remaining > 0 {
+                let Some((start, count)) = current.as_mut() else {
+                    break;
+                };
+
+                let selected = remaining.min(*count);
+                if !selector.skip && selected > 0 {
+                    runs.push_back((*start, selected));
+                }
+                *start += selected;
+                *count -= selected;
+                remaining -= selected;
+
+                if *count == 0 {
+                    current = read_runs.next();
+                }
+            }
+        }
+
+        Self { runs }
+    }
+
+    /// Take the positions of the next `num_rows` rows.
+    fn next_positions(&mut self, num_rows: usize) -> Result<Int64Array> {
+        let mut positions = Vec::with_capacity(num_rows);
+
+        while positions.len() < num_rows {
+            let Some((start, count)) = self.runs.front_mut() else {
+                return Err(Error::new(
+                    ErrorKind::Unexpected,
+                    "Record batch reader returned more rows than were selected",
+                ));
+            };
+
+            let taken = (*count).min((num_rows - positions.len()) as u64);
+            positions.extend((*start..*start + taken).map(|pos| pos as i64));
+            *start += taken;
+            *count -= taken;
+            if *count == 0 {
+                self.runs.pop_front();
+            }
+        }
+
+        Ok(Int64Array::from(positions))
+    }
+
+    /// Insert the `_pos` column of the rows of `batch` at `index`.
+    fn add_pos_column(&mut self, batch: RecordBatch, index: usize) -> Result<RecordBatch> {
+        let positions = self.next_positions(batch.num_rows())?;
+
+        let pos_field = pos_field();
+        let arrow_field = Field::new(&pos_field.name, DataType::Int64, !pos_field.required)
+            .with_metadata(HashMap::from([(
+                PARQUET_FIELD_ID_META_KEY.to_string(),
+                pos_field.id.to_string(),
+            )]));
+
+        let mut fields: Vec<FieldRef> = batch.schema().fields().iter().cloned().collect();
+        let mut columns = batch.columns().to_vec();
+        fields.insert(index, Arc::new(arrow_field));
+        columns.insert(index, Arc::new(positions) as ArrayRef);
+
+        let options = RecordBatchOptions::new().with_row_count(Some(batch.num_rows()));
+        Ok(RecordBatch::try_new_with_options(
+            Arc::new(ArrowSchema::new(fields)),
+            columns,
+            &options,
+        )?)
+    }
+}
+
 /// Build the map of parquet field id to Parquet column index in the schema.
 /// Returns None if the Parquet file doesn't have field IDs embedded (e.g., migrated tables).
 fn build_field_id_map(parquet_schema: &SchemaDescriptor) -> Result<Option<HashMap<i32, usize>>> {
@@ -1752,12 +1938,13 @@ mod tests {
     use roaring::RoaringTreemap;
     use tempfile::TempDir;
 
-    use crate::arrow::reader::{CollectFieldIdVisitor, PARQUET_FIELD_ID_META_KEY};
+    use crate::arrow::reader::{CollectFieldIdVisitor, RowPositions, PARQUET_FIELD_ID_META_KEY};
     use crate::arrow::{ArrowReader, ArrowReaderBuilder};
     use crate::delete_vector::DeleteVector;
     use crate::expr::visitors::bound_predicate_visitor::visit;
     use crate::expr::{Bind, Predicate, Reference};
     use crate::io::FileIO;
+    use crate::metadata_columns::RESERVED_FIELD_ID_POS;
     use crate::scan::{FileScanTask, FileScanTaskDeleteFile};
     use crate::spec::{
         DataContentType, DataFileFormat, Datum, NestedField, PrimitiveType, Schema, SchemaRef, Type,
@@ -4044,4 +4231,356 @@ message schema {
         assert_eq!(name_col.value(2), "Charlie");
         assert_eq!(name_col.value(3), "Dave");
     }
+
+    /// Write a data file with an `id` column holding each row's position, split into
+    /// row groups of `row_group_size` rows.
+    fn write_pos_test_file(
+        table_location: &str,
+        num_rows: i32,
+        row_group_size: usize,
+    ) -> (SchemaRef, String) {
+        use arrow_array::Int32Array;
+
+        let table_schema = Arc::new(
+            Schema::builder()
+                .with_schema_id(1)
+                .with_fields(vec![NestedField::required(
+                    1,
+                    "id",
+                    Type::Primitive(PrimitiveType::Int),
+                )
+                .into()])
+                .build()
+                .unwrap(),
+        );
+        let arrow_schema = Arc::new(ArrowSchema::new(vec![Field::new(
+            "id",
+            DataType::Int32,
+            false,
+        )
+        .with_metadata(HashMap::from([(
+            PARQUET_FIELD_ID_META_KEY.to_string(),
+            "1".to_string(),
+        )]))]));
+
+        let data_file_path = format!("{table_location}/data.parquet");
+        let batch = RecordBatch::try_new(
+            arrow_schema.clone(),
+            vec![Arc::new(Int32Array::from_iter_values(0..num_rows))],
+        )
+        .unwrap();
+        let props = WriterProperties::builder()
+            .set_max_row_group_size(row_group_size)
+            .build();
+        let file = File::create(&data_file_path).unwrap();
+        let mut writer = ArrowWriter::try_new(file, arrow_schema, Some(props)).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+
+        (table_schema, data_file_path)
+    }
+
+    /// Write a position delete file deleting `positions` of `data_file_path`.
+    fn write_pos_test_deletes(
+        table_location: &str,
+        data_file_path: &str,
+        positions: &[i64],
+    ) -> FileScanTaskDeleteFile {
+        use arrow_array::Int64Array;
+
+        let delete_file_path = format!("{table_location}/deletes.parquet");
+        let delete_schema = Arc::new(ArrowSchema::new(vec![
+            Field::new("file_path", DataType::Utf8, false).with_metadata(HashMap::from([(
+                PARQUET_FIELD_ID_META_KEY.to_string(),
+                "2147483546".to_string(),
+            )])),
+            Field::new("pos", DataType::Int64, false).with_metadata(HashMap::from([(
+                PARQUET_FIELD_ID_META_KEY.to_string(),
+                "2147483545".to_string(),
+            )])),
+        ]));
+        let delete_batch = RecordBatch::try_new(delete_schema.clone(), vec![
+            Arc::new(StringArray::from_iter_values(
+                positions.iter().map(|_| data_file_path),
+            )),
+            Arc::new(Int64Array::from_iter_values(positions.iter().copied())),
+        ])
+        .unwrap();
+
+        let file = File::create(&delete_file_path).unwrap();
+        let mut writer = ArrowWriter::try_new(file, delete_schema, None).unwrap();
+        writer.write(&delete_batch).unwrap();
+        writer.close().unwrap();
+
+        FileScanTaskDeleteFile {
+            file_path: delete_file_path,
+            file_type: DataContentType::PositionDeletes,
+            partition_spec_id: 0,
+            equality_ids: None,
+        }
+    }
+
+    /// Collect the `id` and `_pos` columns of the batches read for `task`.
+    fn read_ids_and_positions(
+        table_location: &str,
+        task: FileScanTask,
+        id_index: usize,
+        pos_index: usize,
+    ) -> (Vec<i32>, Vec<i64>) {
+        let file_io = FileIO::from_path(table_location).unwrap();
+        // Small batches so that batches end in the middle of row groups and runs
+        let reader = ArrowReaderBuilder::new(file_io).with_batch_size(64).build();
+        let batches = reader
+            .read(vec![task])
+            .unwrap()
+            .collect::<Result<Vec<RecordBatch>, _>>()
+            .unwrap();
+
+        let mut ids = vec![];
+        let mut positions = vec![];
+        for batch in &batches {
+            assert_eq!(batch.schema().field(pos_index).name(), "_pos");
+            ids.extend(
+                batch
+                    .column(id_index)
+                    .as_primitive::<arrow_array::types::Int32Type>()
+                    .values()
+                    .iter(),
+            );
+            positions.extend(
+                batch
+                    .column(pos_index)
+                    .as_primitive::<arrow_array::types::Int64Type>()
+                    .values()
+                    .iter(),
+            );
+        }
+        (ids, positions)
+    }
+
+    #[test]
+    fn test_read_pos_with_position_deletes() {
+        let tmp_dir = TempDir::new().unwrap();
+        let table_location = tmp_dir.path().to_str().unwrap().to_string();
+
+        // 3 row groups of 100 rows, deletes at row group boundaries and inside a group
+        let (table_schema, data_file_path) = write_pos_test_file(&table_location, 300, 100);
+        let deleted = [5, 99, 100, 150, 151, 299];
+        let delete_file = write_pos_test_deletes(&table_location, &data_file_path, &deleted);
+
+        let task = FileScanTask {
+            start: 0,
+            length: 0,
+            record_count: Some(300),
+            data_file_path,
+            data_file_format: DataFileFormat::Parquet,
+            schema: table_schema,
+            project_field_ids: vec![RESERVED_FIELD_ID_POS, 1],
+            predicate: None,
+            deletes: vec![delete_file],
+            partition: None,
+            partition_spec: None,
+            name_mapping: None,
+            case_sensitive: false,
+        };
+        let (ids, positions) = read_ids_and_positions(&table_location, task, 1, 0);
+
+        let expected: Vec<i32> = (0..300)
+            .filter(|id| !deleted.contains(&(*id as i64)))
+            .collect();
+        assert_eq!(ids, expected);
+        assert_eq!(
+            positions,
+            expected.iter().map(|&id| id as i64).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_read_pos_with_row_group_selection() {
+        use parquet::file::reader::{FileReader, SerializedFileReader};
+
+        let tmp_dir = TempDir::new().unwrap();
+        let table_location = tmp_dir.path().to_str().unwrap().to_string();
+
+        let (table_schema, data_file_path) = write_pos_test_file(&table_location, 300, 100);
+        // Position 5 lies in the skipped first row group
+        let deleted = [5, 100, 199, 250];
+        let delete_file = write_pos_test_deletes(&table_location, &data_file_path, &deleted);
+
+        // Read only the last two row groups via the task's byte range
+        let metadata_reader =
+            SerializedFileReader::new(File::open(&data_file_path).unwrap()).unwrap();
+        let metadata = metadata_reader.metadata();
+        assert_eq!(metadata.num_row_groups(), 3);
+        let start = 4 + metadata.row_group(0).compressed_size() as u64;
+        let length = (metadata.row_group(1).compressed_size()
+            + metadata.row_group(2).compressed_size()) as u64;
+
+        let task = FileScanTask {
+            start,
+            length,
+            record_count: Some(200),
+            data_file_path,
+            data_file_format: DataFileFormat::Parquet,
+            schema: table_schema,
+            project_field_ids: vec![1, RESERVED_FIELD_ID_POS],
+            predicate: None,
+            deletes: vec![delete_file],
+            partition: None,
+            partition_spec: None,
+            name_mapping: None,
+            case_sensitive: false,
+        };
+        let (ids, positions) = read_ids_and_positions(&table_location, task, 0, 1);
+
+        let expected: Vec<i32> = (100..300)
+            .filter(|id| !deleted.contains(&(*id as i64)))
+            .collect();
+        assert_eq!(ids, expected);
+        assert_eq!(
+            positions,
+            expected.iter().map(|&id| id as i64).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_row_positions() {
+        let schema_descr = get_test_schema_descr();
+        let columns: Vec<ColumnChunkMetaData> = schema_descr
+            .columns()
+            .iter()
+            .map(|ptr| ColumnChunkMetaData::builder(ptr.clone()).build().unwrap())
+            .collect();
+        let row_groups = vec![
+            build_test_row_group_meta(schema_descr.clone(), columns.clone(), 100, 0),
+            build_test_row_group_meta(schema_descr.clone(), columns.clone(), 50, 1),
+            build_test_row_group_meta(schema_descr.clone(), columns.clone(), 100, 2),
+        ];
+
+        // The selection spans the rows of the selected row groups 0 and 2
+        let row_selection = RowSelection::from(vec![
+            RowSelector::select(10),
+            RowSelector::skip(95),
+            RowSelector::select(95),
+        ]);
+        let mut row_positions =
+            RowPositions::new(&row_groups, &Some(vec![0, 2]), Some(&row_selection));
+
+        assert_eq!(
+            row_positions.next_positions(8).unwrap().values().to_vec(),
+            (0..8).collect::<Vec<i64>>()
+        );
+        assert_eq!(
+            row_positions.next_positions(97).unwrap().values().to_vec(),
+            [8, 9].into_iter().chain(155..250).collect::<Vec<i64>>()
+        );
+        assert!(row_positions.next_positions(1).is_err());
+
+        // Without selection, all rows of all row groups are read in order
+        let mut row_positions = RowPositions::new(&row_groups, &None, None);
+        assert_eq!(
+            row_positions.next_positions(250).unwrap().values().to_vec(),
+            (0..250).collect::<Vec<i64>>()
+        );
+    }
+
+    #[test]
+    fn test_read_pos_with_row_group_pruning() {
+        let tmp_dir = TempDir::new().unwrap();
+        let table_location = tmp_dir.path().to_str().unwrap().to_string();
+        let (table_schema, data_file_path) = write_pos_test_file(&table_location, 300, 100);
+        let deleted = [5, 260];
+        let delete_file = write_pos_test_deletes(&table_location, &data_file_path, &deleted);
+
+        let predicate = Reference::new("id")
+            .greater_than_or_equal_to(Datum::int(250))
+            .bind(table_schema.clone(), true)
+            .unwrap();
+        let task = FileScanTask {
+            start: 0,
+            length: 0,
+            record_count: Some(300),
+            data_file_path,
+            data_file_format: DataFileFormat::Parquet,
+            schema: table_schema,
+            project_field_ids: vec![1, RESERVED_FIELD_ID_POS],
+            predicate: Some(predicate),
+            deletes: vec![delete_file],
+            partition: None,
+            partition_spec: None,
+            name_mapping: None,
+            case_sensitive: false,
+        };
+
+        // Only the last row group can match, its rows are returned unfiltered
+        let file_io = FileIO::from_path(&table_location).unwrap();
+        let reader = ArrowReaderBuilder::new(file_io)
+            .with_row_filtering_enabled(false)
+            .build();
+        let batches = reader
+            .read(vec![task])
+            .unwrap()
+            .collect::<Result<Vec<RecordBatch>, _>>()
+            .unwrap();
+
+        let mut ids: Vec<i32> = vec![];
+        let mut positions: Vec<i64> = vec![];
+        for batch in &batches {
+            ids.extend(
+                batch
+                    .column(0)
+                    .as_primitive::<arrow_array::types::Int32Type>()
+                    .values()
+                    .iter(),
+            );
+            positions.extend(
+                batch
+                    .column(1)
+                    .as_primitive::<arrow_array::types::Int64Type>()
+                    .values()
+                    .iter(),
+            );
+        }
+        let expected: Vec<i32> = (200..300).filter(|&id| id != 260).collect();
+        assert_eq!(ids, expected);
+        assert_eq!(
+            positions,
+            expected.iter().map(|&id| id as i64).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_read_pos_with_predicate_unsupported() {
+        let tmp_dir = TempDir::new().unwrap();
+        let table_location = tmp_dir.path().to_str().unwrap().to_string();
+        let (table_schema, data_file_path) = write_pos_test_file(&table_location, 10, 100);
+
+        let predicate = Reference::new("id")
+            .greater_than(Datum::int(5))
+            .bind(table_schema.clone(), true)
+            .unwrap();
+        let task = FileScanTask {
+            start: 0,
+            length: 0,
+            record_count: Some(10),
+            data_file_path,
+            data_file_format: DataFileFormat::Parquet,
+            schema: table_schema,
+            project_field_ids: vec![1, RESERVED_FIELD_ID_POS],
+            predicate: Some(predicate),
+            deletes: vec![],
+            partition: None,
+            partition_spec: None,
+            name_mapping: None,
+            case_sensitive: false,
+        };
+
+        let file_io = FileIO::from_path(&table_location).unwrap();
+        let reader = ArrowReaderBuilder::new(file_io).build();
+        let err = reader
+            .read(vec![task])
+            .and_then(|batches| batches.collect::<Result<Vec<RecordBatch>, _>>())
+            .unwrap_err();
+        assert!(err.to_string().contains("FeatureUnsupported"), "{err}");
+    }
 }
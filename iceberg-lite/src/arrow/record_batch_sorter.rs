@@ -0,0 +1,252 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Sorting of record batches by a sort order.
+
+use std::sync::Arc;
+
+use arrow_array::RecordBatch;
+use arrow_ord::sort::{SortColumn, SortOptions, lexsort_to_indices};
+use arrow_select::take::take_record_batch;
+
+use super::record_batch_projector::RecordBatchProjector;
+use crate::spec::{NullOrder, Schema, SortDirection, SortOrder};
+use crate::transform::{BoxedTransformFunction, create_transform_function};
+use crate::{Error, ErrorKind, Result};
+
+/// Sorts record batches by the sort order of a table.
+///
+/// The source columns of the sort fields are projected from the batch by field id and
+/// their transforms applied, so that rows are ordered by the transformed values.
+#[derive(Debug)]
+pub struct RecordBatchSorter {
+    projector: RecordBatchProjector,
+    transform_functions: Vec<BoxedTransformFunction>,
+    sort_options: Vec<SortOptions>,
+}
+
+impl RecordBatchSorter {
+    /// Create a new RecordBatchSorter.
+    ///
+    /// # Arguments
+    ///
+    /// * `sort_order` - The sort order to sort by
+    /// * `table_schema` - The Iceberg table schema the sort order is bound to
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sort order is unsorted or its fields cannot be projected.
+    pub fn try_new(sort_order: &SortOrder, table_schema: &Schema) -> Result<Self> {
+        if sort_order.is_unsorted() {
+            return Err(Error::new(
+                ErrorKind::DataInvalid,
+                "Cannot create record batch sorter for unsorted order",
+            ));
+        }
+
+        let transform_functions = sort_order
+            .fields
+            .iter()
+            .map(|field| create_transform_function(&field.transform))
+            .collect::<Result<Vec<_>>>()?;
+        let source_field_ids: Vec<i32> = sort_order
+            .fields
+            .iter()
+            .map(|field| field.source_id)
+            .collect();
+        let projector = RecordBatchProjector::from_iceberg_schema(
+            Arc::new(table_schema.clone()),
+            &source_field_ids,
+        )?;
+        let sort_options = sort_order
+            .fields
+            .iter()
+            .map(|field| SortOptions {
+                descending: field.direction == SortDirection::Descending,
+                nulls_first: field.null_order == NullOrder::First,
+            })
+            .collect();
+
+        Ok(Self {
+            projector,
+            transform_functions,
+            sort_options,
+        })
+    }
+
+    /// Sort the rows of a record batch.
+    pub fn sort(&self, batch: &RecordBatch) -> Result<RecordBatch> {
+        let source_columns = self.projector.project_column(batch.columns())?;
+
+        let mut sort_columns = Vec::with_capacity(source_columns.len());
+        for ((source_column, transform_fn), options) in source_columns
+            .into_iter()
+            .zip(&self.transform_functions)
+            .zip(&self.sort_options)
+        {
+            sort_columns.push(SortColumn {
+                values: transform_fn.transform(source_column)?,
+                options: Some(*options),
+            });
+        }
+
+        let indices = lexsort_to_indices(&sort_columns, None)?;
+        Ok(take_record_batch(batch, &indices)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use arrow_array::{Int32Array, StringArray};
+    use arrow_schema::{DataType, Field, Schema as ArrowSchema};
+    use parquet::arrow::PARQUET_FIELD_ID_META_KEY;
+
+    use super::*;
+    use crate::spec::{NestedField, PrimitiveType, SortField, Transform, Type};
+
+    fn table_schema() -> Schema {
+        Schema::builder()
+            .with_schema_id(0)
+            .with_fields(vec![
+                NestedField::required(1, "id", Type::Primitive(PrimitiveType::Int))
+                    .into(),
+                NestedField::optional(
+                    2,
+                    "name",
+                    Type::Primitive(PrimitiveType::String),
+                )
+                .into(),
+            ])
+            .build()
+            .unwrap()
+    }
+
+    fn batch() -> RecordBatch {
+        let field = |name: &str, data_type: DataType, id: i32| {
+            Field::new(name, data_type, true).with_metadata(HashMap::from([(
+                PARQUET_FIELD_ID_META_KEY.to_string(),
+                id.to_string(),
+            )]))
+        };
+        let arrow_schema = Arc::new(ArrowSchema::new(vec![
+            field("id", DataType::Int32, 1),
+            field("name", DataType::Utf8, 2),
+        ]));
+
+        RecordBatch::try_new(
+            arrow_schema,
+            vec![
+                Arc::new(Int32Array::from(vec![3, 1, 4, 2, 5])),
+                Arc::new(StringArray::from(vec![
+                    Some("b"),
+                    None,
+                    Some("a"),
+                    Some("b"),
+                    Some("c"),
+                ])),
+            ],
+        )
+        .unwrap()
+    }
+
+    fn sort_field(
+        source_id: i32,
+        transform: Transform,
+        direction: SortDirection,
+        null_order: NullOrder,
+    ) -> SortField {
+        SortField::builder()
+            .source_id(source_id)
+            .transform(transform)
+            .direction(direction)
+            .null_order(null_order)
+            .build()
+    }
+
+    fn ids(batch: &RecordBatch) -> Vec<i32> {
+        batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int32Array>()
+            .unwrap()
+            .values()
+            .to_vec()
+    }
+
+    #[test]
+    fn test_sort_record_batch() {
+        let sort_order = SortOrder::builder()
+            .with_order_id(1)
+            .with_sort_field(sort_field(
+                2,
+                Transform::Identity,
+                SortDirection::Descending,
+                NullOrder::Last,
+            ))
+            .with_sort_field(sort_field(
+                1,
+                Transform::Identity,
+                SortDirection::Ascending,
+                NullOrder::First,
+            ))
+            .build(&table_schema())
+            .unwrap();
+
+        let sorter =
+            RecordBatchSorter::try_new(&sort_order, &table_schema()).unwrap();
+        let sorted = sorter.sort(&batch()).unwrap();
+
+        assert_eq!(ids(&sorted), vec![5, 2, 3, 4, 1]);
+        assert_eq!(sorted.schema(), batch().schema());
+    }
+
+    #[test]
+    fn test_sort_record_batch_with_transform() {
+        let sort_order = SortOrder::builder()
+            .with_order_id(1)
+            .with_sort_field(sort_field(
+                1,
+                Transform::Truncate(2),
+                SortDirection::Descending,
+                NullOrder::First,
+            ))
+            .with_sort_field(sort_field(
+                1,
+                Transform::Identity,
+                SortDirection::Ascending,
+                NullOrder::First,
+            ))
+            .build(&table_schema())
+            .unwrap();
+
+        let sorter =
+            RecordBatchSorter::try_new(&sort_order, &table_schema()).unwrap();
+        let sorted = sorter.sort(&batch()).unwrap();
+
+        // truncate(2) groups 2 and 3 as well as 4 and 5
+        assert_eq!(ids(&sorted), vec![4, 5, 2, 3, 1]);
+    }
+
+    #[test]
+    fn test_unsorted_order_error() {
+        let result =
+            RecordBatchSorter::try_new(&SortOrder::unsorted_order(), &table_schema());
+        assert!(result.is_err());
+    }
+}
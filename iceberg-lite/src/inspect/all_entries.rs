@@ -0,0 +1,84 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use super::data_file::all_entries;
+use super::entries::{entries_scan, entries_schema};
+use crate::Result;
+use crate::scan::ArrowRecordBatchIterator;
+use crate::table::Table;
+
+/// All entries table: the manifest entries of all the snapshots of the table,
+/// deleted entries included.
+///
+/// A manifest shared between snapshots is read once.
+pub struct AllEntriesTable<'a> {
+    table: &'a Table,
+}
+
+impl<'a> AllEntriesTable<'a> {
+    /// Create a new All entries table instance.
+    pub fn new(table: &'a Table) -> Self {
+        Self { table }
+    }
+
+    /// Returns the iceberg schema of the all entries table, the schema of the
+    /// entries table.
+    pub fn schema(&self) -> Result<crate::spec::Schema> {
+        entries_schema(self.table)
+    }
+
+    /// Scans the all entries table.
+    pub fn scan(&self) -> Result<ArrowRecordBatchIterator> {
+        entries_scan(self.table, &all_entries(self.table)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::Int32Type;
+
+    use crate::scan::tests::TableTestFixture;
+
+    #[test]
+    fn test_all_entries_table() {
+        let mut fixture = TableTestFixture::new();
+        fixture.setup_manifest_files();
+        fixture.setup_parent_manifest_list();
+
+        let batch = fixture
+            .table
+            .inspect()
+            .all_entries()
+            .scan()
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+
+        // The manifest shared by both snapshots is read once: existing, added
+        // and deleted entries.
+        let mut status = batch
+            .column_by_name("status")
+            .unwrap()
+            .as_primitive::<Int32Type>()
+            .values()
+            .to_vec();
+        status.sort();
+        assert_eq!(status, [0, 1, 2]);
+    }
+}
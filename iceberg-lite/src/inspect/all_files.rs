@@ -0,0 +1,108 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use super::data_file::all_entries;
+use super::files::{FileContent, files_scan, files_schema};
+use crate::Result;
+use crate::scan::ArrowRecordBatchIterator;
+use crate::table::Table;
+
+/// All files table: the live data and delete files of the manifests of all
+/// the snapshots of the table.
+///
+/// A manifest shared between snapshots is read once, so a file is listed once
+/// for each manifest it is live in.
+pub struct AllFilesTable<'a> {
+    table: &'a Table,
+    content: FileContent,
+}
+
+impl<'a> AllFilesTable<'a> {
+    /// Create a new All files table instance, listing data and delete files.
+    pub fn new(table: &'a Table) -> Self {
+        Self {
+            table,
+            content: FileContent::All,
+        }
+    }
+
+    /// Create a new All files table instance, listing data files only.
+    pub fn data_files(table: &'a Table) -> Self {
+        Self {
+            table,
+            content: FileContent::Data,
+        }
+    }
+
+    /// Create a new All files table instance, listing delete files only.
+    pub fn delete_files(table: &'a Table) -> Self {
+        Self {
+            table,
+            content: FileContent::Deletes,
+        }
+    }
+
+    /// Returns the iceberg schema of the all files table, the schema of the
+    /// files table.
+    pub fn schema(&self) -> Result<crate::spec::Schema> {
+        files_schema(self.table)
+    }
+
+    /// Scans the all files table.
+    pub fn scan(&self) -> Result<ArrowRecordBatchIterator> {
+        files_scan(self.table, &all_entries(self.table)?, self.content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scan::tests::TableTestFixture;
+
+    #[test]
+    fn test_all_files_tables() {
+        let mut fixture = TableTestFixture::new();
+        fixture.setup_manifest_files();
+        fixture.setup_parent_manifest_list();
+        let inspect = fixture.table.inspect();
+
+        // The manifest shared by both snapshots is read once.
+        let files = inspect.all_files().scan().unwrap().next().unwrap();
+        assert_eq!(files.unwrap().num_rows(), 2);
+
+        let data_files = inspect.all_data_files().scan().unwrap().next().unwrap();
+        assert_eq!(data_files.unwrap().num_rows(), 2);
+
+        let delete_files = inspect.all_delete_files().scan().unwrap().next().unwrap();
+        assert_eq!(delete_files.unwrap().num_rows(), 0);
+    }
+
+    #[test]
+    fn test_all_files_table_without_snapshots() {
+        let fixture = TableTestFixture::new_empty();
+
+        let batch = fixture
+            .table
+            .inspect()
+            .all_files()
+            .scan()
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(batch.num_rows(), 0);
+    }
+}
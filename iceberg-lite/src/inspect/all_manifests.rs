@@ -0,0 +1,142 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::iter::repeat_n;
+use std::sync::Arc;
+
+use arrow_array::{Int64Array, RecordBatch};
+
+use super::ManifestsTable;
+use super::data_file::all_snapshots;
+use crate::Result;
+use crate::arrow::schema_to_arrow_schema;
+use crate::scan::ArrowRecordBatchIterator;
+use crate::spec::{NestedField, PrimitiveType, Type};
+use crate::table::Table;
+
+/// All manifests table: the manifests of all the snapshots of the table.
+///
+/// A manifest shared between snapshots is listed once for each snapshot, with
+/// the snapshot in `reference_snapshot_id`.
+pub struct AllManifestsTable<'a> {
+    table: &'a Table,
+}
+
+impl<'a> AllManifestsTable<'a> {
+    /// Create a new All manifests table instance.
+    pub fn new(table: &'a Table) -> Self {
+        Self { table }
+    }
+
+    /// Returns the iceberg schema of the all manifests table.
+    pub fn schema(&self) -> crate::spec::Schema {
+        let mut fields = ManifestsTable::new(self.table)
+            .schema()
+            .as_struct()
+            .fields()
+            .to_vec();
+        fields.push(Arc::new(NestedField::required(
+            18,
+            "reference_snapshot_id",
+            Type::Primitive(PrimitiveType::Long),
+        )));
+
+        crate::spec::Schema::builder()
+            .with_fields(fields)
+            .build()
+            .unwrap()
+    }
+
+    /// Scans the all manifests table.
+    pub fn scan(&self) -> Result<ArrowRecordBatchIterator> {
+        let schema = schema_to_arrow_schema(&self.schema())?;
+
+        let metadata = self.table.metadata_ref();
+        let object_cache = self.table.object_cache();
+        let manifest_lists = all_snapshots(&metadata)
+            .into_iter()
+            .map(|snapshot| {
+                let manifest_list =
+                    object_cache.get_manifest_list(snapshot, &metadata)?;
+                Ok((snapshot.snapshot_id(), manifest_list))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut columns = ManifestsTable::new(self.table).columns(
+            manifest_lists
+                .iter()
+                .flat_map(|(_, manifest_list)| manifest_list.entries()),
+        )?;
+        columns.push(Arc::new(Int64Array::from_iter_values(
+            manifest_lists
+                .iter()
+                .flat_map(|(snapshot_id, manifest_list)| {
+                    repeat_n(*snapshot_id, manifest_list.entries().len())
+                }),
+        )));
+
+        let batch = RecordBatch::try_new(Arc::new(schema), columns)?;
+        Ok(Box::new(vec![Ok(batch)].into_iter()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::Int64Type;
+
+    use crate::scan::tests::TableTestFixture;
+
+    #[test]
+    fn test_all_manifests_table() {
+        let mut fixture = TableTestFixture::new();
+        fixture.setup_manifest_files();
+        fixture.setup_parent_manifest_list();
+
+        let batch = fixture
+            .table
+            .inspect()
+            .all_manifests()
+            .scan()
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+
+        // The manifest is listed once for each snapshot
+        assert_eq!(batch.num_rows(), 2);
+        let path = batch.column_by_name("path").unwrap().as_string::<i32>();
+        assert_eq!(path.value(0), path.value(1));
+
+        let reference_snapshot_id = batch
+            .column_by_name("reference_snapshot_id")
+            .unwrap()
+            .as_primitive::<Int64Type>();
+        assert_eq!(
+            reference_snapshot_id.values().to_vec(),
+            [3051729675574597004, 3055729675574597004]
+        );
+        let added_snapshot_id = batch
+            .column_by_name("added_snapshot_id")
+            .unwrap()
+            .as_primitive::<Int64Type>();
+        assert_eq!(
+            added_snapshot_id.values().to_vec(),
+            [3055729675574597004, 3055729675574597004]
+        );
+    }
+}
@@ -0,0 +1,732 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Schema and columns shared by the metadata tables that list data files.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use arrow_array::{
+    ArrayRef, BinaryArray, BooleanArray, Decimal128Array, Float32Array, Float64Array,
+    Int32Array, Int64Array, LargeBinaryArray, ListArray, MapArray, StringArray,
+    StructArray,
+};
+use arrow_buffer::{NullBuffer, OffsetBuffer};
+use arrow_cast::cast::cast;
+use arrow_schema::{DataType, FieldRef, Fields};
+
+use crate::spec::{
+    DataFile, Datum, ListType, ManifestEntryRef, MapType, NestedField,
+    NestedFieldRef, PrimitiveLiteral, PrimitiveType, Schema, SnapshotRef, StructType,
+    TableMetadata, Type,
+};
+use crate::table::Table;
+use crate::{Error, ErrorKind, Result};
+
+/// The highest field id of the `data_file` struct.
+const DATA_FILE_HIGHEST_FIELD_ID: i32 = 141;
+
+/// Returns the partition type of the metadata tables: the union of the fields
+/// of all the partition specs of the table, in the order of the specs.
+pub(super) fn partition_type(metadata: &TableMetadata) -> Result<StructType> {
+    let mut specs = metadata.partition_specs_iter().collect::<Vec<_>>();
+    specs.sort_by_key(|spec| spec.spec_id());
+
+    let mut fields: Vec<NestedFieldRef> = vec![];
+    for spec in specs {
+        let spec_type = spec.partition_type(metadata.current_schema())?;
+        for field in spec_type.fields() {
+            if !fields.iter().any(|f| f.id == field.id) {
+                fields.push(field.clone());
+            }
+        }
+    }
+    Ok(StructType::new(fields))
+}
+
+/// Returns the fields of the `data_file` struct. The `partition` field is
+/// left out for unpartitioned tables.
+pub(super) fn data_file_fields(partition_type: &StructType) -> Vec<NestedFieldRef> {
+    let metrics_map = |key_id, value_id| {
+        Type::Map(MapType::new(
+            Arc::new(NestedField::map_key_element(
+                key_id,
+                Type::Primitive(PrimitiveType::Int),
+            )),
+            Arc::new(NestedField::map_value_element(
+                value_id,
+                Type::Primitive(PrimitiveType::Long),
+                true,
+            )),
+        ))
+    };
+    let bounds_map = |key_id, value_id| {
+        Type::Map(MapType::new(
+            Arc::new(NestedField::map_key_element(
+                key_id,
+                Type::Primitive(PrimitiveType::Int),
+            )),
+            Arc::new(NestedField::map_value_element(
+                value_id,
+                Type::Primitive(PrimitiveType::Binary),
+                true,
+            )),
+        ))
+    };
+    let list = |element_id, element_type| {
+        Type::List(ListType::new(Arc::new(NestedField::list_element(
+            element_id,
+            Type::Primitive(element_type),
+            true,
+        ))))
+    };
+
+    let mut fields = vec![
+        NestedField::optional(134, "content", Type::Primitive(PrimitiveType::Int)),
+        NestedField::required(
+            100,
+            "file_path",
+            Type::Primitive(PrimitiveType::String),
+        ),
+        NestedField::required(
+            101,
+            "file_format",
+            Type::Primitive(PrimitiveType::String),
+        ),
+        NestedField::optional(141, "spec_id", Type::Primitive(PrimitiveType::Int)),
+    ];
+    if !partition_type.fields().is_empty() {
+        fields.push(NestedField::required(
+            102,
+            "partition",
+            Type::Struct(partition_type.clone()),
+        ));
+    }
+    fields.extend([
+        NestedField::required(
+            103,
+            "record_count",
+            Type::Primitive(PrimitiveType::Long),
+        ),
+        NestedField::required(
+            104,
+            "file_size_in_bytes",
+            Type::Primitive(PrimitiveType::Long),
+        ),
+        NestedField::optional(108, "column_sizes", metrics_map(117, 118)),
+        NestedField::optional(109, "value_counts", metrics_map(119, 120)),
+        NestedField::optional(110, "null_value_counts", metrics_map(121, 122)),
+        NestedField::optional(137, "nan_value_counts", metrics_map(138, 139)),
+        NestedField::optional(125, "lower_bounds", bounds_map(126, 127)),
+        NestedField::optional(128, "upper_bounds", bounds_map(129, 130)),
+        NestedField::optional(
+            131,
+            "key_metadata",
+            Type::Primitive(PrimitiveType::Binary),
+        ),
+        NestedField::optional(132, "split_offsets", list(133, PrimitiveType::Long)),
+        NestedField::optional(135, "equality_ids", list(136, PrimitiveType::Int)),
+        NestedField::optional(
+            140,
+            "sort_order_id",
+            Type::Primitive(PrimitiveType::Int),
+        ),
+    ]);
+    fields.into_iter().map(Arc::new).collect()
+}
+
+/// Returns the `readable_metrics` field: for each primitive column of
+/// `schema`, ordered by name, the metrics of the column and its bounds in the
+/// type of the column.
+///
+/// Field ids are assigned after the ids of the table schema and of the
+/// `data_file` struct, like the Java implementation does.
+pub(super) fn readable_metrics_field(
+    schema: &Schema,
+    partition_type: &StructType,
+) -> NestedFieldRef {
+    let mut next_id = partition_type
+        .fields()
+        .iter()
+        .map(|field| field.id)
+        .chain([schema.highest_field_id(), DATA_FILE_HIGHEST_FIELD_ID])
+        .max()
+        .unwrap_or(DATA_FILE_HIGHEST_FIELD_ID);
+    let mut next_id = move || {
+        next_id += 1;
+        next_id
+    };
+
+    let mut columns = schema
+        .field_id_to_fields()
+        .iter()
+        .filter_map(|(id, field)| {
+            let column_type = field.field_type.as_primitive_type()?;
+            Some((schema.name_by_field_id(*id)?, column_type.clone()))
+        })
+        .collect::<Vec<_>>();
+    columns.sort_by(|a, b| a.0.cmp(b.0));
+
+    let mut metrics_fields = Vec::with_capacity(columns.len());
+    for (name, column_type) in columns {
+        let long = Type::Primitive(PrimitiveType::Long);
+        let metrics = vec![
+            NestedField::optional(next_id(), "column_size", long.clone()),
+            NestedField::optional(next_id(), "value_count", long.clone()),
+            NestedField::optional(next_id(), "null_value_count", long.clone()),
+            NestedField::optional(next_id(), "nan_value_count", long),
+            NestedField::optional(
+                next_id(),
+                "lower_bound",
+                Type::Primitive(column_type.clone()),
+            ),
+            NestedField::optional(
+                next_id(),
+                "upper_bound",
+                Type::Primitive(column_type),
+            ),
+        ];
+        metrics_fields.push(Arc::new(NestedField::optional(
+            next_id(),
+            name,
+            Type::Struct(StructType::new(
+                metrics.into_iter().map(Arc::new).collect(),
+            )),
+        )));
+    }
+
+    Arc::new(NestedField::optional(
+        next_id(),
+        "readable_metrics",
+        Type::Struct(StructType::new(metrics_fields)),
+    ))
+}
+
+/// Returns the entries of the manifests of the current snapshot of `table`,
+/// deleted entries included.
+pub(super) fn current_entries(table: &Table) -> Result<Vec<ManifestEntryRef>> {
+    let metadata = table.metadata_ref();
+    let Some(snapshot) = metadata.current_snapshot() else {
+        return Ok(vec![]);
+    };
+
+    let object_cache = table.object_cache();
+    let manifest_list = object_cache.get_manifest_list(snapshot, &metadata)?;
+    let mut entries = vec![];
+    for manifest_file in manifest_list.entries() {
+        let manifest = object_cache.get_manifest(manifest_file)?;
+        entries.extend(manifest.entries().iter().cloned());
+    }
+    Ok(entries)
+}
+
+/// Returns all the snapshots of the table, oldest first.
+pub(super) fn all_snapshots(metadata: &TableMetadata) -> Vec<&SnapshotRef> {
+    let mut snapshots = metadata.snapshots().collect::<Vec<_>>();
+    snapshots.sort_by_key(|snapshot| {
+        (snapshot.sequence_number(), snapshot.timestamp_ms())
+    });
+    snapshots
+}
+
+/// Returns the manifest entries of all the snapshots of `table`, oldest
+/// snapshot first. Manifests shared between snapshots are read once, through
+/// the object cache of the table.
+pub(super) fn all_entries(table: &Table) -> Result<Vec<ManifestEntryRef>> {
+    let metadata = table.metadata_ref();
+    let object_cache = table.object_cache();
+    let mut manifest_paths = HashSet::new();
+    let mut entries = vec![];
+    for snapshot in all_snapshots(&metadata) {
+        let manifest_list = object_cache.get_manifest_list(snapshot, &metadata)?;
+        for manifest_file in manifest_list.entries() {
+            if !manifest_paths.insert(manifest_file.manifest_path.clone()) {
+                continue;
+            }
+            let manifest = object_cache.get_manifest(manifest_file)?;
+            entries.extend(manifest.entries().iter().cloned());
+        }
+    }
+    Ok(entries)
+}
+
+/// Returns the partition values of `file` for `partition_type`. Fields that
+/// are not in the partition spec of the file are null.
+pub(super) fn partition_values(
+    metadata: &TableMetadata,
+    partition_type: &StructType,
+    file: &DataFile,
+) -> Vec<Option<PrimitiveLiteral>> {
+    let spec = metadata.partition_spec_by_id(file.partition_spec_id);
+    partition_type
+        .fields()
+        .iter()
+        .map(|field| {
+            let position = spec?
+                .fields()
+                .iter()
+                .position(|spec_field| spec_field.field_id == field.id)?;
+            file.partition()
+                .fields()
+                .get(position)?
+                .as_ref()?
+                .as_primitive_literal()
+        })
+        .collect()
+}
+
+/// Builds the arrays of the `data_file` `fields` of `files`.
+pub(super) fn data_file_arrays(
+    metadata: &TableMetadata,
+    partition_type: &StructType,
+    files: &[&DataFile],
+    fields: &[FieldRef],
+) -> Result<Vec<ArrayRef>> {
+    fields
+        .iter()
+        .map(|field| {
+            let data_type = field.data_type();
+            let array: ArrayRef = match field.name().as_str() {
+                "content" => Arc::new(Int32Array::from_iter_values(
+                    files.iter().map(|file| file.content_type() as i32),
+                )),
+                "file_path" => Arc::new(StringArray::from_iter_values(
+                    files.iter().map(|file| file.file_path()),
+                )),
+                "file_format" => Arc::new(StringArray::from_iter_values(
+                    files
+                        .iter()
+                        .map(|file| file.file_format().to_string().to_uppercase()),
+                )),
+                "spec_id" => Arc::new(Int32Array::from_iter_values(
+                    files.iter().map(|file| file.partition_spec_id),
+                )),
+                "partition" => {
+                    let rows = files
+                        .iter()
+                        .map(|file| partition_values(metadata, partition_type, file))
+                        .collect::<Vec<_>>();
+                    partition_array(data_type, &rows)?
+                }
+                "record_count" => Arc::new(Int64Array::from_iter_values(
+                    files.iter().map(|file| file.record_count() as i64),
+                )),
+                "file_size_in_bytes" => Arc::new(Int64Array::from_iter_values(
+                    files.iter().map(|file| file.file_size_in_bytes() as i64),
+                )),
+                "column_sizes" => metrics_map_array(
+                    data_type,
+                    files.iter().map(|file| file.column_sizes()),
+                )?,
+                "value_counts" => metrics_map_array(
+                    data_type,
+                    files.iter().map(|file| file.value_counts()),
+                )?,
+                "null_value_counts" => metrics_map_array(
+                    data_type,
+                    files.iter().map(|file| file.null_value_counts()),
+                )?,
+                "nan_value_counts" => metrics_map_array(
+                    data_type,
+                    files.iter().map(|file| file.nan_value_counts()),
+                )?,
+                "lower_bounds" => bounds_map_array(
+                    data_type,
+                    files.iter().map(|file| file.lower_bounds()),
+                )?,
+                "upper_bounds" => bounds_map_array(
+                    data_type,
+                    files.iter().map(|file| file.upper_bounds()),
+                )?,
+                "key_metadata" => Arc::new(
+                    files
+                        .iter()
+                        .map(|file| file.key_metadata())
+                        .collect::<LargeBinaryArray>(),
+                ),
+                "split_offsets" => {
+                    let offsets = files
+                        .iter()
+                        .map(|file| file.split_offsets())
+                        .collect::<Vec<_>>();
+                    list_array(
+                        data_type,
+                        offsets.iter().map(|offsets| offsets.map(<[i64]>::len)),
+                        Arc::new(Int64Array::from_iter_values(
+                            offsets.iter().flatten().flat_map(|o| o.iter().copied()),
+                        )),
+                    )?
+                }
+                "equality_ids" => {
+                    let ids = files
+                        .iter()
+                        .map(|file| file.equality_ids())
+                        .collect::<Vec<_>>();
+                    list_array(
+                        data_type,
+                        ids.iter().map(|ids| ids.as_ref().map(Vec::len)),
+                        Arc::new(Int32Array::from_iter_values(
+                            ids.iter().flatten().flat_map(|ids| ids.iter().copied()),
+                        )),
+                    )?
+                }
+                "sort_order_id" => Arc::new(Int32Array::from(
+                    files
+                        .iter()
+                        .map(|file| file.sort_order_id())
+                        .collect::<Vec<_>>(),
+                )),
+                name => {
+                    return Err(Error::new(
+                        ErrorKind::Unexpected,
+                        format!("Unexpected data file column {name}"),
+                    ));
+                }
+            };
+            Ok(array)
+        })
+        .collect()
+}
+
+/// Builds the `readable_metrics` array of `files`, with the metrics of the
+/// columns of `schema`.
+pub(super) fn readable_metrics_array(
+    schema: &Schema,
+    files: &[&DataFile],
+    data_type: &DataType,
+) -> Result<ArrayRef> {
+    let columns = struct_fields(data_type)?;
+    let arrays = columns
+        .iter()
+        .map(|column| {
+            let field_id =
+                schema.field_id_by_name(column.name()).ok_or_else(|| {
+                    Error::new(
+                        ErrorKind::Unexpected,
+                        format!("Column {} not found in table schema", column.name()),
+                    )
+                })?;
+            let metrics = struct_fields(column.data_type())?;
+            let counts = |metric: fn(&DataFile) -> &HashMap<i32, u64>| {
+                Arc::new(Int64Array::from(
+                    files
+                        .iter()
+                        .map(|file| metric(file).get(&field_id).map(|c| *c as i64))
+                        .collect::<Vec<_>>(),
+                )) as ArrayRef
+            };
+            let bounds =
+                |data_type: &DataType,
+                 metric: fn(&DataFile) -> &HashMap<i32, Datum>| {
+                    literal_array(
+                        data_type,
+                        &files
+                            .iter()
+                            .map(|file| {
+                                metric(file)
+                                    .get(&field_id)
+                                    .map(|datum| datum.literal().clone())
+                            })
+                            .collect::<Vec<_>>(),
+                    )
+                };
+            let arrays = metrics
+                .iter()
+                .map(|metric| match metric.name().as_str() {
+                    "column_size" => Ok(counts(DataFile::column_sizes)),
+                    "value_count" => Ok(counts(DataFile::value_counts)),
+                    "null_value_count" => Ok(counts(DataFile::null_value_counts)),
+                    "nan_value_count" => Ok(counts(DataFile::nan_value_counts)),
+                    "lower_bound" => {
+                        bounds(metric.data_type(), DataFile::lower_bounds)
+                    }
+                    "upper_bound" => {
+                        bounds(metric.data_type(), DataFile::upper_bounds)
+                    }
+                    name => Err(Error::new(
+                        ErrorKind::Unexpected,
+                        format!("Unexpected readable metric {name}"),
+                    )),
+                })
+                .collect::<Result<Vec<_>>>()?;
+            struct_array(metrics, arrays, files.len())
+        })
+        .collect::<Result<Vec<_>>>()?;
+    struct_array(columns, arrays, files.len())
+}
+
+/// Builds a struct array of partition values, one row of `rows` per value.
+pub(super) fn partition_array(
+    data_type: &DataType,
+    rows: &[Vec<Option<PrimitiveLiteral>>],
+) -> Result<ArrayRef> {
+    let fields = struct_fields(data_type)?;
+    let arrays = fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| {
+            let values = rows
+                .iter()
+                .map(|row| row.get(index).cloned().flatten())
+                .collect::<Vec<_>>();
+            literal_array(field.data_type(), &values)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    struct_array(fields, arrays, rows.len())
+}
+
+/// Builds an array of type `data_type` from primitive literals.
+///
+/// Literals of a promoted type (`int` to `long`, `float` to `double`) are
+/// widened, any other mismatch is an error.
+pub(super) fn literal_array(
+    data_type: &DataType,
+    values: &[Option<PrimitiveLiteral>],
+) -> Result<ArrayRef> {
+    let array: ArrayRef =
+        match data_type {
+            DataType::Boolean => Arc::new(BooleanArray::from(literals(
+                values,
+                data_type,
+                |literal| match literal {
+                    PrimitiveLiteral::Boolean(value) => Some(*value),
+                    _ => None,
+                },
+            )?)),
+            DataType::Int32 | DataType::Date32 => {
+                Arc::new(Int32Array::from(literals(values, data_type, |literal| {
+                    match literal {
+                        PrimitiveLiteral::Int(value) => Some(*value),
+                        _ => None,
+                    }
+                })?))
+            }
+            DataType::Int64 | DataType::Time64(_) | DataType::Timestamp(_, _) => {
+                Arc::new(Int64Array::from(literals(values, data_type, |literal| {
+                    match literal {
+                        PrimitiveLiteral::Long(value) => Some(*value),
+                        PrimitiveLiteral::Int(value) => Some(*value as i64),
+                        _ => None,
+                    }
+                })?))
+            }
+            DataType::Float32 => Arc::new(Float32Array::from(literals(
+                values,
+                data_type,
+                |literal| match literal {
+                    PrimitiveLiteral::Float(value) => Some(value.0),
+                    _ => None,
+                },
+            )?)),
+            DataType::Float64 => Arc::new(Float64Array::from(literals(
+                values,
+                data_type,
+                |literal| match literal {
+                    PrimitiveLiteral::Double(value) => Some(value.0),
+                    PrimitiveLiteral::Float(value) => Some(value.0 as f64),
+                    _ => None,
+                },
+            )?)),
+            DataType::Utf8 => Arc::new(
+                literals(values, data_type, |literal| match literal {
+                    PrimitiveLiteral::String(value) => Some(value.clone()),
+                    _ => None,
+                })?
+                .into_iter()
+                .collect::<StringArray>(),
+            ),
+            DataType::Decimal128(precision, scale) => Arc::new(
+                Decimal128Array::from(literals(values, data_type, |literal| {
+                    match literal {
+                        PrimitiveLiteral::Int128(value) => Some(*value),
+                        _ => None,
+                    }
+                })?)
+                .with_precision_and_scale(*precision, *scale)?,
+            ),
+            DataType::LargeBinary | DataType::FixedSizeBinary(_) => Arc::new(
+                literals(values, data_type, |literal| match literal {
+                    PrimitiveLiteral::Binary(value) => Some(value.clone()),
+                    PrimitiveLiteral::UInt128(value) => {
+                        Some(value.to_be_bytes().to_vec())
+                    }
+                    _ => None,
+                })?
+                .into_iter()
+                .collect::<BinaryArray>(),
+            ),
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::FeatureUnsupported,
+                    format!("Unsupported metadata column type {data_type}"),
+                ));
+            }
+        };
+
+    if array.data_type() == data_type {
+        Ok(array)
+    } else {
+        Ok(cast(&array, data_type)?)
+    }
+}
+
+/// Extracts the values of `literals` with `value`, failing on literals of
+/// another type than `data_type`.
+fn literals<T>(
+    literals: &[Option<PrimitiveLiteral>],
+    data_type: &DataType,
+    value: impl Fn(&PrimitiveLiteral) -> Option<T>,
+) -> Result<Vec<Option<T>>> {
+    literals
+        .iter()
+        .map(|literal| match literal {
+            None => Ok(None),
+            Some(literal) => value(literal).map(Some).ok_or_else(|| {
+                Error::new(
+                    ErrorKind::DataInvalid,
+                    format!("Literal {literal:?} does not match type {data_type}"),
+                )
+            }),
+        })
+        .collect()
+}
+
+fn struct_fields(data_type: &DataType) -> Result<&Fields> {
+    match data_type {
+        DataType::Struct(fields) => Ok(fields),
+        _ => Err(unexpected_type(data_type)),
+    }
+}
+
+fn struct_array(
+    fields: &Fields,
+    arrays: Vec<ArrayRef>,
+    len: usize,
+) -> Result<ArrayRef> {
+    if fields.is_empty() {
+        return Ok(Arc::new(StructArray::new_empty_fields(len, None)));
+    }
+    Ok(Arc::new(StructArray::try_new(
+        fields.clone(),
+        arrays,
+        None,
+    )?))
+}
+
+/// Builds a `map<int, long>` array of file metrics, sorted by field id.
+fn metrics_map_array<'a>(
+    data_type: &DataType,
+    rows: impl Iterator<Item = &'a HashMap<i32, u64>>,
+) -> Result<ArrayRef> {
+    let mut lengths = vec![];
+    let mut keys = vec![];
+    let mut values = vec![];
+    for metrics in rows {
+        let mut metrics = metrics.iter().collect::<Vec<_>>();
+        metrics.sort();
+        lengths.push(metrics.len());
+        for (key, value) in metrics {
+            keys.push(*key);
+            values.push(*value as i64);
+        }
+    }
+    map_array(
+        data_type,
+        lengths,
+        Arc::new(Int32Array::from(keys)),
+        Arc::new(Int64Array::from(values)),
+    )
+}
+
+/// Builds a `map<int, binary>` array of serialized file bounds, sorted by
+/// field id.
+fn bounds_map_array<'a>(
+    data_type: &DataType,
+    rows: impl Iterator<Item = &'a HashMap<i32, Datum>>,
+) -> Result<ArrayRef> {
+    let mut lengths = vec![];
+    let mut keys = vec![];
+    let mut values = vec![];
+    for bounds in rows {
+        let mut bounds = bounds.iter().collect::<Vec<_>>();
+        bounds.sort_by_key(|(key, _)| **key);
+        lengths.push(bounds.len());
+        for (key, value) in bounds {
+            keys.push(*key);
+            values.push(value.to_bytes()?.to_vec());
+        }
+    }
+    map_array(
+        data_type,
+        lengths,
+        Arc::new(Int32Array::from(keys)),
+        Arc::new(LargeBinaryArray::from_iter_values(values)),
+    )
+}
+
+fn map_array(
+    data_type: &DataType,
+    lengths: Vec<usize>,
+    keys: ArrayRef,
+    values: ArrayRef,
+) -> Result<ArrayRef> {
+    let DataType::Map(entries_field, ordered) = data_type else {
+        return Err(unexpected_type(data_type));
+    };
+    let entries = StructArray::try_new(
+        struct_fields(entries_field.data_type())?.clone(),
+        vec![keys, values],
+        None,
+    )?;
+    Ok(Arc::new(MapArray::try_new(
+        entries_field.clone(),
+        OffsetBuffer::from_lengths(lengths),
+        entries,
+        None,
+        *ordered,
+    )?))
+}
+
+/// Builds a list array of `values`, `lengths` giving the length of each list
+/// or `None` for a null list.
+fn list_array(
+    data_type: &DataType,
+    lengths: impl Iterator<Item = Option<usize>>,
+    values: ArrayRef,
+) -> Result<ArrayRef> {
+    let DataType::List(field) = data_type else {
+        return Err(unexpected_type(data_type));
+    };
+    let lengths = lengths.collect::<Vec<_>>();
+    let nulls =
+        NullBuffer::from(lengths.iter().map(Option::is_some).collect::<Vec<_>>());
+    Ok(Arc::new(ListArray::try_new(
+        field.clone(),
+        OffsetBuffer::from_lengths(
+            lengths.into_iter().map(Option::unwrap_or_default),
+        ),
+        values,
+        Some(nulls),
+    )?))
+}
+
+fn unexpected_type(data_type: &DataType) -> Error {
+    Error::new(
+        ErrorKind::Unexpected,
+        format!("Unexpected metadata column type {data_type}"),
+    )
+}
@@ -0,0 +1,210 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+
+use arrow_array::{ArrayRef, Int32Array, Int64Array, RecordBatch, StructArray};
+use arrow_schema::DataType;
+
+use super::data_file::{
+    current_entries, data_file_arrays, data_file_fields, partition_type,
+    readable_metrics_array, readable_metrics_field,
+};
+use crate::arrow::schema_to_arrow_schema;
+use crate::scan::ArrowRecordBatchIterator;
+use crate::spec::{ManifestEntryRef, NestedField, PrimitiveType, StructType, Type};
+use crate::table::Table;
+use crate::{Error, ErrorKind, Result};
+
+/// Entries table: the manifest entries of the current snapshot, deleted
+/// entries included.
+pub struct EntriesTable<'a> {
+    table: &'a Table,
+}
+
+impl<'a> EntriesTable<'a> {
+    /// Create a new Entries table instance.
+    pub fn new(table: &'a Table) -> Self {
+        Self { table }
+    }
+
+    /// Returns the iceberg schema of the entries table.
+    pub fn schema(&self) -> Result<crate::spec::Schema> {
+        entries_schema(self.table)
+    }
+
+    /// Scans the entries table.
+    pub fn scan(&self) -> Result<ArrowRecordBatchIterator> {
+        entries_scan(self.table, &current_entries(self.table)?)
+    }
+}
+
+/// Returns the iceberg schema of the tables that list manifest entries.
+pub(super) fn entries_schema(table: &Table) -> Result<crate::spec::Schema> {
+    let metadata = table.metadata();
+    let partition_type = partition_type(metadata)?;
+    let fields = vec![
+        Arc::new(NestedField::required(
+            0,
+            "status",
+            Type::Primitive(PrimitiveType::Int),
+        )),
+        Arc::new(NestedField::optional(
+            1,
+            "snapshot_id",
+            Type::Primitive(PrimitiveType::Long),
+        )),
+        Arc::new(NestedField::optional(
+            3,
+            "sequence_number",
+            Type::Primitive(PrimitiveType::Long),
+        )),
+        Arc::new(NestedField::optional(
+            4,
+            "file_sequence_number",
+            Type::Primitive(PrimitiveType::Long),
+        )),
+        Arc::new(NestedField::required(
+            2,
+            "data_file",
+            Type::Struct(StructType::new(data_file_fields(&partition_type))),
+        )),
+        readable_metrics_field(metadata.current_schema(), &partition_type),
+    ];
+    crate::spec::Schema::builder().with_fields(fields).build()
+}
+
+/// Scans `entries`, deleted entries included.
+pub(super) fn entries_scan(
+    table: &Table,
+    entries: &[ManifestEntryRef],
+) -> Result<ArrowRecordBatchIterator> {
+    let metadata = table.metadata();
+    let partition_type = partition_type(metadata)?;
+    let schema = schema_to_arrow_schema(&entries_schema(table)?)?;
+
+    let files = entries
+        .iter()
+        .map(|entry| &entry.data_file)
+        .collect::<Vec<_>>();
+
+    let DataType::Struct(file_fields) =
+        schema.field_with_name("data_file")?.data_type()
+    else {
+        return Err(Error::new(
+            ErrorKind::Unexpected,
+            "data_file column of the entries table is not a struct",
+        ));
+    };
+    let data_file = StructArray::try_new(
+        file_fields.clone(),
+        data_file_arrays(metadata, &partition_type, &files, file_fields)?,
+        None,
+    )?;
+    let readable_metrics = readable_metrics_array(
+        metadata.current_schema(),
+        &files,
+        schema.field_with_name("readable_metrics")?.data_type(),
+    )?;
+
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(Int32Array::from_iter_values(
+            entries.iter().map(|entry| entry.status as i32),
+        )),
+        Arc::new(Int64Array::from(
+            entries
+                .iter()
+                .map(|entry| entry.snapshot_id)
+                .collect::<Vec<_>>(),
+        )),
+        Arc::new(Int64Array::from(
+            entries
+                .iter()
+                .map(|entry| entry.sequence_number)
+                .collect::<Vec<_>>(),
+        )),
+        Arc::new(Int64Array::from(
+            entries
+                .iter()
+                .map(|entry| entry.file_sequence_number)
+                .collect::<Vec<_>>(),
+        )),
+        Arc::new(data_file),
+        readable_metrics,
+    ];
+    let batch = RecordBatch::try_new(Arc::new(schema), columns)?;
+    Ok(Box::new(vec![Ok(batch)].into_iter()))
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::{Int32Type, Int64Type};
+
+    use crate::scan::tests::TableTestFixture;
+
+    #[test]
+    fn test_entries_table() {
+        let mut fixture = TableTestFixture::new();
+        fixture.setup_manifest_files();
+
+        let batch = fixture
+            .table
+            .inspect()
+            .entries()
+            .scan()
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+
+        let status = batch
+            .column_by_name("status")
+            .unwrap()
+            .as_primitive::<Int32Type>();
+        let snapshot_id = batch
+            .column_by_name("snapshot_id")
+            .unwrap()
+            .as_primitive::<Int64Type>();
+        let data_file = batch.column_by_name("data_file").unwrap().as_struct();
+        let file_paths = data_file
+            .column_by_name("file_path")
+            .unwrap()
+            .as_string::<i32>();
+
+        // Deleted entries are included with the id of the deleting snapshot, snapshot ids
+        // are inherited.
+        let mut entries = file_paths
+            .iter()
+            .map(|path| path.unwrap().rsplit('/').next().unwrap())
+            .zip(status.values().iter().copied())
+            .zip(snapshot_id.iter().map(Option::unwrap))
+            .collect::<Vec<_>>();
+        entries.sort();
+        assert_eq!(
+            entries,
+            [
+                (("1.parquet", 1), 3055729675574597004),
+                (("2.parquet", 2), 3055729675574597004),
+                (("3.parquet", 0), 3051729675574597004),
+            ]
+        );
+
+        assert!(data_file.column_by_name("partition").is_some());
+        assert!(batch.column_by_name("readable_metrics").is_some());
+    }
+}
@@ -0,0 +1,270 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+
+use arrow_array::RecordBatch;
+
+use super::data_file::{
+    current_entries, data_file_arrays, data_file_fields, partition_type,
+    readable_metrics_array, readable_metrics_field,
+};
+use crate::Result;
+use crate::arrow::schema_to_arrow_schema;
+use crate::scan::ArrowRecordBatchIterator;
+use crate::spec::{DataContentType, ManifestEntryRef};
+use crate::table::Table;
+
+/// The files a [`FilesTable`] or an [`AllFilesTable`](super::AllFilesTable) lists.
+#[derive(Debug, Clone, Copy)]
+pub(super) enum FileContent {
+    All,
+    Data,
+    Deletes,
+}
+
+/// Files table: the live data and delete files of the current snapshot.
+pub struct FilesTable<'a> {
+    table: &'a Table,
+    content: FileContent,
+}
+
+impl<'a> FilesTable<'a> {
+    /// Create a new Files table instance, listing data and delete files.
+    pub fn new(table: &'a Table) -> Self {
+        Self {
+            table,
+            content: FileContent::All,
+        }
+    }
+
+    /// Create a new Files table instance, listing data files only.
+    pub fn data_files(table: &'a Table) -> Self {
+        Self {
+            table,
+            content: FileContent::Data,
+        }
+    }
+
+    /// Create a new Files table instance, listing delete files only.
+    pub fn delete_files(table: &'a Table) -> Self {
+        Self {
+            table,
+            content: FileContent::Deletes,
+        }
+    }
+
+    /// Returns the iceberg schema of the files table.
+    ///
+    /// The `partition` column is left out for unpartitioned tables.
+    pub fn schema(&self) -> Result<crate::spec::Schema> {
+        files_schema(self.table)
+    }
+
+    /// Scans the files table.
+    pub fn scan(&self) -> Result<ArrowRecordBatchIterator> {
+        files_scan(self.table, &current_entries(self.table)?, self.content)
+    }
+}
+
+/// Returns the iceberg schema of the tables that list data and delete files.
+pub(super) fn files_schema(table: &Table) -> Result<crate::spec::Schema> {
+    let metadata = table.metadata();
+    let partition_type = partition_type(metadata)?;
+    let mut fields = data_file_fields(&partition_type);
+    fields.push(readable_metrics_field(
+        metadata.current_schema(),
+        &partition_type,
+    ));
+    crate::spec::Schema::builder().with_fields(fields).build()
+}
+
+/// Scans the live files of `entries` that hold `content`.
+pub(super) fn files_scan(
+    table: &Table,
+    entries: &[ManifestEntryRef],
+    content: FileContent,
+) -> Result<ArrowRecordBatchIterator> {
+    let metadata = table.metadata();
+    let partition_type = partition_type(metadata)?;
+    let schema = schema_to_arrow_schema(&files_schema(table)?)?;
+
+    let files = entries
+        .iter()
+        .filter(|entry| entry.is_alive())
+        .map(|entry| &entry.data_file)
+        .filter(|file| match content {
+            FileContent::All => true,
+            FileContent::Data => file.content_type() == DataContentType::Data,
+            FileContent::Deletes => file.content_type() != DataContentType::Data,
+        })
+        .collect::<Vec<_>>();
+
+    let (readable_metrics, file_fields) =
+        schema.fields().split_last().expect("readable_metrics");
+    let mut columns =
+        data_file_arrays(metadata, &partition_type, &files, file_fields)?;
+    columns.push(readable_metrics_array(
+        metadata.current_schema(),
+        &files,
+        readable_metrics.data_type(),
+    )?);
+
+    let batch = RecordBatch::try_new(Arc::new(schema), columns)?;
+    Ok(Box::new(vec![Ok(batch)].into_iter()))
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::{Int32Type, Int64Type};
+    use arrow_schema::DataType;
+
+    use crate::scan::tests::TableTestFixture;
+
+    fn file_name(path: Option<&str>) -> &str {
+        path.unwrap().rsplit('/').next().unwrap()
+    }
+
+    #[test]
+    fn test_files_table() {
+        let mut fixture = TableTestFixture::new();
+        fixture.setup_manifest_files();
+
+        let batch = fixture
+            .table
+            .inspect()
+            .files()
+            .scan()
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+
+        let columns = batch
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| field.name().clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            columns,
+            [
+                "content",
+                "file_path",
+                "file_format",
+                "spec_id",
+                "partition",
+                "record_count",
+                "file_size_in_bytes",
+                "column_sizes",
+                "value_counts",
+                "null_value_counts",
+                "nan_value_counts",
+                "lower_bounds",
+                "upper_bounds",
+                "key_metadata",
+                "split_offsets",
+                "equality_ids",
+                "sort_order_id",
+                "readable_metrics",
+            ]
+        );
+
+        // The deleted file is left out.
+        let file_paths = batch
+            .column_by_name("file_path")
+            .unwrap()
+            .as_string::<i32>();
+        let partitions = batch.column_by_name("partition").unwrap().as_struct();
+        let x = partitions
+            .column_by_name("x")
+            .unwrap()
+            .as_primitive::<Int64Type>();
+        let mut files = file_paths
+            .iter()
+            .map(file_name)
+            .zip(x.values().iter().copied())
+            .collect::<Vec<_>>();
+        files.sort();
+        assert_eq!(files, [("1.parquet", 100), ("3.parquet", 300)]);
+
+        let content = batch.column_by_name("content").unwrap();
+        assert!(
+            content
+                .as_primitive::<Int32Type>()
+                .values()
+                .iter()
+                .all(|c| *c == 0)
+        );
+        let file_formats = batch.column_by_name("file_format").unwrap();
+        assert!(
+            file_formats
+                .as_string::<i32>()
+                .iter()
+                .all(|format| format == Some("PARQUET"))
+        );
+
+        // Bounds are read in the type of their column.
+        let readable_metrics = batch
+            .column_by_name("readable_metrics")
+            .unwrap()
+            .as_struct();
+        assert_eq!(
+            readable_metrics.column_names(),
+            ["a", "bool", "dbl", "i32", "i64", "x", "y", "z"]
+        );
+        let dbl = readable_metrics.column_by_name("dbl").unwrap().as_struct();
+        assert_eq!(
+            dbl.column_by_name("lower_bound").unwrap().data_type(),
+            &DataType::Float64
+        );
+        assert_eq!(dbl.column_by_name("value_count").unwrap().null_count(), 2);
+    }
+
+    #[test]
+    fn test_data_and_delete_files_tables() {
+        let mut fixture = TableTestFixture::new();
+        fixture.setup_manifest_files();
+        let inspect = fixture.table.inspect();
+
+        let data_files = inspect.data_files().scan().unwrap().next().unwrap();
+        assert_eq!(data_files.unwrap().num_rows(), 2);
+
+        let delete_files = inspect.delete_files().scan().unwrap().next().unwrap();
+        assert_eq!(delete_files.unwrap().num_rows(), 0);
+    }
+
+    #[test]
+    fn test_files_table_unpartitioned() {
+        let mut fixture = TableTestFixture::new_unpartitioned();
+        fixture.setup_unpartitioned_manifest_files();
+
+        let batch = fixture
+            .table
+            .inspect()
+            .files()
+            .scan()
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+
+        assert!(batch.column_by_name("partition").is_none());
+        assert_eq!(batch.num_rows(), 2);
+    }
+}
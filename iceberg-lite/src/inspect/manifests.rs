@@ -18,19 +18,20 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use arrow_array::RecordBatch;
 use arrow_array::builder::{
     BooleanBuilder, GenericListBuilder, ListBuilder, PrimitiveBuilder, StringBuilder,
     StructBuilder,
 };
 use arrow_array::types::{Int32Type, Int64Type};
+use arrow_array::{ArrayRef, RecordBatch};
 use arrow_schema::{DataType, Field, Fields};
 
 use crate::Result;
 use crate::arrow::schema_to_arrow_schema;
 use crate::scan::ArrowRecordBatchIterator;
 use crate::spec::{
-    Datum, FieldSummary, ListType, NestedField, PrimitiveType, StructType, Type,
+    Datum, FieldSummary, ListType, ManifestContentType, ManifestFile, NestedField,
+    PrimitiveType, StructType, Type,
 };
 use crate::table::Table;
 
@@ -154,6 +155,26 @@ impl<'a> ManifestsTable<'a> {
     pub fn scan(&self) -> Result<ArrowRecordBatchIterator> {
         let schema = schema_to_arrow_schema(&self.schema())?;
 
+        let manifest_list = match self.table.metadata().current_snapshot() {
+            Some(snapshot) => Some(snapshot.load_manifest_list(
+                self.table.file_io(),
+                &self.table.metadata_ref(),
+            )?),
+            None => None,
+        };
+        let manifests = manifest_list
+            .iter()
+            .flat_map(|manifest_list| manifest_list.entries());
+
+        let batch = RecordBatch::try_new(Arc::new(schema), self.columns(manifests)?)?;
+        Ok(Box::new(vec![Ok(batch)].into_iter()))
+    }
+
+    /// Returns the columns of the manifests table for `manifests`.
+    pub(super) fn columns<'m>(
+        &self,
+        manifests: impl IntoIterator<Item = &'m ManifestFile>,
+    ) -> Result<Vec<ArrayRef>> {
         let mut content = PrimitiveBuilder::<Int32Type>::new();
         let mut path = StringBuilder::new();
         let mut length = PrimitiveBuilder::<Int64Type>::new();
@@ -167,64 +188,61 @@ impl<'a> ManifestsTable<'a> {
         let mut deleted_delete_files_count = PrimitiveBuilder::<Int32Type>::new();
         let mut partition_summaries = self.partition_summary_builder()?;
 
-        if let Some(snapshot) = self.table.metadata().current_snapshot() {
-            let manifest_list = snapshot.load_manifest_list(
-                self.table.file_io(),
-                &self.table.metadata_ref(),
-            )?;
-            for manifest in manifest_list.entries() {
-                content.append_value(manifest.content as i32);
-                path.append_value(manifest.manifest_path.clone());
-                length.append_value(manifest.manifest_length);
-                partition_spec_id.append_value(manifest.partition_spec_id);
-                added_snapshot_id.append_value(manifest.added_snapshot_id);
-                added_data_files_count
-                    .append_value(manifest.added_files_count.unwrap_or(0) as i32);
-                existing_data_files_count
-                    .append_value(manifest.existing_files_count.unwrap_or(0) as i32);
-                deleted_data_files_count
-                    .append_value(manifest.deleted_files_count.unwrap_or(0) as i32);
-                added_delete_files_count
-                    .append_value(manifest.added_files_count.unwrap_or(0) as i32);
-                existing_delete_files_count
-                    .append_value(manifest.existing_files_count.unwrap_or(0) as i32);
-                deleted_delete_files_count
-                    .append_value(manifest.deleted_files_count.unwrap_or(0) as i32);
+        for manifest in manifests {
+            content.append_value(manifest.content as i32);
+            path.append_value(manifest.manifest_path.clone());
+            length.append_value(manifest.manifest_length);
+            partition_spec_id.append_value(manifest.partition_spec_id);
+            added_snapshot_id.append_value(manifest.added_snapshot_id);
 
-                let spec = self
-                    .table
-                    .metadata()
-                    .partition_spec_by_id(manifest.partition_spec_id)
-                    .unwrap();
-                let spec_struct = spec
-                    .partition_type(self.table.metadata().current_schema())
-                    .unwrap();
-                self.append_partition_summaries(
-                    &mut partition_summaries,
-                    &manifest.partitions.clone().unwrap_or_else(Vec::new),
-                    spec_struct,
-                );
-            }
+            // A manifest tracks either data or delete files, the counts of the
+            // other kind are zero.
+            let counts = [
+                manifest.added_files_count.unwrap_or(0) as i32,
+                manifest.existing_files_count.unwrap_or(0) as i32,
+                manifest.deleted_files_count.unwrap_or(0) as i32,
+            ];
+            let (data_counts, delete_counts) = match manifest.content {
+                ManifestContentType::Data => (counts, [0; 3]),
+                ManifestContentType::Deletes => ([0; 3], counts),
+            };
+            added_data_files_count.append_value(data_counts[0]);
+            existing_data_files_count.append_value(data_counts[1]);
+            deleted_data_files_count.append_value(data_counts[2]);
+            added_delete_files_count.append_value(delete_counts[0]);
+            existing_delete_files_count.append_value(delete_counts[1]);
+            deleted_delete_files_count.append_value(delete_counts[2]);
+
+            let spec = self
+                .table
+                .metadata()
+                .partition_spec_by_id(manifest.partition_spec_id)
+                .unwrap();
+            let spec_struct = spec
+                .partition_type(self.table.metadata().current_schema())
+                .unwrap();
+            self.append_partition_summaries(
+                &mut partition_summaries,
+                &manifest.partitions.clone().unwrap_or_else(Vec::new),
+                spec_struct,
+            );
         }
 
-        let batch = RecordBatch::try_new(
-            Arc::new(schema),
-            vec![
-                Arc::new(content.finish()),
-                Arc::new(path.finish()),
-                Arc::new(length.finish()),
-                Arc::new(partition_spec_id.finish()),
-                Arc::new(added_snapshot_id.finish()),
-                Arc::new(added_data_files_count.finish()),
-                Arc::new(existing_data_files_count.finish()),
-                Arc::new(deleted_data_files_count.finish()),
-                Arc::new(added_delete_files_count.finish()),
-                Arc::new(existing_delete_files_count.finish()),
-                Arc::new(deleted_delete_files_count.finish()),
-                Arc::new(partition_summaries.finish()),
-            ],
-        )?;
-        Ok(Box::new(vec![Ok(batch)].into_iter()))
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(content.finish()),
+            Arc::new(path.finish()),
+            Arc::new(length.finish()),
+            Arc::new(partition_spec_id.finish()),
+            Arc::new(added_snapshot_id.finish()),
+            Arc::new(added_data_files_count.finish()),
+            Arc::new(existing_data_files_count.finish()),
+            Arc::new(deleted_data_files_count.finish()),
+            Arc::new(added_delete_files_count.finish()),
+            Arc::new(existing_delete_files_count.finish()),
+            Arc::new(deleted_delete_files_count.finish()),
+            Arc::new(partition_summaries.finish()),
+        ];
+        Ok(columns)
     }
 
     fn partition_summary_builder(
@@ -357,15 +375,15 @@ mod tests {
                 ],
                 added_delete_files_count: PrimitiveArray<Int32>
                 [
-                  1,
+                  0,
                 ],
                 existing_delete_files_count: PrimitiveArray<Int32>
                 [
-                  1,
+                  0,
                 ],
                 deleted_delete_files_count: PrimitiveArray<Int32>
                 [
-                  1,
+                  0,
                 ],
                 partition_summaries: ListArray
                 [
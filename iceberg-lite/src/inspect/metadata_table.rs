@@ -15,7 +15,11 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use super::{ManifestsTable, SnapshotsTable};
+use super::{
+    AllEntriesTable, AllFilesTable, AllManifestsTable, EntriesTable, FilesTable,
+    HistoryTable, ManifestsTable, MetadataLogEntriesTable, PartitionsTable,
+    RefsTable, SnapshotsTable,
+};
 use crate::table::Table;
 
 /// Metadata table is used to inspect a table's history, snapshots, and other metadata as a table.
@@ -34,6 +38,32 @@ pub enum MetadataTableType {
     Snapshots,
     /// [`ManifestsTable`]
     Manifests,
+    /// [`FilesTable`]
+    Files,
+    /// [`FilesTable`] of data files
+    DataFiles,
+    /// [`FilesTable`] of delete files
+    DeleteFiles,
+    /// [`EntriesTable`]
+    Entries,
+    /// [`HistoryTable`]
+    History,
+    /// [`RefsTable`]
+    Refs,
+    /// [`PartitionsTable`]
+    Partitions,
+    /// [`MetadataLogEntriesTable`]
+    MetadataLogEntries,
+    /// [`AllManifestsTable`]
+    AllManifests,
+    /// [`AllFilesTable`]
+    AllFiles,
+    /// [`AllFilesTable`] of data files
+    AllDataFiles,
+    /// [`AllFilesTable`] of delete files
+    AllDeleteFiles,
+    /// [`AllEntriesTable`]
+    AllEntries,
 }
 
 impl MetadataTableType {
@@ -42,6 +72,19 @@ impl MetadataTableType {
         match self {
             MetadataTableType::Snapshots => "snapshots",
             MetadataTableType::Manifests => "manifests",
+            MetadataTableType::Files => "files",
+            MetadataTableType::DataFiles => "data_files",
+            MetadataTableType::DeleteFiles => "delete_files",
+            MetadataTableType::Entries => "entries",
+            MetadataTableType::History => "history",
+            MetadataTableType::Refs => "refs",
+            MetadataTableType::Partitions => "partitions",
+            MetadataTableType::MetadataLogEntries => "metadata_log_entries",
+            MetadataTableType::AllManifests => "all_manifests",
+            MetadataTableType::AllFiles => "all_files",
+            MetadataTableType::AllDataFiles => "all_data_files",
+            MetadataTableType::AllDeleteFiles => "all_delete_files",
+            MetadataTableType::AllEntries => "all_entries",
         }
     }
 
@@ -59,6 +102,19 @@ impl TryFrom<&str> for MetadataTableType {
         match value {
             "snapshots" => Ok(Self::Snapshots),
             "manifests" => Ok(Self::Manifests),
+            "files" => Ok(Self::Files),
+            "data_files" => Ok(Self::DataFiles),
+            "delete_files" => Ok(Self::DeleteFiles),
+            "entries" => Ok(Self::Entries),
+            "history" => Ok(Self::History),
+            "refs" => Ok(Self::Refs),
+            "partitions" => Ok(Self::Partitions),
+            "metadata_log_entries" => Ok(Self::MetadataLogEntries),
+            "all_manifests" => Ok(Self::AllManifests),
+            "all_files" => Ok(Self::AllFiles),
+            "all_data_files" => Ok(Self::AllDataFiles),
+            "all_delete_files" => Ok(Self::AllDeleteFiles),
+            "all_entries" => Ok(Self::AllEntries),
             _ => Err(format!("invalid metadata table type: {value}")),
         }
     }
@@ -79,4 +135,68 @@ impl<'a> MetadataTable<'a> {
     pub fn manifests(&self) -> ManifestsTable<'_> {
         ManifestsTable::new(self.0)
     }
+    /// Get the files table.
+    pub fn files(&self) -> FilesTable<'_> {
+        FilesTable::new(self.0)
+    }
+
+    /// Get the data files table.
+    pub fn data_files(&self) -> FilesTable<'_> {
+        FilesTable::data_files(self.0)
+    }
+
+    /// Get the delete files table.
+    pub fn delete_files(&self) -> FilesTable<'_> {
+        FilesTable::delete_files(self.0)
+    }
+
+    /// Get the entries table.
+    pub fn entries(&self) -> EntriesTable<'_> {
+        EntriesTable::new(self.0)
+    }
+
+    /// Get the history table.
+    pub fn history(&self) -> HistoryTable<'_> {
+        HistoryTable::new(self.0)
+    }
+
+    /// Get the refs table.
+    pub fn refs(&self) -> RefsTable<'_> {
+        RefsTable::new(self.0)
+    }
+
+    /// Get the partitions table.
+    pub fn partitions(&self) -> PartitionsTable<'_> {
+        PartitionsTable::new(self.0)
+    }
+
+    /// Get the metadata log entries table.
+    pub fn metadata_log_entries(&self) -> MetadataLogEntriesTable<'_> {
+        MetadataLogEntriesTable::new(self.0)
+    }
+
+    /// Get the all manifests table.
+    pub fn all_manifests(&self) -> AllManifestsTable<'_> {
+        AllManifestsTable::new(self.0)
+    }
+
+    /// Get the all files table.
+    pub fn all_files(&self) -> AllFilesTable<'_> {
+        AllFilesTable::new(self.0)
+    }
+
+    /// Get the all data files table.
+    pub fn all_data_files(&self) -> AllFilesTable<'_> {
+        AllFilesTable::data_files(self.0)
+    }
+
+    /// Get the all delete files table.
+    pub fn all_delete_files(&self) -> AllFilesTable<'_> {
+        AllFilesTable::delete_files(self.0)
+    }
+
+    /// Get the all entries table.
+    pub fn all_entries(&self) -> AllEntriesTable<'_> {
+        AllEntriesTable::new(self.0)
+    }
 }
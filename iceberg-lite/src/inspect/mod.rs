@@ -17,10 +17,29 @@
 
 //! Metadata table APIs.
 
+mod all_entries;
+mod all_files;
+mod all_manifests;
+mod data_file;
+mod entries;
+mod files;
+mod history;
 mod manifests;
+mod metadata_log_entries;
 mod metadata_table;
+mod partitions;
+mod refs;
 mod snapshots;
 
+pub use all_entries::AllEntriesTable;
+pub use all_files::AllFilesTable;
+pub use all_manifests::AllManifestsTable;
+pub use entries::EntriesTable;
+pub use files::FilesTable;
+pub use history::HistoryTable;
 pub use manifests::ManifestsTable;
+pub use metadata_log_entries::MetadataLogEntriesTable;
 pub use metadata_table::*;
+pub use partitions::PartitionsTable;
+pub use refs::RefsTable;
 pub use snapshots::SnapshotsTable;
@@ -0,0 +1,334 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use arrow_array::builder::{PrimitiveBuilder, TimestampMicrosecondBuilder};
+use arrow_array::types::{Int32Type, Int64Type};
+use arrow_array::{ArrayRef, RecordBatch};
+
+use super::data_file::{
+    current_entries, partition_array, partition_type, partition_values,
+};
+use crate::Result;
+use crate::arrow::schema_to_arrow_schema;
+use crate::scan::ArrowRecordBatchIterator;
+use crate::spec::{
+    DataContentType, NestedField, PrimitiveLiteral, PrimitiveType, Type,
+};
+use crate::table::Table;
+
+/// Partitions table: the live files of the current snapshot, aggregated by
+/// partition.
+///
+/// Unpartitioned tables have neither a `partition` nor a `spec_id` column, and
+/// a single row once they have files.
+pub struct PartitionsTable<'a> {
+    table: &'a Table,
+}
+
+/// The aggregated files of a partition.
+#[derive(Default)]
+struct PartitionStats {
+    spec_id: i32,
+    record_count: i64,
+    file_count: i32,
+    total_data_file_size_in_bytes: i64,
+    position_delete_record_count: i64,
+    position_delete_file_count: i32,
+    equality_delete_record_count: i64,
+    equality_delete_file_count: i32,
+    /// Commit timestamp and id of the latest snapshot that added a file.
+    last_updated: Option<(i64, i64)>,
+}
+
+impl<'a> PartitionsTable<'a> {
+    /// Create a new Partitions table instance.
+    pub fn new(table: &'a Table) -> Self {
+        Self { table }
+    }
+
+    /// Returns the iceberg schema of the partitions table.
+    pub fn schema(&self) -> Result<crate::spec::Schema> {
+        let partition_type = partition_type(self.table.metadata())?;
+        let mut fields = vec![];
+        if !partition_type.fields().is_empty() {
+            fields.extend([
+                NestedField::required(1, "partition", Type::Struct(partition_type)),
+                NestedField::required(
+                    4,
+                    "spec_id",
+                    Type::Primitive(PrimitiveType::Int),
+                ),
+            ]);
+        }
+        fields.extend([
+            NestedField::required(
+                2,
+                "record_count",
+                Type::Primitive(PrimitiveType::Long),
+            ),
+            NestedField::required(
+                3,
+                "file_count",
+                Type::Primitive(PrimitiveType::Int),
+            ),
+            NestedField::required(
+                11,
+                "total_data_file_size_in_bytes",
+                Type::Primitive(PrimitiveType::Long),
+            ),
+            NestedField::required(
+                5,
+                "position_delete_record_count",
+                Type::Primitive(PrimitiveType::Long),
+            ),
+            NestedField::required(
+                6,
+                "position_delete_file_count",
+                Type::Primitive(PrimitiveType::Int),
+            ),
+            NestedField::required(
+                7,
+                "equality_delete_record_count",
+                Type::Primitive(PrimitiveType::Long),
+            ),
+            NestedField::required(
+                8,
+                "equality_delete_file_count",
+                Type::Primitive(PrimitiveType::Int),
+            ),
+            NestedField::optional(
+                9,
+                "last_updated_at",
+                Type::Primitive(PrimitiveType::Timestamptz),
+            ),
+            NestedField::optional(
+                10,
+                "last_updated_snapshot_id",
+                Type::Primitive(PrimitiveType::Long),
+            ),
+        ]);
+        crate::spec::Schema::builder()
+            .with_fields(fields.into_iter().map(|f| f.into()))
+            .build()
+    }
+
+    /// Scans the partitions table.
+    pub fn scan(&self) -> Result<ArrowRecordBatchIterator> {
+        let metadata = self.table.metadata();
+        let partition_type = partition_type(metadata)?;
+        let schema = schema_to_arrow_schema(&self.schema()?)?;
+
+        // Partitions in the order their first file was listed.
+        let mut partitions: Vec<Vec<Option<PrimitiveLiteral>>> = vec![];
+        let mut stats: HashMap<Vec<Option<PrimitiveLiteral>>, PartitionStats> =
+            HashMap::new();
+        for entry in current_entries(self.table)? {
+            if !entry.is_alive() {
+                continue;
+            }
+            let file = &entry.data_file;
+            let partition = partition_values(metadata, &partition_type, file);
+            let partition_stats =
+                stats.entry(partition.clone()).or_insert_with(|| {
+                    partitions.push(partition);
+                    PartitionStats::default()
+                });
+
+            partition_stats.spec_id = file.partition_spec_id;
+            let record_count = file.record_count() as i64;
+            match file.content_type() {
+                DataContentType::Data => {
+                    partition_stats.record_count += record_count;
+                    partition_stats.file_count += 1;
+                    partition_stats.total_data_file_size_in_bytes +=
+                        file.file_size_in_bytes() as i64;
+                }
+                DataContentType::PositionDeletes => {
+                    partition_stats.position_delete_record_count += record_count;
+                    partition_stats.position_delete_file_count += 1;
+                }
+                DataContentType::EqualityDeletes => {
+                    partition_stats.equality_delete_record_count += record_count;
+                    partition_stats.equality_delete_file_count += 1;
+                }
+            }
+
+            let snapshot = entry
+                .snapshot_id
+                .and_then(|snapshot_id| metadata.snapshot_by_id(snapshot_id));
+            if let Some(snapshot) = snapshot
+                && partition_stats
+                    .last_updated
+                    .is_none_or(|(timestamp_ms, _)| {
+                        timestamp_ms < snapshot.timestamp_ms()
+                    })
+            {
+                partition_stats.last_updated =
+                    Some((snapshot.timestamp_ms(), snapshot.snapshot_id()));
+            }
+        }
+
+        let mut spec_id = PrimitiveBuilder::<Int32Type>::new();
+        let mut record_count = PrimitiveBuilder::<Int64Type>::new();
+        let mut file_count = PrimitiveBuilder::<Int32Type>::new();
+        let mut total_data_file_size_in_bytes = PrimitiveBuilder::<Int64Type>::new();
+        let mut position_delete_record_count = PrimitiveBuilder::<Int64Type>::new();
+        let mut position_delete_file_count = PrimitiveBuilder::<Int32Type>::new();
+        let mut equality_delete_record_count = PrimitiveBuilder::<Int64Type>::new();
+        let mut equality_delete_file_count = PrimitiveBuilder::<Int32Type>::new();
+        let mut last_updated_at =
+            TimestampMicrosecondBuilder::new().with_timezone("+00:00");
+        let mut last_updated_snapshot_id = PrimitiveBuilder::<Int64Type>::new();
+        for partition in &partitions {
+            let stats = &stats[partition];
+            spec_id.append_value(stats.spec_id);
+            record_count.append_value(stats.record_count);
+            file_count.append_value(stats.file_count);
+            total_data_file_size_in_bytes
+                .append_value(stats.total_data_file_size_in_bytes);
+            position_delete_record_count
+                .append_value(stats.position_delete_record_count);
+            position_delete_file_count.append_value(stats.position_delete_file_count);
+            equality_delete_record_count
+                .append_value(stats.equality_delete_record_count);
+            equality_delete_file_count.append_value(stats.equality_delete_file_count);
+            last_updated_at.append_option(
+                stats
+                    .last_updated
+                    .map(|(timestamp_ms, _)| timestamp_ms * 1000),
+            );
+            last_updated_snapshot_id.append_option(
+                stats.last_updated.map(|(_, snapshot_id)| snapshot_id),
+            );
+        }
+
+        let mut columns: Vec<ArrayRef> = vec![];
+        if !partition_type.fields().is_empty() {
+            columns.push(partition_array(
+                schema.field_with_name("partition")?.data_type(),
+                &partitions,
+            )?);
+            columns.push(Arc::new(spec_id.finish()));
+        }
+        columns.extend([
+            Arc::new(record_count.finish()) as ArrayRef,
+            Arc::new(file_count.finish()),
+            Arc::new(total_data_file_size_in_bytes.finish()),
+            Arc::new(position_delete_record_count.finish()),
+            Arc::new(position_delete_file_count.finish()),
+            Arc::new(equality_delete_record_count.finish()),
+            Arc::new(equality_delete_file_count.finish()),
+            Arc::new(last_updated_at.finish()),
+            Arc::new(last_updated_snapshot_id.finish()),
+        ]);
+
+        let batch = RecordBatch::try_new(Arc::new(schema), columns)?;
+        Ok(Box::new(vec![Ok(batch)].into_iter()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::{Int32Type, Int64Type};
+
+    use crate::scan::tests::TableTestFixture;
+
+    #[test]
+    fn test_partitions_table() {
+        let mut fixture = TableTestFixture::new();
+        fixture.setup_manifest_files();
+
+        let batch = fixture
+            .table
+            .inspect()
+            .partitions()
+            .scan()
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+
+        let partition = batch.column_by_name("partition").unwrap().as_struct();
+        let x = partition.column_by_name("x").unwrap();
+        let int64 = |name| {
+            batch
+                .column_by_name(name)
+                .unwrap()
+                .as_primitive::<Int64Type>()
+        };
+        let record_count = int64("record_count");
+        let last_updated_snapshot_id = int64("last_updated_snapshot_id");
+        let file_count = batch
+            .column_by_name("file_count")
+            .unwrap()
+            .as_primitive::<Int32Type>();
+
+        let mut partitions = (0..batch.num_rows())
+            .map(|row| {
+                (
+                    x.as_primitive::<Int64Type>().value(row),
+                    record_count.value(row),
+                    file_count.value(row),
+                    last_updated_snapshot_id.value(row),
+                )
+            })
+            .collect::<Vec<_>>();
+        partitions.sort();
+        // The deleted file is not counted.
+        assert_eq!(
+            partitions,
+            [
+                (100, 1, 1, 3055729675574597004),
+                (300, 1, 1, 3051729675574597004),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_partitions_table_unpartitioned() {
+        let mut fixture = TableTestFixture::new_unpartitioned();
+        fixture.setup_unpartitioned_manifest_files();
+
+        let batch = fixture
+            .table
+            .inspect()
+            .partitions()
+            .scan()
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+
+        assert!(batch.column_by_name("partition").is_none());
+        assert!(batch.column_by_name("spec_id").is_none());
+        assert_eq!(batch.num_rows(), 1);
+        let int64 = |name| {
+            batch
+                .column_by_name(name)
+                .unwrap()
+                .as_primitive::<Int64Type>()
+                .value(0)
+        };
+        assert_eq!(int64("record_count"), 2);
+        assert_eq!(int64("total_data_file_size_in_bytes"), 200);
+        assert_eq!(int64("last_updated_snapshot_id"), 3055729675574597004);
+    }
+}
@@ -0,0 +1,182 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+
+use arrow_array::RecordBatch;
+use arrow_array::builder::{PrimitiveBuilder, StringBuilder};
+use arrow_array::types::{Int32Type, Int64Type};
+
+use crate::Result;
+use crate::arrow::schema_to_arrow_schema;
+use crate::scan::ArrowRecordBatchIterator;
+use crate::spec::{NestedField, PrimitiveType, SnapshotRetention, Type};
+use crate::table::Table;
+
+/// Refs table: the branches and tags of the table, ordered by name.
+pub struct RefsTable<'a> {
+    table: &'a Table,
+}
+
+impl<'a> RefsTable<'a> {
+    /// Create a new Refs table instance.
+    pub fn new(table: &'a Table) -> Self {
+        Self { table }
+    }
+
+    /// Returns the iceberg schema of the refs table.
+    pub fn schema(&self) -> crate::spec::Schema {
+        let fields = vec![
+            NestedField::required(1, "name", Type::Primitive(PrimitiveType::String)),
+            NestedField::required(2, "type", Type::Primitive(PrimitiveType::String)),
+            NestedField::required(
+                3,
+                "snapshot_id",
+                Type::Primitive(PrimitiveType::Long),
+            ),
+            NestedField::optional(
+                4,
+                "max_reference_age_in_ms",
+                Type::Primitive(PrimitiveType::Long),
+            ),
+            NestedField::optional(
+                5,
+                "min_snapshots_to_keep",
+                Type::Primitive(PrimitiveType::Int),
+            ),
+            NestedField::optional(
+                6,
+                "max_snapshot_age_in_ms",
+                Type::Primitive(PrimitiveType::Long),
+            ),
+        ];
+        crate::spec::Schema::builder()
+            .with_fields(fields.into_iter().map(|f| f.into()))
+            .build()
+            .unwrap()
+    }
+
+    /// Scans the refs table.
+    pub fn scan(&self) -> Result<ArrowRecordBatchIterator> {
+        let schema = schema_to_arrow_schema(&self.schema())?;
+
+        let mut name = StringBuilder::new();
+        let mut ref_type = StringBuilder::new();
+        let mut snapshot_id = PrimitiveBuilder::<Int64Type>::new();
+        let mut max_reference_age_in_ms = PrimitiveBuilder::<Int64Type>::new();
+        let mut min_snapshots_to_keep = PrimitiveBuilder::<Int32Type>::new();
+        let mut max_snapshot_age_in_ms = PrimitiveBuilder::<Int64Type>::new();
+
+        let mut refs = self.table.metadata().refs.iter().collect::<Vec<_>>();
+        refs.sort_by(|a, b| a.0.cmp(b.0));
+        for (ref_name, reference) in refs {
+            name.append_value(ref_name);
+            snapshot_id.append_value(reference.snapshot_id);
+            match &reference.retention {
+                SnapshotRetention::Branch {
+                    min_snapshots_to_keep: min_snapshots,
+                    max_snapshot_age_ms,
+                    max_ref_age_ms,
+                } => {
+                    ref_type.append_value("BRANCH");
+                    max_reference_age_in_ms.append_option(*max_ref_age_ms);
+                    min_snapshots_to_keep.append_option(*min_snapshots);
+                    max_snapshot_age_in_ms.append_option(*max_snapshot_age_ms);
+                }
+                SnapshotRetention::Tag { max_ref_age_ms } => {
+                    ref_type.append_value("TAG");
+                    max_reference_age_in_ms.append_option(*max_ref_age_ms);
+                    min_snapshots_to_keep.append_null();
+                    max_snapshot_age_in_ms.append_null();
+                }
+            }
+        }
+
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(name.finish()),
+                Arc::new(ref_type.finish()),
+                Arc::new(snapshot_id.finish()),
+                Arc::new(max_reference_age_in_ms.finish()),
+                Arc::new(min_snapshots_to_keep.finish()),
+                Arc::new(max_snapshot_age_in_ms.finish()),
+            ],
+        )?;
+        Ok(Box::new(vec![Ok(batch)].into_iter()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::Result;
+    use crate::scan::tests::TableTestFixture;
+    use crate::test_utils::check_record_batches;
+
+    #[test]
+    fn test_refs_table() {
+        let table = TableTestFixture::new().table;
+
+        let batch_iterator = table.inspect().refs().scan().unwrap();
+
+        check_record_batches(
+            batch_iterator.collect::<Result<Vec<_>>>().unwrap(),
+            expect![[r#"
+                Field { "name": Utf8, metadata: {"PARQUET:field_id": "1"} },
+                Field { "type": Utf8, metadata: {"PARQUET:field_id": "2"} },
+                Field { "snapshot_id": Int64, metadata: {"PARQUET:field_id": "3"} },
+                Field { "max_reference_age_in_ms": nullable Int64, metadata: {"PARQUET:field_id": "4"} },
+                Field { "min_snapshots_to_keep": nullable Int32, metadata: {"PARQUET:field_id": "5"} },
+                Field { "max_snapshot_age_in_ms": nullable Int64, metadata: {"PARQUET:field_id": "6"} }"#]],
+            expect![[r#"
+                name: StringArray
+                [
+                  "main",
+                  "test",
+                ],
+                type: StringArray
+                [
+                  "BRANCH",
+                  "TAG",
+                ],
+                snapshot_id: PrimitiveArray<Int64>
+                [
+                  3055729675574597004,
+                  3051729675574597004,
+                ],
+                max_reference_age_in_ms: PrimitiveArray<Int64>
+                [
+                  null,
+                  10000000,
+                ],
+                min_snapshots_to_keep: PrimitiveArray<Int32>
+                [
+                  null,
+                  null,
+                ],
+                max_snapshot_age_in_ms: PrimitiveArray<Int64>
+                [
+                  null,
+                  null,
+                ]"#]],
+            &[],
+            Some("name"),
+        );
+    }
+}
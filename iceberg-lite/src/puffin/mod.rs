@@ -28,9 +28,15 @@ pub use compression::CompressionCodec;
 mod metadata;
 pub use metadata::{BlobMetadata, CREATED_BY_PROPERTY, FileMetadata};
 
+mod ndv;
+pub use ndv::{NDV_PROPERTY, write_ndv_statistics};
+
 mod reader;
 pub use reader::PuffinReader;
 
+mod theta;
+pub use theta::ThetaSketch;
+
 mod writer;
 pub use writer::PuffinWriter;
 
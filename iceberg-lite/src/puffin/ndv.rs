@@ -0,0 +1,198 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+
+use crate::arrow::arrow_primitive_to_literal;
+use crate::io::OutputFile;
+use crate::puffin::blob::{APACHE_DATASKETCHES_THETA_V1, Blob};
+use crate::puffin::compression::CompressionCodec;
+use crate::puffin::metadata::CREATED_BY_PROPERTY;
+use crate::puffin::theta::ThetaSketch;
+use crate::puffin::writer::PuffinWriter;
+use crate::spec::{BlobMetadata, Datum, Literal, StatisticsFile, Type};
+use crate::table::Table;
+use crate::{Error, ErrorKind, Result};
+
+/// The blob property holding the estimated number of distinct values.
+pub const NDV_PROPERTY: &str = "ndv";
+
+/// Computes the number of distinct values of the columns of a snapshot and
+/// writes them to a statistics file.
+///
+/// Every top-level primitive column of the snapshot's schema gets an
+/// `apache-datasketches-theta-v1` blob, whose sketch is updated with the
+/// binary single-value serialization of the column's non-null values. Its
+/// `ndv` property holds the estimate.
+///
+/// Returns the statistics file, to be registered with
+/// [`Transaction::update_statistics`](crate::transaction::Transaction::update_statistics).
+pub fn write_ndv_statistics(
+    table: &Table,
+    snapshot_id: i64,
+    output_file: &OutputFile,
+) -> Result<StatisticsFile> {
+    let snapshot = table
+        .metadata()
+        .snapshot_by_id(snapshot_id)
+        .ok_or_else(|| {
+            Error::new(
+                ErrorKind::DataInvalid,
+                format!("Snapshot with id {snapshot_id} not found"),
+            )
+        })?;
+    let schema = snapshot.schema(table.metadata())?;
+
+    let fields: Vec<_> = schema
+        .as_struct()
+        .fields()
+        .iter()
+        .filter(|field| field.field_type.is_primitive())
+        .collect();
+    let mut sketches = vec![ThetaSketch::new(); fields.len()];
+
+    if !fields.is_empty() {
+        let batches = table
+            .scan()
+            .snapshot_id(snapshot_id)
+            .select(fields.iter().map(|field| &field.name))
+            .build()?
+            .to_arrow()?;
+        for batch in batches {
+            let batch = batch?;
+            for (i, field) in fields.iter().enumerate() {
+                let Type::Primitive(primitive_type) = field.field_type.as_ref()
+                else {
+                    continue;
+                };
+                let values =
+                    arrow_primitive_to_literal(batch.column(i), &field.field_type)?;
+                for value in values.into_iter().flatten() {
+                    let Literal::Primitive(literal) = value else {
+                        continue;
+                    };
+                    let datum = Datum::new(primitive_type.clone(), literal);
+                    sketches[i].update(&datum.to_bytes()?);
+                }
+            }
+        }
+    }
+
+    let properties = HashMap::from([(
+        CREATED_BY_PROPERTY.to_string(),
+        format!("iceberg-lite version {}", env!("CARGO_PKG_VERSION")),
+    )]);
+    let mut writer = PuffinWriter::new(output_file, properties, false)?;
+    let mut blob_metadata = Vec::with_capacity(fields.len());
+    for (field, sketch) in fields.iter().zip(sketches) {
+        let properties = HashMap::from([(
+            NDV_PROPERTY.to_string(),
+            (sketch.estimate() as u64).to_string(),
+        )]);
+        blob_metadata.push(BlobMetadata {
+            r#type: APACHE_DATASKETCHES_THETA_V1.to_string(),
+            snapshot_id,
+            sequence_number: snapshot.sequence_number(),
+            fields: vec![field.id],
+            properties: properties.clone(),
+        });
+        writer.add(
+            Blob::builder()
+                .r#type(APACHE_DATASKETCHES_THETA_V1.to_string())
+                .fields(vec![field.id])
+                .snapshot_id(snapshot_id)
+                .sequence_number(snapshot.sequence_number())
+                .data(sketch.to_compact_bytes())
+                .properties(properties)
+                .build(),
+            CompressionCodec::None,
+        )?;
+    }
+    let (file_size_in_bytes, file_footer_size_in_bytes) = writer.close()?;
+
+    Ok(StatisticsFile {
+        snapshot_id,
+        statistics_path: output_file.location().to_string(),
+        file_size_in_bytes: file_size_in_bytes as i64,
+        file_footer_size_in_bytes: file_footer_size_in_bytes as i64,
+        key_metadata: None,
+        blob_metadata,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puffin::reader::PuffinReader;
+    use crate::scan::tests::TableTestFixture;
+
+    #[test]
+    fn test_write_ndv_statistics() {
+        let mut fixture = TableTestFixture::new();
+        fixture.setup_manifest_files();
+
+        let table = &fixture.table;
+        let snapshot = table.metadata().current_snapshot().unwrap();
+        let output_file = table
+            .file_io()
+            .new_output(format!("{}/metadata/stats.puffin", fixture.table_location))
+            .unwrap();
+
+        let statistics =
+            write_ndv_statistics(table, snapshot.snapshot_id(), &output_file)
+                .unwrap();
+
+        assert_eq!(statistics.snapshot_id, snapshot.snapshot_id());
+        assert_eq!(statistics.statistics_path, output_file.location());
+
+        let input_file = output_file.to_input_file();
+        assert_eq!(
+            statistics.file_size_in_bytes as usize,
+            input_file.read().unwrap().len()
+        );
+
+        let ndvs: Vec<_> = statistics
+            .blob_metadata
+            .iter()
+            .map(|blob| (blob.fields.clone(), blob.properties[NDV_PROPERTY].clone()))
+            .collect();
+        assert_eq!(
+            ndvs,
+            vec![
+                (vec![1], "1".to_string()),
+                (vec![2], "4".to_string()),
+                (vec![3], "2".to_string()),
+                (vec![4], "2".to_string()),
+                (vec![5], "3".to_string()),
+                (vec![6], "3".to_string()),
+                (vec![7], "3".to_string()),
+                (vec![8], "2".to_string()),
+            ]
+        );
+
+        let reader = PuffinReader::new(input_file);
+        let file_metadata = reader.file_metadata().unwrap().clone();
+        assert_eq!(file_metadata.blobs.len(), 8);
+        for (blob_metadata, statistics_blob) in
+            file_metadata.blobs.iter().zip(&statistics.blob_metadata)
+        {
+            assert_eq!(blob_metadata.r#type, APACHE_DATASKETCHES_THETA_V1);
+            assert_eq!(blob_metadata.fields, statistics_blob.fields);
+            assert_eq!(blob_metadata.properties, statistics_blob.properties);
+        }
+    }
+}
@@ -0,0 +1,285 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::BTreeSet;
+
+/// The update seed of Apache DataSketches, which readers of
+/// `apache-datasketches-theta-v1` blobs expect.
+const DEFAULT_UPDATE_SEED: u64 = 9001;
+
+/// The nominal number of entries retained by a sketch, the default of Apache
+/// DataSketches.
+const DEFAULT_NOMINAL_ENTRIES: usize = 4096;
+
+/// Theta of a sketch that has not started sampling, standing for 1.0.
+const MAX_THETA: u64 = i64::MAX as u64;
+
+const SERIAL_VERSION: u8 = 3;
+const COMPACT_FAMILY_ID: u8 = 3;
+const READ_ONLY_FLAG: u8 = 1 << 1;
+const EMPTY_FLAG: u8 = 1 << 2;
+const COMPACT_FLAG: u8 = 1 << 3;
+const ORDERED_FLAG: u8 = 1 << 4;
+
+/// A Theta sketch estimating the number of distinct values it is updated
+/// with.
+///
+/// Values are hashed like Apache DataSketches does. The sketch retains the
+/// smallest hashes and serializes as a compact ordered sketch, which is the
+/// payload of `apache-datasketches-theta-v1` blobs.
+#[derive(Debug, Clone)]
+pub struct ThetaSketch {
+    nominal_entries: usize,
+    theta: u64,
+    hashes: BTreeSet<u64>,
+}
+
+impl Default for ThetaSketch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ThetaSketch {
+    /// Returns an empty sketch retaining the default number of entries.
+    pub fn new() -> Self {
+        Self {
+            nominal_entries: DEFAULT_NOMINAL_ENTRIES,
+            theta: MAX_THETA,
+            hashes: BTreeSet::new(),
+        }
+    }
+
+    /// Updates the sketch with a value. Empty values are ignored.
+    pub fn update(&mut self, value: &[u8]) {
+        if value.is_empty() {
+            return;
+        }
+
+        let hash = murmur3_x64_128(value, DEFAULT_UPDATE_SEED).0 >> 1;
+        if hash == 0 || hash >= self.theta || !self.hashes.insert(hash) {
+            return;
+        }
+        if self.hashes.len() > self.nominal_entries {
+            self.theta = self.hashes.pop_last().unwrap_or(MAX_THETA);
+        }
+    }
+
+    /// Whether the sketch has not been updated with any value.
+    pub fn is_empty(&self) -> bool {
+        self.hashes.is_empty() && self.theta == MAX_THETA
+    }
+
+    /// The estimated number of distinct values.
+    pub fn estimate(&self) -> f64 {
+        let count = self.hashes.len() as f64;
+        if self.theta == MAX_THETA {
+            count
+        } else {
+            count * MAX_THETA as f64 / self.theta as f64
+        }
+    }
+
+    /// Serializes the sketch as a compact ordered sketch, in the format of
+    /// serialization version 3 of Apache DataSketches.
+    pub fn to_compact_bytes(&self) -> Vec<u8> {
+        let preamble_longs: u8 = if self.is_empty() {
+            1
+        } else if self.theta == MAX_THETA {
+            2
+        } else {
+            3
+        };
+        let mut flags = READ_ONLY_FLAG | COMPACT_FLAG | ORDERED_FLAG;
+        if self.is_empty() {
+            flags |= EMPTY_FLAG;
+        }
+
+        let mut bytes =
+            Vec::with_capacity((preamble_longs as usize + self.hashes.len()) * 8);
+        bytes.extend([
+            preamble_longs,
+            SERIAL_VERSION,
+            COMPACT_FAMILY_ID,
+            0,
+            0,
+            flags,
+        ]);
+        bytes.extend(seed_hash(DEFAULT_UPDATE_SEED).to_le_bytes());
+        if preamble_longs > 1 {
+            bytes.extend((self.hashes.len() as u32).to_le_bytes());
+            // The sampling probability p, which compact sketches do not use
+            bytes.extend(1.0f32.to_le_bytes());
+        }
+        if preamble_longs > 2 {
+            bytes.extend(self.theta.to_le_bytes());
+        }
+        for hash in &self.hashes {
+            bytes.extend(hash.to_le_bytes());
+        }
+        bytes
+    }
+}
+
+/// The hash of `seed` stored in serialized sketches, so that readers can
+/// check that they use the seed the sketch was built with.
+fn seed_hash(seed: u64) -> u16 {
+    murmur3_x64_128(&seed.to_le_bytes(), 0).0 as u16
+}
+
+/// The 128-bit x64 variant of MurmurHash3.
+fn murmur3_x64_128(data: &[u8], seed: u64) -> (u64, u64) {
+    const C1: u64 = 0x87c3_7b91_1142_53d5;
+    const C2: u64 = 0x4cf5_ad43_2745_937f;
+
+    fn mix_k1(k1: u64) -> u64 {
+        k1.wrapping_mul(C1).rotate_left(31).wrapping_mul(C2)
+    }
+
+    fn mix_k2(k2: u64) -> u64 {
+        k2.wrapping_mul(C2).rotate_left(33).wrapping_mul(C1)
+    }
+
+    fn fmix(mut k: u64) -> u64 {
+        k ^= k >> 33;
+        k = k.wrapping_mul(0xff51_afd7_ed55_8ccd);
+        k ^= k >> 33;
+        k = k.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+        k ^ (k >> 33)
+    }
+
+    let (mut h1, mut h2) = (seed, seed);
+
+    let mut blocks = data.chunks_exact(16);
+    for block in &mut blocks {
+        let (k1, k2) = block.split_at(8);
+        h1 ^= mix_k1(u64::from_le_bytes(k1.try_into().unwrap()));
+        h1 = h1
+            .rotate_left(27)
+            .wrapping_add(h2)
+            .wrapping_mul(5)
+            .wrapping_add(0x52dc_e729);
+        h2 ^= mix_k2(u64::from_le_bytes(k2.try_into().unwrap()));
+        h2 = h2
+            .rotate_left(31)
+            .wrapping_add(h1)
+            .wrapping_mul(5)
+            .wrapping_add(0x3849_5ab5);
+    }
+
+    let tail = blocks.remainder();
+    let (mut k1, mut k2) = (0u64, 0u64);
+    for (i, byte) in tail.iter().enumerate() {
+        if i < 8 {
+            k1 |= (*byte as u64) << (8 * i);
+        } else {
+            k2 |= (*byte as u64) << (8 * (i - 8));
+        }
+    }
+    if tail.len() > 8 {
+        h2 ^= mix_k2(k2);
+    }
+    if !tail.is_empty() {
+        h1 ^= mix_k1(k1);
+    }
+
+    h1 ^= data.len() as u64;
+    h2 ^= data.len() as u64;
+    h1 = h1.wrapping_add(h2);
+    h2 = h2.wrapping_add(h1);
+    h1 = fmix(h1);
+    h2 = fmix(h2);
+    h1 = h1.wrapping_add(h2);
+    h2 = h2.wrapping_add(h1);
+
+    (h1, h2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_murmur3_x64_128() {
+        assert_eq!(
+            murmur3_x64_128(b"The quick brown fox jumps over the lazy dog", 0),
+            (0xe34b_bc7b_bc07_1b6c, 0x7a43_3ca9_c49a_9347)
+        );
+        assert_eq!(
+            murmur3_x64_128(&1i32.to_le_bytes(), DEFAULT_UPDATE_SEED),
+            (0x49f3_c104_4555_38bb, 0xac25_f801_cd86_0ad5)
+        );
+    }
+
+    #[test]
+    fn test_seed_hash() {
+        assert_eq!(seed_hash(DEFAULT_UPDATE_SEED), 0x93cc);
+    }
+
+    #[test]
+    fn test_empty_sketch() {
+        let mut sketch = ThetaSketch::new();
+        sketch.update(&[]);
+
+        assert!(sketch.is_empty());
+        assert_eq!(sketch.estimate(), 0.0);
+        assert_eq!(
+            sketch.to_compact_bytes(),
+            vec![1, 3, 3, 0, 0, 0x1e, 0xcc, 0x93]
+        );
+    }
+
+    #[test]
+    fn test_exact_sketch() {
+        let mut sketch = ThetaSketch::new();
+        for i in 0..1000i64 {
+            sketch.update(&(i % 100).to_le_bytes());
+        }
+
+        assert_eq!(sketch.estimate(), 100.0);
+
+        let bytes = sketch.to_compact_bytes();
+        assert_eq!(bytes.len(), (2 + 100) * 8);
+        assert_eq!(bytes[..8], [2, 3, 3, 0, 0, 0x1a, 0xcc, 0x93]);
+        assert_eq!(bytes[8..12], 100u32.to_le_bytes());
+        assert_eq!(bytes[12..16], 1.0f32.to_le_bytes());
+    }
+
+    #[test]
+    fn test_estimating_sketch() {
+        let mut sketch = ThetaSketch::new();
+        for i in 0..100_000i64 {
+            sketch.update(&i.to_le_bytes());
+        }
+
+        let estimate = sketch.estimate();
+        assert!(
+            (estimate - 100_000.0).abs() < 100_000.0 * 0.05,
+            "{estimate}"
+        );
+
+        let bytes = sketch.to_compact_bytes();
+        assert_eq!(bytes.len(), (3 + DEFAULT_NOMINAL_ENTRIES) * 8);
+        assert_eq!(bytes[0], 3);
+        assert_eq!(bytes[16..24], sketch.theta.to_le_bytes());
+        let hashes: Vec<u64> = bytes[24..]
+            .chunks_exact(8)
+            .map(|hash| u64::from_le_bytes(hash.try_into().unwrap()))
+            .collect();
+        assert!(hashes.is_sorted() && hashes.iter().all(|hash| *hash < sketch.theta));
+    }
+}
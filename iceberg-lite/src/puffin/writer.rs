@@ -86,12 +86,16 @@ impl PuffinWriter {
         Ok(())
     }
 
-    /// Finalizes the Puffin file
-    pub fn close(mut self) -> Result<()> {
+    /// Finalizes the Puffin file, returning its size and the size of its footer
+    pub fn close(mut self) -> Result<(u64, u64)> {
         self.write_header_once()?;
+        let footer_offset = self.num_bytes_written;
         self.write_footer()?;
         self.writer.close()?;
-        Ok(())
+        Ok((
+            self.num_bytes_written,
+            self.num_bytes_written - footer_offset,
+        ))
     }
 
     fn write(&mut self, bytes: &[u8]) -> Result<()> {
@@ -551,7 +551,7 @@ pub mod tests {
     use crate::arrow::ArrowReaderBuilder;
     use crate::expr::{BoundPredicate, Reference};
     use crate::io::{FileIO, OutputFile};
-    use crate::metadata_columns::RESERVED_COL_NAME_FILE;
+    use crate::metadata_columns::{RESERVED_COL_NAME_FILE, RESERVED_COL_NAME_POS};
     use crate::scan::FileScanTask;
     use crate::spec::{
         DataContentType, DataFileBuilder, DataFileFormat, Datum, Literal,
@@ -1255,6 +1255,31 @@ pub mod tests {
             }
         }
 
+        pub fn setup_parent_manifest_list(&mut self) {
+            // The parent snapshot shares the manifests of the current snapshot
+            let current_snapshot = self.table.metadata().current_snapshot().unwrap();
+            let parent_snapshot = current_snapshot
+                .parent_snapshot(self.table.metadata())
+                .unwrap();
+            let manifest_list = current_snapshot
+                .load_manifest_list(self.table.file_io(), self.table.metadata())
+                .unwrap();
+
+            let mut manifest_list_write = ManifestListWriter::v2(
+                self.table
+                    .file_io()
+                    .new_output(parent_snapshot.manifest_list())
+                    .unwrap(),
+                parent_snapshot.snapshot_id(),
+                parent_snapshot.parent_snapshot_id(),
+                parent_snapshot.sequence_number(),
+            );
+            manifest_list_write
+                .add_manifests(manifest_list.entries().iter().cloned())
+                .unwrap();
+            manifest_list_write.close().unwrap();
+        }
+
         pub fn setup_deadlock_manifests(&mut self) {
             let current_snapshot = self.table.metadata().current_snapshot().unwrap();
             let _parent_snapshot = current_snapshot
@@ -2111,6 +2136,41 @@ pub mod tests {
         assert_eq!(total_rows, 2048);
     }
 
+    #[test]
+    fn test_select_pos_column() {
+        use arrow_array::cast::AsArray;
+
+        let mut fixture = TableTestFixture::new();
+        fixture.setup_manifest_files();
+
+        let table_scan = fixture
+            .table
+            .scan()
+            .select(["x", RESERVED_COL_NAME_POS])
+            .build()
+            .unwrap();
+
+        let batch_iterator = table_scan.to_arrow().unwrap();
+        let batches: Vec<_> = batch_iterator.collect::<Result<Vec<_>>>().unwrap();
+
+        let schema = batches[0].schema();
+        assert_eq!(schema.field(1).name(), RESERVED_COL_NAME_POS);
+
+        // Positions restart at 0 for each of the two files read
+        let positions: Vec<i64> = batches
+            .iter()
+            .flat_map(|batch| {
+                batch
+                    .column(1)
+                    .as_primitive::<arrow_array::types::Int64Type>()
+                    .values()
+                    .to_vec()
+            })
+            .collect();
+        let expected: Vec<i64> = (0..1024).chain(0..1024).collect();
+        assert_eq!(positions, expected);
+    }
+
     #[test]
     fn test_file_column_with_multiple_files() {
         use std::collections::HashSet;
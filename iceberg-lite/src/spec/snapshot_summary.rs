@@ -516,6 +516,27 @@ fn update_totals(
         .insert(total_property.to_string(), new_total.to_string());
 }
 
+impl Summary {
+    /// Total number of records in the data files of the snapshot, if recorded.
+    pub fn total_records(&self) -> Option<u64> {
+        self.total(TOTAL_RECORDS)
+    }
+
+    /// Total size in bytes of the data and delete files of the snapshot, if recorded.
+    pub fn total_files_size(&self) -> Option<u64> {
+        self.total(TOTAL_FILE_SIZE)
+    }
+
+    /// Total number of position deletes in the snapshot, if recorded.
+    pub fn total_position_deletes(&self) -> Option<u64> {
+        self.total(TOTAL_POSITION_DELETES)
+    }
+
+    fn total(&self, property: &str) -> Option<u64> {
+        self.additional_properties.get(property)?.parse().ok()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
@@ -865,6 +886,22 @@ mod tests {
         assert_eq!(props.get(DELETED_RECORDS).unwrap(), "50");
     }
 
+    #[test]
+    fn test_summary_totals() {
+        let summary = Summary {
+            operation: Operation::Append,
+            additional_properties: HashMap::from([
+                (TOTAL_RECORDS.to_string(), "100".to_string()),
+                (TOTAL_FILE_SIZE.to_string(), "2048".to_string()),
+                (TOTAL_POSITION_DELETES.to_string(), "invalid".to_string()),
+            ]),
+        };
+
+        assert_eq!(summary.total_records(), Some(100));
+        assert_eq!(summary.total_files_size(), Some(2048));
+        assert_eq!(summary.total_position_deletes(), None);
+    }
+
     #[test]
     fn test_snapshot_summary_collector_merge() {
         let schema = Arc::new(
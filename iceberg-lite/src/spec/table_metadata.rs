@@ -338,6 +338,22 @@ impl TableMetadata {
         })
     }
 
+    /// Get the snapshot that was current at a timestamp, in milliseconds since
+    /// the Unix epoch.
+    ///
+    /// The snapshot is looked up in the snapshot log. Returns `None` if the
+    /// table had no current snapshot at that time, or it no longer exists.
+    pub fn snapshot_as_of_timestamp(
+        &self,
+        timestamp_ms: i64,
+    ) -> Option<&SnapshotRef> {
+        self.snapshot_log
+            .iter()
+            .take_while(|log| log.timestamp_ms <= timestamp_ms)
+            .last()
+            .and_then(|log| self.snapshot_by_id(log.snapshot_id))
+    }
+
     /// Return all sort orders.
     #[inline]
     pub fn sort_orders_iter(&self) -> impl ExactSizeIterator<Item = &SortOrderRef> {
@@ -3635,6 +3651,23 @@ mod tests {
             .clone()
         );
     }
+
+    #[test]
+    fn test_snapshot_as_of_timestamp() {
+        let table_meta_data = get_test_table_metadata("TableMetadataV2Valid.json");
+        let snapshot_id = |timestamp_ms| {
+            table_meta_data
+                .snapshot_as_of_timestamp(timestamp_ms)
+                .map(|snapshot| snapshot.snapshot_id())
+        };
+
+        assert_eq!(snapshot_id(1515100955769), None);
+        assert_eq!(snapshot_id(1515100955770), Some(3051729675574597004));
+        assert_eq!(snapshot_id(1555100955769), Some(3051729675574597004));
+        assert_eq!(snapshot_id(1555100955770), Some(3055729675574597004));
+        assert_eq!(snapshot_id(i64::MAX), Some(3055729675574597004));
+    }
+
     #[test]
     fn test_default_sort_order() {
         let default_sort_order_id = 1234;
@@ -49,6 +49,12 @@ pub struct TableProperties {
     pub write_format_default: String,
     /// The target file size for files.
     pub write_target_file_size_bytes: usize,
+    /// The max age of snapshots to keep when expiring snapshots.
+    pub max_snapshot_age_ms: i64,
+    /// The minimum number of snapshots of a branch to keep when expiring snapshots.
+    pub min_snapshots_to_keep: i32,
+    /// The max age of branches and tags to keep when expiring snapshots.
+    pub max_ref_age_ms: i64,
 }
 
 impl TableProperties {
@@ -137,6 +143,20 @@ impl TableProperties {
     pub const PROPERTY_WRITE_TARGET_FILE_SIZE_BYTES: &str = "write.target-file-size-bytes";
     /// Default target file size
     pub const PROPERTY_WRITE_TARGET_FILE_SIZE_BYTES_DEFAULT: usize = 512 * 1024 * 1024; // 512 MB
+
+    /// Max age of snapshots to keep when expiring snapshots.
+    pub const PROPERTY_MAX_SNAPSHOT_AGE_MS: &str = "history.expire.max-snapshot-age-ms";
+    /// Default max age of snapshots
+    pub const PROPERTY_MAX_SNAPSHOT_AGE_MS_DEFAULT: i64 = 5 * 24 * 60 * 60 * 1000; // 5 days
+    /// Minimum number of snapshots of a branch to keep when expiring snapshots.
+    pub const PROPERTY_MIN_SNAPSHOTS_TO_KEEP: &str = "history.expire.min-snapshots-to-keep";
+    /// Default minimum number of snapshots to keep
+    pub const PROPERTY_MIN_SNAPSHOTS_TO_KEEP_DEFAULT: i32 = 1;
+    /// Max age of branches and tags to keep when expiring snapshots. The main branch never
+    /// expires.
+    pub const PROPERTY_MAX_REF_AGE_MS: &str = "history.expire.max-ref-age-ms";
+    /// Default max age of branches and tags
+    pub const PROPERTY_MAX_REF_AGE_MS_DEFAULT: i64 = i64::MAX;
 }
 
 impl TryFrom<&HashMap<String, String>> for TableProperties {
@@ -175,6 +195,21 @@ impl TryFrom<&HashMap<String, String>> for TableProperties {
                 TableProperties::PROPERTY_WRITE_TARGET_FILE_SIZE_BYTES,
                 TableProperties::PROPERTY_WRITE_TARGET_FILE_SIZE_BYTES_DEFAULT,
             )?,
+            max_snapshot_age_ms: parse_property(
+                props,
+                TableProperties::PROPERTY_MAX_SNAPSHOT_AGE_MS,
+                TableProperties::PROPERTY_MAX_SNAPSHOT_AGE_MS_DEFAULT,
+            )?,
+            min_snapshots_to_keep: parse_property(
+                props,
+                TableProperties::PROPERTY_MIN_SNAPSHOTS_TO_KEEP,
+                TableProperties::PROPERTY_MIN_SNAPSHOTS_TO_KEEP_DEFAULT,
+            )?,
+            max_ref_age_ms: parse_property(
+                props,
+                TableProperties::PROPERTY_MAX_REF_AGE_MS,
+                TableProperties::PROPERTY_MAX_REF_AGE_MS_DEFAULT,
+            )?,
         })
     }
 }
@@ -207,6 +242,18 @@ mod tests {
             table_properties.write_target_file_size_bytes,
             TableProperties::PROPERTY_WRITE_TARGET_FILE_SIZE_BYTES_DEFAULT
         );
+        assert_eq!(
+            table_properties.max_snapshot_age_ms,
+            TableProperties::PROPERTY_MAX_SNAPSHOT_AGE_MS_DEFAULT
+        );
+        assert_eq!(
+            table_properties.min_snapshots_to_keep,
+            TableProperties::PROPERTY_MIN_SNAPSHOTS_TO_KEEP_DEFAULT
+        );
+        assert_eq!(
+            table_properties.max_ref_age_ms,
+            TableProperties::PROPERTY_MAX_REF_AGE_MS_DEFAULT
+        );
     }
 
     #[test]
@@ -0,0 +1,606 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::{BTreeSet, HashSet};
+use std::sync::Arc;
+
+use crate::error::Result;
+use crate::io::FileIO;
+use crate::spec::{
+    DataContentType, MAIN_BRANCH, SnapshotReference, SnapshotRetention,
+    TableMetadata, TableProperties,
+};
+use crate::table::Table;
+use crate::transaction::{ActionCommit, TransactionAction};
+use crate::{Error, ErrorKind, TableRequirement, TableUpdate};
+
+/// ExpireSnapshotsAction is a transaction action that removes old snapshots and the
+/// branches and tags that expired from the table metadata.
+///
+/// A snapshot is kept when a branch or tag needs it, or when it is not older than
+/// [`ExpireSnapshotsAction::expire_older_than`]. Walking back from its head, a branch
+/// keeps its `min-snapshots-to-keep` latest snapshots and the ones younger than its
+/// `max-snapshot-age-ms`. Branches and tags other than `main` expire once their
+/// snapshot is older than their `max-ref-age-ms`. Retention left unset on a reference
+/// defaults to the `history.expire.*` table properties.
+///
+/// The action only updates the table metadata. Once it is committed,
+/// [`ExpiredFiles::compute`] finds the files that only expired snapshots referenced,
+/// which can then be deleted with [`ExpiredFiles::delete`].
+pub struct ExpireSnapshotsAction {
+    older_than_ms: Option<i64>,
+    retain_last: Option<i32>,
+    snapshot_ids: BTreeSet<i64>,
+}
+
+impl ExpireSnapshotsAction {
+    pub(crate) fn new() -> Self {
+        Self {
+            older_than_ms: None,
+            retain_last: None,
+            snapshot_ids: BTreeSet::new(),
+        }
+    }
+
+    /// Expire snapshots older than `timestamp_ms`, instead of the ones older than the
+    /// `history.expire.max-snapshot-age-ms` table property.
+    ///
+    /// Branches with a `max-snapshot-age-ms` of their own keep using it.
+    pub fn expire_older_than(mut self, timestamp_ms: i64) -> Self {
+        self.older_than_ms = Some(timestamp_ms);
+        self
+    }
+
+    /// Keep the `num_snapshots` latest snapshots of each branch, instead of the
+    /// `history.expire.min-snapshots-to-keep` table property.
+    ///
+    /// Branches with a `min-snapshots-to-keep` of their own keep using it.
+    pub fn retain_last(mut self, num_snapshots: i32) -> Self {
+        self.retain_last = Some(num_snapshots);
+        self
+    }
+
+    /// Expire the snapshot with `snapshot_id`, whatever its age.
+    pub fn expire_snapshot_id(mut self, snapshot_id: i64) -> Self {
+        self.snapshot_ids.insert(snapshot_id);
+        self
+    }
+}
+
+impl TransactionAction for ExpireSnapshotsAction {
+    fn commit(self: Arc<Self>, table: &Table) -> Result<ActionCommit> {
+        let metadata = table.metadata();
+        let props =
+            TableProperties::try_from(metadata.properties()).map_err(|e| {
+                Error::new(ErrorKind::DataInvalid, "Invalid table properties")
+                    .with_source(e)
+            })?;
+
+        let min_snapshots_to_keep =
+            self.retain_last.unwrap_or(props.min_snapshots_to_keep);
+        if min_snapshots_to_keep < 1 {
+            return Err(Error::new(
+                ErrorKind::DataInvalid,
+                format!(
+                    "Number of snapshots to retain must be at least 1, got \
+                     {min_snapshots_to_keep}"
+                ),
+            ));
+        }
+
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let retention = Retention {
+            now_ms,
+            older_than_ms: self
+                .older_than_ms
+                .unwrap_or(now_ms.saturating_sub(props.max_snapshot_age_ms)),
+            min_snapshots_to_keep,
+            max_ref_age_ms: props.max_ref_age_ms,
+        };
+
+        let mut expired_refs = vec![];
+        let mut retained_refs = vec![];
+        for (ref_name, reference) in &metadata.refs {
+            if retention.ref_expired(metadata, ref_name, reference) {
+                expired_refs.push(ref_name.clone());
+            } else {
+                retained_refs.push((ref_name, reference));
+            }
+        }
+        expired_refs.sort();
+
+        for snapshot_id in &self.snapshot_ids {
+            let refs = retained_refs
+                .iter()
+                .filter(|(_, reference)| reference.snapshot_id == *snapshot_id)
+                .map(|(ref_name, _)| ref_name.as_str())
+                .collect::<Vec<_>>();
+            if !refs.is_empty() {
+                return Err(Error::new(
+                    ErrorKind::DataInvalid,
+                    format!(
+                        "Cannot expire snapshot {snapshot_id}, still referenced by \
+                         {}",
+                        refs.join(", ")
+                    ),
+                ));
+            }
+        }
+
+        // Snapshots needed by the retained branches and tags
+        let mut retained_ids = HashSet::new();
+        let mut referenced_ids = HashSet::new();
+        for (_, reference) in &retained_refs {
+            retained_ids.extend(retention.ref_snapshots(metadata, reference));
+            referenced_ids.extend(ancestor_ids(metadata, reference.snapshot_id));
+        }
+        // Snapshots no branch or tag refers to are kept until they are old enough
+        retained_ids.extend(
+            metadata
+                .snapshots()
+                .filter(|snapshot| {
+                    !referenced_ids.contains(&snapshot.snapshot_id())
+                        && snapshot.timestamp_ms() >= retention.older_than_ms
+                })
+                .map(|snapshot| snapshot.snapshot_id()),
+        );
+
+        let mut expired_ids = metadata
+            .snapshots()
+            .map(|snapshot| snapshot.snapshot_id())
+            .filter(|snapshot_id| {
+                !retained_ids.contains(snapshot_id)
+                    || self.snapshot_ids.contains(snapshot_id)
+            })
+            .collect::<Vec<_>>();
+        expired_ids.sort();
+
+        let mut updates = expired_refs
+            .into_iter()
+            .map(|ref_name| TableUpdate::RemoveSnapshotRef { ref_name })
+            .collect::<Vec<_>>();
+        if !expired_ids.is_empty() {
+            updates.push(TableUpdate::RemoveSnapshots {
+                snapshot_ids: expired_ids,
+            });
+        }
+        if updates.is_empty() {
+            return Ok(ActionCommit::new(vec![], vec![]));
+        }
+
+        let requirements = vec![
+            TableRequirement::UuidMatch {
+                uuid: metadata.uuid(),
+            },
+            TableRequirement::RefSnapshotIdMatch {
+                r#ref: MAIN_BRANCH.to_string(),
+                snapshot_id: metadata.current_snapshot_id(),
+            },
+        ];
+
+        Ok(ActionCommit::new(updates, requirements))
+    }
+}
+
+/// The retention defaults of an [`ExpireSnapshotsAction`], as of `now_ms`.
+struct Retention {
+    now_ms: i64,
+    older_than_ms: i64,
+    min_snapshots_to_keep: i32,
+    max_ref_age_ms: i64,
+}
+
+impl Retention {
+    /// Whether the branch or tag `ref_name` is older than its max age.
+    fn ref_expired(
+        &self,
+        metadata: &TableMetadata,
+        ref_name: &str,
+        reference: &SnapshotReference,
+    ) -> bool {
+        if ref_name == MAIN_BRANCH {
+            return false;
+        }
+        let max_ref_age_ms = match reference.retention {
+            SnapshotRetention::Branch { max_ref_age_ms, .. }
+            | SnapshotRetention::Tag { max_ref_age_ms } => max_ref_age_ms,
+        }
+        .unwrap_or(self.max_ref_age_ms);
+
+        match metadata.snapshot_by_id(reference.snapshot_id) {
+            Some(snapshot) => {
+                self.now_ms.saturating_sub(snapshot.timestamp_ms()) > max_ref_age_ms
+            }
+            None => true,
+        }
+    }
+
+    /// The snapshots a retained branch or tag keeps.
+    fn ref_snapshots(
+        &self,
+        metadata: &TableMetadata,
+        reference: &SnapshotReference,
+    ) -> Vec<i64> {
+        let SnapshotRetention::Branch {
+            min_snapshots_to_keep,
+            max_snapshot_age_ms,
+            ..
+        } = reference.retention
+        else {
+            return vec![reference.snapshot_id];
+        };
+        let min_snapshots_to_keep = min_snapshots_to_keep
+            .unwrap_or(self.min_snapshots_to_keep)
+            .max(1) as usize;
+        let older_than_ms = max_snapshot_age_ms
+            .map(|max_age_ms| self.now_ms.saturating_sub(max_age_ms))
+            .unwrap_or(self.older_than_ms);
+
+        let mut snapshot_ids = vec![];
+        let mut snapshot = metadata.snapshot_by_id(reference.snapshot_id);
+        while let Some(current) = snapshot {
+            if snapshot_ids.len() >= min_snapshots_to_keep
+                && current.timestamp_ms() < older_than_ms
+            {
+                break;
+            }
+            snapshot_ids.push(current.snapshot_id());
+            snapshot = current
+                .parent_snapshot_id()
+                .and_then(|parent_id| metadata.snapshot_by_id(parent_id));
+        }
+        snapshot_ids
+    }
+}
+
+/// The ids of the snapshot `snapshot_id` and of its ancestors.
+fn ancestor_ids(metadata: &TableMetadata, snapshot_id: i64) -> Vec<i64> {
+    let mut snapshot_ids = vec![];
+    let mut snapshot = metadata.snapshot_by_id(snapshot_id);
+    while let Some(current) = snapshot {
+        snapshot_ids.push(current.snapshot_id());
+        snapshot = current
+            .parent_snapshot_id()
+            .and_then(|parent_id| metadata.snapshot_by_id(parent_id));
+    }
+    snapshot_ids
+}
+
+/// The files that only snapshots removed from a table referenced.
+///
+/// Manifest lists and manifests are no longer reachable once no remaining snapshot
+/// lists them. Data and delete files are no longer reachable once no manifest of a
+/// remaining snapshot has them as live entries.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ExpiredFiles {
+    /// Manifest lists of the removed snapshots.
+    pub manifest_lists: Vec<String>,
+    /// Manifests no remaining snapshot lists.
+    pub manifests: Vec<String>,
+    /// Data files no remaining snapshot reads.
+    pub data_files: Vec<String>,
+    /// Delete files no remaining snapshot reads.
+    pub delete_files: Vec<String>,
+}
+
+impl ExpiredFiles {
+    /// Compute the files of the snapshots of `base` that `table` no longer
+    /// references, `table` being `base` once snapshots were expired.
+    pub fn compute(base: &Table, table: &Table) -> Result<Self> {
+        let base_metadata = base.metadata_ref();
+        let metadata = table.metadata_ref();
+        let object_cache = base.object_cache();
+
+        // Files reachable from the remaining snapshots
+        let mut manifest_lists = HashSet::new();
+        let mut manifests = HashSet::new();
+        let mut files = HashSet::new();
+        for snapshot in metadata.snapshots() {
+            manifest_lists.insert(snapshot.manifest_list().to_string());
+            let manifest_list =
+                object_cache.get_manifest_list(snapshot, &metadata)?;
+            for manifest_file in manifest_list.entries() {
+                if !manifests.insert(manifest_file.manifest_path.clone()) {
+                    continue;
+                }
+                let manifest = object_cache.get_manifest(manifest_file)?;
+                files.extend(
+                    manifest
+                        .entries()
+                        .iter()
+                        .filter(|entry| entry.is_alive())
+                        .map(|entry| entry.file_path().to_string()),
+                );
+            }
+        }
+
+        let mut expired = ExpiredFiles::default();
+        let mut expired_manifests = HashSet::new();
+        let mut expired_files = HashSet::new();
+        for snapshot in base_metadata.snapshots() {
+            if metadata.snapshot_by_id(snapshot.snapshot_id()).is_some()
+                || manifest_lists.contains(snapshot.manifest_list())
+            {
+                continue;
+            }
+            expired
+                .manifest_lists
+                .push(snapshot.manifest_list().to_string());
+
+            let manifest_list =
+                object_cache.get_manifest_list(snapshot, &base_metadata)?;
+            for manifest_file in manifest_list.entries() {
+                if manifests.contains(&manifest_file.manifest_path)
+                    || !expired_manifests.insert(manifest_file.manifest_path.clone())
+                {
+                    continue;
+                }
+                expired.manifests.push(manifest_file.manifest_path.clone());
+
+                let manifest = object_cache.get_manifest(manifest_file)?;
+                for entry in manifest.entries() {
+                    let file_path = entry.file_path();
+                    if files.contains(file_path)
+                        || !expired_files.insert(file_path.to_string())
+                    {
+                        continue;
+                    }
+                    match entry.content_type() {
+                        DataContentType::Data => {
+                            expired.data_files.push(file_path.to_string())
+                        }
+                        _ => expired.delete_files.push(file_path.to_string()),
+                    }
+                }
+            }
+        }
+
+        expired.manifest_lists.sort();
+        expired.manifests.sort();
+        expired.data_files.sort();
+        expired.delete_files.sort();
+        Ok(expired)
+    }
+
+    /// Returns `true` if no file expired.
+    pub fn is_empty(&self) -> bool {
+        self.manifest_lists.is_empty()
+            && self.manifests.is_empty()
+            && self.data_files.is_empty()
+            && self.delete_files.is_empty()
+    }
+
+    /// Delete the expired files: data and delete files first, then manifests and
+    /// manifest lists, so that a failed cleanup can be run again.
+    pub fn delete(&self, file_io: &FileIO) -> Result<()> {
+        for path in self
+            .data_files
+            .iter()
+            .chain(&self.delete_files)
+            .chain(&self.manifests)
+            .chain(&self.manifest_lists)
+        {
+            file_io.delete(path)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::ExpiredFiles;
+    use crate::TableUpdate;
+    use crate::spec::{
+        DataContentType, DataFile, DataFileBuilder, DataFileFormat, Literal,
+        SnapshotReference, SnapshotRetention, Struct,
+    };
+    use crate::table::Table;
+    use crate::transaction::tests::make_v2_minimal_table;
+    use crate::transaction::{
+        ApplyTransactionAction, StagedTransaction, Transaction, TransactionAction,
+    };
+
+    fn data_file(table: &Table, path: &str) -> DataFile {
+        DataFileBuilder::default()
+            .content(DataContentType::Data)
+            .file_path(path.to_string())
+            .file_format(DataFileFormat::Parquet)
+            .file_size_in_bytes(100)
+            .record_count(1)
+            .partition_spec_id(table.metadata().default_partition_spec_id())
+            .partition(Struct::from_iter([Some(Literal::long(300))]))
+            .build()
+            .unwrap()
+    }
+
+    fn append(staged: &mut StagedTransaction, paths: &[&str]) {
+        let tx = Transaction::new(staged.table());
+        let files = paths
+            .iter()
+            .map(|path| data_file(staged.table(), path))
+            .collect::<Vec<_>>();
+        let tx = tx.fast_append().add_data_files(files).apply(tx).unwrap();
+        staged.stage(tx).unwrap();
+    }
+
+    /// A table with a snapshot for each of the three appended data files.
+    fn table_with_snapshots() -> Table {
+        let table = make_v2_minimal_table();
+        let mut staged = StagedTransaction::new(&table);
+        append(&mut staged, &["test/1.parquet"]);
+        append(&mut staged, &["test/2.parquet"]);
+        append(&mut staged, &["test/3.parquet"]);
+        staged.table().clone()
+    }
+
+    fn snapshot_ids(table: &Table) -> Vec<i64> {
+        let mut snapshot_ids = table
+            .metadata()
+            .snapshots()
+            .map(|snapshot| snapshot.snapshot_id())
+            .collect::<Vec<_>>();
+        snapshot_ids.sort();
+        snapshot_ids
+    }
+
+    #[test]
+    fn test_expire_snapshots() {
+        let table = table_with_snapshots();
+        let current_snapshot_id = table.metadata().current_snapshot_id().unwrap();
+        let tx = Transaction::new(&table);
+        let action = tx
+            .expire_snapshots()
+            .expire_older_than(i64::MAX)
+            .retain_last(1);
+        let mut action_commit = Arc::new(action).commit(&table).unwrap();
+
+        let mut expired_ids = snapshot_ids(&table);
+        expired_ids.retain(|snapshot_id| *snapshot_id != current_snapshot_id);
+        assert_eq!(
+            action_commit.take_updates(),
+            vec![TableUpdate::RemoveSnapshots {
+                snapshot_ids: expired_ids
+            }]
+        );
+        assert_eq!(action_commit.take_requirements().len(), 2);
+    }
+
+    #[test]
+    fn test_expire_snapshots_within_retention() {
+        let table = table_with_snapshots();
+        let tx = Transaction::new(&table);
+        let mut action_commit =
+            Arc::new(tx.expire_snapshots()).commit(&table).unwrap();
+
+        assert!(action_commit.take_updates().is_empty());
+        assert!(action_commit.take_requirements().is_empty());
+    }
+
+    #[test]
+    fn test_expire_snapshots_retain_last_zero() {
+        let table = table_with_snapshots();
+        let tx = Transaction::new(&table);
+        let action = tx.expire_snapshots().retain_last(0);
+
+        assert!(Arc::new(action).commit(&table).is_err());
+    }
+
+    #[test]
+    fn test_expire_referenced_snapshot() {
+        let table = table_with_snapshots();
+        let current_snapshot_id = table.metadata().current_snapshot_id().unwrap();
+        let tx = Transaction::new(&table);
+        let action = tx
+            .expire_snapshots()
+            .expire_snapshot_id(current_snapshot_id);
+
+        let err = Arc::new(action).commit(&table).err().unwrap();
+        assert!(err.to_string().contains("still referenced by main"));
+    }
+
+    #[test]
+    fn test_expire_snapshots_keeps_tagged_snapshot() {
+        let table = table_with_snapshots();
+        let snapshot_ids = snapshot_ids(&table);
+        let current_snapshot_id = table.metadata().current_snapshot_id().unwrap();
+        let tagged_snapshot_id = table
+            .metadata()
+            .snapshots()
+            .find(|snapshot| snapshot.parent_snapshot_id().is_none())
+            .unwrap()
+            .snapshot_id();
+
+        let builder = table.metadata().clone().into_builder(None);
+        let metadata = TableUpdate::SetSnapshotRef {
+            ref_name: "v1".to_string(),
+            reference: SnapshotReference {
+                snapshot_id: tagged_snapshot_id,
+                retention: SnapshotRetention::Tag {
+                    max_ref_age_ms: None,
+                },
+            },
+        }
+        .apply(builder)
+        .unwrap()
+        .build()
+        .unwrap()
+        .metadata;
+        let table = table.with_metadata(Arc::new(metadata));
+
+        let tx = Transaction::new(&table);
+        let action = tx
+            .expire_snapshots()
+            .expire_older_than(i64::MAX)
+            .retain_last(1);
+        let mut action_commit = Arc::new(action).commit(&table).unwrap();
+
+        let expired_ids = snapshot_ids
+            .into_iter()
+            .filter(|snapshot_id| {
+                *snapshot_id != current_snapshot_id
+                    && *snapshot_id != tagged_snapshot_id
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            action_commit.take_updates(),
+            vec![TableUpdate::RemoveSnapshots {
+                snapshot_ids: expired_ids
+            }]
+        );
+    }
+
+    #[test]
+    fn test_expired_files() {
+        let table = make_v2_minimal_table();
+        let mut staged = StagedTransaction::new(&table);
+        append(&mut staged, &["test/1.parquet", "test/2.parquet"]);
+        let tx = Transaction::new(staged.table());
+        let tx = tx.truncate().apply(tx).unwrap();
+        staged.stage(tx).unwrap();
+        append(&mut staged, &["test/3.parquet"]);
+
+        let base = staged.table().clone();
+        let tx = Transaction::new(&base);
+        let tx = tx
+            .expire_snapshots()
+            .expire_older_than(i64::MAX)
+            .retain_last(1)
+            .apply(tx)
+            .unwrap();
+        staged.stage(tx).unwrap();
+        assert_eq!(staged.table().metadata().snapshots().count(), 1);
+
+        // The appended and the truncated snapshots expired, and with them the
+        // files the last append no longer reads.
+        let expired = ExpiredFiles::compute(&base, staged.table()).unwrap();
+        assert_eq!(expired.manifest_lists.len(), 2);
+        assert_eq!(expired.manifests.len(), 2);
+        assert_eq!(expired.data_files, ["test/1.parquet", "test/2.parquet"]);
+        assert!(expired.delete_files.is_empty());
+
+        expired.delete(base.file_io()).unwrap();
+        for path in expired.manifest_lists.iter().chain(&expired.manifests) {
+            assert!(!base.file_io().exists(path).unwrap());
+        }
+
+        // Nothing expires once the snapshots are gone
+        let expired = ExpiredFiles::compute(staged.table(), staged.table()).unwrap();
+        assert!(expired.is_empty());
+    }
+}
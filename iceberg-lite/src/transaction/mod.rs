@@ -53,16 +53,24 @@ mod action;
 
 pub use action::*;
 mod append;
+mod expire_snapshots;
+mod row_delta;
 mod snapshot;
 mod sort_order;
+mod staged;
+mod truncate;
 mod update_location;
 mod update_properties;
+mod update_schema;
 mod update_statistics;
 mod upgrade_format_version;
 
 use std::sync::Arc;
 use std::time::Duration;
 
+pub use expire_snapshots::ExpiredFiles;
+pub use staged::{ChangedDataFiles, StagedTransaction};
+
 use backon::{BackoffBuilder, ExponentialBackoff, ExponentialBuilder};
 
 use crate::error::Result;
@@ -70,9 +78,13 @@ use crate::spec::TableProperties;
 use crate::table::Table;
 use crate::transaction::action::BoxedTransactionAction;
 use crate::transaction::append::FastAppendAction;
+use crate::transaction::expire_snapshots::ExpireSnapshotsAction;
+use crate::transaction::row_delta::RowDeltaAction;
 use crate::transaction::sort_order::ReplaceSortOrderAction;
+use crate::transaction::truncate::TruncateAction;
 use crate::transaction::update_location::UpdateLocationAction;
 use crate::transaction::update_properties::UpdatePropertiesAction;
+use crate::transaction::update_schema::UpdateSchemaAction;
 use crate::transaction::update_statistics::UpdateStatisticsAction;
 use crate::transaction::upgrade_format_version::UpgradeFormatVersionAction;
 use crate::{Catalog, Error, ErrorKind, TableCommit, TableRequirement, TableUpdate};
@@ -135,11 +147,32 @@ impl Transaction {
         UpdatePropertiesAction::new()
     }
 
+    /// Creates an update schema action, evolving the current schema.
+    pub fn update_schema(&self) -> UpdateSchemaAction {
+        UpdateSchemaAction::new()
+    }
+
     /// Creates a fast append action.
     pub fn fast_append(&self) -> FastAppendAction {
         FastAppendAction::new()
     }
 
+    /// Creates a row delta action, adding data and delete files in one snapshot.
+    pub fn row_delta(&self) -> RowDeltaAction {
+        RowDeltaAction::new()
+    }
+
+    /// Creates a truncate action, removing all data and delete files in one snapshot.
+    pub fn truncate(&self) -> TruncateAction {
+        TruncateAction::new()
+    }
+
+    /// Creates an expire snapshots action, removing snapshots and references that
+    /// are no longer retained.
+    pub fn expire_snapshots(&self) -> ExpireSnapshotsAction {
+        ExpireSnapshotsAction::new()
+    }
+
     /// Creates replace sort order action.
     pub fn replace_sort_order(&self) -> ReplaceSortOrderAction {
         ReplaceSortOrderAction::new()
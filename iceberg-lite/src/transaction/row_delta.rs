@@ -0,0 +1,332 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::error::Result;
+use crate::spec::{DataFile, ManifestEntry, ManifestFile, Operation};
+use crate::table::Table;
+use crate::transaction::snapshot::{
+    DefaultManifestProcess, SnapshotProduceOperation, SnapshotProducer,
+};
+use crate::transaction::{ActionCommit, TransactionAction};
+
+/// RowDeltaAction is a transaction action that adds data files and delete files to the
+/// table in a single snapshot.
+///
+/// Delete files encode row-level deletes of existing data files. Together with added data
+/// files they express row-level changes such as updates, which are committed atomically.
+pub struct RowDeltaAction {
+    // below are properties used to create SnapshotProducer when commit
+    commit_uuid: Option<Uuid>,
+    key_metadata: Option<Vec<u8>>,
+    snapshot_properties: HashMap<String, String>,
+    added_data_files: Vec<DataFile>,
+    added_delete_files: Vec<DataFile>,
+}
+
+impl RowDeltaAction {
+    pub(crate) fn new() -> Self {
+        Self {
+            commit_uuid: None,
+            key_metadata: None,
+            snapshot_properties: HashMap::default(),
+            added_data_files: vec![],
+            added_delete_files: vec![],
+        }
+    }
+
+    /// Add data files to the snapshot.
+    pub fn add_data_files(
+        mut self,
+        data_files: impl IntoIterator<Item = DataFile>,
+    ) -> Self {
+        self.added_data_files.extend(data_files);
+        self
+    }
+
+    /// Add position or equality delete files to the snapshot.
+    pub fn add_delete_files(
+        mut self,
+        delete_files: impl IntoIterator<Item = DataFile>,
+    ) -> Self {
+        self.added_delete_files.extend(delete_files);
+        self
+    }
+
+    /// Set commit UUID for the snapshot.
+    pub fn set_commit_uuid(mut self, commit_uuid: Uuid) -> Self {
+        self.commit_uuid = Some(commit_uuid);
+        self
+    }
+
+    /// Set key metadata for manifest files.
+    pub fn set_key_metadata(mut self, key_metadata: Vec<u8>) -> Self {
+        self.key_metadata = Some(key_metadata);
+        self
+    }
+
+    /// Set snapshot summary properties.
+    pub fn set_snapshot_properties(
+        mut self,
+        snapshot_properties: HashMap<String, String>,
+    ) -> Self {
+        self.snapshot_properties = snapshot_properties;
+        self
+    }
+}
+
+impl TransactionAction for RowDeltaAction {
+    fn commit(self: Arc<Self>, table: &Table) -> Result<ActionCommit> {
+        let snapshot_producer = SnapshotProducer::new(
+            table,
+            self.commit_uuid.unwrap_or_else(Uuid::now_v7),
+            self.key_metadata.clone(),
+            self.snapshot_properties.clone(),
+            self.added_data_files.clone(),
+        )
+        .with_added_delete_files(self.added_delete_files.clone());
+
+        // validate added files
+        snapshot_producer.validate_added_data_files()?;
+        snapshot_producer.validate_added_delete_files()?;
+
+        // Checks duplicate files
+        snapshot_producer.validate_duplicate_files()?;
+
+        snapshot_producer.commit(
+            RowDeltaOperation {
+                adds_data_files: !self.added_data_files.is_empty(),
+                adds_delete_files: !self.added_delete_files.is_empty(),
+            },
+            DefaultManifestProcess,
+        )
+    }
+}
+
+struct RowDeltaOperation {
+    adds_data_files: bool,
+    adds_delete_files: bool,
+}
+
+impl SnapshotProduceOperation for RowDeltaOperation {
+    fn operation(&self) -> Operation {
+        match (self.adds_data_files, self.adds_delete_files) {
+            (true, false) => Operation::Append,
+            (false, true) => Operation::Delete,
+            _ => Operation::Overwrite,
+        }
+    }
+
+    fn delete_entries(
+        &self,
+        _snapshot_produce: &SnapshotProducer<'_>,
+    ) -> Result<Vec<ManifestEntry>> {
+        Ok(vec![])
+    }
+
+    fn existing_manifest(
+        &self,
+        snapshot_produce: &SnapshotProducer<'_>,
+    ) -> Result<Vec<ManifestFile>> {
+        let Some(snapshot) = snapshot_produce.table.metadata().current_snapshot()
+        else {
+            return Ok(vec![]);
+        };
+
+        let manifest_list = snapshot.load_manifest_list(
+            snapshot_produce.table.file_io(),
+            &snapshot_produce.table.metadata_ref(),
+        )?;
+
+        // Data and delete manifests are carried forward, no file is removed
+        Ok(manifest_list
+            .entries()
+            .iter()
+            .filter(|entry| entry.has_added_files() || entry.has_existing_files())
+            .cloned()
+            .collect())
+    }
+
+    fn truncates_table(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::TableUpdate;
+    use crate::spec::{
+        DataContentType, DataFile, DataFileBuilder, DataFileFormat, Literal,
+        ManifestContentType, Operation, Snapshot, Struct,
+    };
+    use crate::table::Table;
+    use crate::transaction::tests::make_v2_minimal_table;
+    use crate::transaction::{
+        ApplyTransactionAction, StagedTransaction, Transaction, TransactionAction,
+    };
+
+    fn file(table: &Table, content: DataContentType, path: &str) -> DataFile {
+        let mut builder = DataFileBuilder::default();
+        builder
+            .content(content)
+            .file_path(path.to_string())
+            .file_format(DataFileFormat::Parquet)
+            .file_size_in_bytes(100)
+            .record_count(2)
+            .partition_spec_id(table.metadata().default_partition_spec_id())
+            .partition(Struct::from_iter([Some(Literal::long(300))]));
+        if content == DataContentType::PositionDeletes {
+            builder.referenced_data_file(Some("test/1.parquet".to_string()));
+        }
+        builder.build().unwrap()
+    }
+
+    fn new_snapshot(updates: &[TableUpdate]) -> &Snapshot {
+        if let TableUpdate::AddSnapshot { snapshot } = &updates[0] {
+            snapshot
+        } else {
+            unreachable!()
+        }
+    }
+
+    /// A table with one appended data file.
+    fn table_with_data() -> Table {
+        let table = make_v2_minimal_table();
+        let mut staged = StagedTransaction::new(&table);
+        let tx = Transaction::new(staged.table());
+        let tx = tx
+            .fast_append()
+            .add_data_files(vec![file(
+                &table,
+                DataContentType::Data,
+                "test/1.parquet",
+            )])
+            .apply(tx)
+            .unwrap();
+        staged.stage(tx).unwrap();
+        staged.table().clone()
+    }
+
+    #[test]
+    fn test_row_delta_deletes() {
+        let table = table_with_data();
+        let tx = Transaction::new(&table);
+        let action = tx.row_delta().add_delete_files(vec![file(
+            &table,
+            DataContentType::PositionDeletes,
+            "test/1-deletes.parquet",
+        )]);
+        let mut action_commit = Arc::new(action).commit(&table).unwrap();
+        let updates = action_commit.take_updates();
+
+        let snapshot = new_snapshot(&updates);
+        assert_eq!(snapshot.summary().operation, Operation::Delete);
+        let properties = &snapshot.summary().additional_properties;
+        assert_eq!(properties.get("added-delete-files").unwrap(), "1");
+        assert_eq!(properties.get("added-position-deletes").unwrap(), "2");
+        assert_eq!(properties.get("total-data-files").unwrap(), "1");
+        assert_eq!(properties.get("total-records").unwrap(), "2");
+
+        // The data manifest is carried forward next to the new delete manifest
+        let manifest_list = snapshot
+            .load_manifest_list(table.file_io(), table.metadata())
+            .unwrap();
+        let contents: Vec<_> = manifest_list
+            .entries()
+            .iter()
+            .map(|manifest| manifest.content)
+            .collect();
+        assert_eq!(
+            contents,
+            vec![ManifestContentType::Data, ManifestContentType::Deletes]
+        );
+
+        let manifest = manifest_list.entries()[1]
+            .load_manifest(table.file_io())
+            .unwrap();
+        assert_eq!(manifest.entries().len(), 1);
+        let delete_file = manifest.entries()[0].data_file();
+        assert_eq!(delete_file.content_type(), DataContentType::PositionDeletes);
+        assert_eq!(
+            delete_file.referenced_data_file(),
+            Some("test/1.parquet".to_string())
+        );
+    }
+
+    #[test]
+    fn test_row_delta_data_and_deletes() {
+        let table = table_with_data();
+        let tx = Transaction::new(&table);
+        let action = tx
+            .row_delta()
+            .add_data_files(vec![file(
+                &table,
+                DataContentType::Data,
+                "test/2.parquet",
+            )])
+            .add_delete_files(vec![file(
+                &table,
+                DataContentType::PositionDeletes,
+                "test/1-deletes.parquet",
+            )]);
+        let mut action_commit = Arc::new(action).commit(&table).unwrap();
+        let updates = action_commit.take_updates();
+
+        let snapshot = new_snapshot(&updates);
+        assert_eq!(snapshot.summary().operation, Operation::Overwrite);
+        let properties = &snapshot.summary().additional_properties;
+        // The totals of the previous snapshot are kept
+        assert_eq!(properties.get("total-data-files").unwrap(), "2");
+        assert_eq!(properties.get("total-delete-files").unwrap(), "1");
+
+        let manifest_list = snapshot
+            .load_manifest_list(table.file_io(), table.metadata())
+            .unwrap();
+        assert_eq!(manifest_list.entries().len(), 3);
+    }
+
+    #[test]
+    fn test_row_delta_invalid_files() {
+        let table = table_with_data();
+        let tx = Transaction::new(&table);
+
+        // Nothing to commit
+        assert!(Arc::new(tx.row_delta()).commit(&table).is_err());
+
+        // Data files cannot be added as delete files
+        let action = tx.row_delta().add_delete_files(vec![file(
+            &table,
+            DataContentType::Data,
+            "test/2.parquet",
+        )]);
+        assert!(Arc::new(action).commit(&table).is_err());
+
+        // Delete files cannot be added as data files
+        let action = tx.row_delta().add_data_files(vec![file(
+            &table,
+            DataContentType::PositionDeletes,
+            "test/1-deletes.parquet",
+        )]);
+        assert!(Arc::new(action).commit(&table).is_err());
+    }
+}
@@ -15,18 +15,18 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::ops::RangeFrom;
 
 use uuid::Uuid;
 
 use crate::error::Result;
 use crate::spec::{
-    DataFile, DataFileFormat, FormatVersion, MAIN_BRANCH, ManifestContentType,
-    ManifestEntry, ManifestFile, ManifestListWriter, ManifestWriter,
-    ManifestWriterBuilder, Operation, Snapshot, SnapshotReference, SnapshotRetention,
-    SnapshotSummaryCollector, Struct, StructType, Summary, TableProperties,
-    update_snapshot_summaries,
+    DataContentType, DataFile, DataFileFormat, FormatVersion, MAIN_BRANCH,
+    ManifestContentType, ManifestEntry, ManifestFile, ManifestListWriter,
+    ManifestWriter, ManifestWriterBuilder, Operation, PartitionSpecRef, Snapshot,
+    SnapshotReference, SnapshotRetention, SnapshotSummaryCollector, Struct,
+    StructType, Summary, TableProperties, update_snapshot_summaries,
 };
 use crate::table::Table;
 use crate::transaction::ActionCommit;
@@ -57,8 +57,9 @@ const META_ROOT_PATH: &str = "metadata";
 ///    - An `Append` operation typically includes all existing manifests plus new ones
 ///    - An `Overwrite` operation might exclude manifests for partitions being overwritten
 ///
-/// 3. **Delete Entry Processing**: The `delete_entries()` method is intended for future delete
-///    operations to specify which manifest entries should be marked as deleted.
+/// 3. **Delete Entry Processing**: The `delete_entries()` method specifies which live manifest
+///    entries are removed by the snapshot. They are written as deleted entries to new
+///    manifests and counted as removed in the snapshot summary.
 pub(crate) trait SnapshotProduceOperation: Send + Sync {
     /// Returns the operation type that will be recorded in the snapshot summary.
     ///
@@ -67,7 +68,9 @@ pub(crate) trait SnapshotProduceOperation: Send + Sync {
     fn operation(&self) -> Operation;
 
     /// Returns manifest entries that should be marked as deleted in the new snapshot.
-    #[allow(unused)]
+    ///
+    /// Manifests of the current snapshot holding these entries must not be returned by
+    /// [`SnapshotProduceOperation::existing_manifest`].
     fn delete_entries(
         &self,
         snapshot_produce: &SnapshotProducer,
@@ -85,6 +88,12 @@ pub(crate) trait SnapshotProduceOperation: Send + Sync {
         &self,
         snapshot_produce: &SnapshotProducer<'_>,
     ) -> Result<Vec<ManifestFile>>;
+
+    /// Returns `true` if the operation replaces all data of the table, in which case
+    /// the totals of the previous snapshot summary are not carried forward.
+    fn truncates_table(&self) -> bool {
+        self.operation() == Operation::Overwrite
+    }
 }
 
 pub(crate) struct DefaultManifestProcess;
@@ -114,6 +123,7 @@ pub(crate) struct SnapshotProducer<'a> {
     key_metadata: Option<Vec<u8>>,
     snapshot_properties: HashMap<String, String>,
     added_data_files: Vec<DataFile>,
+    added_delete_files: Vec<DataFile>,
     // A counter used to generate unique manifest file names.
     // It starts from 0 and increments for each new manifest file.
     // Note: This counter is limited to the range of (0..u64::MAX).
@@ -135,10 +145,20 @@ impl<'a> SnapshotProducer<'a> {
             key_metadata,
             snapshot_properties,
             added_data_files,
+            added_delete_files: vec![],
             manifest_counter: (0..),
         }
     }
 
+    /// Add delete files to the snapshot, written to a delete manifest.
+    pub(crate) fn with_added_delete_files(
+        mut self,
+        delete_files: Vec<DataFile>,
+    ) -> Self {
+        self.added_delete_files = delete_files;
+        self
+    }
+
     pub(crate) fn validate_added_data_files(&self) -> Result<()> {
         for data_file in &self.added_data_files {
             if data_file.content_type() != crate::spec::DataContentType::Data {
@@ -165,6 +185,49 @@ impl<'a> SnapshotProducer<'a> {
         Ok(())
     }
 
+    pub(crate) fn validate_added_delete_files(&self) -> Result<()> {
+        if self.added_delete_files.is_empty() {
+            return Ok(());
+        }
+
+        if self.table.metadata().format_version() == FormatVersion::V1 {
+            return Err(Error::new(
+                ErrorKind::FeatureUnsupported,
+                "Delete files are not supported in format version 1",
+            ));
+        }
+
+        for delete_file in &self.added_delete_files {
+            if !matches!(
+                delete_file.content_type(),
+                DataContentType::PositionDeletes | DataContentType::EqualityDeletes
+            ) {
+                return Err(Error::new(
+                    ErrorKind::DataInvalid,
+                    format!(
+                        "Only delete content types are allowed for delete files, got {:?} for {}",
+                        delete_file.content_type(),
+                        delete_file.file_path()
+                    ),
+                ));
+            }
+            if self.table.metadata().default_partition_spec_id()
+                != delete_file.partition_spec_id
+            {
+                return Err(Error::new(
+                    ErrorKind::DataInvalid,
+                    "Delete file partition spec id does not match table default partition spec id",
+                ));
+            }
+            Self::validate_partition_value(
+                delete_file.partition(),
+                self.table.metadata().default_partition_type(),
+            )?;
+        }
+
+        Ok(())
+    }
+
     pub(crate) fn validate_duplicate_files(&self) -> Result<()> {
         let new_files: HashSet<&str> = self
             .added_data_files
@@ -228,6 +291,7 @@ impl<'a> SnapshotProducer<'a> {
     fn new_manifest_writer(
         &mut self,
         content: ManifestContentType,
+        partition_spec: &PartitionSpecRef,
     ) -> Result<ManifestWriter> {
         let new_manifest_path = format!(
             "{}/{}/{}-m{}.{}",
@@ -243,11 +307,7 @@ impl<'a> SnapshotProducer<'a> {
             Some(self.snapshot_id),
             self.key_metadata.clone(),
             self.table.metadata().current_schema().clone(),
-            self.table
-                .metadata()
-                .default_partition_spec()
-                .as_ref()
-                .clone(),
+            partition_spec.as_ref().clone(),
         );
         match self.table.metadata().format_version() {
             FormatVersion::V1 => Ok(builder.build_v1()),
@@ -295,19 +355,30 @@ impl<'a> SnapshotProducer<'a> {
         Ok(())
     }
 
-    // Write manifest file for added data files and return the ManifestFile for ManifestList.
-    fn write_added_manifest(&mut self) -> Result<ManifestFile> {
-        let added_data_files = std::mem::take(&mut self.added_data_files);
-        if added_data_files.is_empty() {
+    // Write manifest file for added data or delete files and return the ManifestFile for
+    // ManifestList.
+    fn write_added_manifest(
+        &mut self,
+        content: ManifestContentType,
+    ) -> Result<ManifestFile> {
+        let added_files = match content {
+            ManifestContentType::Data => std::mem::take(&mut self.added_data_files),
+            ManifestContentType::Deletes => {
+                std::mem::take(&mut self.added_delete_files)
+            }
+        };
+        if added_files.is_empty() {
             return Err(Error::new(
                 ErrorKind::PreconditionFailed,
-                "No added data files found when write an added manifest file",
+                format!(
+                    "No added files found when write an added {content} manifest file"
+                ),
             ));
         }
 
         let snapshot_id = self.snapshot_id;
         let format_version = self.table.metadata().format_version();
-        let manifest_entries = added_data_files.into_iter().map(|data_file| {
+        let manifest_entries = added_files.into_iter().map(|data_file| {
             let builder = ManifestEntry::builder()
                 .status(crate::spec::ManifestStatus::Added)
                 .data_file(data_file);
@@ -319,13 +390,57 @@ impl<'a> SnapshotProducer<'a> {
                 builder.build()
             }
         });
-        let mut writer = self.new_manifest_writer(ManifestContentType::Data)?;
+        let partition_spec = self.table.metadata().default_partition_spec().clone();
+        let mut writer = self.new_manifest_writer(content, &partition_spec)?;
         for entry in manifest_entries {
             writer.add_entry(entry)?;
         }
         writer.write_manifest_file()
     }
 
+    // The partition spec with the given id, which files written under older specs of the
+    // table still refer to.
+    fn partition_spec(&self, spec_id: i32) -> Result<PartitionSpecRef> {
+        self.table
+            .metadata()
+            .partition_spec_by_id(spec_id)
+            .cloned()
+            .ok_or_else(|| {
+                Error::new(
+                    ErrorKind::DataInvalid,
+                    format!("Partition spec with id {spec_id} not found"),
+                )
+            })
+    }
+
+    // Write manifest files marking the given entries as deleted and return the ManifestFiles
+    // for ManifestList. A manifest holds the files of a single partition spec, so entries of
+    // different specs go to separate manifests.
+    fn write_deleted_manifests(
+        &mut self,
+        content: ManifestContentType,
+        entries: Vec<ManifestEntry>,
+    ) -> Result<Vec<ManifestFile>> {
+        let mut entries_by_spec: BTreeMap<i32, Vec<ManifestEntry>> = BTreeMap::new();
+        for entry in entries {
+            entries_by_spec
+                .entry(entry.data_file().partition_spec_id)
+                .or_default()
+                .push(entry);
+        }
+
+        let mut manifest_files = Vec::with_capacity(entries_by_spec.len());
+        for (spec_id, entries) in entries_by_spec {
+            let partition_spec = self.partition_spec(spec_id)?;
+            let mut writer = self.new_manifest_writer(content, &partition_spec)?;
+            for entry in entries {
+                writer.add_delete_entry(entry)?;
+            }
+            manifest_files.push(writer.write_manifest_file()?);
+        }
+        Ok(manifest_files)
+    }
+
     fn manifest_file<OP: SnapshotProduceOperation, MP: ManifestProcess>(
         &mut self,
         snapshot_produce_operation: &OP,
@@ -336,10 +451,15 @@ impl<'a> SnapshotProducer<'a> {
         // TODO: Allowing snapshot property setup with no added data files is a workaround.
         // We should clean it up after all necessary actions are supported.
         // For details, please refer to https://github.com/apache/iceberg-rust/issues/1548
-        if self.added_data_files.is_empty() && self.snapshot_properties.is_empty() {
+        let deleted_entries = snapshot_produce_operation.delete_entries(self)?;
+        if self.added_data_files.is_empty()
+            && self.added_delete_files.is_empty()
+            && deleted_entries.is_empty()
+            && self.snapshot_properties.is_empty()
+        {
             return Err(Error::new(
                 ErrorKind::PreconditionFailed,
-                "No added data files or added snapshot properties found when write a manifest file",
+                "No added data files, delete files, deleted entries or snapshot properties found when write a manifest file",
             ));
         }
 
@@ -349,12 +469,32 @@ impl<'a> SnapshotProducer<'a> {
 
         // Process added entries.
         if !self.added_data_files.is_empty() {
-            let added_manifest = self.write_added_manifest()?;
+            let added_manifest =
+                self.write_added_manifest(ManifestContentType::Data)?;
+            manifest_files.push(added_manifest);
+        }
+
+        // Process added delete files.
+        if !self.added_delete_files.is_empty() {
+            let added_manifest =
+                self.write_added_manifest(ManifestContentType::Deletes)?;
             manifest_files.push(added_manifest);
         }
 
-        // # TODO
-        // Support process delete entries.
+        // Process deleted entries, data and delete files going to separate manifests.
+        let (deleted_data_entries, deleted_delete_entries): (Vec<_>, Vec<_>) =
+            deleted_entries
+                .into_iter()
+                .partition(|entry| entry.content_type() == DataContentType::Data);
+        for (content, entries) in [
+            (ManifestContentType::Data, deleted_data_entries),
+            (ManifestContentType::Deletes, deleted_delete_entries),
+        ] {
+            if !entries.is_empty() {
+                manifest_files
+                    .extend(self.write_deleted_manifests(content, entries)?);
+            }
+        }
 
         let manifest_files = manifest_process.process_manifests(self, manifest_files);
         Ok(manifest_files)
@@ -383,7 +523,8 @@ impl<'a> SnapshotProducer<'a> {
 
         summary_collector.set_partition_summary_limit(partition_summary_limit);
 
-        for data_file in &self.added_data_files {
+        for data_file in self.added_data_files.iter().chain(&self.added_delete_files)
+        {
             summary_collector.add_file(
                 data_file,
                 table_metadata.current_schema().clone(),
@@ -391,10 +532,16 @@ impl<'a> SnapshotProducer<'a> {
             );
         }
 
-        let previous_snapshot = table_metadata
-            .snapshot_by_id(self.snapshot_id)
-            .and_then(|snapshot| snapshot.parent_snapshot_id())
-            .and_then(|parent_id| table_metadata.snapshot_by_id(parent_id));
+        for entry in snapshot_produce_operation.delete_entries(self)? {
+            summary_collector.remove_file(
+                entry.data_file(),
+                table_metadata.current_schema().clone(),
+                self.partition_spec(entry.data_file().partition_spec_id)?,
+            );
+        }
+
+        // The new snapshot is not part of the metadata yet, its parent is the current one
+        let previous_snapshot = table_metadata.current_snapshot();
 
         let mut additional_properties = summary_collector.build();
         additional_properties.extend(self.snapshot_properties.clone());
@@ -407,7 +554,7 @@ impl<'a> SnapshotProducer<'a> {
         update_snapshot_summaries(
             summary,
             previous_snapshot.map(|s| s.summary()),
-            snapshot_produce_operation.operation() == Operation::Overwrite,
+            snapshot_produce_operation.truncates_table(),
         )
     }
 
@@ -460,8 +607,8 @@ impl<'a> SnapshotProducer<'a> {
         };
 
         // Calling self.summary() before self.manifest_file() is important because self.added_data_files
-        // will be set to an empty vec after self.manifest_file() returns, resulting in an empty summary
-        // being generated.
+        // and self.added_delete_files will be set to an empty vec after self.manifest_file() returns,
+        // resulting in an empty summary being generated.
         let summary = self.summary(&snapshot_produce_operation).map_err(|err| {
             Error::new(ErrorKind::Unexpected, "Failed to create snapshot summary.")
                 .with_source(err)
@@ -0,0 +1,642 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashSet;
+
+use arrow_array::{Array, StringArray};
+
+use crate::arrow::delete_file_loader::BasicDeleteFileLoader;
+use crate::error::Result;
+use crate::io::FileIO;
+use crate::spec::{
+    DataContentType, DataFile, MAIN_BRANCH, ManifestStatus, TableProperties,
+};
+use crate::table::Table;
+use crate::transaction::{ApplyTransactionAction, Transaction};
+use crate::{Catalog, Error, ErrorKind, TableCommit, TableRequirement, TableUpdate};
+
+/// A transaction whose actions are applied as soon as they are staged.
+///
+/// [`Transaction`] replays its actions against the latest table metadata
+/// when it is committed. A `StagedTransaction` instead applies the actions of
+/// each staged [`Transaction`] right away, so [`StagedTransaction::table`]
+/// reflects all changes made so far and can be read before they are committed
+/// to the catalog.
+///
+/// Requirements that only hold for the staged table are checked while
+/// staging. The remaining ones are checked against the catalog on commit.
+/// When the base table changed meanwhile, staged snapshots that only add data
+/// and delete files are rebased on the latest table and the commit is retried,
+/// see [`StagedTransaction::rebase`].
+#[derive(Clone)]
+pub struct StagedTransaction {
+    base: Table,
+    table: Table,
+    updates: Vec<TableUpdate>,
+    requirements: Vec<TableRequirement>,
+}
+
+impl StagedTransaction {
+    /// Creates a new staged transaction on top of `table`.
+    pub fn new(table: &Table) -> Self {
+        Self {
+            base: table.clone(),
+            table: table.clone(),
+            updates: vec![],
+            requirements: vec![],
+        }
+    }
+
+    /// The table the transaction started from.
+    pub fn base(&self) -> &Table {
+        &self.base
+    }
+
+    /// The table with all staged updates applied.
+    pub fn table(&self) -> &Table {
+        &self.table
+    }
+
+    /// The updates staged so far.
+    pub fn updates(&self) -> &[TableUpdate] {
+        &self.updates
+    }
+
+    /// Returns `true` if nothing has been staged.
+    pub fn is_empty(&self) -> bool {
+        self.updates.is_empty()
+    }
+
+    /// Apply the actions of `tx` to the staged table.
+    ///
+    /// The transaction's own table is ignored, its actions are applied on top
+    /// of [`StagedTransaction::table`]. Returns the updates added by `tx`.
+    pub fn stage(&mut self, tx: Transaction) -> Result<&[TableUpdate]> {
+        let mut table = self.table.clone();
+        let mut updates = vec![];
+        let mut requirements = vec![];
+
+        for action in tx.actions {
+            let action_commit = action.commit(&table)?;
+            table = Transaction::apply(
+                table,
+                action_commit,
+                &mut updates,
+                &mut requirements,
+            )?;
+        }
+
+        let staged_from = self.updates.len();
+        self.table = table;
+        self.updates.extend(updates);
+        self.requirements
+            .extend(requirements.into_iter().filter(|requirement| {
+                requirement.check(Some(self.base.metadata())).is_ok()
+            }));
+
+        Ok(&self.updates[staged_from..])
+    }
+
+    /// Build the catalog commit for the staged updates.
+    pub fn into_table_commit(self) -> TableCommit {
+        TableCommit::builder()
+            .ident(self.base.identifier().to_owned())
+            .updates(self.updates)
+            .requirements(self.requirements)
+            .build()
+    }
+
+    /// Commit the staged updates to `catalog`.
+    ///
+    /// A commit that conflicts with a concurrent change is rebased on the
+    /// latest table and retried, following the table's commit retry
+    /// properties.
+    pub fn commit(mut self, catalog: &dyn Catalog) -> Result<Table> {
+        if self.is_empty() {
+            return Ok(self.table);
+        }
+
+        let table_props = TableProperties::try_from(
+            self.base.metadata().properties(),
+        )
+        .map_err(|e| {
+            Error::new(ErrorKind::DataInvalid, "Invalid table properties")
+                .with_source(e)
+        })?;
+
+        let mut backoff = Transaction::build_backoff(table_props)?;
+        loop {
+            match catalog.update_table(self.clone().into_table_commit()) {
+                Ok(table) => return Ok(table),
+                Err(e) if e.retryable() => {
+                    let Some(delay) = backoff.next() else {
+                        return Err(e);
+                    };
+                    std::thread::sleep(delay);
+
+                    let latest = catalog.load_table(self.base.identifier())?;
+                    self = self.rebase(&latest)?;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Apply the staged changes to `table`, a later version of the base table,
+    /// as if they had been staged on top of it.
+    ///
+    /// The data and delete files of each staged snapshot are added again in a
+    /// snapshot of their own. Fails with [`ErrorKind::CatalogCommitConflicts`]
+    /// when other changes were staged, or when a data file the staged position
+    /// deletes apply to was removed or gained deletes since the base table.
+    pub fn rebase(&self, table: &Table) -> Result<Self> {
+        if table.metadata_location() == self.base.metadata_location()
+            && table.metadata() == self.base.metadata()
+        {
+            return Ok(self.clone());
+        }
+
+        let staged_snapshots = self.staged_snapshots()?;
+        self.validate_deleted_data_files(table, &staged_snapshots)?;
+
+        let mut rebased = StagedTransaction::new(table);
+        for snapshot in staged_snapshots {
+            let tx = Transaction::new(rebased.table());
+            let tx = if snapshot.delete_files.is_empty() {
+                tx.fast_append()
+                    .add_data_files(snapshot.data_files)
+                    .apply(tx)?
+            } else {
+                tx.row_delta()
+                    .add_data_files(snapshot.data_files)
+                    .add_delete_files(snapshot.delete_files)
+                    .apply(tx)?
+            };
+            rebased.stage(tx)?;
+        }
+
+        Ok(rebased)
+    }
+
+    /// The files added by each staged snapshot.
+    ///
+    /// Fails if anything but snapshots adding files was staged.
+    fn staged_snapshots(&self) -> Result<Vec<StagedSnapshot>> {
+        let file_io = self.table.file_io();
+        let mut staged_snapshots = vec![];
+
+        for update in &self.updates {
+            let snapshot = match update {
+                TableUpdate::AddSnapshot { snapshot } => snapshot,
+                TableUpdate::SetSnapshotRef { ref_name, .. }
+                    if ref_name == MAIN_BRANCH =>
+                {
+                    continue;
+                }
+                _ => {
+                    return Err(rebase_conflict(
+                        "Only added data and delete files can be applied to a \
+                         concurrently updated table",
+                    ));
+                }
+            };
+
+            let mut staged_snapshot = StagedSnapshot::default();
+            let manifest_list =
+                snapshot.load_manifest_list(file_io, self.table.metadata())?;
+            for manifest_file in manifest_list.entries().iter().filter(|manifest| {
+                manifest.added_snapshot_id == snapshot.snapshot_id()
+            }) {
+                let manifest = manifest_file.load_manifest(file_io)?;
+                for entry in manifest.entries() {
+                    if entry.status() != ManifestStatus::Added {
+                        return Err(rebase_conflict(
+                            "Removed files cannot be applied to a concurrently \
+                             updated table",
+                        ));
+                    }
+
+                    // Row ids are assigned again when the file is added
+                    let mut data_file = entry.data_file().clone();
+                    data_file.first_row_id = None;
+                    match data_file.content_type() {
+                        DataContentType::Data => {
+                            staged_snapshot.data_files.push(data_file)
+                        }
+                        _ => staged_snapshot.delete_files.push(data_file),
+                    }
+                }
+            }
+            staged_snapshots.push(staged_snapshot);
+        }
+
+        Ok(staged_snapshots)
+    }
+
+    /// Check that no data file the staged position deletes apply to was
+    /// removed or gained deletes in the snapshots `table` added after the base
+    /// table.
+    fn validate_deleted_data_files(
+        &self,
+        table: &Table,
+        staged_snapshots: &[StagedSnapshot],
+    ) -> Result<()> {
+        let added_data_files: HashSet<&str> = staged_snapshots
+            .iter()
+            .flat_map(|snapshot| &snapshot.data_files)
+            .map(|data_file| data_file.file_path())
+            .collect();
+
+        let mut deleted_data_files = HashSet::new();
+        for delete_file in staged_snapshots
+            .iter()
+            .flat_map(|snapshot| &snapshot.delete_files)
+        {
+            deleted_data_files
+                .extend(referenced_data_files(self.table.file_io(), delete_file)?);
+        }
+        deleted_data_files.retain(|path| !added_data_files.contains(path.as_str()));
+        if deleted_data_files.is_empty() {
+            return Ok(());
+        }
+
+        let base_snapshot_id = self.base.metadata().current_snapshot_id();
+        match ChangedDataFiles::since(table, base_snapshot_id)? {
+            ChangedDataFiles::All => Err(rebase_conflict(
+                "The table was concurrently changed in a way the deletes of the \
+                 transaction cannot be applied to",
+            )),
+            ChangedDataFiles::Files(changed) => {
+                match deleted_data_files
+                    .iter()
+                    .find(|path| changed.contains(*path))
+                {
+                    Some(path) => Err(rebase_conflict(format!(
+                        "Rows deleted by the transaction from {path} were concurrently \
+                         changed"
+                    ))),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+/// The data files changed by the snapshots committed on top of a base
+/// snapshot of a table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChangedDataFiles {
+    /// The data files that were removed or got rows deleted.
+    Files(HashSet<String>),
+    /// Any data file may have changed, because equality deletes were added or
+    /// the base snapshot is no longer an ancestor of the current one.
+    All,
+}
+
+impl ChangedDataFiles {
+    /// The data files changed by the snapshots of `table` committed after
+    /// the snapshot `base_snapshot_id`, `None` when the base had no snapshot.
+    pub fn since(table: &Table, base_snapshot_id: Option<i64>) -> Result<Self> {
+        let mut changed = HashSet::new();
+
+        // Walk back from the latest snapshot to the base snapshot
+        let metadata = table.metadata();
+        let mut snapshot = metadata.current_snapshot();
+        while let Some(current) = snapshot {
+            if Some(current.snapshot_id()) == base_snapshot_id {
+                return Ok(Self::Files(changed));
+            }
+
+            let manifest_list =
+                current.load_manifest_list(table.file_io(), metadata)?;
+            for manifest_file in manifest_list.entries().iter().filter(|manifest| {
+                manifest.added_snapshot_id == current.snapshot_id()
+            }) {
+                let manifest = manifest_file.load_manifest(table.file_io())?;
+                for entry in manifest.entries() {
+                    match (entry.content_type(), entry.status()) {
+                        (DataContentType::Data, ManifestStatus::Deleted) => {
+                            changed.insert(entry.file_path().to_string());
+                        }
+                        (DataContentType::EqualityDeletes, ManifestStatus::Added) => {
+                            return Ok(Self::All);
+                        }
+                        (DataContentType::PositionDeletes, ManifestStatus::Added) => {
+                            changed.extend(referenced_data_files(
+                                table.file_io(),
+                                entry.data_file(),
+                            )?);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+
+            snapshot = current
+                .parent_snapshot_id()
+                .and_then(|parent_id| metadata.snapshot_by_id(parent_id));
+        }
+
+        match base_snapshot_id {
+            None => Ok(Self::Files(changed)),
+            Some(_) => Ok(Self::All),
+        }
+    }
+
+    /// Whether the data file at `path` was changed.
+    pub fn contains(&self, path: &str) -> bool {
+        match self {
+            Self::Files(files) => files.contains(path),
+            Self::All => true,
+        }
+    }
+}
+
+/// The data and delete files added by a staged snapshot.
+#[derive(Default)]
+struct StagedSnapshot {
+    data_files: Vec<DataFile>,
+    delete_files: Vec<DataFile>,
+}
+
+/// The data files the delete file `delete_file` applies to.
+///
+/// Fails for equality deletes, which may apply to any data file.
+fn referenced_data_files(
+    file_io: &FileIO,
+    delete_file: &DataFile,
+) -> Result<Vec<String>> {
+    if delete_file.content_type() == DataContentType::EqualityDeletes {
+        return Err(rebase_conflict(
+            "Equality deletes cannot be applied to a concurrently updated table",
+        ));
+    }
+    if let Some(path) = delete_file.referenced_data_file() {
+        return Ok(vec![path]);
+    }
+
+    let mut paths = vec![];
+    for batch in BasicDeleteFileLoader::new(file_io.clone())
+        .parquet_to_batch_iterator(delete_file.file_path())?
+    {
+        let batch = batch?;
+        let Some(file_paths) = batch.column(0).as_any().downcast_ref::<StringArray>()
+        else {
+            return Err(Error::new(
+                ErrorKind::DataInvalid,
+                "Could not downcast file paths array to StringArray",
+            ));
+        };
+        paths.extend(file_paths.iter().flatten().map(str::to_string));
+    }
+    // Deletes are sorted by file path
+    paths.dedup();
+
+    Ok(paths)
+}
+
+fn rebase_conflict(message: impl Into<String>) -> Error {
+    Error::new(ErrorKind::CatalogCommitConflicts, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::memory::tests::new_memory_catalog;
+    use crate::spec::{
+        DataContentType, DataFile, DataFileBuilder, DataFileFormat, Literal, Struct,
+    };
+    use crate::table::Table;
+    use crate::transaction::tests::make_v3_minimal_table_in_catalog;
+    use crate::transaction::{
+        ApplyTransactionAction, ChangedDataFiles, StagedTransaction, Transaction,
+    };
+    use crate::{Catalog, ErrorKind};
+
+    fn file_with_rows(record_count: u64) -> DataFile {
+        DataFileBuilder::default()
+            .content(DataContentType::Data)
+            .file_path(format!("test/{record_count}.parquet"))
+            .file_format(DataFileFormat::Parquet)
+            .file_size_in_bytes(100)
+            .record_count(record_count)
+            .partition(Struct::from_iter([Some(Literal::long(0))]))
+            .partition_spec_id(0)
+            .build()
+            .unwrap()
+    }
+
+    /// A row delta deleting rows of the data file `data_file_path`.
+    fn delete(table: &Table, data_file_path: &str, path: &str) -> Transaction {
+        let delete_file = DataFileBuilder::default()
+            .content(DataContentType::PositionDeletes)
+            .file_path(path.to_string())
+            .file_format(DataFileFormat::Parquet)
+            .file_size_in_bytes(100)
+            .record_count(1)
+            .referenced_data_file(Some(data_file_path.to_string()))
+            .partition(Struct::from_iter([Some(Literal::long(0))]))
+            .partition_spec_id(0)
+            .build()
+            .unwrap();
+
+        let tx = Transaction::new(table);
+        tx.row_delta()
+            .add_delete_files(vec![delete_file])
+            .apply(tx)
+            .unwrap()
+    }
+
+    fn append(table: &Table, record_count: u64) -> Transaction {
+        let tx = Transaction::new(table);
+        tx.fast_append()
+            .add_data_files(vec![file_with_rows(record_count)])
+            .apply(tx)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_staged_appends() {
+        let catalog = new_memory_catalog();
+        let table = make_v3_minimal_table_in_catalog(&catalog);
+
+        let mut staged = StagedTransaction::new(&table);
+        assert!(staged.is_empty());
+
+        let tx = append(staged.table(), 30);
+        assert_eq!(staged.stage(tx).unwrap().len(), 2);
+        let first_snapshot = staged.table().metadata().current_snapshot_id();
+        assert!(first_snapshot.is_some());
+
+        let tx = append(staged.table(), 17);
+        staged.stage(tx).unwrap();
+        let second = staged.table().metadata().current_snapshot().unwrap();
+        assert_eq!(second.parent_snapshot_id(), first_snapshot);
+        assert_eq!(staged.table().metadata().next_row_id(), 47);
+
+        // Nothing is visible in the catalog before commit
+        let loaded = catalog.load_table(table.identifier()).unwrap();
+        assert!(loaded.metadata().current_snapshot().is_none());
+
+        let committed = staged.commit(&catalog).unwrap();
+        assert_eq!(committed.metadata().snapshots().count(), 2);
+        assert_eq!(committed.metadata().next_row_id(), 47);
+    }
+
+    #[test]
+    fn test_staged_commit_rebased() {
+        let catalog = new_memory_catalog();
+        let table = make_v3_minimal_table_in_catalog(&catalog);
+
+        let mut staged = StagedTransaction::new(&table);
+        staged.stage(append(staged.table(), 30)).unwrap();
+
+        // A concurrent commit moves the main branch of the base table
+        let concurrent = append(&table, 10).commit(&catalog).unwrap();
+
+        // The staged append is applied on top of it
+        let committed = staged.commit(&catalog).unwrap();
+        let snapshot = committed.metadata().current_snapshot().unwrap();
+        assert_eq!(
+            snapshot.parent_snapshot_id(),
+            concurrent.metadata().current_snapshot_id()
+        );
+        assert_eq!(committed.metadata().snapshots().count(), 2);
+        assert_eq!(committed.metadata().next_row_id(), 40);
+    }
+
+    #[test]
+    fn test_staged_deletes_rebased() {
+        let catalog = new_memory_catalog();
+        let table = make_v3_minimal_table_in_catalog(&catalog);
+        let table = append(&table, 30).commit(&catalog).unwrap();
+        let table = append(&table, 17).commit(&catalog).unwrap();
+
+        let mut staged = StagedTransaction::new(&table);
+        staged
+            .stage(delete(
+                staged.table(),
+                "test/30.parquet",
+                "test/30-deletes.parquet",
+            ))
+            .unwrap();
+
+        // Concurrent deletes of rows of another data file do not conflict
+        delete(&table, "test/17.parquet", "test/17-deletes.parquet")
+            .commit(&catalog)
+            .unwrap();
+
+        let committed = staged.commit(&catalog).unwrap();
+        assert_eq!(committed.metadata().snapshots().count(), 4);
+    }
+
+    #[test]
+    fn test_staged_commit_conflict() {
+        let catalog = new_memory_catalog();
+        let table = make_v3_minimal_table_in_catalog(&catalog);
+        let table = append(&table, 30).commit(&catalog).unwrap();
+
+        let mut staged = StagedTransaction::new(&table);
+        staged
+            .stage(delete(
+                staged.table(),
+                "test/30.parquet",
+                "test/30-deletes-1.parquet",
+            ))
+            .unwrap();
+
+        // Rows of the same data file are deleted concurrently
+        delete(&table, "test/30.parquet", "test/30-deletes-2.parquet")
+            .commit(&catalog)
+            .unwrap();
+
+        let err = staged.commit(&catalog).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::CatalogCommitConflicts);
+    }
+
+    #[test]
+    fn test_changed_data_files() {
+        let catalog = new_memory_catalog();
+        let table = make_v3_minimal_table_in_catalog(&catalog);
+        let table = append(&table, 30).commit(&catalog).unwrap();
+        let base = append(&table, 17).commit(&catalog).unwrap();
+        let base_snapshot_id = base.metadata().current_snapshot_id();
+
+        let unchanged = ChangedDataFiles::since(&base, base_snapshot_id).unwrap();
+        assert_eq!(unchanged, ChangedDataFiles::Files(HashSet::new()));
+
+        // Appends change no data file, deletes change the file they apply to
+        let table = append(&base, 10).commit(&catalog).unwrap();
+        let table = delete(&table, "test/30.parquet", "test/30-deletes.parquet")
+            .commit(&catalog)
+            .unwrap();
+        let changed = ChangedDataFiles::since(&table, base_snapshot_id).unwrap();
+        assert!(changed.contains("test/30.parquet"));
+        assert!(!changed.contains("test/17.parquet"));
+        assert!(!changed.contains("test/10.parquet"));
+
+        // Truncating removes all data files
+        let tx = Transaction::new(&table);
+        let table = tx.truncate().apply(tx).unwrap().commit(&catalog).unwrap();
+        let changed = ChangedDataFiles::since(&table, base_snapshot_id).unwrap();
+        assert!(changed.contains("test/17.parquet"));
+        assert!(changed.contains("test/10.parquet"));
+
+        // Snapshots that are no longer ancestors may have changed anything
+        let changed = ChangedDataFiles::since(&table, Some(-1)).unwrap();
+        assert_eq!(changed, ChangedDataFiles::All);
+    }
+
+    #[test]
+    fn test_staged_properties_not_rebased() {
+        let catalog = new_memory_catalog();
+        let table = make_v3_minimal_table_in_catalog(&catalog);
+
+        let mut staged = StagedTransaction::new(&table);
+        staged.stage(append(staged.table(), 30)).unwrap();
+        let tx = Transaction::new(staged.table());
+        let tx = tx
+            .update_table_properties()
+            .set("key".to_string(), "value".to_string())
+            .apply(tx)
+            .unwrap();
+        staged.stage(tx).unwrap();
+
+        append(&table, 10).commit(&catalog).unwrap();
+
+        let err = staged.commit(&catalog).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::CatalogCommitConflicts);
+    }
+
+    #[test]
+    fn test_staged_truncate_not_rebased() {
+        let catalog = new_memory_catalog();
+        let table = make_v3_minimal_table_in_catalog(&catalog);
+        let table = append(&table, 30).commit(&catalog).unwrap();
+
+        let mut staged = StagedTransaction::new(&table);
+        let tx = Transaction::new(staged.table());
+        let tx = tx.truncate().apply(tx).unwrap();
+        staged.stage(tx).unwrap();
+
+        append(&table, 10).commit(&catalog).unwrap();
+
+        let err = staged.commit(&catalog).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::CatalogCommitConflicts);
+    }
+}
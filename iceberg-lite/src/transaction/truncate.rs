@@ -0,0 +1,350 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::error::Result;
+use crate::spec::{ManifestEntry, ManifestFile, Operation};
+use crate::table::Table;
+use crate::transaction::snapshot::{
+    DefaultManifestProcess, SnapshotProduceOperation, SnapshotProducer,
+};
+use crate::transaction::{ActionCommit, TransactionAction};
+
+/// TruncateAction is a transaction action that removes all data files and delete files of
+/// the table in a single `delete` snapshot.
+///
+/// The removed files are kept as deleted entries of the new snapshot's manifests. They are
+/// not deleted from storage, as older snapshots still reference them until they expire.
+/// Truncating a table without live files commits no snapshot.
+pub struct TruncateAction {
+    // below are properties used to create SnapshotProducer when commit
+    commit_uuid: Option<Uuid>,
+    key_metadata: Option<Vec<u8>>,
+    snapshot_properties: HashMap<String, String>,
+}
+
+impl TruncateAction {
+    pub(crate) fn new() -> Self {
+        Self {
+            commit_uuid: None,
+            key_metadata: None,
+            snapshot_properties: HashMap::default(),
+        }
+    }
+
+    /// Set commit UUID for the snapshot.
+    pub fn set_commit_uuid(mut self, commit_uuid: Uuid) -> Self {
+        self.commit_uuid = Some(commit_uuid);
+        self
+    }
+
+    /// Set key metadata for manifest files.
+    pub fn set_key_metadata(mut self, key_metadata: Vec<u8>) -> Self {
+        self.key_metadata = Some(key_metadata);
+        self
+    }
+
+    /// Set snapshot summary properties.
+    pub fn set_snapshot_properties(
+        mut self,
+        snapshot_properties: HashMap<String, String>,
+    ) -> Self {
+        self.snapshot_properties = snapshot_properties;
+        self
+    }
+}
+
+/// The live entries of the data and delete manifests of the current snapshot.
+fn live_entries(table: &Table) -> Result<Vec<ManifestEntry>> {
+    let Some(snapshot) = table.metadata().current_snapshot() else {
+        return Ok(vec![]);
+    };
+
+    let manifest_list =
+        snapshot.load_manifest_list(table.file_io(), &table.metadata_ref())?;
+    let mut entries = vec![];
+    for manifest_file in manifest_list.entries() {
+        if !manifest_file.has_added_files() && !manifest_file.has_existing_files() {
+            continue;
+        }
+        let manifest = manifest_file.load_manifest(table.file_io())?;
+        entries.extend(
+            manifest
+                .entries()
+                .iter()
+                .filter(|entry| entry.is_alive())
+                .map(|entry| entry.as_ref().clone()),
+        );
+    }
+
+    Ok(entries)
+}
+
+impl TransactionAction for TruncateAction {
+    fn commit(self: Arc<Self>, table: &Table) -> Result<ActionCommit> {
+        let deleted_entries = live_entries(table)?;
+        if deleted_entries.is_empty() {
+            return Ok(ActionCommit::new(vec![], vec![]));
+        }
+
+        let snapshot_producer = SnapshotProducer::new(
+            table,
+            self.commit_uuid.unwrap_or_else(Uuid::now_v7),
+            self.key_metadata.clone(),
+            self.snapshot_properties.clone(),
+            vec![],
+        );
+
+        snapshot_producer.commit(
+            TruncateOperation { deleted_entries },
+            DefaultManifestProcess,
+        )
+    }
+}
+
+struct TruncateOperation {
+    deleted_entries: Vec<ManifestEntry>,
+}
+
+impl SnapshotProduceOperation for TruncateOperation {
+    fn operation(&self) -> Operation {
+        Operation::Delete
+    }
+
+    fn delete_entries(
+        &self,
+        _snapshot_produce: &SnapshotProducer<'_>,
+    ) -> Result<Vec<ManifestEntry>> {
+        Ok(self.deleted_entries.clone())
+    }
+
+    fn existing_manifest(
+        &self,
+        _snapshot_produce: &SnapshotProducer<'_>,
+    ) -> Result<Vec<ManifestFile>> {
+        // Every live file is deleted, no manifest is carried forward
+        Ok(vec![])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::TableUpdate;
+    use crate::spec::{
+        DataContentType, DataFile, DataFileBuilder, DataFileFormat, Literal,
+        ManifestContentType, ManifestStatus, Operation, Snapshot, Struct, Transform,
+        UnboundPartitionSpec,
+    };
+    use crate::table::Table;
+    use crate::transaction::tests::make_v2_minimal_table;
+    use crate::transaction::{
+        ApplyTransactionAction, StagedTransaction, Transaction, TransactionAction,
+    };
+
+    fn file(table: &Table, content: DataContentType, path: &str) -> DataFile {
+        let mut builder = DataFileBuilder::default();
+        builder
+            .content(content)
+            .file_path(path.to_string())
+            .file_format(DataFileFormat::Parquet)
+            .file_size_in_bytes(100)
+            .record_count(2)
+            .partition_spec_id(table.metadata().default_partition_spec_id())
+            .partition(Struct::from_iter([Some(Literal::long(300))]));
+        if content == DataContentType::PositionDeletes {
+            builder.referenced_data_file(Some("test/1.parquet".to_string()));
+        }
+        builder.build().unwrap()
+    }
+
+    fn new_snapshot(updates: &[TableUpdate]) -> &Snapshot {
+        if let TableUpdate::AddSnapshot { snapshot } = &updates[0] {
+            snapshot
+        } else {
+            unreachable!()
+        }
+    }
+
+    /// A table with two appended data files and a delete file.
+    fn table_with_data() -> Table {
+        let table = make_v2_minimal_table();
+        let mut staged = StagedTransaction::new(&table);
+
+        let tx = Transaction::new(staged.table());
+        let tx = tx
+            .fast_append()
+            .add_data_files(vec![
+                file(&table, DataContentType::Data, "test/1.parquet"),
+                file(&table, DataContentType::Data, "test/2.parquet"),
+            ])
+            .apply(tx)
+            .unwrap();
+        staged.stage(tx).unwrap();
+
+        let tx = Transaction::new(staged.table());
+        let tx = tx
+            .row_delta()
+            .add_delete_files(vec![file(
+                &table,
+                DataContentType::PositionDeletes,
+                "test/1-deletes.parquet",
+            )])
+            .apply(tx)
+            .unwrap();
+        staged.stage(tx).unwrap();
+
+        staged.table().clone()
+    }
+
+    #[test]
+    fn test_truncate() {
+        let table = table_with_data();
+        let tx = Transaction::new(&table);
+        let mut action_commit = Arc::new(tx.truncate()).commit(&table).unwrap();
+        let updates = action_commit.take_updates();
+
+        let snapshot = new_snapshot(&updates);
+        assert_eq!(snapshot.summary().operation, Operation::Delete);
+        let properties = &snapshot.summary().additional_properties;
+        assert_eq!(properties.get("deleted-data-files").unwrap(), "2");
+        assert_eq!(properties.get("deleted-records").unwrap(), "4");
+        assert_eq!(properties.get("removed-delete-files").unwrap(), "1");
+        assert_eq!(properties.get("removed-position-deletes").unwrap(), "2");
+        assert_eq!(properties.get("total-data-files").unwrap(), "0");
+        assert_eq!(properties.get("total-delete-files").unwrap(), "0");
+        assert_eq!(properties.get("total-records").unwrap(), "0");
+        assert_eq!(properties.get("total-position-deletes").unwrap(), "0");
+
+        // Only manifests of deleted entries remain
+        let manifest_list = snapshot
+            .load_manifest_list(table.file_io(), table.metadata())
+            .unwrap();
+        let contents: Vec<_> = manifest_list
+            .entries()
+            .iter()
+            .map(|manifest| manifest.content)
+            .collect();
+        assert_eq!(
+            contents,
+            vec![ManifestContentType::Data, ManifestContentType::Deletes]
+        );
+        for manifest_file in manifest_list.entries() {
+            assert!(!manifest_file.has_added_files());
+            assert!(!manifest_file.has_existing_files());
+            let manifest = manifest_file.load_manifest(table.file_io()).unwrap();
+            assert!(
+                manifest
+                    .entries()
+                    .iter()
+                    .all(|entry| entry.status() == ManifestStatus::Deleted)
+            );
+        }
+    }
+
+    #[test]
+    fn test_truncate_partition_specs() {
+        // Partition by `y` instead of `x` and append a file with the new spec
+        let table = Transaction::update_table_metadata(
+            table_with_data(),
+            &[
+                TableUpdate::AddSpec {
+                    spec: UnboundPartitionSpec::builder()
+                        .add_partition_field(2, "y".to_string(), Transform::Identity)
+                        .unwrap()
+                        .build(),
+                },
+                TableUpdate::SetDefaultSpec { spec_id: -1 },
+            ],
+        )
+        .unwrap();
+        let new_spec_id = table.metadata().default_partition_spec_id();
+        let mut staged = StagedTransaction::new(&table);
+        let tx = Transaction::new(staged.table());
+        let tx = tx
+            .fast_append()
+            .add_data_files(vec![file(
+                &table,
+                DataContentType::Data,
+                "test/3.parquet",
+            )])
+            .apply(tx)
+            .unwrap();
+        staged.stage(tx).unwrap();
+        let table = staged.table().clone();
+
+        let tx = Transaction::new(&table);
+        let mut action_commit = Arc::new(tx.truncate()).commit(&table).unwrap();
+        let updates = action_commit.take_updates();
+
+        let snapshot = new_snapshot(&updates);
+        let properties = &snapshot.summary().additional_properties;
+        assert_eq!(properties.get("deleted-data-files").unwrap(), "3");
+        assert_eq!(properties.get("total-data-files").unwrap(), "0");
+
+        // Deleted entries are written to a manifest per partition spec
+        let manifest_list = snapshot
+            .load_manifest_list(table.file_io(), table.metadata())
+            .unwrap();
+        let manifests: Vec<_> = manifest_list
+            .entries()
+            .iter()
+            .map(|manifest| (manifest.content, manifest.partition_spec_id))
+            .collect();
+        assert_eq!(
+            manifests,
+            vec![
+                (ManifestContentType::Data, 0),
+                (ManifestContentType::Data, new_spec_id),
+                (ManifestContentType::Deletes, 0),
+            ]
+        );
+        for manifest_file in manifest_list.entries() {
+            let manifest = manifest_file.load_manifest(table.file_io()).unwrap();
+            assert!(manifest.entries().iter().all(|entry| {
+                entry.data_file().partition_spec_id == manifest_file.partition_spec_id
+            }));
+        }
+    }
+
+    #[test]
+    fn test_truncate_scans_no_files() {
+        let table = table_with_data();
+        let mut staged = StagedTransaction::new(&table);
+        let tx = Transaction::new(staged.table());
+        let tx = tx.truncate().apply(tx).unwrap();
+        staged.stage(tx).unwrap();
+
+        let tasks = staged.table().scan().build().unwrap().plan_files().unwrap();
+        assert!(tasks.is_empty());
+    }
+
+    #[test]
+    fn test_truncate_empty_table() {
+        let table = make_v2_minimal_table();
+        let tx = Transaction::new(&table);
+        let mut action_commit = Arc::new(tx.truncate()).commit(&table).unwrap();
+
+        assert!(action_commit.take_updates().is_empty());
+        assert!(action_commit.take_requirements().is_empty());
+    }
+}
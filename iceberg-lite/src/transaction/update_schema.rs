@@ -0,0 +1,574 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use crate::spec::{
+    ListType, MapType, NestedField, NestedFieldRef, PrimitiveType, Schema,
+    StructType, TableMetadata, Type,
+};
+use crate::table::Table;
+use crate::transaction::{ActionCommit, TransactionAction};
+use crate::{Error, ErrorKind, Result, TableRequirement, TableUpdate};
+
+/// A column to add, whose field ids are assigned at commit time.
+#[derive(Debug, PartialEq, Clone)]
+struct PendingColumn {
+    name: String,
+    field_type: Type,
+    doc: Option<String>,
+}
+
+/// Transaction action evolving the current schema of a table.
+///
+/// Columns are referred to by their name in the current schema, nested fields of
+/// structs by their dot-separated full name. Added columns are optional top-level
+/// columns, whose field ids (and those of their nested fields) are assigned from
+/// the table's last column id, so that ids of deleted columns are never reused.
+///
+/// The evolved schema is added to the table and set as its current schema.
+pub struct UpdateSchemaAction {
+    additions: Vec<PendingColumn>,
+    deletions: Vec<String>,
+    renames: Vec<(String, String)>,
+    type_updates: Vec<(String, PrimitiveType)>,
+    optionals: Vec<String>,
+}
+
+impl UpdateSchemaAction {
+    /// Creates a new [`UpdateSchemaAction`] without changes.
+    pub fn new() -> Self {
+        UpdateSchemaAction {
+            additions: vec![],
+            deletions: vec![],
+            renames: vec![],
+            type_updates: vec![],
+            optionals: vec![],
+        }
+    }
+
+    /// Adds an optional top-level column.
+    pub fn add_column(self, name: &str, field_type: Type) -> Self {
+        self.add_column_with_doc(name, field_type, None)
+    }
+
+    /// Adds an optional top-level column with a doc string.
+    pub fn add_column_with_doc(
+        mut self,
+        name: &str,
+        field_type: Type,
+        doc: Option<String>,
+    ) -> Self {
+        self.additions.push(PendingColumn {
+            name: name.to_string(),
+            field_type,
+            doc,
+        });
+        self
+    }
+
+    /// Deletes a column and its nested fields.
+    pub fn delete_column(mut self, name: &str) -> Self {
+        self.deletions.push(name.to_string());
+        self
+    }
+
+    /// Renames a column. Only the last part of the name of a nested field changes.
+    pub fn rename_column(mut self, name: &str, new_name: &str) -> Self {
+        self.renames.push((name.to_string(), new_name.to_string()));
+        self
+    }
+
+    /// Widens the type of a primitive column, from `int` to `long`, from `float` to
+    /// `double`, or to a `decimal` with a larger precision and the same scale.
+    pub fn update_column(mut self, name: &str, new_type: PrimitiveType) -> Self {
+        self.type_updates.push((name.to_string(), new_type));
+        self
+    }
+
+    /// Makes a required column optional.
+    pub fn make_column_optional(mut self, name: &str) -> Self {
+        self.optionals.push(name.to_string());
+        self
+    }
+}
+
+impl Default for UpdateSchemaAction {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The changes of existing fields, by field id.
+#[derive(Default)]
+struct FieldChanges {
+    deletions: HashSet<i32>,
+    renames: HashMap<i32, String>,
+    type_updates: HashMap<i32, PrimitiveType>,
+    optionals: HashSet<i32>,
+}
+
+impl FieldChanges {
+    fn resolve(
+        action: &UpdateSchemaAction,
+        metadata: &TableMetadata,
+        schema: &Schema,
+    ) -> Result<Self> {
+        let field_by_name = |name: &str| {
+            schema.field_by_name(name).ok_or_else(|| {
+                Error::new(
+                    ErrorKind::DataInvalid,
+                    format!("Cannot find field {name} in table schema"),
+                )
+            })
+        };
+
+        let mut changes = FieldChanges::default();
+        for name in &action.deletions {
+            let field = field_by_name(name)?;
+            check_deletable(metadata, schema, name, field.id)?;
+            changes.deletions.insert(field.id);
+        }
+
+        let check_not_deleted = |name: &str, id: i32| {
+            if changes.deletions.contains(&id) {
+                return Err(Error::new(
+                    ErrorKind::DataInvalid,
+                    format!("Cannot update field {name}, it is deleted"),
+                ));
+            }
+            Ok(())
+        };
+
+        let mut renames = HashMap::new();
+        for (name, new_name) in &action.renames {
+            let field = field_by_name(name)?;
+            check_not_deleted(name, field.id)?;
+            renames.insert(field.id, new_name.clone());
+        }
+
+        let mut type_updates = HashMap::new();
+        for (name, new_type) in &action.type_updates {
+            let field = field_by_name(name)?;
+            check_not_deleted(name, field.id)?;
+            let Some(current_type) = field.field_type.as_primitive_type() else {
+                return Err(Error::new(
+                    ErrorKind::DataInvalid,
+                    format!("Cannot change type of non-primitive field {name}"),
+                ));
+            };
+            if !is_valid_promotion(current_type, new_type) {
+                return Err(Error::new(
+                    ErrorKind::DataInvalid,
+                    format!(
+                        "Cannot change type of field {name} from {current_type} to {new_type}"
+                    ),
+                ));
+            }
+            type_updates.insert(field.id, new_type.clone());
+        }
+
+        let mut optionals = HashSet::new();
+        for name in &action.optionals {
+            let field = field_by_name(name)?;
+            check_not_deleted(name, field.id)?;
+            optionals.insert(field.id);
+        }
+
+        changes.renames = renames;
+        changes.type_updates = type_updates;
+        changes.optionals = optionals;
+        Ok(changes)
+    }
+
+    fn update_fields(
+        &self,
+        fields: &[NestedFieldRef],
+    ) -> Result<Vec<NestedFieldRef>> {
+        fields
+            .iter()
+            .filter(|field| !self.deletions.contains(&field.id))
+            .map(|field| self.update_field(field).map(Arc::new))
+            .collect()
+    }
+
+    fn update_field(&self, field: &NestedField) -> Result<NestedField> {
+        let mut field = field.clone();
+        if let Some(new_name) = self.renames.get(&field.id) {
+            field.name = new_name.clone();
+        }
+        if let Some(new_type) = self.type_updates.get(&field.id) {
+            field.field_type = Box::new(Type::Primitive(new_type.clone()));
+        }
+        if self.optionals.contains(&field.id) {
+            field.required = false;
+        }
+
+        let field_type = match field.field_type.as_ref() {
+            Type::Primitive(_) => None,
+            Type::Struct(struct_type) => {
+                let fields = self.update_fields(struct_type.fields())?;
+                check_unique_names(&fields)?;
+                Some(Type::Struct(StructType::new(fields)))
+            }
+            Type::List(list_type) => Some(Type::List(ListType::new(Arc::new(
+                self.update_field(&list_type.element_field)?,
+            )))),
+            Type::Map(map_type) => Some(Type::Map(MapType::new(
+                Arc::new(self.update_field(&map_type.key_field)?),
+                Arc::new(self.update_field(&map_type.value_field)?),
+            ))),
+        };
+        if let Some(field_type) = field_type {
+            field.field_type = Box::new(field_type);
+        }
+        Ok(field)
+    }
+}
+
+/// Whether a field of type `from` can be read as `to`.
+fn is_valid_promotion(from: &PrimitiveType, to: &PrimitiveType) -> bool {
+    match (from, to) {
+        (from, to) if from == to => true,
+        (PrimitiveType::Int, PrimitiveType::Long) => true,
+        (PrimitiveType::Float, PrimitiveType::Double) => true,
+        (
+            PrimitiveType::Decimal { precision, scale },
+            PrimitiveType::Decimal {
+                precision: new_precision,
+                scale: new_scale,
+            },
+        ) => new_precision >= precision && new_scale == scale,
+        _ => false,
+    }
+}
+
+/// Refuses deleting fields that identify rows or that the default partition spec or
+/// sort order are derived from.
+fn check_deletable(
+    metadata: &TableMetadata,
+    schema: &Schema,
+    name: &str,
+    field_id: i32,
+) -> Result<()> {
+    let referenced_by = if schema.identifier_field_ids().any(|id| id == field_id) {
+        Some("the identifier fields")
+    } else if metadata
+        .default_partition_spec()
+        .fields()
+        .iter()
+        .any(|field| field.source_id == field_id)
+    {
+        Some("the default partition spec")
+    } else if metadata
+        .default_sort_order()
+        .fields
+        .iter()
+        .any(|field| field.source_id == field_id)
+    {
+        Some("the default sort order")
+    } else {
+        None
+    };
+
+    match referenced_by {
+        Some(referenced_by) => Err(Error::new(
+            ErrorKind::DataInvalid,
+            format!(
+                "Cannot delete field {name}, it is referenced by {referenced_by}"
+            ),
+        )),
+        None => Ok(()),
+    }
+}
+
+fn check_unique_names(fields: &[NestedFieldRef]) -> Result<()> {
+    let mut names = HashSet::with_capacity(fields.len());
+    for field in fields {
+        if !names.insert(field.name.as_str()) {
+            return Err(Error::new(
+                ErrorKind::DataInvalid,
+                format!(
+                    "Cannot use name {}, another field has the same name",
+                    field.name
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Returns `field_type` with fresh ids for its nested fields, assigned in order after
+/// `last_id`.
+fn assign_fresh_ids(field_type: &Type, last_id: &mut i32) -> Type {
+    fn fresh_field(field: &NestedFieldRef, last_id: &mut i32) -> NestedField {
+        *last_id += 1;
+        let mut fresh = field.as_ref().clone();
+        fresh.id = *last_id;
+        fresh
+    }
+
+    match field_type {
+        Type::Primitive(_) => field_type.clone(),
+        Type::Struct(struct_type) => {
+            // Ids of a struct's fields are assigned before those of their nested fields
+            let mut fields: Vec<_> = struct_type
+                .fields()
+                .iter()
+                .map(|field| fresh_field(field, last_id))
+                .collect();
+            for field in &mut fields {
+                field.field_type =
+                    Box::new(assign_fresh_ids(&field.field_type, last_id));
+            }
+            Type::Struct(StructType::new(fields.into_iter().map(Arc::new).collect()))
+        }
+        Type::List(list_type) => {
+            let mut element = fresh_field(&list_type.element_field, last_id);
+            element.field_type =
+                Box::new(assign_fresh_ids(&element.field_type, last_id));
+            Type::List(ListType::new(Arc::new(element)))
+        }
+        Type::Map(map_type) => {
+            let mut key = fresh_field(&map_type.key_field, last_id);
+            let mut value = fresh_field(&map_type.value_field, last_id);
+            key.field_type = Box::new(assign_fresh_ids(&key.field_type, last_id));
+            value.field_type = Box::new(assign_fresh_ids(&value.field_type, last_id));
+            Type::Map(MapType::new(Arc::new(key), Arc::new(value)))
+        }
+    }
+}
+
+impl TransactionAction for UpdateSchemaAction {
+    fn commit(self: Arc<Self>, table: &Table) -> Result<ActionCommit> {
+        let metadata = table.metadata();
+        let schema = metadata.current_schema();
+
+        let changes = FieldChanges::resolve(&self, metadata, schema)?;
+        let mut fields = changes.update_fields(schema.as_struct().fields())?;
+
+        let mut last_column_id = metadata.last_column_id();
+        for column in &self.additions {
+            last_column_id += 1;
+            let id = last_column_id;
+            let field_type =
+                assign_fresh_ids(&column.field_type, &mut last_column_id);
+            let mut field = NestedField::optional(id, &column.name, field_type);
+            field.doc = column.doc.clone();
+            fields.push(Arc::new(field));
+        }
+        check_unique_names(&fields)?;
+
+        if fields.as_slice() == schema.as_struct().fields() {
+            return Ok(ActionCommit::new(vec![], vec![]));
+        }
+
+        let new_schema = Schema::builder()
+            .with_schema_id(schema.schema_id())
+            .with_fields(fields)
+            .with_identifier_field_ids(schema.identifier_field_ids())
+            .build()?;
+
+        let updates = vec![
+            TableUpdate::AddSchema { schema: new_schema },
+            TableUpdate::SetCurrentSchema { schema_id: -1 },
+        ];
+
+        let requirements = vec![
+            TableRequirement::CurrentSchemaIdMatch {
+                current_schema_id: schema.schema_id(),
+            },
+            TableRequirement::LastAssignedFieldIdMatch {
+                last_assigned_field_id: metadata.last_column_id(),
+            },
+        ];
+
+        Ok(ActionCommit::new(updates, requirements))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::TableUpdate;
+    use crate::spec::{NestedField, PrimitiveType, StructType, Type};
+    use crate::table::Table;
+    use crate::transaction::tests::make_v2_minimal_table;
+    use crate::transaction::{
+        ApplyTransactionAction, StagedTransaction, Transaction, TransactionAction,
+    };
+
+    fn stage_add_column(table: &Table, name: &str, field_type: Type) -> Table {
+        let mut staged = StagedTransaction::new(table);
+        let tx = Transaction::new(staged.table());
+        let tx = tx
+            .update_schema()
+            .add_column(name, field_type)
+            .apply(tx)
+            .unwrap();
+        staged.stage(tx).unwrap();
+        staged.table().clone()
+    }
+
+    fn field_ids(table: &Table) -> Vec<(i32, String)> {
+        table
+            .metadata()
+            .current_schema()
+            .as_struct()
+            .fields()
+            .iter()
+            .map(|field| (field.id, field.name.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_add_column() {
+        let table = make_v2_minimal_table();
+        let tx = Transaction::new(&table);
+        let point = Type::Struct(StructType::new(vec![
+            Arc::new(NestedField::required(
+                1,
+                "lat",
+                PrimitiveType::Double.into(),
+            )),
+            Arc::new(NestedField::required(
+                2,
+                "lon",
+                PrimitiveType::Double.into(),
+            )),
+        ]));
+        let action = tx
+            .update_schema()
+            .add_column("w", PrimitiveType::String.into())
+            .add_column("point", point);
+        let mut action_commit = Arc::new(action).commit(&table).unwrap();
+
+        let updates = action_commit.take_updates();
+        let TableUpdate::AddSchema { schema } = &updates[0] else {
+            unreachable!()
+        };
+        assert_eq!(updates[1], TableUpdate::SetCurrentSchema { schema_id: -1 });
+
+        let w = schema.field_by_name("w").unwrap();
+        assert_eq!(w.id, 4);
+        assert!(!w.required);
+        assert_eq!(schema.field_by_name("point").unwrap().id, 5);
+        assert_eq!(schema.field_by_name("point.lat").unwrap().id, 6);
+        assert_eq!(schema.field_by_name("point.lon").unwrap().id, 7);
+        assert_eq!(schema.highest_field_id(), 7);
+        assert_eq!(action_commit.take_requirements().len(), 2);
+    }
+
+    #[test]
+    fn test_rename_update_and_make_optional() {
+        let table = stage_add_column(
+            &make_v2_minimal_table(),
+            "i",
+            PrimitiveType::Int.into(),
+        );
+
+        let mut staged = StagedTransaction::new(&table);
+        let tx = Transaction::new(staged.table());
+        let tx = tx
+            .update_schema()
+            .rename_column("y", "v")
+            .update_column("i", PrimitiveType::Long)
+            .make_column_optional("z")
+            .apply(tx)
+            .unwrap();
+        staged.stage(tx).unwrap();
+
+        let schema = staged.table().metadata().current_schema().clone();
+        assert_eq!(
+            field_ids(staged.table()),
+            vec![
+                (1, "x".to_string()),
+                (2, "v".to_string()),
+                (3, "z".to_string()),
+                (4, "i".to_string()),
+            ]
+        );
+        assert_eq!(
+            *schema.field_by_id(4).unwrap().field_type,
+            Type::Primitive(PrimitiveType::Long)
+        );
+        assert!(!schema.field_by_id(3).unwrap().required);
+    }
+
+    #[test]
+    fn test_delete_column_does_not_reuse_field_ids() {
+        let table = stage_add_column(
+            &make_v2_minimal_table(),
+            "w",
+            PrimitiveType::Int.into(),
+        );
+
+        let mut staged = StagedTransaction::new(&table);
+        let tx = Transaction::new(staged.table());
+        let tx = tx.update_schema().delete_column("w").apply(tx).unwrap();
+        staged.stage(tx).unwrap();
+
+        let table = staged.table().clone();
+        assert_eq!(table.metadata().last_column_id(), 4);
+        assert_eq!(field_ids(&table).len(), 3);
+
+        let table = stage_add_column(&table, "w", PrimitiveType::Int.into());
+        assert_eq!(field_ids(&table)[3], (5, "w".to_string()));
+    }
+
+    #[test]
+    fn test_invalid_changes() {
+        let table = make_v2_minimal_table();
+        let commit =
+            |action: super::UpdateSchemaAction| Arc::new(action).commit(&table);
+        let tx = Transaction::new(&table);
+
+        // Narrowing a type
+        assert!(
+            commit(tx.update_schema().update_column("x", PrimitiveType::Int))
+                .is_err()
+        );
+        // Deleting a partition source column
+        assert!(commit(tx.update_schema().delete_column("x")).is_err());
+        // Deleting a sort order source column
+        assert!(commit(tx.update_schema().delete_column("z")).is_err());
+        // Duplicate names
+        assert!(
+            commit(
+                tx.update_schema()
+                    .add_column("x", PrimitiveType::Int.into())
+            )
+            .is_err()
+        );
+        assert!(commit(tx.update_schema().rename_column("y", "x")).is_err());
+        // Unknown column
+        assert!(commit(tx.update_schema().make_column_optional("a")).is_err());
+    }
+
+    #[test]
+    fn test_no_changes() {
+        let table = make_v2_minimal_table();
+        let tx = Transaction::new(&table);
+        let action = tx.update_schema().update_column("x", PrimitiveType::Long);
+        let mut action_commit = Arc::new(action).commit(&table).unwrap();
+
+        assert!(action_commit.take_updates().is_empty());
+        assert!(action_commit.take_requirements().is_empty());
+    }
+}
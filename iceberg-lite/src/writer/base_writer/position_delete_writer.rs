@@ -0,0 +1,353 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! This module provide `PositionDeleteFileWriter`.
+
+use std::sync::{Arc, LazyLock};
+
+use arrow_array::{Array, RecordBatch, StringArray};
+use arrow_schema::SchemaRef as ArrowSchemaRef;
+
+use crate::arrow::schema_to_arrow_schema;
+use crate::metadata_columns::{delete_file_path_field, delete_file_pos_field};
+use crate::spec::{DataContentType, DataFile, PartitionKey, Schema, SchemaRef};
+use crate::writer::file_writer::FileWriterBuilder;
+use crate::writer::file_writer::location_generator::{FileNameGenerator, LocationGenerator};
+use crate::writer::file_writer::rolling_writer::{RollingFileWriter, RollingFileWriterBuilder};
+use crate::writer::{IcebergWriter, IcebergWriterBuilder};
+use crate::{Error, ErrorKind, Result};
+
+static POSITION_DELETE_SCHEMA: LazyLock<SchemaRef> = LazyLock::new(|| {
+    Arc::new(
+        Schema::builder()
+            .with_fields(vec![
+                delete_file_path_field().clone(),
+                delete_file_pos_field().clone(),
+            ])
+            .build()
+            .expect("position delete schema is valid"),
+    )
+});
+
+/// Returns the schema of position delete files, made of the `file_path` and `pos` columns.
+///
+/// This is the schema the file writer of a `PositionDeleteFileWriter` must be created with.
+pub fn position_delete_schema() -> SchemaRef {
+    POSITION_DELETE_SCHEMA.clone()
+}
+
+/// Returns the Arrow schema of the record batches written to a `PositionDeleteFileWriter`.
+pub fn position_delete_arrow_schema() -> Result<ArrowSchemaRef> {
+    Ok(Arc::new(schema_to_arrow_schema(&POSITION_DELETE_SCHEMA)?))
+}
+
+/// Builder for `PositionDeleteFileWriter`.
+#[derive(Debug)]
+pub struct PositionDeleteFileWriterBuilder<
+    B: FileWriterBuilder,
+    L: LocationGenerator,
+    F: FileNameGenerator,
+> {
+    inner: RollingFileWriterBuilder<B, L, F>,
+}
+
+impl<B, L, F> PositionDeleteFileWriterBuilder<B, L, F>
+where
+    B: FileWriterBuilder,
+    L: LocationGenerator,
+    F: FileNameGenerator,
+{
+    /// Create a new `PositionDeleteFileWriterBuilder` using a `RollingFileWriterBuilder`.
+    ///
+    /// The file writer must be created with [`position_delete_schema`].
+    pub fn new(inner: RollingFileWriterBuilder<B, L, F>) -> Self {
+        Self { inner }
+    }
+}
+
+impl<B, L, F> IcebergWriterBuilder for PositionDeleteFileWriterBuilder<B, L, F>
+where
+    B: FileWriterBuilder,
+    L: LocationGenerator,
+    F: FileNameGenerator,
+{
+    type R = PositionDeleteFileWriter<B, L, F>;
+
+    fn build(&self, partition_key: Option<PartitionKey>) -> Result<Self::R> {
+        Ok(PositionDeleteFileWriter {
+            inner: Some(self.inner.build()),
+            partition_key,
+            referenced_data_file: ReferencedDataFile::None,
+        })
+    }
+}
+
+/// The data files referenced by the deletes written so far.
+#[derive(Debug)]
+enum ReferencedDataFile {
+    None,
+    Single(String),
+    Multiple,
+}
+
+/// Writer used to write position delete files.
+///
+/// Record batches follow [`position_delete_arrow_schema`]: each row deletes the row at `pos`
+/// in the data file at `file_path`. Per the Iceberg spec, rows must be written sorted by
+/// `file_path` and then `pos`.
+///
+/// The deletes of a writer must all belong to the partition it is built for. When they all
+/// reference the same data file, the written files record it as their `referenced_data_file`.
+#[derive(Debug)]
+pub struct PositionDeleteFileWriter<
+    B: FileWriterBuilder,
+    L: LocationGenerator,
+    F: FileNameGenerator,
+> {
+    inner: Option<RollingFileWriter<B, L, F>>,
+    partition_key: Option<PartitionKey>,
+    referenced_data_file: ReferencedDataFile,
+}
+
+impl<B, L, F> PositionDeleteFileWriter<B, L, F>
+where
+    B: FileWriterBuilder,
+    L: LocationGenerator,
+    F: FileNameGenerator,
+{
+    fn track_referenced_data_files(&mut self, batch: &RecordBatch) -> Result<()> {
+        if batch.num_columns() != 2 {
+            return Err(Error::new(
+                ErrorKind::DataInvalid,
+                format!(
+                    "Position delete batches must have 2 columns, got {}",
+                    batch.num_columns()
+                ),
+            ));
+        }
+
+        let file_paths = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .ok_or_else(|| {
+                Error::new(
+                    ErrorKind::DataInvalid,
+                    "The file_path column of position deletes must be a string array",
+                )
+            })?;
+        if file_paths.null_count() > 0 {
+            return Err(Error::new(
+                ErrorKind::DataInvalid,
+                "The file_path column of position deletes must not contain nulls",
+            ));
+        }
+
+        for file_path in file_paths.iter().flatten() {
+            match &self.referenced_data_file {
+                ReferencedDataFile::None => {
+                    self.referenced_data_file = ReferencedDataFile::Single(file_path.to_string())
+                }
+                ReferencedDataFile::Single(path) if path != file_path => {
+                    self.referenced_data_file = ReferencedDataFile::Multiple;
+                    break;
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<B, L, F> IcebergWriter for PositionDeleteFileWriter<B, L, F>
+where
+    B: FileWriterBuilder,
+    L: LocationGenerator,
+    F: FileNameGenerator,
+{
+    fn write(&mut self, batch: RecordBatch) -> Result<()> {
+        if self.inner.is_none() {
+            return Err(Error::new(
+                ErrorKind::Unexpected,
+                "Position delete inner writer has been closed.",
+            ));
+        }
+
+        self.track_referenced_data_files(&batch)?;
+        let writer = self.inner.as_mut().expect("writer is open");
+        writer.write(&self.partition_key, &batch)
+    }
+
+    fn close(&mut self) -> Result<Vec<DataFile>> {
+        if let Some(writer) = self.inner.take() {
+            let referenced_data_file = match &self.referenced_data_file {
+                ReferencedDataFile::Single(path) => Some(path.clone()),
+                _ => None,
+            };
+
+            writer
+                .close()?
+                .into_iter()
+                .map(|mut res| {
+                    res.content(DataContentType::PositionDeletes);
+                    res.referenced_data_file(referenced_data_file.clone());
+                    if let Some(pk) = self.partition_key.as_ref() {
+                        res.partition(pk.data().clone());
+                        res.partition_spec_id(pk.spec().spec_id());
+                    }
+                    res.build().map_err(|e| {
+                        Error::new(
+                            ErrorKind::DataInvalid,
+                            format!("Failed to build data file: {e}"),
+                        )
+                    })
+                })
+                .collect()
+        } else {
+            Err(Error::new(
+                ErrorKind::Unexpected,
+                "Position delete inner writer has been closed.",
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use arrow_array::{Int64Array, RecordBatch, StringArray};
+    use arrow_select::concat::concat_batches;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use parquet::file::properties::WriterProperties;
+    use tempfile::TempDir;
+
+    use crate::Result;
+    use crate::io::FileIO;
+    use crate::metadata_columns::{
+        RESERVED_FIELD_ID_DELETE_FILE_PATH, RESERVED_FIELD_ID_DELETE_FILE_POS,
+    };
+    use crate::spec::{DataContentType, DataFileFormat, Struct};
+    use crate::writer::base_writer::position_delete_writer::{
+        PositionDeleteFileWriterBuilder, position_delete_arrow_schema, position_delete_schema,
+    };
+    use crate::writer::file_writer::ParquetWriterBuilder;
+    use crate::writer::file_writer::location_generator::{
+        DefaultFileNameGenerator, DefaultLocationGenerator,
+    };
+    use crate::writer::file_writer::rolling_writer::RollingFileWriterBuilder;
+    use crate::writer::{IcebergWriter, IcebergWriterBuilder};
+
+    fn position_delete_writer_builder(
+        temp_dir: &TempDir,
+    ) -> PositionDeleteFileWriterBuilder<
+        ParquetWriterBuilder,
+        DefaultLocationGenerator,
+        DefaultFileNameGenerator,
+    > {
+        let location_gen = DefaultLocationGenerator::with_data_location(
+            temp_dir.path().to_str().unwrap().to_string(),
+        );
+        let file_name_gen =
+            DefaultFileNameGenerator::new("delete".to_string(), None, DataFileFormat::Parquet);
+        let pw = ParquetWriterBuilder::new(
+            WriterProperties::builder().build(),
+            position_delete_schema(),
+        );
+
+        PositionDeleteFileWriterBuilder::new(RollingFileWriterBuilder::new_with_default_file_size(
+            pw,
+            FileIO::local(),
+            location_gen,
+            file_name_gen,
+        ))
+    }
+
+    fn position_deletes(file_paths: Vec<&str>, positions: Vec<i64>) -> Result<RecordBatch> {
+        Ok(RecordBatch::try_new(
+            position_delete_arrow_schema()?,
+            vec![
+                Arc::new(StringArray::from(file_paths)),
+                Arc::new(Int64Array::from(positions)),
+            ],
+        )?)
+    }
+
+    #[test]
+    fn test_position_delete_writer() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let mut writer = position_delete_writer_builder(&temp_dir).build(None)?;
+
+        let batch = position_deletes(vec!["data/1.parquet"; 3], vec![0, 5, 7])?;
+        writer.write(batch.clone())?;
+        let delete_files = writer.close()?;
+        assert_eq!(delete_files.len(), 1);
+
+        let delete_file = &delete_files[0];
+        assert_eq!(delete_file.content_type(), DataContentType::PositionDeletes);
+        assert_eq!(delete_file.file_format, DataFileFormat::Parquet);
+        assert_eq!(delete_file.partition, Struct::empty());
+        assert_eq!(delete_file.record_count, 3);
+        assert_eq!(
+            delete_file.referenced_data_file(),
+            Some("data/1.parquet".to_string())
+        );
+        assert!(
+            delete_file
+                .value_counts
+                .contains_key(&RESERVED_FIELD_ID_DELETE_FILE_PATH)
+        );
+        assert!(
+            delete_file
+                .value_counts
+                .contains_key(&RESERVED_FIELD_ID_DELETE_FILE_POS)
+        );
+
+        let input_content = FileIO::local().new_input(delete_file.file_path())?.read()?;
+        let reader = ParquetRecordBatchReaderBuilder::try_new(input_content)?.build()?;
+        let batches = reader.collect::<std::result::Result<Vec<_>, _>>()?;
+        let written = concat_batches(&batch.schema(), &batches)?;
+        assert_eq!(written, batch);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_position_delete_writer_multiple_data_files() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let mut writer = position_delete_writer_builder(&temp_dir).build(None)?;
+
+        writer.write(position_deletes(vec!["data/1.parquet"; 2], vec![1, 2])?)?;
+        writer.write(position_deletes(vec!["data/2.parquet"], vec![0])?)?;
+        let delete_files = writer.close()?;
+
+        assert_eq!(delete_files.len(), 1);
+        assert_eq!(delete_files[0].record_count, 3);
+        assert_eq!(delete_files[0].referenced_data_file(), None);
+
+        // The writer cannot be used after close
+        assert!(
+            writer
+                .write(position_deletes(vec!["data/1.parquet"], vec![3])?)
+                .is_err()
+        );
+        assert!(writer.close().is_err());
+
+        Ok(())
+    }
+}
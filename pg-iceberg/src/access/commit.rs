@@ -0,0 +1,328 @@
+//! Per-transaction commit coordinator for Iceberg tables.
+//!
+//! Statements do not commit their changes to the catalog. Instead the actions
+//! they produce are staged per relation in a `StagedTransaction` that lives as
+//! long as the PostgreSQL transaction:
+//!
+//! - Later statements of the transaction read the staged table, see
+//!   `load_iceberg_table`.
+//! - At pre-commit the staged updates of every relation are committed, which
+//!   swaps `metadata_location` in `lakehouse.iceberg_metadata` as part of the
+//!   PostgreSQL transaction. Staged data and delete files are rebased on the
+//!   changes other transactions committed meanwhile, only position deletes of
+//!   concurrently changed data files fail the commit.
+//! - Files written for staged changes are registered as pending deletes, so an
+//!   aborted transaction leaves no orphaned files behind.
+//!
+//! Each subtransaction stages on its own copy of the parent's staged
+//! transaction, which replaces the parent's on `RELEASE SAVEPOINT` and is
+//! discarded on `ROLLBACK TO SAVEPOINT`.
+
+use super::pending_deletes::{
+    register_files_commit_delete, register_files_pending_delete,
+};
+use crate::catalog::{ICEBERG_CATALOG_NAME, IcebergCatalog, IcebergMetadata};
+use crate::error::{IcebergError, IcebergResult};
+use iceberg_lite::ErrorKind;
+use iceberg_lite::catalog::TableUpdate;
+use iceberg_lite::table::Table;
+use iceberg_lite::transaction::{StagedTransaction, Transaction};
+use pg_tam::prelude::{XactEvent, XactHook, XactHookError, register_xact_hook};
+use pgrx::pg_sys;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+/// Staged transactions of a relation by subtransaction nesting level,
+/// innermost last.
+type StagedLevels = Vec<(i32, StagedTransaction)>;
+
+thread_local! {
+    static PENDING_COMMITS: RefCell<HashMap<pg_sys::Oid, StagedLevels>> =
+        RefCell::new(HashMap::new());
+
+    /// Relations whose changes could not be staged, with the nesting level
+    /// and the error.
+    static FAILED_STAGES: RefCell<Vec<(pg_sys::Oid, i32, String)>> =
+        const { RefCell::new(Vec::new()) };
+}
+
+/// The table of `relid` as seen by the current transaction, if it has staged
+/// changes.
+pub fn staged_table(relid: pg_sys::Oid) -> Option<Table> {
+    PENDING_COMMITS.with(|pending| {
+        pending
+            .borrow()
+            .get(&relid)
+            .and_then(|levels| levels.last())
+            .map(|(_, staged)| staged.table().clone())
+    })
+}
+
+/// The committed table the staged changes of `relid` apply to, if it has
+/// staged changes.
+pub fn staged_base(relid: pg_sys::Oid) -> Option<Table> {
+    PENDING_COMMITS.with(|pending| {
+        pending
+            .borrow()
+            .get(&relid)
+            .and_then(|levels| levels.last())
+            .map(|(_, staged)| staged.base().clone())
+    })
+}
+
+/// Stage the actions of `tx` on the relation `relid`.
+///
+/// `base` is the table the transaction starts from when `relid` has no staged
+/// changes yet. Returns the table with the actions applied.
+pub fn stage_transaction(
+    relid: pg_sys::Oid,
+    base: &Table,
+    tx: Transaction,
+) -> IcebergResult<Table> {
+    let nest_level = unsafe { pg_sys::GetCurrentTransactionNestLevel() };
+
+    let mut staged = PENDING_COMMITS
+        .with(|pending| {
+            pending
+                .borrow()
+                .get(&relid)
+                .and_then(|levels| levels.last())
+                .map(|(_, staged)| staged.clone())
+        })
+        .unwrap_or_else(|| StagedTransaction::new(base));
+
+    let updates = staged.stage(tx)?.to_vec();
+    let table = staged.table().clone();
+    register_files_pending_delete(
+        snapshot_files(&table, &updates)?,
+        table.file_io().clone(),
+    );
+
+    PENDING_COMMITS.with(|pending| {
+        let mut pending = pending.borrow_mut();
+        let levels = pending.entry(relid).or_default();
+        match levels.last_mut() {
+            Some((level, current)) if *level == nest_level => *current = staged,
+            _ => levels.push((nest_level, staged)),
+        }
+    });
+
+    Ok(table)
+}
+
+/// Remember that changes to `relid` could not be staged, so that the
+/// transaction fails at pre-commit instead of committing without them.
+pub fn stage_failed(relid: pg_sys::Oid, error: &IcebergError) {
+    let nest_level = unsafe { pg_sys::GetCurrentTransactionNestLevel() };
+    FAILED_STAGES.with(|failed| {
+        failed
+            .borrow_mut()
+            .push((relid, nest_level, error.to_string()));
+    });
+}
+
+/// Manifest lists and manifests written for the snapshots added by `updates`.
+fn snapshot_files(
+    table: &Table,
+    updates: &[TableUpdate],
+) -> IcebergResult<Vec<String>> {
+    let mut paths = vec![];
+
+    for update in updates {
+        let TableUpdate::AddSnapshot { snapshot } = update else {
+            continue;
+        };
+
+        let manifest_list =
+            snapshot.load_manifest_list(table.file_io(), table.metadata())?;
+        paths.extend(
+            manifest_list
+                .entries()
+                .iter()
+                .filter(|manifest| {
+                    manifest.added_snapshot_id == snapshot.snapshot_id()
+                })
+                .map(|manifest| manifest.manifest_path.clone()),
+        );
+        paths.push(snapshot.manifest_list().to_string());
+    }
+
+    Ok(paths)
+}
+
+/// Commit the staged changes of `relid` to the catalog.
+fn commit_staged(relid: pg_sys::Oid, staged: StagedTransaction) -> IcebergResult<()> {
+    if staged.is_empty() {
+        return Ok(());
+    }
+
+    // The relation may have been dropped after it was modified
+    if !IcebergMetadata::exists(relid)? {
+        return Ok(());
+    }
+
+    let file_io = staged.base().file_io().clone();
+    let catalog = IcebergCatalog::new(ICEBERG_CATALOG_NAME, file_io.clone());
+
+    // The rebase runs once commits to the table are serialized, no other
+    // commit lands between it and the update of the catalog
+    let table =
+        catalog.update_table_by_relid_with(relid, || -> IcebergResult<_> {
+            let staged = rebase_on_committed(&catalog, relid, staged)?;
+            Ok(staged.into_table_commit())
+        })?;
+
+    if let Some(location) = table.metadata_location() {
+        register_files_pending_delete(vec![location.to_string()], file_io);
+    }
+
+    Ok(())
+}
+
+/// Rebase the staged changes of `relid` on the latest committed table, when
+/// other transactions committed changes to it after the changes were staged.
+fn rebase_on_committed(
+    catalog: &IcebergCatalog,
+    relid: pg_sys::Oid,
+    staged: StagedTransaction,
+) -> IcebergResult<StagedTransaction> {
+    let committed = IcebergMetadata::get(relid)?;
+    if committed.metadata_location.as_deref() == staged.base().metadata_location() {
+        return Ok(staged);
+    }
+
+    let latest = catalog.load_table_by_relid(relid, staged.base().identifier())?;
+    let rebased = staged.rebase(&latest)?;
+
+    // The rebased snapshots are written anew, the manifests of the staged ones
+    // are no longer referenced once the transaction commits
+    let file_io = latest.file_io().clone();
+    register_files_pending_delete(
+        snapshot_files(rebased.table(), rebased.updates())?,
+        file_io.clone(),
+    );
+    register_files_commit_delete(
+        snapshot_files(staged.table(), staged.updates())?,
+        file_io,
+    );
+
+    Ok(rebased)
+}
+
+fn pre_commit() -> Result<(), XactHookError> {
+    if let Some((relid, _, message)) =
+        FAILED_STAGES.with(|failed| failed.borrow().first().cloned())
+    {
+        return Err(XactHookError::Message(format!(
+            "could not stage changes to relation {relid}: {message}"
+        )));
+    }
+
+    let mut commits: Vec<_> = PENDING_COMMITS
+        .with(|pending| std::mem::take(&mut *pending.borrow_mut()))
+        .into_iter()
+        .filter_map(|(relid, mut levels)| {
+            levels.pop().map(|(_, staged)| (relid, staged))
+        })
+        .collect();
+    commits.sort_by_key(|(relid, _)| relid.to_u32());
+
+    for (relid, staged) in commits {
+        commit_staged(relid, staged).map_err(|e| match &e {
+            IcebergError::IcebergError(err)
+                if err.kind() == ErrorKind::CatalogCommitConflicts =>
+            {
+                XactHookError::SerializationFailure(format!(
+                    "could not commit changes to relation {relid} due to a concurrent update: {e}"
+                ))
+            }
+            _ => XactHookError::Message(format!(
+                "could not commit changes to relation {relid}: {e}"
+            )),
+        })?;
+    }
+
+    Ok(())
+}
+
+fn reset() {
+    PENDING_COMMITS.with(|pending| pending.borrow_mut().clear());
+    FAILED_STAGES.with(|failed| failed.borrow_mut().clear());
+}
+
+/// Replace the parent's staged transactions with the ones of the committed
+/// subtransaction at `nest_level`.
+fn sub_commit(nest_level: i32) {
+    PENDING_COMMITS.with(|pending| {
+        for levels in pending.borrow_mut().values_mut() {
+            let Some((level, _)) = levels.last_mut() else {
+                continue;
+            };
+            if *level < nest_level {
+                continue;
+            }
+            *level = nest_level - 1;
+
+            // The parent's copy is superseded by the committed one
+            let len = levels.len();
+            if len > 1 && levels[len - 2].0 == nest_level - 1 {
+                levels.remove(len - 2);
+            }
+        }
+    });
+
+    FAILED_STAGES.with(|failed| {
+        for (_, level, _) in failed.borrow_mut().iter_mut() {
+            if *level >= nest_level {
+                *level = nest_level - 1;
+            }
+        }
+    });
+}
+
+/// Discard the staged transactions of the aborted subtransaction at
+/// `nest_level`.
+fn sub_abort(nest_level: i32) {
+    PENDING_COMMITS.with(|pending| {
+        let mut pending = pending.borrow_mut();
+        for levels in pending.values_mut() {
+            levels.retain(|(level, _)| *level < nest_level);
+        }
+        pending.retain(|_, levels| !levels.is_empty());
+    });
+
+    FAILED_STAGES.with(|failed| {
+        failed
+            .borrow_mut()
+            .retain(|(_, level, _)| *level < nest_level);
+    });
+}
+
+struct IcebergCommitHook;
+
+impl XactHook for IcebergCommitHook {
+    fn on_event(&self, event: XactEvent) -> Result<(), XactHookError> {
+        match event {
+            XactEvent::PreCommit => pre_commit()?,
+            XactEvent::PrePrepare => {
+                let has_changes =
+                    PENDING_COMMITS.with(|pending| !pending.borrow().is_empty());
+                if has_changes {
+                    return Err(XactHookError::Message(
+                        "cannot PREPARE a transaction that has modified Iceberg tables"
+                            .to_string(),
+                    ));
+                }
+            }
+            XactEvent::Commit | XactEvent::Abort => reset(),
+            XactEvent::SubCommit(nest_level) => sub_commit(nest_level),
+            XactEvent::SubAbort(nest_level) => sub_abort(nest_level),
+        }
+
+        Ok(())
+    }
+}
+
+pub fn init_hook() {
+    register_xact_hook(Box::new(IcebergCommitHook));
+}
@@ -0,0 +1,574 @@
+//! Conversion between Arrow and PostgreSQL values.
+//!
+//! Record batches produced by iceberg-lite carry the Arrow types derived from
+//! the Iceberg schema. The PostgreSQL attribute type decides which `Cell`
+//! variant a value is converted into, so e.g. an `int2` column is read back
+//! from the Iceberg `int` (Arrow `Int32`) it was widened to. In the other
+//! direction the Arrow type of the target field decides how a `Cell` is
+//! appended to a column builder.
+
+use crate::error::{IcebergError, IcebergResult};
+use arrow_array::builder::{
+    ArrayBuilder, BooleanBuilder, Date32Builder, Decimal128Builder,
+    FixedSizeBinaryBuilder, Float32Builder, Float64Builder, Int32Builder,
+    Int64Builder, LargeBinaryBuilder, StringBuilder, Time64MicrosecondBuilder,
+    TimestampMicrosecondBuilder,
+};
+use arrow_array::cast::AsArray;
+use arrow_array::types::{
+    Date32Type, Decimal128Type, Float32Type, Float64Type, Int32Type, Int64Type,
+    Time64MicrosecondType, TimestampMicrosecondType, TimestampNanosecondType,
+};
+use arrow_array::Array;
+use arrow_schema::{DataType, TimeUnit};
+use pg_tam::prelude::Cell;
+use pgrx::datum::{Date, Time, Timestamp, TimestampWithTimeZone, Uuid};
+use pgrx::{pg_sys, AnyNumeric, FromDatum, IntoDatum, JsonB, PgBuiltInOids, PgOid};
+use std::str::FromStr;
+
+/// Days between the Unix epoch (1970-01-01) and the PostgreSQL epoch (2000-01-01).
+pub(crate) const UNIX_TO_PG_EPOCH_DAYS: i32 = 10_957;
+
+/// Microseconds between the Unix epoch and the PostgreSQL epoch.
+pub(crate) const UNIX_TO_PG_EPOCH_MICROS: i64 =
+    UNIX_TO_PG_EPOCH_DAYS as i64 * 86_400 * 1_000_000;
+
+/// Convert the value at `row` of an Arrow array into a `Cell` for a column of
+/// PostgreSQL type `type_oid`.
+///
+/// Returns `Ok(None)` for SQL NULL values.
+pub(crate) fn arrow_value_to_cell(
+    array: &dyn Array,
+    row: usize,
+    type_oid: pg_sys::Oid,
+    attname: &str,
+) -> IcebergResult<Option<Cell>> {
+    if array.is_null(row) {
+        return Ok(None);
+    }
+
+    let mismatch = || {
+        IcebergError::IncompatibleColumnType(
+            attname.to_string(),
+            array.data_type().to_string(),
+        )
+    };
+
+    let cell = match PgOid::from(type_oid) {
+        PgOid::BuiltIn(PgBuiltInOids::BOOLOID) => {
+            Cell::Bool(array.as_boolean_opt().ok_or_else(mismatch)?.value(row))
+        }
+        PgOid::BuiltIn(PgBuiltInOids::INT2OID) => {
+            let value = array
+                .as_primitive_opt::<Int32Type>()
+                .ok_or_else(mismatch)?
+                .value(row);
+            Cell::I16(i16::try_from(value).map_err(|_| {
+                IcebergError::DatumConversionError(format!(
+                    "value {value} of column '{attname}' is out of range for type smallint"
+                ))
+            })?)
+        }
+        PgOid::BuiltIn(PgBuiltInOids::INT4OID) => Cell::I32(
+            array
+                .as_primitive_opt::<Int32Type>()
+                .ok_or_else(mismatch)?
+                .value(row),
+        ),
+        PgOid::BuiltIn(PgBuiltInOids::INT8OID) => Cell::I64(
+            array
+                .as_primitive_opt::<Int64Type>()
+                .ok_or_else(mismatch)?
+                .value(row),
+        ),
+        PgOid::BuiltIn(PgBuiltInOids::FLOAT4OID) => Cell::F32(
+            array
+                .as_primitive_opt::<Float32Type>()
+                .ok_or_else(mismatch)?
+                .value(row),
+        ),
+        PgOid::BuiltIn(PgBuiltInOids::FLOAT8OID) => Cell::F64(
+            array
+                .as_primitive_opt::<Float64Type>()
+                .ok_or_else(mismatch)?
+                .value(row),
+        ),
+        PgOid::BuiltIn(PgBuiltInOids::NUMERICOID) => {
+            let DataType::Decimal128(_, scale) = array.data_type() else {
+                return Err(mismatch());
+            };
+            let value = array
+                .as_primitive_opt::<Decimal128Type>()
+                .ok_or_else(mismatch)?
+                .value(row);
+            Cell::Numeric(AnyNumeric::from_str(&decimal128_to_string(
+                value, *scale,
+            ))?)
+        }
+        PgOid::BuiltIn(PgBuiltInOids::TEXTOID)
+        | PgOid::BuiltIn(PgBuiltInOids::VARCHAROID)
+        | PgOid::BuiltIn(PgBuiltInOids::BPCHAROID)
+        | PgOid::BuiltIn(PgBuiltInOids::JSONOID) => Cell::String(
+            string_value(array, row).ok_or_else(mismatch)?.to_string(),
+        ),
+        PgOid::BuiltIn(PgBuiltInOids::JSONBOID) => {
+            let value = string_value(array, row).ok_or_else(mismatch)?;
+            Cell::Json(JsonB(serde_json::from_str(value)?))
+        }
+        PgOid::BuiltIn(PgBuiltInOids::DATEOID) => {
+            let days = array
+                .as_primitive_opt::<Date32Type>()
+                .ok_or_else(mismatch)?
+                .value(row);
+            let datum = pg_sys::Datum::from(days - UNIX_TO_PG_EPOCH_DAYS);
+            Cell::Date(unsafe { Date::from_datum(datum, false) }.ok_or_else(mismatch)?)
+        }
+        PgOid::BuiltIn(PgBuiltInOids::TIMEOID) => {
+            let micros = array
+                .as_primitive_opt::<Time64MicrosecondType>()
+                .ok_or_else(mismatch)?
+                .value(row);
+            let datum = pg_sys::Datum::from(micros);
+            Cell::Time(unsafe { Time::from_datum(datum, false) }.ok_or_else(mismatch)?)
+        }
+        PgOid::BuiltIn(PgBuiltInOids::TIMESTAMPOID) => {
+            let micros = timestamp_micros(array, row).ok_or_else(mismatch)?;
+            let datum = pg_sys::Datum::from(micros - UNIX_TO_PG_EPOCH_MICROS);
+            Cell::Timestamp(
+                unsafe { Timestamp::from_datum(datum, false) }.ok_or_else(mismatch)?,
+            )
+        }
+        PgOid::BuiltIn(PgBuiltInOids::TIMESTAMPTZOID) => {
+            let micros = timestamp_micros(array, row).ok_or_else(mismatch)?;
+            let datum = pg_sys::Datum::from(micros - UNIX_TO_PG_EPOCH_MICROS);
+            Cell::Timestamptz(
+                unsafe { TimestampWithTimeZone::from_datum(datum, false) }
+                    .ok_or_else(mismatch)?,
+            )
+        }
+        PgOid::BuiltIn(PgBuiltInOids::BYTEAOID) => {
+            let bytes = binary_value(array, row).ok_or_else(mismatch)?;
+            let datum = bytes.into_datum().ok_or_else(mismatch)?;
+            Cell::Bytea(datum.cast_mut_ptr())
+        }
+        PgOid::BuiltIn(PgBuiltInOids::UUIDOID) => {
+            let bytes = binary_value(array, row).ok_or_else(mismatch)?;
+            Cell::Uuid(
+                Uuid::from_slice(bytes).map_err(IcebergError::DatumConversionError)?,
+            )
+        }
+        _ => return Err(IcebergError::UnsupportedColumnType(attname.to_string())),
+    };
+
+    Ok(Some(cell))
+}
+
+/// Convert the value at `row` of an Arrow array into JSON, for values of
+/// nested types that are read as `jsonb`.
+///
+/// Maps become objects keyed by the text of their keys, structs objects keyed
+/// by field name and lists arrays. Dates and timestamps are kept as the days
+/// and microseconds since the Unix epoch, binary values are hex-encoded.
+pub(crate) fn arrow_value_to_json(
+    array: &dyn Array,
+    row: usize,
+) -> IcebergResult<serde_json::Value> {
+    use serde_json::{Map, Value};
+
+    if array.is_null(row) {
+        return Ok(Value::Null);
+    }
+
+    let value = match array.data_type() {
+        DataType::Boolean => Value::Bool(array.as_boolean().value(row)),
+        DataType::Int32 => array.as_primitive::<Int32Type>().value(row).into(),
+        DataType::Int64 => array.as_primitive::<Int64Type>().value(row).into(),
+        DataType::Float32 => {
+            (array.as_primitive::<Float32Type>().value(row) as f64).into()
+        }
+        DataType::Float64 => array.as_primitive::<Float64Type>().value(row).into(),
+        DataType::Date32 => array.as_primitive::<Date32Type>().value(row).into(),
+        DataType::Time64(TimeUnit::Microsecond) => array
+            .as_primitive::<Time64MicrosecondType>()
+            .value(row)
+            .into(),
+        DataType::Timestamp(_, _) => timestamp_micros(array, row)
+            .map(Value::from)
+            .unwrap_or(Value::Null),
+        DataType::Decimal128(_, scale) => Value::String(decimal128_to_string(
+            array.as_primitive::<Decimal128Type>().value(row),
+            *scale,
+        )),
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View => {
+            Value::String(string_value(array, row).unwrap_or_default().to_string())
+        }
+        DataType::Binary
+        | DataType::LargeBinary
+        | DataType::BinaryView
+        | DataType::FixedSizeBinary(_) => Value::String(
+            binary_value(array, row)
+                .unwrap_or_default()
+                .iter()
+                .map(|b| format!("{b:02x}"))
+                .collect(),
+        ),
+        DataType::List(_) | DataType::LargeList(_) => {
+            let values = match array.as_list_opt::<i32>() {
+                Some(list) => list.value(row),
+                None => array.as_list::<i64>().value(row),
+            };
+            Value::Array(
+                (0..values.len())
+                    .map(|i| arrow_value_to_json(&values, i))
+                    .collect::<IcebergResult<_>>()?,
+            )
+        }
+        DataType::Map(_, _) => {
+            let entries = array.as_map().value(row);
+            let mut object = Map::new();
+            for i in 0..entries.len() {
+                let key = match arrow_value_to_json(entries.column(0), i)? {
+                    Value::String(key) => key,
+                    key => key.to_string(),
+                };
+                let value = arrow_value_to_json(entries.column(1), i)?;
+                object.insert(key, value);
+            }
+            Value::Object(object)
+        }
+        DataType::Struct(fields) => {
+            let columns = array.as_struct().columns();
+            let mut object = Map::new();
+            for (field, column) in fields.iter().zip(columns) {
+                let value = arrow_value_to_json(column, row)?;
+                object.insert(field.name().clone(), value);
+            }
+            Value::Object(object)
+        }
+        data_type => {
+            return Err(IcebergError::DatumConversionError(format!(
+                "cannot convert Arrow type {data_type} to json"
+            )));
+        }
+    };
+
+    Ok(value)
+}
+
+/// Append a `Cell` to an Arrow column builder whose field has type `data_type`.
+///
+/// `None` appends a SQL NULL.
+pub(crate) fn append_cell(
+    builder: &mut dyn ArrayBuilder,
+    data_type: &DataType,
+    cell: Option<&Cell>,
+    attname: &str,
+) -> IcebergResult<()> {
+    let mismatch = || {
+        IcebergError::IncompatibleColumnType(
+            attname.to_string(),
+            data_type.to_string(),
+        )
+    };
+
+    match data_type {
+        DataType::Boolean => {
+            let builder =
+                downcast_builder::<BooleanBuilder>(builder, attname, data_type)?;
+            match cell {
+                None => builder.append_null(),
+                Some(Cell::Bool(v)) => builder.append_value(*v),
+                Some(_) => return Err(mismatch()),
+            }
+        }
+        DataType::Int32 => {
+            let builder =
+                downcast_builder::<Int32Builder>(builder, attname, data_type)?;
+            match cell {
+                None => builder.append_null(),
+                Some(Cell::I8(v)) => builder.append_value(*v as i32),
+                Some(Cell::I16(v)) => builder.append_value(*v as i32),
+                Some(Cell::I32(v)) => builder.append_value(*v),
+                Some(_) => return Err(mismatch()),
+            }
+        }
+        DataType::Int64 => {
+            let builder =
+                downcast_builder::<Int64Builder>(builder, attname, data_type)?;
+            match cell {
+                None => builder.append_null(),
+                Some(Cell::I64(v)) => builder.append_value(*v),
+                Some(_) => return Err(mismatch()),
+            }
+        }
+        DataType::Float32 => {
+            let builder =
+                downcast_builder::<Float32Builder>(builder, attname, data_type)?;
+            match cell {
+                None => builder.append_null(),
+                Some(Cell::F32(v)) => builder.append_value(*v),
+                Some(_) => return Err(mismatch()),
+            }
+        }
+        DataType::Float64 => {
+            let builder =
+                downcast_builder::<Float64Builder>(builder, attname, data_type)?;
+            match cell {
+                None => builder.append_null(),
+                Some(Cell::F64(v)) => builder.append_value(*v),
+                Some(_) => return Err(mismatch()),
+            }
+        }
+        DataType::Decimal128(precision, scale) => {
+            let builder =
+                downcast_builder::<Decimal128Builder>(builder, attname, data_type)?;
+            match cell {
+                None => builder.append_null(),
+                Some(Cell::Numeric(v)) => {
+                    let text = v.to_string();
+                    let value = numeric_to_decimal128(&text, *precision, *scale)
+                        .ok_or_else(|| {
+                            IcebergError::DatumConversionError(format!(
+                                "value {text} of column '{attname}' does not fit into {data_type}"
+                            ))
+                        })?;
+                    builder.append_value(value);
+                }
+                Some(_) => return Err(mismatch()),
+            }
+        }
+        DataType::Utf8 => {
+            let builder =
+                downcast_builder::<StringBuilder>(builder, attname, data_type)?;
+            match cell {
+                None => builder.append_null(),
+                Some(Cell::String(v)) => builder.append_value(v),
+                Some(Cell::Json(v)) => {
+                    builder.append_value(serde_json::to_string(&v.0)?)
+                }
+                Some(_) => return Err(mismatch()),
+            }
+        }
+        DataType::Date32 => {
+            let builder =
+                downcast_builder::<Date32Builder>(builder, attname, data_type)?;
+            match cell {
+                None => builder.append_null(),
+                Some(Cell::Date(v)) => {
+                    let datum = (*v).into_datum().ok_or_else(mismatch)?;
+                    let days = (datum.value() as i32)
+                        .checked_add(UNIX_TO_PG_EPOCH_DAYS)
+                        .ok_or_else(|| out_of_range(attname, data_type))?;
+                    builder.append_value(days);
+                }
+                Some(_) => return Err(mismatch()),
+            }
+        }
+        DataType::Time64(TimeUnit::Microsecond) => {
+            let builder = downcast_builder::<Time64MicrosecondBuilder>(
+                builder, attname, data_type,
+            )?;
+            match cell {
+                None => builder.append_null(),
+                Some(Cell::Time(v)) => {
+                    let datum = (*v).into_datum().ok_or_else(mismatch)?;
+                    builder.append_value(datum.value() as i64);
+                }
+                Some(_) => return Err(mismatch()),
+            }
+        }
+        DataType::Timestamp(TimeUnit::Microsecond, _) => {
+            let builder = downcast_builder::<TimestampMicrosecondBuilder>(
+                builder, attname, data_type,
+            )?;
+            let datum = match cell {
+                None => {
+                    builder.append_null();
+                    return Ok(());
+                }
+                Some(Cell::Timestamp(v)) => (*v).into_datum(),
+                Some(Cell::Timestamptz(v)) => (*v).into_datum(),
+                Some(_) => return Err(mismatch()),
+            };
+            let micros = (datum.ok_or_else(mismatch)?.value() as i64)
+                .checked_add(UNIX_TO_PG_EPOCH_MICROS)
+                .ok_or_else(|| out_of_range(attname, data_type))?;
+            builder.append_value(micros);
+        }
+        DataType::LargeBinary => {
+            let builder =
+                downcast_builder::<LargeBinaryBuilder>(builder, attname, data_type)?;
+            match cell {
+                None => builder.append_null(),
+                Some(Cell::Bytea(v)) => {
+                    let bytes = unsafe {
+                        <&[u8]>::from_datum(pg_sys::Datum::from(*v), false)
+                    }
+                    .ok_or_else(mismatch)?;
+                    builder.append_value(bytes);
+                }
+                Some(_) => return Err(mismatch()),
+            }
+        }
+        DataType::FixedSizeBinary(16) => {
+            let builder = downcast_builder::<FixedSizeBinaryBuilder>(
+                builder, attname, data_type,
+            )?;
+            match cell {
+                None => builder.append_null(),
+                Some(Cell::Uuid(v)) => builder.append_value(v.as_bytes())?,
+                Some(_) => return Err(mismatch()),
+            }
+        }
+        _ => return Err(IcebergError::UnsupportedColumnType(attname.to_string())),
+    }
+
+    Ok(())
+}
+
+fn downcast_builder<'a, T: ArrayBuilder>(
+    builder: &'a mut dyn ArrayBuilder,
+    attname: &str,
+    data_type: &DataType,
+) -> IcebergResult<&'a mut T> {
+    builder.as_any_mut().downcast_mut::<T>().ok_or_else(|| {
+        IcebergError::IncompatibleColumnType(
+            attname.to_string(),
+            data_type.to_string(),
+        )
+    })
+}
+
+fn out_of_range(attname: &str, data_type: &DataType) -> IcebergError {
+    IcebergError::DatumConversionError(format!(
+        "value of column '{attname}' is out of range for {data_type}"
+    ))
+}
+
+fn string_value(array: &dyn Array, row: usize) -> Option<&str> {
+    match array.data_type() {
+        DataType::Utf8 => Some(array.as_string::<i32>().value(row)),
+        DataType::LargeUtf8 => Some(array.as_string::<i64>().value(row)),
+        DataType::Utf8View => Some(array.as_string_view().value(row)),
+        _ => None,
+    }
+}
+
+fn binary_value(array: &dyn Array, row: usize) -> Option<&[u8]> {
+    match array.data_type() {
+        DataType::Binary => Some(array.as_binary::<i32>().value(row)),
+        DataType::LargeBinary => Some(array.as_binary::<i64>().value(row)),
+        DataType::BinaryView => Some(array.as_binary_view().value(row)),
+        DataType::FixedSizeBinary(_) => {
+            Some(array.as_fixed_size_binary().value(row))
+        }
+        _ => None,
+    }
+}
+
+/// Read a timestamp value as microseconds since the Unix epoch.
+fn timestamp_micros(array: &dyn Array, row: usize) -> Option<i64> {
+    match array.data_type() {
+        DataType::Timestamp(TimeUnit::Microsecond, _) => Some(
+            array
+                .as_primitive::<TimestampMicrosecondType>()
+                .value(row),
+        ),
+        DataType::Timestamp(TimeUnit::Nanosecond, _) => Some(
+            array.as_primitive::<TimestampNanosecondType>().value(row) / 1_000,
+        ),
+        _ => None,
+    }
+}
+
+/// Render an unscaled decimal value as its textual representation.
+fn decimal128_to_string(value: i128, scale: i8) -> String {
+    let digits = value.unsigned_abs().to_string();
+    let sign = if value < 0 { "-" } else { "" };
+
+    if scale <= 0 {
+        let zeros = "0".repeat(scale.unsigned_abs() as usize);
+        return format!("{sign}{digits}{zeros}");
+    }
+
+    let scale = scale as usize;
+    if digits.len() > scale {
+        let (int_part, frac_part) = digits.split_at(digits.len() - scale);
+        format!("{sign}{int_part}.{frac_part}")
+    } else {
+        format!("{sign}0.{digits:0>scale$}")
+    }
+}
+
+/// Parse the textual representation of a numeric into an unscaled decimal
+/// value of the given precision and scale, rounding half away from zero.
+///
+/// Returns `None` for special values (`NaN`, `Infinity`) and for values that
+/// do not fit into the precision.
+fn numeric_to_decimal128(text: &str, precision: u8, scale: i8) -> Option<i128> {
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text),
+    };
+    let (int_part, frac_part) = digits.split_once('.').unwrap_or((digits, ""));
+    if int_part.is_empty()
+        || !int_part
+            .bytes()
+            .chain(frac_part.bytes())
+            .all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let scale = scale.max(0) as usize;
+    let kept = frac_part.get(..scale).unwrap_or(frac_part);
+
+    let mut value: i128 = 0;
+    for b in int_part.bytes().chain(kept.bytes()) {
+        value = value.checked_mul(10)?.checked_add((b - b'0') as i128)?;
+    }
+    for _ in kept.len()..scale {
+        value = value.checked_mul(10)?;
+    }
+    if frac_part.as_bytes().get(scale).is_some_and(|&b| b >= b'5') {
+        value = value.checked_add(1)?;
+    }
+
+    if value >= 10i128.checked_pow(precision as u32)? {
+        return None;
+    }
+
+    Some(if negative { -value } else { value })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decimal128_to_string() {
+        assert_eq!(decimal128_to_string(12345, 2), "123.45");
+        assert_eq!(decimal128_to_string(-12345, 2), "-123.45");
+        assert_eq!(decimal128_to_string(5, 3), "0.005");
+        assert_eq!(decimal128_to_string(-5, 3), "-0.005");
+        assert_eq!(decimal128_to_string(42, 0), "42");
+        assert_eq!(decimal128_to_string(42, -2), "4200");
+    }
+
+    #[test]
+    fn test_numeric_to_decimal128() {
+        assert_eq!(numeric_to_decimal128("123.45", 10, 2), Some(12345));
+        assert_eq!(numeric_to_decimal128("-123.45", 10, 2), Some(-12345));
+        assert_eq!(numeric_to_decimal128("1.5", 10, 3), Some(1500));
+        assert_eq!(numeric_to_decimal128("0.005", 10, 2), Some(1));
+        assert_eq!(numeric_to_decimal128("-2.345", 10, 2), Some(-235));
+        assert_eq!(numeric_to_decimal128("42", 10, 0), Some(42));
+        assert_eq!(numeric_to_decimal128("99.995", 4, 2), None);
+        assert_eq!(numeric_to_decimal128("NaN", 10, 2), None);
+        assert_eq!(numeric_to_decimal128("Infinity", 10, 2), None);
+        assert_eq!(
+            numeric_to_decimal128("12345678901234567890123456789012.345678", 38, 6),
+            Some(12345678901234567890123456789012345678)
+        );
+    }
+}
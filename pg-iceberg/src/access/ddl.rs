@@ -1,20 +1,46 @@
+//! DDL callbacks of Iceberg tables.
+//!
+//! TRUNCATE gives the relation a new file locator, or truncates it in place
+//! when it was created in the same (sub)transaction. Either way the truncate
+//! is staged as a `delete` snapshot removing all data and delete files, which
+//! is committed with the transaction, see `access::commit`. The removed files
+//! stay referenced by older snapshots and are left for snapshot expiration.
+
+use super::commit::stage_transaction;
+use crate::catalog::{IcebergMetadata, load_iceberg_table};
 use crate::error::{IcebergError, IcebergResult};
+use iceberg_lite::transaction::{ApplyTransactionAction, Transaction};
 use pg_tam::prelude::*;
 use pgrx::pg_sys;
 
 pub struct IcebergDdl;
 
+/// Stage the removal of all rows of the table of `rel`.
+fn truncate_table(rel: &RelationHandle) -> IcebergResult<()> {
+    // New relations get their file locator before their table is created
+    if !IcebergMetadata::exists(rel.oid())? {
+        return Ok(());
+    }
+
+    let table = load_iceberg_table(rel)?;
+    let tx = Transaction::new(&table);
+    let tx = tx.truncate().apply(tx)?;
+    stage_transaction(rel.oid(), &table, tx)?;
+    Ok(())
+}
+
 impl AmDdl<IcebergError> for IcebergDdl {
     fn relation_set_new_filelocator(
-        _rel: &RelationHandle,
+        rel: &RelationHandle,
         _newrlocator: &RelFileLocator,
         _persistence: u8,
     ) -> IcebergResult<(pg_sys::TransactionId, pg_sys::MultiXactId)> {
+        truncate_table(rel)?;
         Ok((pg_sys::InvalidTransactionId, 0u32.into()))
     }
 
-    fn relation_nontransactional_truncate(_rel: &RelationHandle) -> IcebergResult<()> {
-        Ok(())
+    fn relation_nontransactional_truncate(rel: &RelationHandle) -> IcebergResult<()> {
+        truncate_table(rel)
     }
 
     fn relation_copy_data(
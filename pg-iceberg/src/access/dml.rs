@@ -1,81 +1,339 @@
+//! Data modification of Iceberg tables.
+//!
+//! Inserted rows are buffered into Parquet data files through an
+//! `IcebergTableWriter`. Deleted rows are identified by their TID, which is
+//! resolved to a data file and row position, see `access::tid`, and collected
+//! until they are written into position delete files. An update deletes the
+//! old row and inserts its new version.
+//!
+//! When the modify session ends the written files are staged as a new
+//! snapshot, a fast append when only rows were inserted and a row delta
+//! otherwise. The snapshot is committed to the PostgreSQL catalog when the
+//! transaction commits, see `access::commit`.
+//!
+//! Rows are not locked. When another transaction committed changes to a data
+//! file after the table was read, removing the file or deleting rows from it,
+//! deleting or updating the rows of that file reports a concurrent update, as
+//! committing would fail on the changed file anyway. Rows of other data files
+//! can be changed, the staged snapshot is rebased at commit.
+
+use super::commit::{stage_failed, stage_transaction, staged_base};
+use super::pending_deletes::register_files_pending_delete;
+use super::scan::fetch_row;
+use super::tid::decode_tid;
+use super::time_travel;
+use super::writer::{IcebergTableWriter, write_position_deletes};
+use crate::catalog::{
+    ICEBERG_CATALOG_NAME, IcebergCatalog, IcebergMetadata, load_iceberg_table,
+};
 use crate::error::{IcebergError, IcebergResult};
+use iceberg_lite::table::Table;
+use iceberg_lite::transaction::{
+    ApplyTransactionAction, ChangedDataFiles, Transaction,
+};
 use pg_tam::prelude::*;
 use pgrx::pg_sys;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+pub struct IcebergModify {
+    /// The modified relation, open for as long as the session lasts.
+    rel: pg_sys::Relation,
+    relid: pg_sys::Oid,
+    table: Table,
+    writer: Option<IcebergTableWriter>,
+    /// Positions of the deleted rows by data file path.
+    deletes: BTreeMap<String, BTreeSet<u64>>,
+    /// The data files changed by concurrent transactions, collected when the
+    /// first row is deleted or updated.
+    concurrent_changes: Option<ChangedDataFiles>,
+}
+
+impl IcebergModify {
+    fn writer(&mut self) -> IcebergResult<&mut IcebergTableWriter> {
+        if self.writer.is_none() {
+            self.writer = Some(IcebergTableWriter::new(&self.table)?);
+        }
+        Ok(self.writer.as_mut().expect("writer is initialized"))
+    }
+
+    /// Whether another transaction committed changes to the data file at
+    /// `file_path` after the current transaction read the table.
+    fn concurrently_changed(&mut self, file_path: &str) -> IcebergResult<bool> {
+        if self.concurrent_changes.is_none() {
+            self.concurrent_changes = Some(self.load_concurrent_changes()?);
+        }
+
+        Ok(self
+            .concurrent_changes
+            .as_ref()
+            .is_some_and(|changes| changes.contains(file_path)))
+    }
+
+    /// The data files changed by the transactions committed since the current
+    /// transaction read the table.
+    fn load_concurrent_changes(&self) -> IcebergResult<ChangedDataFiles> {
+        let base = staged_base(self.relid).unwrap_or_else(|| self.table.clone());
+        let committed = IcebergMetadata::get(self.relid)?;
+        if committed.metadata_location.as_deref() == base.metadata_location() {
+            return Ok(ChangedDataFiles::Files(HashSet::new()));
+        }
+
+        let catalog =
+            IcebergCatalog::new(ICEBERG_CATALOG_NAME, base.file_io().clone());
+        let latest = catalog.load_table_by_relid(self.relid, base.identifier())?;
+        Ok(ChangedDataFiles::since(
+            &latest,
+            base.metadata().current_snapshot_id(),
+        )?)
+    }
+
+    /// Check whether the row at `position` of `file_path`, identified by
+    /// `tid`, can be deleted by the command `cid`, filling `tmfd` when it
+    /// cannot.
+    fn check_row_change(
+        &mut self,
+        tid: &ItemPointer,
+        file_path: &str,
+        position: u64,
+        cid: pg_sys::CommandId,
+        tmfd: &mut TM_FailureData,
+    ) -> IcebergResult<pg_sys::TM_Result::Type> {
+        // Rows reached more than once by a command, e.g. through a join, are
+        // only changed the first time
+        if self
+            .deletes
+            .get(file_path)
+            .is_some_and(|positions| positions.contains(&position))
+        {
+            *tmfd = TM_FailureData {
+                ctid: *tid,
+                xmax: unsafe { pg_sys::GetCurrentTransactionId() },
+                cmax: cid,
+                traversed: false,
+            };
+            return Ok(pg_sys::TM_Result::TM_SelfModified);
+        }
+
+        // The new version of a concurrently updated row cannot be located,
+        // so `ctid` points to the row itself
+        if self.concurrently_changed(file_path)? {
+            *tmfd = TM_FailureData {
+                ctid: *tid,
+                xmax: pg_sys::InvalidTransactionId,
+                cmax: pg_sys::CommandId::MAX,
+                traversed: false,
+            };
+            return Ok(pg_sys::TM_Result::TM_Updated);
+        }
 
-pub struct IcebergModify;
+        Ok(pg_sys::TM_Result::TM_Ok)
+    }
+
+    /// Close the data file writer, write the collected deletes and stage the
+    /// written files as a new snapshot.
+    fn stage_changes(&mut self) -> IcebergResult<()> {
+        let data_files = match self.writer.take() {
+            Some(writer) => writer.close()?,
+            None => vec![],
+        };
+        let deletes = std::mem::take(&mut self.deletes);
+        let delete_files = if deletes.is_empty() {
+            vec![]
+        } else {
+            write_position_deletes(&self.table, &deletes)?
+        };
+        if data_files.is_empty() && delete_files.is_empty() {
+            return Ok(());
+        }
+
+        register_files_pending_delete(
+            data_files
+                .iter()
+                .chain(&delete_files)
+                .map(|f| f.file_path().to_string())
+                .collect(),
+            self.table.file_io().clone(),
+        );
+
+        let tx = Transaction::new(&self.table);
+        let tx = if delete_files.is_empty() {
+            tx.fast_append().add_data_files(data_files).apply(tx)?
+        } else {
+            tx.row_delta()
+                .add_data_files(data_files)
+                .add_delete_files(delete_files)
+                .apply(tx)?
+        };
+        self.table = stage_transaction(self.relid, &self.table, tx)?;
+
+        Ok(())
+    }
+
+    /// Stage the written files, remembering a failure so that it aborts the
+    /// transaction at commit.
+    fn finish_changes(&mut self) -> IcebergResult<()> {
+        self.stage_changes()
+            .inspect_err(|e| stage_failed(self.relid, e))
+    }
+
+    /// Close the data file writer, remove the files it wrote and forget the
+    /// collected deletes.
+    fn discard_changes(&mut self) {
+        self.deletes.clear();
+        let Some(writer) = self.writer.take() else {
+            return;
+        };
+
+        // Best effort, files left behind are only orphaned
+        let Ok(data_files) = writer.close() else {
+            return;
+        };
+        for data_file in data_files {
+            let _ = self.table.file_io().delete(data_file.file_path());
+        }
+    }
+}
 
 impl AmDml<IcebergError> for IcebergModify {
-    fn new(_rel: pg_sys::Relation) -> IcebergResult<Self> {
-        Ok(IcebergModify)
+    fn new(rel: pg_sys::Relation) -> IcebergResult<Self> {
+        let rel = unsafe { RelationHandle::from_raw(rel) };
+        time_travel::check_modify(&rel.relation_name())?;
+        let table = load_iceberg_table(&rel)?;
+
+        Ok(IcebergModify {
+            rel: rel.as_raw(),
+            relid: rel.oid(),
+            table,
+            writer: None,
+            deletes: BTreeMap::new(),
+            concurrent_changes: None,
+        })
     }
 
     fn begin_modify(&mut self) -> IcebergResult<()> {
-        Err(IcebergError::NotImplemented("begin_modify"))
+        Ok(())
     }
 
     fn end_modify(&mut self) -> IcebergResult<()> {
-        Err(IcebergError::NotImplemented("end_modify"))
+        // The session is also torn down when the transaction aborts, in which
+        // case the written files must not be staged.
+        if !unsafe { pg_sys::IsTransactionState() } {
+            self.discard_changes();
+            return Ok(());
+        }
+
+        self.finish_changes()
     }
 
     fn tuple_insert(
         &mut self,
-        _row: &Row,
+        row: &Row,
         _cid: pg_sys::CommandId,
         _options: i32,
         _bistate: Option<&mut BulkInsertStateHandle>,
     ) -> IcebergResult<()> {
-        Err(IcebergError::NotImplemented("tuple_insert"))
+        self.writer()?.write_row(row)
     }
 
     fn multi_insert(
         &mut self,
-        _rows: &[Row],
+        rows: &[Row],
         _cid: pg_sys::CommandId,
         _options: i32,
         _bistate: Option<&mut BulkInsertStateHandle>,
     ) -> IcebergResult<()> {
-        Err(IcebergError::NotImplemented("multi_insert"))
+        let writer = self.writer()?;
+        for row in rows {
+            writer.write_row(row)?;
+        }
+        Ok(())
+    }
+
+    fn finish_bulk_insert(&mut self, _options: i32) -> IcebergResult<()> {
+        self.finish_changes()
     }
 
     fn tuple_delete(
         &mut self,
-        _tid: &ItemPointer,
-        _cid: pg_sys::CommandId,
+        tid: &ItemPointer,
+        cid: pg_sys::CommandId,
         _snapshot: &SnapshotHandle,
         _crosscheck: Option<&SnapshotHandle>,
         _wait: bool,
-        _tmfd: &mut TM_FailureData,
+        tmfd: &mut TM_FailureData,
         _changing_part: bool,
     ) -> IcebergResult<pg_sys::TM_Result::Type> {
-        Err(IcebergError::NotImplemented("tuple_delete"))
+        let (file_path, position) = decode_tid(self.relid, tid)?;
+        let result = self.check_row_change(tid, &file_path, position, cid, tmfd)?;
+        if result != pg_sys::TM_Result::TM_Ok {
+            return Ok(result);
+        }
+
+        self.deletes.entry(file_path).or_default().insert(position);
+        Ok(pg_sys::TM_Result::TM_Ok)
     }
 
     fn tuple_update(
         &mut self,
-        _otid: &ItemPointer,
-        _row: &Row,
-        _cid: pg_sys::CommandId,
+        otid: &ItemPointer,
+        row: &Row,
+        cid: pg_sys::CommandId,
         _snapshot: &SnapshotHandle,
         _crosscheck: Option<&SnapshotHandle>,
         _wait: bool,
-        _tmfd: &mut TM_FailureData,
-        _lockmode: &mut pg_sys::LockTupleMode::Type,
-        _update_indexes: &mut pg_sys::TU_UpdateIndexes::Type,
+        tmfd: &mut TM_FailureData,
+        lockmode: &mut pg_sys::LockTupleMode::Type,
+        update_indexes: &mut pg_sys::TU_UpdateIndexes::Type,
     ) -> IcebergResult<pg_sys::TM_Result::Type> {
-        Err(IcebergError::NotImplemented("tuple_update"))
+        *lockmode = pg_sys::LockTupleMode::LockTupleExclusive;
+        *update_indexes = pg_sys::TU_UpdateIndexes::TU_None;
+
+        let (file_path, position) = decode_tid(self.relid, otid)?;
+        let result = self.check_row_change(otid, &file_path, position, cid, tmfd)?;
+        if result != pg_sys::TM_Result::TM_Ok {
+            return Ok(result);
+        }
+
+        // The old row is deleted and its new version written to a new data
+        // file, both are staged together as one snapshot
+        self.deletes.entry(file_path).or_default().insert(position);
+        self.writer()?.write_row(row)?;
+        Ok(pg_sys::TM_Result::TM_Ok)
     }
 
     fn tuple_lock(
         &mut self,
-        _tid: &ItemPointer,
+        tid: &ItemPointer,
         _snapshot: &SnapshotHandle,
-        _row: &mut Row,
+        row: &mut Row,
         _cid: pg_sys::CommandId,
         _mode: pg_sys::LockTupleMode::Type,
         _wait_policy: pg_sys::LockWaitPolicy::Type,
         _flags: u8,
-        _tmfd: &mut TM_FailureData,
+        tmfd: &mut TM_FailureData,
     ) -> IcebergResult<pg_sys::TM_Result::Type> {
-        Err(IcebergError::NotImplemented("tuple_lock"))
+        let rel = unsafe { RelationHandle::from_raw(self.rel) };
+
+        // Under READ COMMITTED the executor locks the latest version of a
+        // concurrently updated row to recheck it. That version cannot be
+        // located, and the transaction could not commit on top of the
+        // concurrent changes to its data file anyway.
+        let (file_path, _) = decode_tid(self.relid, tid)?;
+        if self.concurrently_changed(&file_path)? {
+            return Err(IcebergError::ConcurrentUpdate(rel.relation_name()));
+        }
+
+        // Rows are not locked, the row only needs to exist
+        if !fetch_row(&rel, tid, row)? {
+            *tmfd = TM_FailureData {
+                ctid: *tid,
+                xmax: pg_sys::InvalidTransactionId,
+                cmax: pg_sys::CommandId::MAX,
+                traversed: false,
+            };
+            return Ok(pg_sys::TM_Result::TM_Deleted);
+        }
+
+        Ok(pg_sys::TM_Result::TM_Ok)
     }
 }
@@ -0,0 +1,335 @@
+//! Translation of PostgreSQL scan keys into Iceberg predicates.
+//!
+//! A scan passes its keys, and those of its plan's quals, to
+//! `TableScanBuilder::with_filter`, which prunes manifests, data files and row
+//! groups by their statistics. Keys are translated when the predicate selects
+//! at least the rows PostgreSQL's operator does:
+//!
+//! - btree strategies become comparisons, `SK_SEARCHNULL` and
+//!   `SK_SEARCHNOTNULL` null tests and `SK_SEARCHARRAY` equality `IN` lists;
+//! - arguments are converted to the exact type of the column, keys whose
+//!   arguments cannot be represented in it are not translated;
+//! - strings are compared bytewise by the reader, which matches PostgreSQL for
+//!   equality under deterministic collations and for ordering under the C
+//!   collation only.
+//!
+//! The rows read are not filtered by the predicate. The scan key test of
+//! `pg_tam` checks every returned row against all scan keys, the executor
+//! against all quals.
+
+use super::convert::{UNIX_TO_PG_EPOCH_DAYS, UNIX_TO_PG_EPOCH_MICROS};
+use crate::catalog::FieldIds;
+use iceberg_lite::expr::{Predicate, Reference};
+use iceberg_lite::spec::{Datum, PrimitiveType, Schema, Type};
+use pg_tam::prelude::{RelationHandle, ScanKeyHandle};
+use pgrx::datum::Uuid;
+use pgrx::{AnyNumeric, FromDatum, PgBuiltInOids, PgOid, pg_sys};
+use rust_decimal::Decimal;
+
+/// Largest scale of a `rust_decimal::Decimal`.
+const MAX_DECIMAL_SCALE: u32 = 28;
+
+/// The predicate selecting the rows that may satisfy all `keys` of a scan of
+/// `rel`, `None` when no key can be translated.
+pub fn scan_keys_to_predicate(
+    rel: &RelationHandle,
+    schema: &Schema,
+    field_ids: &FieldIds,
+    keys: &[pg_sys::ScanKeyData],
+) -> Option<Predicate> {
+    keys.iter()
+        .filter_map(|key| scan_key_to_predicate(rel, schema, field_ids, key))
+        .reduce(Predicate::and)
+}
+
+fn scan_key_to_predicate(
+    rel: &RelationHandle,
+    schema: &Schema,
+    field_ids: &FieldIds,
+    key: &pg_sys::ScanKeyData,
+) -> Option<Predicate> {
+    let field = schema.field_by_id(field_ids.field_id(key.sk_attno)?)?;
+    let Type::Primitive(field_type) = field.field_type.as_ref() else {
+        return None;
+    };
+    let column_type = column_type(rel, key.sk_attno)?;
+    let reference = Reference::new(field.name.clone());
+    let flags = key.sk_flags as u32;
+
+    if (flags & pg_sys::SK_SEARCHNULL) != 0 {
+        return Some(reference.is_null());
+    }
+    if (flags & pg_sys::SK_SEARCHNOTNULL) != 0 {
+        return Some(reference.is_not_null());
+    }
+    // Comparisons with NULL are never true
+    if (flags & pg_sys::SK_ISNULL) != 0 {
+        return Some(Predicate::AlwaysFalse);
+    }
+
+    let strategy = key.sk_strategy as u32;
+    if !is_pushable_strategy(strategy, column_type, key.sk_collation) {
+        return None;
+    }
+
+    // The type of the argument, or of the array elements of `SK_SEARCHARRAY`
+    let argument_type = if key.sk_subtype == pg_sys::InvalidOid {
+        column_type
+    } else {
+        key.sk_subtype
+    };
+
+    if (flags & pg_sys::SK_SEARCHARRAY) != 0 {
+        if strategy != pg_sys::BTEqualStrategyNumber {
+            return None;
+        }
+        // NULL elements never compare equal
+        let literals = unsafe { ScanKeyHandle::array_elements(key) }
+            .into_iter()
+            .flatten()
+            .map(|element| to_iceberg_datum(element, argument_type, field_type))
+            .collect::<Option<Vec<_>>>()?;
+        return Some(reference.is_in(literals));
+    }
+
+    let datum = to_iceberg_datum(key.sk_argument, argument_type, field_type)?;
+    comparison(strategy, reference, datum)
+}
+
+/// The comparison of `reference` with `datum` for a btree `strategy`.
+fn comparison(
+    strategy: u32,
+    reference: Reference,
+    datum: Datum,
+) -> Option<Predicate> {
+    Some(match strategy {
+        pg_sys::BTLessStrategyNumber => reference.less_than(datum),
+        pg_sys::BTLessEqualStrategyNumber => reference.less_than_or_equal_to(datum),
+        pg_sys::BTEqualStrategyNumber => reference.equal_to(datum),
+        pg_sys::BTGreaterEqualStrategyNumber => {
+            reference.greater_than_or_equal_to(datum)
+        }
+        pg_sys::BTGreaterStrategyNumber => reference.greater_than(datum),
+        _ => return None,
+    })
+}
+
+/// Whether the reader evaluates `strategy` on a column of `column_type` like
+/// PostgreSQL does under `collation`.
+fn is_pushable_strategy(
+    strategy: u32,
+    column_type: pg_sys::Oid,
+    collation: pg_sys::Oid,
+) -> bool {
+    if !(pg_sys::BTLessStrategyNumber..=pg_sys::BTGreaterStrategyNumber)
+        .contains(&strategy)
+    {
+        return false;
+    }
+
+    match PgOid::from(column_type) {
+        PgOid::BuiltIn(PgBuiltInOids::TEXTOID | PgBuiltInOids::VARCHAROID) => {
+            if collation == pg_sys::InvalidOid {
+                return false;
+            }
+            let binary = collation == pg_sys::Oid::from(pg_sys::C_COLLATION_OID)
+                || collation == pg_sys::Oid::from(pg_sys::POSIX_COLLATION_OID);
+            binary
+                || (strategy == pg_sys::BTEqualStrategyNumber
+                    && unsafe { pg_sys::get_collation_isdeterministic(collation) })
+        }
+        // Padding, names and JSON do not compare like the strings they are
+        // stored as
+        PgOid::BuiltIn(
+            PgBuiltInOids::BPCHAROID
+            | PgBuiltInOids::NAMEOID
+            | PgBuiltInOids::JSONOID
+            | PgBuiltInOids::JSONBOID,
+        ) => false,
+        _ => true,
+    }
+}
+
+/// The type of the attribute `attno` of `rel`, `None` for dropped columns.
+fn column_type(
+    rel: &RelationHandle,
+    attno: pg_sys::AttrNumber,
+) -> Option<pg_sys::Oid> {
+    unsafe {
+        let tup_desc = (*rel.as_raw()).rd_att;
+        let natts = (*tup_desc).natts as usize;
+        let attrs = std::slice::from_raw_parts((*tup_desc).attrs.as_ptr(), natts);
+        let attr = attrs.get((attno as usize).checked_sub(1)?)?;
+        (!attr.attisdropped).then_some(attr.atttypid)
+    }
+}
+
+/// Convert the PostgreSQL `value` of type `type_oid` into a datum of
+/// `field_type`, `None` when it cannot be represented exactly.
+fn to_iceberg_datum(
+    value: pg_sys::Datum,
+    type_oid: pg_sys::Oid,
+    field_type: &PrimitiveType,
+) -> Option<Datum> {
+    let pg_oid = PgOid::from(type_oid);
+
+    unsafe {
+        match (pg_oid, field_type) {
+            (PgOid::BuiltIn(PgBuiltInOids::BOOLOID), PrimitiveType::Boolean) => {
+                Some(Datum::bool(bool::from_datum(value, false)?))
+            }
+            (
+                PgOid::BuiltIn(
+                    PgBuiltInOids::INT2OID
+                    | PgBuiltInOids::INT4OID
+                    | PgBuiltInOids::INT8OID,
+                ),
+                PrimitiveType::Int | PrimitiveType::Long,
+            ) => {
+                let value = match pg_oid {
+                    PgOid::BuiltIn(PgBuiltInOids::INT2OID) => {
+                        i16::from_datum(value, false)? as i64
+                    }
+                    PgOid::BuiltIn(PgBuiltInOids::INT4OID) => {
+                        i32::from_datum(value, false)? as i64
+                    }
+                    _ => i64::from_datum(value, false)?,
+                };
+                match field_type {
+                    PrimitiveType::Int => {
+                        Some(Datum::int(i32::try_from(value).ok()?))
+                    }
+                    _ => Some(Datum::long(value)),
+                }
+            }
+            // The reader orders -0 before 0, PostgreSQL considers them equal.
+            // Both order NaN after every other value.
+            (PgOid::BuiltIn(PgBuiltInOids::FLOAT4OID), PrimitiveType::Float) => {
+                let value = f32::from_datum(value, false)?;
+                (value.is_finite() && value != 0.0).then(|| Datum::float(value))
+            }
+            (
+                PgOid::BuiltIn(PgBuiltInOids::FLOAT4OID | PgBuiltInOids::FLOAT8OID),
+                PrimitiveType::Double,
+            ) => {
+                let value = match pg_oid {
+                    PgOid::BuiltIn(PgBuiltInOids::FLOAT4OID) => {
+                        f32::from_datum(value, false)? as f64
+                    }
+                    _ => f64::from_datum(value, false)?,
+                };
+                (value.is_finite() && value != 0.0).then(|| Datum::double(value))
+            }
+            (
+                PgOid::BuiltIn(PgBuiltInOids::NUMERICOID),
+                PrimitiveType::Decimal { precision, scale },
+            ) => {
+                let value = AnyNumeric::from_datum(value, false)?;
+                decimal_datum(&value.to_string(), *precision, *scale)
+            }
+            (
+                PgOid::BuiltIn(PgBuiltInOids::TEXTOID | PgBuiltInOids::VARCHAROID),
+                PrimitiveType::String,
+            ) => Some(Datum::string(String::from_datum(value, false)?)),
+            (PgOid::BuiltIn(PgBuiltInOids::DATEOID), PrimitiveType::Date) => {
+                let days =
+                    (value.value() as i32).checked_add(UNIX_TO_PG_EPOCH_DAYS)?;
+                Some(Datum::date(days))
+            }
+            (PgOid::BuiltIn(PgBuiltInOids::TIMEOID), PrimitiveType::Time) => {
+                Datum::time_micros(value.value() as i64).ok()
+            }
+            (
+                PgOid::BuiltIn(PgBuiltInOids::TIMESTAMPOID),
+                PrimitiveType::Timestamp,
+            ) => {
+                let micros =
+                    (value.value() as i64).checked_add(UNIX_TO_PG_EPOCH_MICROS)?;
+                Some(Datum::timestamp_micros(micros))
+            }
+            (
+                PgOid::BuiltIn(PgBuiltInOids::TIMESTAMPTZOID),
+                PrimitiveType::Timestamptz,
+            ) => {
+                let micros =
+                    (value.value() as i64).checked_add(UNIX_TO_PG_EPOCH_MICROS)?;
+                Some(Datum::timestamptz_micros(micros))
+            }
+            (PgOid::BuiltIn(PgBuiltInOids::UUIDOID), PrimitiveType::Uuid) => {
+                let value = Uuid::from_datum(value, false)?;
+                Some(Datum::uuid(uuid::Uuid::from_bytes(*value.as_bytes())))
+            }
+            (PgOid::BuiltIn(PgBuiltInOids::BYTEAOID), PrimitiveType::Binary) => {
+                Some(Datum::binary(<&[u8]>::from_datum(value, false)?.to_vec()))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The decimal datum of type `decimal(precision, scale)` for the numeric
+/// `text`, `None` when it has more fractional digits than `scale` or more
+/// digits than `precision`.
+fn decimal_datum(text: &str, precision: u32, scale: u32) -> Option<Datum> {
+    if scale > MAX_DECIMAL_SCALE {
+        return None;
+    }
+
+    let mut value = Decimal::from_str_exact(text).ok()?.normalize();
+    if value.scale() > scale {
+        return None;
+    }
+    value.rescale(scale);
+    if value.mantissa().unsigned_abs() >= 10u128.checked_pow(precision)? {
+        return None;
+    }
+
+    Datum::decimal_with_precision(value, precision).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decimal_datum() {
+        let datum = decimal_datum("12.5", 10, 2).unwrap();
+        assert_eq!(
+            datum.data_type(),
+            &PrimitiveType::Decimal {
+                precision: 10,
+                scale: 2
+            }
+        );
+        assert_eq!(datum.to_string(), "12.50");
+
+        // Trailing zeros do not count as fractional digits
+        assert!(decimal_datum("-3.1400", 3, 2).is_some());
+
+        assert!(decimal_datum("12.505", 10, 2).is_none());
+        assert!(decimal_datum("123456", 5, 0).is_none());
+        assert!(decimal_datum("NaN", 10, 2).is_none());
+    }
+
+    #[test]
+    fn test_comparison_strategies() {
+        let reference = Reference::new("id");
+        assert_eq!(
+            comparison(
+                pg_sys::BTLessStrategyNumber,
+                reference.clone(),
+                Datum::int(5)
+            ),
+            Some(reference.clone().less_than(Datum::int(5)))
+        );
+        assert_eq!(
+            comparison(
+                pg_sys::BTEqualStrategyNumber,
+                reference.clone(),
+                Datum::int(5)
+            ),
+            Some(reference.clone().equal_to(Datum::int(5)))
+        );
+        assert_eq!(comparison(0, reference, Datum::int(5)), None);
+    }
+}
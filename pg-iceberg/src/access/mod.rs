@@ -1,6 +1,13 @@
+pub mod commit;
+pub mod convert;
 pub mod ddl;
 pub mod dml;
+pub mod filter;
 pub mod index;
+pub mod parallel;
 pub mod pending_deletes;
 pub mod relation;
 pub mod scan;
+pub mod tid;
+pub mod time_travel;
+pub mod writer;
@@ -0,0 +1,161 @@
+//! Parallel sequential scans of Iceberg tables.
+//!
+//! The leader shares the table it scans with the workers through the parallel
+//! scan descriptor, which extends PostgreSQL's `ParallelTableScanDescData`
+//! with a counter of claimed file scan tasks, followed by the table's metadata
+//! location and its metadata serialized as JSON:
+//!
+//! - The leader serializes the table once, when PostgreSQL estimates the size
+//!   of the descriptor, and copies the same bytes into the descriptor when it
+//!   is initialized. The serialized tables are kept until the end of the
+//!   transaction.
+//! - Every participant loads the shared table and plans its data files.
+//!   Planning is deterministic, so the task at an index is the same for all
+//!   of them, and changes staged by the leader's transaction are included.
+//! - Participants claim whole file scan tasks by incrementing the counter, so
+//!   that every data file is read by exactly one of them.
+//!
+//! Workers number data files in their own backend, see `access::tid`, so the
+//! rows of parallel scans are returned without TID.
+
+use crate::catalog::{iceberg_table, load_iceberg_table};
+use crate::error::{IcebergError, IcebergResult};
+use crate::storage::create_storage_context;
+use iceberg_lite::spec::TableMetadata;
+use iceberg_lite::table::Table;
+use pg_tam::prelude::*;
+use pgrx::pg_sys;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Shared state of a parallel scan, followed by the metadata location and the
+/// table metadata.
+#[repr(C)]
+struct IcebergParallelScanDesc {
+    base: pg_sys::ParallelTableScanDescData,
+    /// Index of the next file scan task to claim.
+    next_task: AtomicU64,
+    /// Length of the metadata location, zero when the table has none.
+    location_len: usize,
+    /// Length of the table metadata JSON.
+    metadata_len: usize,
+}
+
+/// The metadata location and serialized metadata of a shared table.
+struct SharedTable {
+    location: String,
+    metadata: Vec<u8>,
+}
+
+thread_local! {
+    /// The tables last serialized for a parallel scan, by relation.
+    static SHARED_TABLES: RefCell<HashMap<pg_sys::Oid, Rc<SharedTable>>> =
+        RefCell::new(HashMap::new());
+}
+
+/// The metadata location and serialized metadata of the table of `rel` as
+/// seen by the current transaction.
+fn table_to_share(rel: &RelationHandle) -> IcebergResult<SharedTable> {
+    let table = load_iceberg_table(rel)?;
+    let location = table.metadata_location().unwrap_or_default().to_string();
+    let metadata = serde_json::to_vec(table.metadata())?;
+    Ok(SharedTable { location, metadata })
+}
+
+/// Drop the tables serialized for parallel scans.
+pub fn reset_shared_tables() {
+    SHARED_TABLES.with(|tables| tables.borrow_mut().clear());
+}
+
+/// Size of the descriptor sharing `location` and `metadata`, aligned so that
+/// the snapshot PostgreSQL stores after it is too.
+fn desc_size(location: &str, metadata: &[u8]) -> pg_sys::Size {
+    let size = size_of::<IcebergParallelScanDesc>() + location.len() + metadata.len();
+    size.next_multiple_of(pg_sys::MAXIMUM_ALIGNOF as usize)
+}
+
+/// Size of the shared descriptor of a parallel scan of `rel`.
+///
+/// The serialized table is kept for `parallelscan_initialize`, so that the
+/// descriptor is filled with exactly the bytes its size was estimated for.
+pub fn parallelscan_estimate(rel: &RelationHandle) -> IcebergResult<pg_sys::Size> {
+    let shared = table_to_share(rel)?;
+    let size = desc_size(&shared.location, &shared.metadata);
+    SHARED_TABLES
+        .with(|tables| tables.borrow_mut().insert(rel.oid(), Rc::new(shared)));
+    Ok(size)
+}
+
+/// Initialize the shared descriptor of a parallel scan of `rel`, returning its
+/// size.
+pub fn parallelscan_initialize(
+    rel: &RelationHandle,
+    pscan: &ParallelTableScanDescHandle,
+) -> IcebergResult<pg_sys::Size> {
+    let shared = SHARED_TABLES
+        .with(|tables| tables.borrow().get(&rel.oid()).cloned())
+        .ok_or_else(|| IcebergError::ParallelScanNotEstimated(rel.relation_name()))?;
+    let (location, metadata) = (&shared.location, &shared.metadata);
+
+    unsafe {
+        let desc = pscan.as_raw() as *mut IcebergParallelScanDesc;
+        (*desc).base.phs_locator = (*rel.as_raw()).rd_locator;
+        (*desc).base.phs_syncscan = false;
+        std::ptr::write(&raw mut (*desc).next_task, AtomicU64::new(0));
+        (*desc).location_len = location.len();
+        (*desc).metadata_len = metadata.len();
+
+        let data = desc.add(1) as *mut u8;
+        std::ptr::copy_nonoverlapping(location.as_ptr(), data, location.len());
+        std::ptr::copy_nonoverlapping(
+            metadata.as_ptr(),
+            data.add(location.len()),
+            metadata.len(),
+        );
+    }
+
+    Ok(desc_size(location, metadata))
+}
+
+/// Reset the shared descriptor for a rescan, so that all tasks are claimed
+/// again.
+pub fn parallelscan_reinitialize(pscan: &ParallelTableScanDescHandle) {
+    let desc = pscan.as_raw() as *const IcebergParallelScanDesc;
+    unsafe { (*desc).next_task.store(0, Ordering::Relaxed) };
+}
+
+/// The table shared by the leader of a parallel scan of `rel`.
+pub fn load_shared_table(
+    rel: &RelationHandle,
+    pscan: &ParallelTableScanDescHandle,
+) -> IcebergResult<Table> {
+    let (location, metadata) = unsafe {
+        let desc = pscan.as_raw() as *const IcebergParallelScanDesc;
+        let data = desc.add(1) as *const u8;
+        (
+            std::slice::from_raw_parts(data, (*desc).location_len),
+            std::slice::from_raw_parts(
+                data.add((*desc).location_len),
+                (*desc).metadata_len,
+            ),
+        )
+    };
+
+    let location = String::from_utf8_lossy(location).to_string();
+    let metadata: TableMetadata = serde_json::from_slice(metadata)?;
+    let ctx = create_storage_context(rel.tablespace_oid())?;
+    iceberg_table(
+        rel,
+        ctx.file_io,
+        (!location.is_empty()).then_some(location),
+        metadata,
+    )
+}
+
+/// Claim the next file scan task of a parallel scan, returning its index.
+pub fn claim_task(pscan: &ParallelTableScanDescHandle) -> usize {
+    let desc = pscan.as_raw() as *const IcebergParallelScanDesc;
+    unsafe { (*desc).next_task.fetch_add(1, Ordering::Relaxed) as usize }
+}
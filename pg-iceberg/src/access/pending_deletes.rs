@@ -2,7 +2,8 @@
 //!
 //! This module provides the pending delete mechanism for Iceberg tables,
 //! allowing table directories to be cleaned up when transactions abort (for CREATE)
-//! or commit (for DROP).
+//! or commit (for DROP), and files written by a transaction to be cleaned up
+//! when it aborts.
 //!
 //! # Background
 //!
@@ -104,6 +105,39 @@ impl PendingDelete for IcebergPendingDelete {
     }
 }
 
+/// Pending delete entry for files written by a transaction.
+///
+/// Data, manifest and metadata files are only referenced by the table once the
+/// transaction commits, so they are deleted if it aborts. Files the transaction
+/// wrote but no longer references are deleted once it commits.
+#[derive(Debug)]
+pub struct IcebergPendingFileDelete {
+    /// The files to delete
+    paths: Vec<String>,
+    /// The FileIO instance for performing the delete
+    file_io: FileIO,
+    /// Whether to execute on commit (true) or abort (false)
+    at_commit: bool,
+}
+
+impl PendingDelete for IcebergPendingFileDelete {
+    fn execute(&self) {
+        // Best-effort deletion - log errors but don't fail
+        for path in &self.paths {
+            if let Err(e) = self.file_io.delete(path) {
+                pg_tam::diag::report_warning(&format!(
+                    "Failed to delete file '{}': {}",
+                    path, e
+                ));
+            }
+        }
+    }
+
+    fn at_commit(&self) -> bool {
+        self.at_commit
+    }
+}
+
 /// Register a pending delete for an Iceberg table directory (abort cleanup).
 ///
 /// This should be called immediately after creating the table directory.
@@ -131,3 +165,48 @@ pub fn register_drop_table_pending_delete(location: String, file_io: FileIO) {
     let pending = IcebergPendingDelete::new_for_commit(location, file_io);
     register_pending_delete(Box::new(pending));
 }
+
+/// Register a pending delete for files written by the transaction (abort cleanup).
+///
+/// This should be called once the files are closed. If the transaction
+/// aborts, the files will be deleted.
+///
+/// # Arguments
+///
+/// * `paths` - The locations of the written files
+/// * `file_io` - The FileIO instance used for deletion
+pub fn register_files_pending_delete(paths: Vec<String>, file_io: FileIO) {
+    if paths.is_empty() {
+        return;
+    }
+
+    let pending = IcebergPendingFileDelete {
+        paths,
+        file_io,
+        at_commit: false,
+    };
+    register_pending_delete(Box::new(pending));
+}
+
+/// Register a pending delete for files the transaction wrote but no longer
+/// references (commit cleanup).
+///
+/// If the transaction aborts, the files are deleted by the abort cleanup
+/// registered when they were written.
+///
+/// # Arguments
+///
+/// * `paths` - The locations of the superseded files
+/// * `file_io` - The FileIO instance used for deletion
+pub fn register_files_commit_delete(paths: Vec<String>, file_io: FileIO) {
+    if paths.is_empty() {
+        return;
+    }
+
+    let pending = IcebergPendingFileDelete {
+        paths,
+        file_io,
+        at_commit: true,
+    };
+    register_pending_delete(Box::new(pending));
+}
@@ -1,25 +1,134 @@
+//! Relation callbacks of Iceberg tables.
+//!
+//! The summary of the current snapshot records the number of rows and the
+//! total size of the table's files. Sizes are reported in pseudo-pages, the
+//! number of blocks the files would fill, so that the planner costs scans and
+//! sizes parallel ones by the data read.
+//!
+//! The main fork is reported with as many blocks, ANALYZE samples them as
+//! chunks of the table's files, see `access::scan`.
+//!
+//! Sizes are cached per relation and metadata location, so that planning
+//! does not read the table metadata while the table is unchanged.
+
+use super::commit::staged_table;
+use super::scan::fetch_row;
+use crate::catalog::{IcebergMetadata, load_iceberg_table};
 use crate::error::{IcebergError, IcebergResult};
+use iceberg_lite::table::Table;
 use pg_tam::prelude::*;
 use pgrx::pg_sys;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+/// The size of an Iceberg table as recorded by its current snapshot.
+#[derive(Clone, Copy, Default)]
+pub struct TableSize {
+    /// Live rows, the records of the data files without position deletes.
+    pub tuples: u64,
+    /// Total size of the data and delete files.
+    pub bytes: u64,
+}
+
+impl TableSize {
+    pub fn of(table: &Table) -> Self {
+        let Some(summary) = table
+            .metadata()
+            .current_snapshot()
+            .map(|snapshot| snapshot.summary())
+        else {
+            return TableSize::default();
+        };
+
+        let records = summary.total_records().unwrap_or(0);
+        let deletes = summary.total_position_deletes().unwrap_or(0);
+        TableSize {
+            tuples: records.saturating_sub(deletes),
+            bytes: summary.total_files_size().unwrap_or(0),
+        }
+    }
+
+    /// The number of blocks the table's files would fill.
+    pub fn pages(&self) -> pg_sys::BlockNumber {
+        self.bytes
+            .div_ceil(pg_sys::BLCKSZ as u64)
+            .min(pg_sys::BlockNumber::MAX as u64 - 1) as pg_sys::BlockNumber
+    }
+}
+
+thread_local! {
+    /// Table sizes by relation with the metadata location they were read from.
+    static TABLE_SIZES: RefCell<HashMap<pg_sys::Oid, (String, TableSize)>> =
+        RefCell::new(HashMap::new());
+}
+
+/// The size of the table of `rel` as seen by the current transaction.
+fn table_size(rel: &RelationHandle) -> IcebergResult<TableSize> {
+    // Staged changes are not written to a metadata location yet
+    if let Some(table) = staged_table(rel.oid()) {
+        return Ok(TableSize::of(&table));
+    }
+
+    let relid = rel.oid();
+    let location = IcebergMetadata::get(relid)?.metadata_location;
+    let cached = TABLE_SIZES.with(|sizes| {
+        sizes
+            .borrow()
+            .get(&relid)
+            .filter(|(cached, _)| Some(cached) == location.as_ref())
+            .map(|(_, size)| *size)
+    });
+    if let Some(size) = cached {
+        return Ok(size);
+    }
+
+    let table = load_iceberg_table(rel)?;
+    let size = TableSize::of(&table);
+    if let Some(location) = table.metadata_location() {
+        TABLE_SIZES.with(|sizes| {
+            sizes
+                .borrow_mut()
+                .insert(relid, (location.to_string(), size))
+        });
+    }
+
+    Ok(size)
+}
 
 pub struct IcebergRelation;
 
 impl AmRelation<IcebergError> for IcebergRelation {
     fn relation_estimate_size(
-        _rel: &RelationHandle,
+        rel: &RelationHandle,
         _attr_widths: Option<&mut [i32]>,
     ) -> IcebergResult<(pg_sys::BlockNumber, f64, f64)> {
-        // Return zeros for now: (pages, tuples, all_visible_pages)
-        // This allows basic DDL operations to complete.
-        Ok((0, 0.0, 0.0))
+        let size = table_size(rel)?;
+        Ok((size.pages(), size.tuples as f64, 0.0))
     }
 
     fn relation_size(
-        _rel: &RelationHandle,
-        _fork_number: pg_sys::ForkNumber::Type,
+        rel: &RelationHandle,
+        fork_number: pg_sys::ForkNumber::Type,
     ) -> IcebergResult<u64> {
-        // Return 0 bytes for now.
-        // Real implementation would query Iceberg metadata for actual data files size.
-        Ok(0)
+        // The table's files stand in for the main fork, there are no others
+        match fork_number {
+            pg_sys::ForkNumber::MAIN_FORKNUM
+            | pg_sys::ForkNumber::InvalidForkNumber => {
+                Ok(table_size(rel)?.pages() as u64 * pg_sys::BLCKSZ as u64)
+            }
+            _ => Ok(0),
+        }
+    }
+
+    fn tuple_fetch_row_version(
+        rel: &RelationHandle,
+        tid: &ItemPointer,
+        _snapshot: &SnapshotHandle,
+        row: &mut Row,
+    ) -> IcebergResult<bool> {
+        // Rows are versioned by Iceberg snapshots rather than PostgreSQL
+        // snapshots, the row is fetched from the table as seen by the
+        // current transaction.
+        fetch_row(rel, tid, row)
     }
 }
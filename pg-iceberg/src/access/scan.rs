@@ -1,45 +1,751 @@
+//! Sequential scan of Iceberg tables.
+//!
+//! The scan loads the table from the metadata location recorded in
+//! `lakehouse.iceberg_metadata`, plans the data files of its current snapshot
+//! and streams the Arrow record batches read from them into PostgreSQL rows.
+//!
+//! Rows are returned with a TID encoding their data file and position, see
+//! `access::tid`, so that they can be deleted. Positions are read through the
+//! `_pos` metadata column, which the reader cannot provide for data files with
+//! equality deletes; rows of such files are returned without TID.
+//!
+//! Only the columns the query references are read, see
+//! `pg_tam::access::projection`, the other attributes are returned as NULL.
+//!
+//! Scan keys, and the quals of the plan's sequential scan that compare a
+//! column with a constant, are translated into an Iceberg predicate, see
+//! `access::filter`. The predicate only prunes the data files and row groups
+//! read, their rows are returned unfiltered so that their positions stay
+//! known; the keys and quals are checked against the returned rows. Parallel
+//! scans use no plan quals, as every participant must plan the same files.
+//!
+//! The time travel settings select a past snapshot to read instead of the
+//! current one, which is then read with its own schema, see
+//! `access::time_travel`. ANALYZE always reads the current snapshot.
+//!
+//! Parallel scans share the table and claim its file scan tasks through the
+//! parallel scan descriptor, see `access::parallel`.
+//!
+//! ANALYZE samples the pseudo-pages of the table, see `access::relation`, as
+//! chunks of its data files in the order they are planned. The block sampler
+//! of `acquire_sample_rows` picks the blocks, the scan takes their numbers
+//! from the read stream without reading the storage-less main fork and reads
+//! only the row groups overlapping the chunk of each. The live rows reported
+//! are the sampled blocks' share of the live rows the snapshot summary sums
+//! from the manifests' record counts, which ANALYZE extrapolates to the table.
+//!
+//! `fetch_row` resolves a TID back to its row. It reads the data file up to
+//! the row's position and keeps the file open, so that fetching the rows of a
+//! file in position order, as the executor does after a scan, reads it once.
+//! The table and the data files planned for it are kept until the end of the
+//! transaction, and reused while its metadata location and the snapshot read
+//! are unchanged.
+
+use super::commit::staged_table;
+use super::convert::arrow_value_to_cell;
+use super::filter::scan_keys_to_predicate;
+use super::parallel::{self, claim_task, load_shared_table};
+use super::relation::TableSize;
+use super::tid::{
+    data_file_ordinal, decode_tid, encode_tid, register_snapshot_files,
+    resolves_earlier_tids,
+};
+use super::time_travel::{scan_schema, scan_snapshot};
+use crate::catalog::{FieldIds, IcebergMetadata, load_iceberg_table};
 use crate::error::{IcebergError, IcebergResult};
+use arrow_array::{Array, Int64Array, RecordBatch};
+use iceberg_lite::arrow::{ArrowReader, ArrowReaderBuilder, ArrowRecordBatchIterator};
+use iceberg_lite::expr::Predicate;
+use iceberg_lite::metadata_columns::RESERVED_FIELD_ID_POS;
+use iceberg_lite::scan::{FileScanTask, TableScanBuilder};
+use iceberg_lite::spec::{DataContentType, Schema, SnapshotRef};
+use iceberg_lite::table::Table;
 use pg_tam::prelude::*;
+use pgrx::pg_sys;
+use std::cell::RefCell;
+use std::collections::{BTreeSet, HashMap};
+use std::ffi::CStr;
+
+/// A PostgreSQL attribute and where its values are found in the record batches.
+struct ScanColumn {
+    name: String,
+    type_oid: pg_sys::Oid,
+    /// Index of the column in the record batches, `None` for dropped columns
+    /// and columns that are not part of the table schema.
+    batch_index: Option<usize>,
+}
+
+pub struct IcebergScan {
+    rel: pg_sys::Relation,
+    relid: pg_sys::Oid,
+    table: Table,
+    /// The past snapshot read, `None` for the current one.
+    snapshot: Option<SnapshotRef>,
+    columns: Vec<ScanColumn>,
+    /// The fields read, `None` for all of them.
+    selection: Option<Vec<String>>,
+    /// Scan keys from the quals of the plan, kept for rescans.
+    plan_keys: Vec<pg_sys::ScanKeyData>,
+    /// The scan keys and plan keys translated into a predicate.
+    filter: Option<Predicate>,
+    reader: ArrowReader,
+    tasks: Vec<FileScanTask>,
+    next_task: usize,
+    /// Shared descriptor of a parallel scan, which hands out the tasks.
+    pscan: Option<pg_sys::ParallelTableScanDesc>,
+    /// Whether the scan samples rows for ANALYZE.
+    sampling: bool,
+    /// Live rows of the table per block, for ANALYZE.
+    block_rows: f64,
+    /// Blocks sampled for ANALYZE so far.
+    sampled_blocks: u64,
+    /// The task of the data file sampled last and the position of the next
+    /// row of it not returned to ANALYZE yet. Row groups overlapping several
+    /// blocks sampled are returned once.
+    sampled_file: Option<(usize, u64)>,
+    batches: Option<ArrowRecordBatchIterator>,
+    batch: Option<RecordBatch>,
+    batch_row: usize,
+    /// Ordinal of the data file being read, when its rows get a TID. The row
+    /// positions are then the last column of the record batches.
+    file_ordinal: Option<u32>,
+}
+
+impl IcebergScan {
+    /// Build the attribute to batch column mapping from the relation's tuple
+    /// descriptor. Attributes are read from the fields `field_ids` maps them
+    /// to, attributes without a field in `schema` read as NULL.
+    ///
+    /// With a `projection`, only the projected attributes are read and the
+    /// names of their fields are returned in batch order. The other attributes
+    /// have no batch column.
+    fn scan_columns(
+        rel: &RelationHandle,
+        schema: &Schema,
+        field_ids: &FieldIds,
+        projection: Option<&BTreeSet<pg_sys::AttrNumber>>,
+    ) -> (Vec<ScanColumn>, Option<Vec<String>>) {
+        let fields = schema.as_struct().fields();
+        let mut selection = projection.map(|_| Vec::new());
+
+        let columns = unsafe {
+            let tup_desc = (*rel.as_raw()).rd_att;
+            let natts = (*tup_desc).natts as usize;
+            let attrs = std::slice::from_raw_parts((*tup_desc).attrs.as_ptr(), natts);
+
+            attrs
+                .iter()
+                .enumerate()
+                .map(|(i, attr)| {
+                    let name = CStr::from_ptr(attr.attname.data.as_ptr())
+                        .to_string_lossy()
+                        .to_string();
+                    let attnum = (i + 1) as pg_sys::AttrNumber;
+                    let field = field_ids.field_id(attnum).and_then(|field_id| {
+                        fields.iter().position(|f| f.id == field_id)
+                    });
+                    let batch_index = match (field, selection.as_mut()) {
+                        _ if attr.attisdropped => None,
+                        (Some(index), Some(selection)) => projection
+                            .is_some_and(|p| p.contains(&attnum))
+                            .then(|| {
+                                selection.push(fields[index].name.clone());
+                                selection.len() - 1
+                            }),
+                        (field, _) => field,
+                    };
+
+                    ScanColumn {
+                        name,
+                        type_oid: attr.atttypid,
+                        batch_index,
+                    }
+                })
+                .collect()
+        };
+
+        // Reading no column would read all of them, the first one is enough to
+        // count the rows
+        if let Some(selection) = selection.as_mut()
+            && selection.is_empty()
+            && let Some(field) = fields.first()
+        {
+            selection.push(field.name.clone());
+        }
+
+        (columns, selection)
+    }
+
+    /// Plan the data files to read, pruned by the scan's filter.
+    fn plan_files(&self) -> IcebergResult<Vec<FileScanTask>> {
+        let scan = table_scan(&self.table, self.snapshot.as_ref());
+        let mut scan = match self.selection.clone() {
+            Some(selection) => scan.select(selection),
+            None => scan.select_all(),
+        };
+        if let Some(filter) = self.filter.clone() {
+            scan = scan.with_filter(filter);
+        }
+        Ok(scan.build()?.plan_files()?)
+    }
+
+    /// Advance to the next record batch, opening the next data file when the
+    /// current one is exhausted. Returns `false` once all files are read.
+    fn next_batch(&mut self) -> IcebergResult<bool> {
+        loop {
+            if let Some(batches) = self.batches.as_mut() {
+                match batches.next() {
+                    Some(batch) => {
+                        self.batch = Some(batch?);
+                        self.batch_row = 0;
+                        return Ok(true);
+                    }
+                    None => self.batches = None,
+                }
+            }
 
-pub struct IcebergScan;
+            let index = match self.pscan {
+                Some(pscan) => unsafe {
+                    claim_task(&ParallelTableScanDescHandle::from_raw(pscan))
+                },
+                None => self.next_task,
+            };
+            let Some(task) = self.tasks.get(index) else {
+                return Ok(false);
+            };
+            self.next_task = index + 1;
+
+            self.file_ordinal = None;
+            if has_positions(task) && self.pscan.is_none() {
+                self.file_ordinal =
+                    data_file_ordinal(self.relid, &task.data_file_path);
+            }
+            let task = match self.file_ordinal {
+                Some(_) => with_positions(task),
+                None => task.clone(),
+            };
+            self.batches = Some(self.reader.clone().read(vec![task])?);
+        }
+    }
+
+    /// Whether the current row of `batch` was returned to ANALYZE for an
+    /// earlier block. Rows of files read without positions are returned again.
+    fn sampled_before(&mut self, batch: &RecordBatch) -> bool {
+        let position = self
+            .file_ordinal
+            .and_then(|_| row_position(batch, self.batch_row));
+        match (position, self.sampled_file.as_mut()) {
+            (Some(position), Some((_, next))) if position >= *next => {
+                *next = position + 1;
+                false
+            }
+            (Some(_), Some(_)) => true,
+            _ => false,
+        }
+    }
+
+    fn fill_row(&self, batch: &RecordBatch, row: &mut Row) -> IcebergResult<()> {
+        fill_cells(&self.columns, batch, self.batch_row, row)?;
+        row.tid = self.file_ordinal.and_then(|ordinal| {
+            let position = row_position(batch, self.batch_row)?;
+            encode_tid(ordinal, position)
+        });
+
+        Ok(())
+    }
+}
+
+/// Start a scan of `snapshot` of `table`, of the current snapshot when `None`.
+fn table_scan<'a>(
+    table: &'a Table,
+    snapshot: Option<&SnapshotRef>,
+) -> TableScanBuilder<'a> {
+    match snapshot {
+        Some(snapshot) => table.scan().snapshot_id(snapshot.snapshot_id()),
+        None => table.scan(),
+    }
+}
+
+/// The id of `snapshot` of `table`, of the current snapshot when `None`.
+fn read_snapshot_id(table: &Table, snapshot: Option<&SnapshotRef>) -> Option<i64> {
+    match snapshot {
+        Some(snapshot) => Some(snapshot.snapshot_id()),
+        None => table.metadata().current_snapshot_id(),
+    }
+}
+
+/// Whether the reader can provide the row positions of `task`, which it
+/// cannot once rows are filtered by equality deletes.
+fn has_positions(task: &FileScanTask) -> bool {
+    task.deletes
+        .iter()
+        .all(|delete| delete.file_type == DataContentType::PositionDeletes)
+}
+
+/// The reader of scans, which only prunes row groups by the task predicate.
+fn scan_reader(table: &Table) -> ArrowReader {
+    ArrowReaderBuilder::new(table.file_io().clone())
+        .with_row_filtering_enabled(false)
+        .build()
+}
+
+/// The predicate of the scan keys `keys` and the plan keys `plan_keys`.
+fn scan_filter(
+    rel: &RelationHandle,
+    schema: &Schema,
+    field_ids: &FieldIds,
+    keys: Option<&ScanKeyHandle>,
+    plan_keys: &[pg_sys::ScanKeyData],
+) -> Option<Predicate> {
+    let mut all_keys = keys.map(|keys| keys.keys().to_vec()).unwrap_or_default();
+    all_keys.extend_from_slice(plan_keys);
+    scan_keys_to_predicate(rel, schema, field_ids, &all_keys)
+}
+
+/// `task` reading the row positions as the last column of its record batches.
+fn with_positions(task: &FileScanTask) -> FileScanTask {
+    let mut task = task.clone();
+    task.project_field_ids.push(RESERVED_FIELD_ID_POS);
+    task
+}
+
+/// The position of the row `batch_row` of a batch read `with_positions`.
+fn row_position(batch: &RecordBatch, batch_row: usize) -> Option<u64> {
+    let positions = batch
+        .columns()
+        .last()?
+        .as_any()
+        .downcast_ref::<Int64Array>()?;
+    if positions.is_null(batch_row) {
+        return None;
+    }
+
+    Some(positions.value(batch_row) as u64)
+}
+
+/// The task of `tasks` whose data file holds the pseudo-page `block` and the
+/// block's offset in the file, `None` for blocks past the data files.
+fn block_chunk(
+    tasks: &[FileScanTask],
+    block: pg_sys::BlockNumber,
+) -> Option<(usize, u64)> {
+    let mut offset = block as u64 * pg_sys::BLCKSZ as u64;
+    for (index, task) in tasks.iter().enumerate() {
+        if offset < task.length {
+            return Some((index, offset));
+        }
+        offset -= task.length;
+    }
+    None
+}
+
+/// Number the data files of `tasks` of the snapshot `snapshot_id` whose rows
+/// get a TID, in the order a scan reads them.
+fn register_data_files(
+    relid: pg_sys::Oid,
+    snapshot_id: Option<i64>,
+    tasks: &[FileScanTask],
+) {
+    register_snapshot_files(
+        relid,
+        snapshot_id,
+        tasks
+            .iter()
+            .filter(|task| has_positions(task))
+            .map(|task| task.data_file_path.as_str()),
+    );
+}
+
+/// Fill the cells of `row` from the row `batch_row` of `batch`.
+fn fill_cells(
+    columns: &[ScanColumn],
+    batch: &RecordBatch,
+    batch_row: usize,
+    row: &mut Row,
+) -> IcebergResult<()> {
+    row.cells.resize_with(columns.len(), || None);
+
+    for (cell, column) in row.cells.iter_mut().zip(columns) {
+        *cell = match column.batch_index {
+            Some(index) => arrow_value_to_cell(
+                batch.column(index).as_ref(),
+                batch_row,
+                column.type_oid,
+                &column.name,
+            )?,
+            None => None,
+        };
+    }
+
+    Ok(())
+}
+
+/// The table read by `fetch_row` and the data files of the snapshot read.
+struct FetchTable {
+    /// Whether the table has changes staged by the current transaction.
+    staged: bool,
+    table: Table,
+    snapshot: Option<SnapshotRef>,
+    snapshot_id: Option<i64>,
+    /// The tasks of the data files whose rows have a TID, in plan order,
+    /// planned on first use.
+    tasks: Option<Vec<FileScanTask>>,
+}
+
+impl FetchTable {
+    /// The table of `rel` as seen by the current transaction. The table and
+    /// the tasks of `cached` are reused while they are still current.
+    fn load(rel: &RelationHandle, cached: Option<Self>) -> IcebergResult<Self> {
+        let relid = rel.oid();
+        let (staged, table) = match staged_table(relid) {
+            Some(table) => (true, table),
+            None => {
+                // Metadata files are never modified once written
+                let location = IcebergMetadata::get(relid)?.metadata_location;
+                let table = cached
+                    .as_ref()
+                    .filter(|cached| {
+                        !cached.staged
+                            && location.is_some()
+                            && cached.table.metadata_location() == location.as_deref()
+                    })
+                    .map(|cached| cached.table.clone());
+                match table {
+                    Some(table) => (false, table),
+                    None => (false, load_iceberg_table(rel)?),
+                }
+            }
+        };
+
+        // Snapshot ids are unique, so the data files of a snapshot planned
+        // before, possibly with staged changes, are still the same
+        let snapshot = scan_snapshot(&table)?;
+        let snapshot_id = read_snapshot_id(&table, snapshot.as_ref());
+        let tasks = cached
+            .filter(|cached| {
+                snapshot_id.is_some() && cached.snapshot_id == snapshot_id
+            })
+            .and_then(|cached| cached.tasks);
+
+        Ok(FetchTable {
+            staged,
+            table,
+            snapshot,
+            snapshot_id,
+            tasks,
+        })
+    }
+
+    /// The tasks of the data files whose rows have a TID.
+    fn tasks(&mut self) -> IcebergResult<&[FileScanTask]> {
+        if self.tasks.is_none() {
+            let tasks = table_scan(&self.table, self.snapshot.as_ref())
+                .select_all()
+                .build()?
+                .plan_files()?;
+            self.tasks = Some(tasks.into_iter().filter(has_positions).collect());
+        }
+
+        Ok(self.tasks.as_deref().unwrap_or_default())
+    }
+}
+
+/// A data file opened by `fetch_row`, positioned after the last fetched row.
+struct FetchCursor {
+    snapshot_id: Option<i64>,
+    file_path: String,
+    columns: Vec<ScanColumn>,
+    batches: ArrowRecordBatchIterator,
+    batch: Option<RecordBatch>,
+    /// Row of `batch` to continue the search from.
+    batch_row: usize,
+    /// Position of the last fetched row, later fetches of lower positions
+    /// need to reopen the file.
+    position: u64,
+}
+
+impl FetchCursor {
+    /// Open the data file at `file_path` of the snapshot read of `fetch`,
+    /// `None` if it is not part of the snapshot or its rows have no TID.
+    fn open(
+        rel: &RelationHandle,
+        fetch: &mut FetchTable,
+        file_path: &str,
+    ) -> IcebergResult<Option<Self>> {
+        let Some(task) = fetch
+            .tasks()?
+            .iter()
+            .find(|task| task.data_file_path == file_path)
+            .cloned()
+        else {
+            return Ok(None);
+        };
+
+        let table = &fetch.table;
+        let field_ids = FieldIds::from_table(table)?;
+        let schema = scan_schema(table, fetch.snapshot.as_ref())?;
+        let reader = scan_reader(table);
+        Ok(Some(FetchCursor {
+            snapshot_id: fetch.snapshot_id,
+            file_path: file_path.to_string(),
+            columns: IcebergScan::scan_columns(rel, &schema, &field_ids, None).0,
+            batches: reader.read(vec![with_positions(&task)])?,
+            batch: None,
+            batch_row: 0,
+            position: 0,
+        }))
+    }
+
+    /// Move to the row at `position`, returning `false` when the file has no
+    /// such row or it is deleted.
+    fn seek(&mut self, position: u64) -> IcebergResult<bool> {
+        self.position = position;
+
+        loop {
+            if let Some(batch) = self.batch.as_ref() {
+                while self.batch_row < batch.num_rows() {
+                    match row_position(batch, self.batch_row) {
+                        Some(row_position) if row_position == position => {
+                            return Ok(true);
+                        }
+                        Some(row_position) if row_position > position => {
+                            return Ok(false);
+                        }
+                        _ => self.batch_row += 1,
+                    }
+                }
+            }
+
+            match self.batches.next() {
+                Some(batch) => {
+                    self.batch = Some(batch?);
+                    self.batch_row = 0;
+                }
+                None => {
+                    self.batch = None;
+                    return Ok(false);
+                }
+            }
+        }
+    }
+}
+
+thread_local! {
+    /// The table last read by `fetch_row` per relation.
+    static FETCH_TABLES: RefCell<HashMap<pg_sys::Oid, FetchTable>> =
+        RefCell::new(HashMap::new());
+    /// The data file last opened by `fetch_row` per relation.
+    static FETCH_CURSORS: RefCell<HashMap<pg_sys::Oid, FetchCursor>> =
+        RefCell::new(HashMap::new());
+}
+
+/// Fetch the row identified by `tid` from the table as seen by the current
+/// transaction. Returns `false` when `tid` does not identify a live row.
+pub fn fetch_row(
+    rel: &RelationHandle,
+    tid: &ItemPointer,
+    row: &mut Row,
+) -> IcebergResult<bool> {
+    let relid = rel.oid();
+    let cached = FETCH_TABLES.with(|tables| tables.borrow_mut().remove(&relid));
+    let mut fetch = FetchTable::load(rel, cached)?;
+
+    let found = fetch_table_row(rel, &mut fetch, tid, row);
+    FETCH_TABLES.with(|tables| tables.borrow_mut().insert(relid, fetch));
+    found
+}
+
+/// Fetch the row identified by `tid` from the snapshot read of `fetch`.
+fn fetch_table_row(
+    rel: &RelationHandle,
+    fetch: &mut FetchTable,
+    tid: &ItemPointer,
+    row: &mut Row,
+) -> IcebergResult<bool> {
+    let relid = rel.oid();
+
+    // TIDs of an earlier transaction are resolved by numbering the data files
+    // the same way again, which only holds for the snapshot they came from
+    let (file_path, position) = match decode_tid(relid, tid) {
+        Ok(resolved) => resolved,
+        Err(_) if resolves_earlier_tids(relid, fetch.snapshot_id) => {
+            register_data_files(relid, fetch.snapshot_id, fetch.tasks()?);
+            match decode_tid(relid, tid) {
+                Ok(resolved) => resolved,
+                Err(_) => return Ok(false),
+            }
+        }
+        Err(_) => return Ok(false),
+    };
+
+    // The cursor is taken out while reading, so that an error leaves no
+    // half-read cursor behind
+    let cursor = FETCH_CURSORS
+        .with(|cursors| cursors.borrow_mut().remove(&relid))
+        .filter(|cursor| {
+            cursor.snapshot_id == fetch.snapshot_id
+                && cursor.file_path == file_path
+                && cursor.position <= position
+        });
+    let mut cursor = match cursor {
+        Some(cursor) => cursor,
+        None => match FetchCursor::open(rel, fetch, &file_path)? {
+            Some(cursor) => cursor,
+            None => return Ok(false),
+        },
+    };
+
+    let found = cursor.seek(position)?;
+    if found {
+        let batch = cursor.batch.as_ref().expect("cursor is on a row");
+        fill_cells(&cursor.columns, batch, cursor.batch_row, row)?;
+        row.tid = Some(*tid);
+    }
+
+    FETCH_CURSORS.with(|cursors| cursors.borrow_mut().insert(relid, cursor));
+    Ok(found)
+}
+
+/// Close the data files opened by `fetch_row` and drop the tables it read.
+pub fn reset_fetch_cursors() {
+    FETCH_CURSORS.with(|cursors| cursors.borrow_mut().clear());
+    FETCH_TABLES.with(|tables| tables.borrow_mut().clear());
+}
 
 impl AmScan<IcebergError> for IcebergScan {
     fn new(
-        _rel: &RelationHandle,
-        _snapshot: &SnapshotHandle,
-        _key: Option<&ScanKeyHandle>,
-        _pscan: Option<&ParallelTableScanDescHandle>,
-        _flags: u32,
+        rel: &RelationHandle,
+        snapshot: &SnapshotHandle,
+        key: Option<&ScanKeyHandle>,
+        pscan: Option<&ParallelTableScanDescHandle>,
+        flags: u32,
     ) -> IcebergResult<Self> {
-        // Initialize scan state
-        Ok(IcebergScan)
+        let table = match pscan {
+            Some(pscan) => load_shared_table(rel, pscan)?,
+            None => load_iceberg_table(rel)?,
+        };
+        // Scan keys are checked against the returned rows
+        let projection =
+            scan_projection(snapshot, pscan, rel.oid()).map(|mut projection| {
+                let keys = key.map(|key| key.keys()).unwrap_or_default();
+                projection.extend(keys.iter().map(|key| key.sk_attno));
+                projection
+            });
+        let plan_keys = match pscan {
+            Some(_) => Vec::new(),
+            None => scan_plan_keys(snapshot, None, rel.oid()),
+        };
+        // The statistics of the table are those of its current snapshot
+        let sampling = flags & pg_sys::ScanOptions::SO_TYPE_ANALYZE != 0;
+        let snapshot = if sampling {
+            None
+        } else {
+            scan_snapshot(&table)?
+        };
+        let schema = scan_schema(&table, snapshot.as_ref())?;
+        let field_ids = FieldIds::from_table(&table)?;
+        let (columns, selection) =
+            Self::scan_columns(rel, &schema, &field_ids, projection.as_ref());
+        let filter = scan_filter(rel, &schema, &field_ids, key, &plan_keys);
+        let reader = scan_reader(&table);
+
+        let size = TableSize::of(&table);
+        let block_rows = match size.pages() {
+            0 => 0.0,
+            pages => size.tuples as f64 / pages as f64,
+        };
+
+        Ok(IcebergScan {
+            rel: rel.as_raw(),
+            relid: rel.oid(),
+            table,
+            snapshot,
+            columns,
+            selection,
+            plan_keys,
+            filter,
+            reader,
+            tasks: Vec::new(),
+            next_task: 0,
+            pscan: pscan.map(|pscan| pscan.as_raw()),
+            sampling,
+            block_rows,
+            sampled_blocks: 0,
+            sampled_file: None,
+            batches: None,
+            batch: None,
+            batch_row: 0,
+            file_ordinal: None,
+        })
     }
 
     fn scan_begin(&mut self) -> IcebergResult<()> {
-        Err(IcebergError::NotImplemented("scan_begin"))
+        self.tasks = self.plan_files()?;
+        if self.pscan.is_none() {
+            let snapshot_id = read_snapshot_id(&self.table, self.snapshot.as_ref());
+            register_data_files(self.relid, snapshot_id, &self.tasks);
+        }
+        Ok(())
     }
 
     fn scan_getnextslot(
         &mut self,
         _direction: ScanDirection,
-        _row: &mut Row,
+        row: &mut Row,
     ) -> IcebergResult<bool> {
-        Err(IcebergError::NotImplemented("scan_getnextslot"))
+        loop {
+            if let Some(batch) = self.batch.take() {
+                if self.batch_row < batch.num_rows() {
+                    self.fill_row(&batch, row)?;
+                    self.batch_row += 1;
+                    self.batch = Some(batch);
+                    return Ok(true);
+                }
+            }
+
+            if !self.next_batch()? {
+                return Ok(false);
+            }
+        }
     }
 
     fn scan_rescan(
         &mut self,
-        _key: Option<&ScanKeyHandle>,
+        key: Option<&ScanKeyHandle>,
         _set_params: bool,
         _allow_strat: bool,
         _allow_sync: bool,
         _allow_pagemode: bool,
     ) -> IcebergResult<()> {
-        Err(IcebergError::NotImplemented("scan_rescan"))
+        // New keys change the data files to read
+        if let Some(key) = key {
+            let rel = unsafe { RelationHandle::from_raw(self.rel) };
+            self.filter = scan_filter(
+                &rel,
+                &scan_schema(&self.table, self.snapshot.as_ref())?,
+                &FieldIds::from_table(&self.table)?,
+                Some(key),
+                &self.plan_keys,
+            );
+            self.tasks = self.plan_files()?;
+            if self.pscan.is_none() {
+                let snapshot_id =
+                    read_snapshot_id(&self.table, self.snapshot.as_ref());
+                register_data_files(self.relid, snapshot_id, &self.tasks);
+            }
+        }
+
+        self.next_task = 0;
+        self.batches = None;
+        self.batch = None;
+        self.batch_row = 0;
+        Ok(())
     }
 
     fn scan_end(&mut self) -> IcebergResult<()> {
-        Err(IcebergError::NotImplemented("scan_end"))
+        self.batches = None;
+        self.batch = None;
+        Ok(())
     }
 
     fn scan_bitmap_next_block(&mut self, _tbmres: &TBMIterateResultHandle) -> IcebergResult<bool> {
@@ -53,4 +759,111 @@ impl AmScan<IcebergError> for IcebergScan {
     ) -> IcebergResult<bool> {
         Err(IcebergError::NotImplemented("scan_bitmap_next_tuple"))
     }
+
+    fn scan_analyze_next_block(
+        &mut self,
+        stream: &ReadStreamHandle,
+    ) -> IcebergResult<bool> {
+        // The blocks of scans not sampling do not exist
+        if !self.sampling {
+            return Ok(false);
+        }
+        let Some(block) = stream.next_block() else {
+            return Ok(false);
+        };
+        self.sampled_blocks += 1;
+        self.batches = None;
+        self.batch = None;
+
+        // Blocks past the data files are the delete files' share, they hold no
+        // rows
+        let Some((index, offset)) = block_chunk(&self.tasks, block) else {
+            return Ok(true);
+        };
+        if self.sampled_file.map(|(sampled, _)| sampled) != Some(index) {
+            self.sampled_file = Some((index, 0));
+        }
+
+        // The reader reads the row groups overlapping the chunk
+        let task = &self.tasks[index];
+        self.file_ordinal = None;
+        if has_positions(task) {
+            self.file_ordinal = data_file_ordinal(self.relid, &task.data_file_path);
+        }
+        let mut chunk = match self.file_ordinal {
+            Some(_) => with_positions(task),
+            None => task.clone(),
+        };
+        chunk.start = offset;
+        chunk.length = (pg_sys::BLCKSZ as u64).min(task.length - offset);
+        self.batches = Some(self.reader.clone().read(vec![chunk])?);
+        Ok(true)
+    }
+
+    fn scan_analyze_next_tuple(
+        &mut self,
+        _oldest_xmin: pg_sys::TransactionId,
+        row: &mut Row,
+    ) -> IcebergResult<(bool, f64, f64)> {
+        // ANALYZE extrapolates the live rows reported to the table by the
+        // blocks sampled
+        let live_rows = self.sampled_blocks as f64 * self.block_rows;
+        loop {
+            if let Some(batch) = self.batch.take() {
+                if self.batch_row < batch.num_rows() {
+                    let returned = self.sampled_before(&batch);
+                    if !returned {
+                        self.fill_row(&batch, row)?;
+                    }
+                    self.batch_row += 1;
+                    self.batch = Some(batch);
+                    if !returned {
+                        return Ok((true, live_rows, 0.0));
+                    }
+                    continue;
+                }
+            }
+
+            match self.batches.as_mut().and_then(|batches| batches.next()) {
+                Some(batch) => {
+                    self.batch = Some(batch?);
+                    self.batch_row = 0;
+                }
+                None => {
+                    self.batches = None;
+                    return Ok((false, live_rows, 0.0));
+                }
+            }
+        }
+    }
+
+    fn parallelscan_estimate(rel: &RelationHandle) -> IcebergResult<pg_sys::Size> {
+        parallel::parallelscan_estimate(rel)
+    }
+
+    fn parallelscan_initialize(
+        rel: &RelationHandle,
+        pscan: &ParallelTableScanDescHandle,
+    ) -> IcebergResult<pg_sys::Size> {
+        parallel::parallelscan_initialize(rel, pscan)
+    }
+
+    fn parallelscan_reinitialize(
+        _rel: &RelationHandle,
+        pscan: &ParallelTableScanDescHandle,
+    ) -> IcebergResult<()> {
+        parallel::parallelscan_reinitialize(pscan);
+        Ok(())
+    }
+
+    fn tuple_tid_valid(&mut self, tid: &ItemPointer) -> IcebergResult<bool> {
+        Ok(decode_tid(self.relid, tid).is_ok())
+    }
+
+    fn tuple_get_latest_tid(&mut self, _tid: &ItemPointer) -> IcebergResult<()> {
+        // Rows are never updated in place. An updated row is written to a new
+        // data file and its TID cannot be derived from the old one, so every
+        // TID is the latest version of its row.
+        Ok(())
+    }
 }
@@ -0,0 +1,243 @@
+//! Tuple identifiers of Iceberg rows.
+//!
+//! An Iceberg row is identified by its data file and its position in that
+//! file, a PostgreSQL row by a 6-byte `ItemPointer`. Each backend numbers the
+//! data files read by its scans per relation, and a row's TID encodes the
+//! ordinal of its data file together with its position:
+//!
+//! - `ordinal << 28 | position` forms a 47-bit row number, whose upper 32 bits
+//!   are the block number and lower 15 bits the offset. Offsets are stored
+//!   plus one, as offset 0 marks an invalid item pointer.
+//! - Rows past the first 2^28 of a data file and data files beyond the first
+//!   2^19 - 1 of a relation get no TID, which keeps block numbers below
+//!   `InvalidBlockNumber`.
+//!
+//! Scans number the data files in the order they read them. The numbering is
+//! kept until the end of the transaction, so TIDs returned by a scan can be
+//! resolved by later statements of the same transaction. Later transactions
+//! of the backend resolve the TIDs of a relation by numbering its data files
+//! the same way again, which only holds while the table is read at the
+//! snapshot the TIDs were produced from. Otherwise, and for TIDs handed out by
+//! other backends, they identify no row. TIDs are not suitable for indexes.
+
+use super::parallel::reset_shared_tables;
+use super::scan::reset_fetch_cursors;
+use crate::error::{IcebergError, IcebergResult};
+use pg_tam::prelude::{
+    ItemPointer, XactEvent, XactHook, XactHookError, register_xact_hook,
+};
+use pgrx::pg_sys;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+const POSITION_BITS: u32 = 28;
+const OFFSET_BITS: u32 = 15;
+const MAX_POSITION: u64 = (1 << POSITION_BITS) - 1;
+const MAX_ORDINAL: u32 = (1 << 19) - 2;
+
+/// The data files of a relation by ordinal.
+#[derive(Default)]
+struct DataFiles {
+    /// The snapshot whose data files are numbered in plan order, `None` once
+    /// the files of several snapshots are.
+    snapshot_id: Option<i64>,
+    paths: Vec<String>,
+    ordinals: HashMap<String, u32>,
+}
+
+thread_local! {
+    static DATA_FILES: RefCell<HashMap<pg_sys::Oid, DataFiles>> =
+        RefCell::new(HashMap::new());
+    /// The snapshot whose data files earlier transactions numbered, per
+    /// relation.
+    static NUMBERED_SNAPSHOTS: RefCell<HashMap<pg_sys::Oid, i64>> =
+        RefCell::new(HashMap::new());
+}
+
+/// The ordinal of the data file at `path` of `relid`, numbering it if it is
+/// not known yet. Returns `None` once the relation has too many data files.
+pub fn data_file_ordinal(relid: pg_sys::Oid, path: &str) -> Option<u32> {
+    DATA_FILES.with(|data_files| {
+        let mut data_files = data_files.borrow_mut();
+        let files = data_files.entry(relid).or_default();
+        if let Some(&ordinal) = files.ordinals.get(path) {
+            return Some(ordinal);
+        }
+
+        let ordinal = files.paths.len() as u32;
+        if ordinal > MAX_ORDINAL {
+            return None;
+        }
+        files.paths.push(path.to_string());
+        files.ordinals.insert(path.to_string(), ordinal);
+        Some(ordinal)
+    })
+}
+
+/// Number the data files at `paths` of the snapshot `snapshot_id` of `relid`,
+/// in plan order.
+pub fn register_snapshot_files<'a>(
+    relid: pg_sys::Oid,
+    snapshot_id: Option<i64>,
+    paths: impl IntoIterator<Item = &'a str>,
+) {
+    DATA_FILES.with(|data_files| {
+        let mut data_files = data_files.borrow_mut();
+        let files = data_files.entry(relid).or_default();
+        if files.paths.is_empty() {
+            files.snapshot_id = snapshot_id;
+        } else if files.snapshot_id != snapshot_id {
+            files.snapshot_id = None;
+        }
+    });
+
+    for path in paths {
+        data_file_ordinal(relid, path);
+    }
+}
+
+/// Whether TIDs of `relid` handed out by an earlier transaction can be
+/// resolved by numbering the data files of the snapshot `snapshot_id` again.
+/// They can if that transaction numbered the files of this snapshot only, and
+/// the current one numbered none yet.
+pub fn resolves_earlier_tids(relid: pg_sys::Oid, snapshot_id: Option<i64>) -> bool {
+    let numbered = DATA_FILES.with(|data_files| {
+        data_files
+            .borrow()
+            .get(&relid)
+            .is_some_and(|files| !files.paths.is_empty())
+    });
+
+    let numbered_snapshot =
+        NUMBERED_SNAPSHOTS.with(|snapshots| snapshots.borrow().get(&relid).copied());
+
+    !numbered && snapshot_id.is_some() && numbered_snapshot == snapshot_id
+}
+
+/// Drop the numbering of the transaction, remembering the snapshot whose data
+/// files it numbered per relation.
+fn reset_data_files() {
+    DATA_FILES.with(|data_files| {
+        NUMBERED_SNAPSHOTS.with(|snapshots| {
+            let mut snapshots = snapshots.borrow_mut();
+            for (relid, files) in data_files.borrow_mut().drain() {
+                match files.snapshot_id {
+                    Some(snapshot_id) => snapshots.insert(relid, snapshot_id),
+                    None => snapshots.remove(&relid),
+                };
+            }
+        })
+    });
+}
+
+/// The TID of the row at `position` in the data file numbered `ordinal`.
+pub fn encode_tid(ordinal: u32, position: u64) -> Option<ItemPointer> {
+    if ordinal > MAX_ORDINAL || position > MAX_POSITION {
+        return None;
+    }
+
+    let row_number = (ordinal as u64) << POSITION_BITS | position;
+    Some(ItemPointer {
+        block_number: (row_number >> OFFSET_BITS) as u32,
+        offset: (row_number & ((1 << OFFSET_BITS) - 1)) as u16 + 1,
+    })
+}
+
+/// The data file path and row position identified by `tid` in `relid`.
+pub fn decode_tid(
+    relid: pg_sys::Oid,
+    tid: &ItemPointer,
+) -> IcebergResult<(String, u64)> {
+    let invalid =
+        || IcebergError::InvalidTid(format!("({},{})", tid.block_number, tid.offset));
+    if !tid.is_valid() {
+        return Err(invalid());
+    }
+
+    let row_number =
+        (tid.block_number as u64) << OFFSET_BITS | (tid.offset - 1) as u64;
+    let ordinal = (row_number >> POSITION_BITS) as usize;
+    let position = row_number & MAX_POSITION;
+
+    DATA_FILES.with(|data_files| {
+        data_files
+            .borrow()
+            .get(&relid)
+            .and_then(|files| files.paths.get(ordinal))
+            .map(|path| (path.clone(), position))
+            .ok_or_else(invalid)
+    })
+}
+
+struct TidHook;
+
+impl XactHook for TidHook {
+    fn on_event(&self, event: XactEvent) -> Result<(), XactHookError> {
+        if matches!(event, XactEvent::Commit | XactEvent::Abort) {
+            reset_data_files();
+            reset_fetch_cursors();
+            reset_shared_tables();
+        }
+        Ok(())
+    }
+}
+
+pub fn init_hook() {
+    register_xact_hook(Box::new(TidHook));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tid_round_trip() {
+        let relid = pg_sys::Oid::from(16384);
+        let first = data_file_ordinal(relid, "s3://bucket/data/a.parquet").unwrap();
+        let second = data_file_ordinal(relid, "s3://bucket/data/b.parquet").unwrap();
+        assert_eq!((first, second), (0, 1));
+        assert_eq!(
+            data_file_ordinal(relid, "s3://bucket/data/a.parquet"),
+            Some(first)
+        );
+
+        for position in [0, 1, 32767, 32768, MAX_POSITION] {
+            let tid = encode_tid(second, position).unwrap();
+            assert!(tid.is_valid());
+            assert_eq!(
+                decode_tid(relid, &tid).unwrap(),
+                ("s3://bucket/data/b.parquet".to_string(), position)
+            );
+        }
+
+        assert!(encode_tid(first, MAX_POSITION + 1).is_none());
+        assert!(decode_tid(relid, &encode_tid(2, 0).unwrap()).is_err());
+        assert!(decode_tid(relid, &ItemPointer::INVALID).is_err());
+    }
+
+    #[test]
+    fn test_earlier_tids() {
+        let relid = pg_sys::Oid::from(16385);
+        register_snapshot_files(relid, Some(1), ["a.parquet", "b.parquet"]);
+        assert!(!resolves_earlier_tids(relid, Some(1)));
+
+        // The next transaction numbers the files of the same snapshot again
+        reset_data_files();
+        assert!(resolves_earlier_tids(relid, Some(1)));
+        assert!(!resolves_earlier_tids(relid, Some(2)));
+
+        // The files of several snapshots are not numbered in plan order
+        register_snapshot_files(relid, Some(1), ["a.parquet", "b.parquet"]);
+        register_snapshot_files(relid, Some(2), ["c.parquet", "a.parquet"]);
+        reset_data_files();
+        assert!(!resolves_earlier_tids(relid, Some(1)));
+        assert!(!resolves_earlier_tids(relid, Some(2)));
+    }
+
+    #[test]
+    fn test_tid_block_number_limit() {
+        let tid = encode_tid(MAX_ORDINAL, MAX_POSITION).unwrap();
+        assert!(tid.block_number < u32::MAX);
+        assert!(encode_tid(MAX_ORDINAL + 1, 0).is_none());
+    }
+}
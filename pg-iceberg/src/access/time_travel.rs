@@ -0,0 +1,189 @@
+//! Time travel: reading Iceberg tables as of a past snapshot.
+//!
+//! Scans read the current snapshot of a table unless one of these settings
+//! selects another one:
+//!
+//! - `iceberg.snapshot_id` reads the snapshot with the given id.
+//! - `iceberg.as_of_timestamp` reads the snapshot that was current at the
+//!   given `timestamptz`, as recorded in the table's snapshot log.
+//! - `iceberg.ref` reads the snapshot a branch or tag refers to.
+//!
+//! At most one of them can be set. They apply to every Iceberg table a query
+//! reads, so they are best set with `SET LOCAL` for a single transaction.
+//!
+//! A past snapshot is read with its own schema: attributes added since read as
+//! NULL, renamed attributes read the field they were mapped to. Tables cannot
+//! be modified while a setting is in effect, as the rows to delete would be
+//! looked up in the past snapshot. ANALYZE ignores them, the statistics of a
+//! table are those of its current snapshot.
+
+use super::convert::UNIX_TO_PG_EPOCH_MICROS;
+use crate::error::{IcebergError, IcebergResult};
+use iceberg_lite::spec::{SchemaRef, SnapshotRef};
+use iceberg_lite::table::Table;
+use pgrx::IntoDatum;
+use pgrx::datum::TimestampWithTimeZone;
+use pgrx::guc::{GucContext, GucFlags, GucRegistry, GucSetting};
+use std::ffi::{CStr, CString};
+use std::str::FromStr;
+
+const SNAPSHOT_ID_GUC: &CStr = c"iceberg.snapshot_id";
+const AS_OF_TIMESTAMP_GUC: &CStr = c"iceberg.as_of_timestamp";
+const REF_GUC: &CStr = c"iceberg.ref";
+
+static SNAPSHOT_ID: GucSetting<Option<CString>> =
+    GucSetting::<Option<CString>>::new(None);
+static AS_OF_TIMESTAMP: GucSetting<Option<CString>> =
+    GucSetting::<Option<CString>>::new(None);
+static REF: GucSetting<Option<CString>> = GucSetting::<Option<CString>>::new(None);
+
+/// The snapshot selected by the time travel settings.
+enum TimeTravel {
+    SnapshotId(i64),
+    /// Milliseconds since the Unix epoch.
+    AsOfTimestamp(i64, String),
+    Ref(String),
+}
+
+/// Register the time travel settings.
+pub fn init_gucs() {
+    GucRegistry::define_string_guc(
+        SNAPSHOT_ID_GUC,
+        c"Reads Iceberg tables as of the snapshot with this id.",
+        c"Scans read the given snapshot instead of the current one.",
+        &SNAPSHOT_ID,
+        GucContext::Userset,
+        GucFlags::default(),
+    );
+    GucRegistry::define_string_guc(
+        AS_OF_TIMESTAMP_GUC,
+        c"Reads Iceberg tables as of this point in time.",
+        c"Scans read the snapshot that was current at the given timestamp.",
+        &AS_OF_TIMESTAMP,
+        GucContext::Userset,
+        GucFlags::default(),
+    );
+    GucRegistry::define_string_guc(
+        REF_GUC,
+        c"Reads Iceberg tables as of this branch or tag.",
+        c"Scans read the snapshot the given branch or tag refers to.",
+        &REF,
+        GucContext::Userset,
+        GucFlags::default(),
+    );
+}
+
+/// The value of a setting, `None` when it is unset or empty.
+fn setting_value(setting: &GucSetting<Option<CString>>) -> Option<String> {
+    setting
+        .get()
+        .map(|value| value.to_string_lossy().trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Parse the time travel settings, `None` when none of them is set.
+fn time_travel() -> IcebergResult<Option<TimeTravel>> {
+    let snapshot_id = setting_value(&SNAPSHOT_ID);
+    let as_of_timestamp = setting_value(&AS_OF_TIMESTAMP);
+    let reference = setting_value(&REF);
+
+    let time_travel = match (snapshot_id, as_of_timestamp, reference) {
+        (None, None, None) => return Ok(None),
+        (Some(value), None, None) => {
+            let snapshot_id = value.parse().map_err(|_| {
+                IcebergError::InvalidTimeTravel(
+                    SNAPSHOT_ID_GUC,
+                    format!("\"{value}\" is not a snapshot id"),
+                )
+            })?;
+            TimeTravel::SnapshotId(snapshot_id)
+        }
+        (None, Some(value), None) => {
+            let invalid = || {
+                IcebergError::InvalidTimeTravel(
+                    AS_OF_TIMESTAMP_GUC,
+                    format!("\"{value}\" is not a finite timestamp"),
+                )
+            };
+            let datum = TimestampWithTimeZone::from_str(&value)
+                .map_err(|_| invalid())?
+                .into_datum()
+                .ok_or_else(invalid)?;
+            let micros = (datum.value() as i64)
+                .checked_add(UNIX_TO_PG_EPOCH_MICROS)
+                .ok_or_else(invalid)?;
+            TimeTravel::AsOfTimestamp(micros.div_euclid(1000), value)
+        }
+        (None, None, Some(value)) => TimeTravel::Ref(value),
+        _ => {
+            return Err(IcebergError::InvalidTimeTravel(
+                REF_GUC,
+                format!(
+                    "only one of {}, {} and {} can be set",
+                    SNAPSHOT_ID_GUC.to_string_lossy(),
+                    AS_OF_TIMESTAMP_GUC.to_string_lossy(),
+                    REF_GUC.to_string_lossy()
+                ),
+            ));
+        }
+    };
+    Ok(Some(time_travel))
+}
+
+/// The past snapshot of `table` scans read, `None` when they read the current
+/// snapshot.
+pub fn scan_snapshot(table: &Table) -> IcebergResult<Option<SnapshotRef>> {
+    let metadata = table.metadata();
+    let snapshot = match time_travel()? {
+        None => return Ok(None),
+        Some(TimeTravel::SnapshotId(snapshot_id)) => {
+            metadata.snapshot_by_id(snapshot_id).ok_or_else(|| {
+                IcebergError::InvalidTimeTravel(
+                    SNAPSHOT_ID_GUC,
+                    format!("snapshot {snapshot_id} does not exist"),
+                )
+            })?
+        }
+        Some(TimeTravel::AsOfTimestamp(timestamp_ms, value)) => metadata
+            .snapshot_as_of_timestamp(timestamp_ms)
+            .ok_or_else(|| {
+                IcebergError::InvalidTimeTravel(
+                    AS_OF_TIMESTAMP_GUC,
+                    format!("no snapshot is current as of \"{value}\""),
+                )
+            })?,
+        Some(TimeTravel::Ref(name)) => {
+            metadata.snapshot_for_ref(&name).ok_or_else(|| {
+                IcebergError::InvalidTimeTravel(
+                    REF_GUC,
+                    format!("branch or tag \"{name}\" does not exist"),
+                )
+            })?
+        }
+    };
+    Ok(Some(snapshot.clone()))
+}
+
+/// The schema scans read `snapshot` of `table` with, the current schema for
+/// the current snapshot.
+pub fn scan_schema(
+    table: &Table,
+    snapshot: Option<&SnapshotRef>,
+) -> IcebergResult<SchemaRef> {
+    match snapshot {
+        Some(snapshot) => Ok(snapshot.schema(table.metadata())?),
+        None => Ok(table.metadata().current_schema().clone()),
+    }
+}
+
+/// Check that no time travel setting is in effect before modifying the table
+/// named `relname`.
+pub fn check_modify(relname: &str) -> IcebergResult<()> {
+    if setting_value(&SNAPSHOT_ID).is_some()
+        || setting_value(&AS_OF_TIMESTAMP).is_some()
+        || setting_value(&REF).is_some()
+    {
+        return Err(IcebergError::TimeTravelModify(relname.to_string()));
+    }
+    Ok(())
+}
@@ -0,0 +1,381 @@
+//! Buffered writer turning PostgreSQL rows into Iceberg data files.
+//!
+//! Rows are accumulated into Arrow column builders following the table's
+//! current schema and flushed as record batches into a rolling Parquet data
+//! file writer. Closing the writer returns the `DataFile`s to be committed.
+//!
+//! Tables with a partition spec split each record batch by partition, and
+//! every partition is written into data files of its own. Rows arrive in no
+//! particular order, so a writer is kept open per partition.
+//!
+//! Tables with a sort order have each record batch sorted before it is
+//! written, so the rows of every flushed batch are ordered within their data
+//! file.
+//!
+//! Deleted rows are written by `write_position_deletes` into Parquet position
+//! delete files, in the partition of the data file they delete from.
+
+use super::convert::append_cell;
+use crate::catalog::FieldIds;
+use crate::error::{IcebergError, IcebergResult};
+use crate::hooks::table_options::OPT_COMPRESSION_CODEC;
+use arrow_array::builder::{ArrayBuilder, make_builder};
+use arrow_array::{Int64Array, RecordBatch, StringArray};
+use arrow_schema::SchemaRef as ArrowSchemaRef;
+use iceberg_lite::arrow::{
+    RecordBatchPartitionSplitter, RecordBatchSorter, schema_to_arrow_schema,
+};
+use iceberg_lite::spec::{
+    DataFile, DataFileFormat, ManifestContentType, PartitionKey, SchemaRef,
+    TableProperties,
+};
+use iceberg_lite::table::Table;
+use iceberg_lite::writer::base_writer::data_file_writer::{
+    DataFileWriter, DataFileWriterBuilder,
+};
+use iceberg_lite::writer::base_writer::position_delete_writer::{
+    PositionDeleteFileWriterBuilder, position_delete_arrow_schema,
+    position_delete_schema,
+};
+use iceberg_lite::writer::file_writer::ParquetWriterBuilder;
+use iceberg_lite::writer::file_writer::location_generator::{
+    DefaultFileNameGenerator, DefaultLocationGenerator,
+};
+use iceberg_lite::writer::file_writer::rolling_writer::RollingFileWriterBuilder;
+use iceberg_lite::writer::partitioning::PartitioningWriter;
+use iceberg_lite::writer::partitioning::fanout_writer::FanoutWriter;
+use iceberg_lite::writer::{IcebergWriter, IcebergWriterBuilder};
+use iceberg_lite::{Error, ErrorKind};
+use parquet::basic::{BrotliLevel, Compression, GzipLevel, ZstdLevel};
+use parquet::file::properties::WriterProperties;
+use pg_tam::prelude::Row;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Number of rows buffered before a record batch is handed to the file writer.
+const WRITE_BATCH_SIZE: usize = 8192;
+
+type ParquetFileWriterBuilder = RollingFileWriterBuilder<
+    ParquetWriterBuilder,
+    DefaultLocationGenerator,
+    DefaultFileNameGenerator,
+>;
+
+type ParquetDataFileWriterBuilder = DataFileWriterBuilder<
+    ParquetWriterBuilder,
+    DefaultLocationGenerator,
+    DefaultFileNameGenerator,
+>;
+
+type ParquetDataFileWriter = DataFileWriter<
+    ParquetWriterBuilder,
+    DefaultLocationGenerator,
+    DefaultFileNameGenerator,
+>;
+
+/// The data file writer of a table, by its partitioning.
+enum DataWriter {
+    Unpartitioned(ParquetDataFileWriter),
+    Partitioned {
+        splitter: RecordBatchPartitionSplitter,
+        writer: FanoutWriter<ParquetDataFileWriterBuilder>,
+    },
+}
+
+impl DataWriter {
+    fn write(&mut self, batch: RecordBatch) -> IcebergResult<()> {
+        match self {
+            DataWriter::Unpartitioned(writer) => writer.write(batch)?,
+            DataWriter::Partitioned { splitter, writer } => {
+                for (partition_key, batch) in splitter.split(&batch)? {
+                    writer.write(partition_key, batch)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn close(self) -> IcebergResult<Vec<DataFile>> {
+        let data_files = match self {
+            DataWriter::Unpartitioned(mut writer) => writer.close()?,
+            DataWriter::Partitioned { writer, .. } => writer.close()?,
+        };
+        Ok(data_files)
+    }
+}
+
+pub struct IcebergTableWriter {
+    arrow_schema: ArrowSchemaRef,
+    /// For each schema field, the index of the row cell holding its value.
+    cell_indexes: Vec<usize>,
+    builders: Vec<Box<dyn ArrayBuilder>>,
+    buffered_rows: usize,
+    /// Sorts the record batches of tables with a sort order.
+    sorter: Option<RecordBatchSorter>,
+    writer: DataWriter,
+}
+
+impl IcebergTableWriter {
+    pub fn new(table: &Table) -> IcebergResult<Self> {
+        let metadata = table.metadata();
+        let schema = metadata.current_schema().clone();
+        let arrow_schema = Arc::new(schema_to_arrow_schema(&schema)?);
+
+        let field_ids = FieldIds::from_table(table)?;
+        let cell_indexes = schema
+            .as_struct()
+            .fields()
+            .iter()
+            .map(|field| {
+                field_ids
+                    .attnum(field.id)
+                    .map(|attnum| (attnum - 1) as usize)
+                    .ok_or_else(|| IcebergError::ColumnNotFound(field.name.clone()))
+            })
+            .collect::<IcebergResult<_>>()?;
+        let builders = arrow_schema
+            .fields()
+            .iter()
+            .map(|field| make_builder(field.data_type(), WRITE_BATCH_SIZE))
+            .collect();
+
+        let sort_order = metadata.default_sort_order();
+        let sorter = if sort_order.is_unsorted() {
+            None
+        } else {
+            Some(RecordBatchSorter::try_new(sort_order, &schema)?)
+        };
+
+        let partition_spec = metadata.default_partition_spec().clone();
+        let file_writer_builder = rolling_file_writer_builder(table, schema.clone())?;
+        let writer_builder = DataFileWriterBuilder::new(file_writer_builder);
+        let writer = if partition_spec.is_unpartitioned() {
+            DataWriter::Unpartitioned(writer_builder.build(None)?)
+        } else {
+            DataWriter::Partitioned {
+                splitter: RecordBatchPartitionSplitter::try_new_with_computed_values(
+                    schema,
+                    partition_spec,
+                )?,
+                writer: FanoutWriter::new(writer_builder),
+            }
+        };
+
+        Ok(Self {
+            arrow_schema,
+            cell_indexes,
+            builders,
+            buffered_rows: 0,
+            sorter,
+            writer,
+        })
+    }
+
+    /// Buffer a row, flushing a record batch once enough rows are collected.
+    pub fn write_row(&mut self, row: &Row) -> IcebergResult<()> {
+        for ((builder, field), &index) in self
+            .builders
+            .iter_mut()
+            .zip(self.arrow_schema.fields())
+            .zip(&self.cell_indexes)
+        {
+            let cell = row.get(index).and_then(Option::as_ref);
+            append_cell(builder.as_mut(), field.data_type(), cell, field.name())?;
+        }
+
+        self.buffered_rows += 1;
+        if self.buffered_rows >= WRITE_BATCH_SIZE {
+            self.flush()?;
+        }
+
+        Ok(())
+    }
+
+    fn flush(&mut self) -> IcebergResult<()> {
+        if self.buffered_rows == 0 {
+            return Ok(());
+        }
+
+        let columns = self.builders.iter_mut().map(|b| b.finish()).collect();
+        let mut batch = RecordBatch::try_new(self.arrow_schema.clone(), columns)?;
+        self.buffered_rows = 0;
+        if let Some(sorter) = &self.sorter {
+            batch = sorter.sort(&batch)?;
+        }
+        self.writer.write(batch)?;
+
+        Ok(())
+    }
+
+    /// Flush the buffered rows and close the data files written so far.
+    pub fn close(mut self) -> IcebergResult<Vec<DataFile>> {
+        self.flush()?;
+        self.writer.close()
+    }
+}
+
+/// Write position delete files deleting the rows at the given positions of
+/// each data file.
+///
+/// Deletes only apply to the data files of their partition, so the deletes
+/// of each partition are written into files of their own.
+pub fn write_position_deletes(
+    table: &Table,
+    deletes: &BTreeMap<String, BTreeSet<u64>>,
+) -> IcebergResult<Vec<DataFile>> {
+    let file_writer_builder =
+        rolling_file_writer_builder(table, position_delete_schema())?;
+    let writer_builder = PositionDeleteFileWriterBuilder::new(file_writer_builder);
+    let arrow_schema = position_delete_arrow_schema()?;
+    let partition_keys = data_file_partition_keys(table, deletes)?;
+
+    let partition = |key: Option<&PartitionKey>| {
+        key.map(|key| (key.spec().spec_id(), key.data().clone()))
+    };
+    let mut partitions: Vec<(Option<&PartitionKey>, Vec<&String>)> = vec![];
+    for file_path in deletes.keys() {
+        let partition_key = partition_keys.get(file_path);
+        match partitions
+            .iter_mut()
+            .find(|(key, _)| partition(*key) == partition(partition_key))
+        {
+            Some((_, file_paths)) => file_paths.push(file_path),
+            None => partitions.push((partition_key, vec![file_path])),
+        }
+    }
+
+    let mut delete_files = vec![];
+    for (partition_key, file_paths) in partitions {
+        let mut writer = writer_builder.build(partition_key.cloned())?;
+
+        // File paths and positions are ordered, so rows are sorted by file
+        // path and position as the spec requires.
+        for file_path in file_paths {
+            for chunk in deletes[file_path]
+                .iter()
+                .map(|&pos| pos as i64)
+                .collect::<Vec<_>>()
+                .chunks(WRITE_BATCH_SIZE)
+            {
+                let batch = RecordBatch::try_new(
+                    arrow_schema.clone(),
+                    vec![
+                        Arc::new(StringArray::from(vec![
+                            file_path.as_str();
+                            chunk.len()
+                        ])),
+                        Arc::new(Int64Array::from(chunk.to_vec())),
+                    ],
+                )?;
+                writer.write(batch)?;
+            }
+        }
+        delete_files.extend(writer.close()?);
+    }
+
+    Ok(delete_files)
+}
+
+/// The partition keys of the partitioned data files the deletes apply to,
+/// read from the manifests of the current snapshot.
+fn data_file_partition_keys(
+    table: &Table,
+    deletes: &BTreeMap<String, BTreeSet<u64>>,
+) -> IcebergResult<HashMap<String, PartitionKey>> {
+    let metadata = table.metadata();
+    let mut partition_keys = HashMap::new();
+    let Some(snapshot) = metadata.current_snapshot() else {
+        return Ok(partition_keys);
+    };
+    if metadata
+        .partition_specs_iter()
+        .all(|spec| spec.is_unpartitioned())
+    {
+        return Ok(partition_keys);
+    }
+
+    let schema = metadata.current_schema();
+    let manifest_list = snapshot.load_manifest_list(table.file_io(), metadata)?;
+    for manifest_file in manifest_list.entries() {
+        if manifest_file.content != ManifestContentType::Data {
+            continue;
+        }
+        let Some(spec) =
+            metadata.partition_spec_by_id(manifest_file.partition_spec_id)
+        else {
+            continue;
+        };
+        if spec.is_unpartitioned() {
+            continue;
+        }
+
+        let manifest = manifest_file.load_manifest(table.file_io())?;
+        for entry in manifest.entries() {
+            let data_file = entry.data_file();
+            if entry.is_alive() && deletes.contains_key(data_file.file_path()) {
+                partition_keys.insert(
+                    data_file.file_path().to_string(),
+                    PartitionKey::new(
+                        spec.as_ref().clone(),
+                        schema.clone(),
+                        data_file.partition().clone(),
+                    ),
+                );
+            }
+        }
+    }
+
+    Ok(partition_keys)
+}
+
+/// Rolling Parquet writer of files with `schema`, following the table's file
+/// size and compression properties.
+fn rolling_file_writer_builder(
+    table: &Table,
+    schema: SchemaRef,
+) -> IcebergResult<ParquetFileWriterBuilder> {
+    let metadata = table.metadata();
+    let properties = metadata.properties();
+    let table_properties = TableProperties::try_from(properties).map_err(|e| {
+        Error::new(ErrorKind::DataInvalid, "Invalid table properties").with_source(e)
+    })?;
+    let writer_properties = WriterProperties::builder()
+        .set_compression(parquet_compression(
+            properties.get(OPT_COMPRESSION_CODEC).map(String::as_str),
+        )?)
+        .build();
+
+    Ok(RollingFileWriterBuilder::new(
+        ParquetWriterBuilder::new(writer_properties, schema),
+        table_properties.write_target_file_size_bytes,
+        table.file_io().clone(),
+        DefaultLocationGenerator::new(metadata.clone())?,
+        DefaultFileNameGenerator::new(
+            Uuid::now_v7().to_string(),
+            None,
+            DataFileFormat::Parquet,
+        ),
+    ))
+}
+
+/// Map the `write.parquet.compression-codec` table property to a Parquet codec.
+fn parquet_compression(codec: Option<&str>) -> IcebergResult<Compression> {
+    let compression = match codec.map(str::to_ascii_lowercase).as_deref() {
+        None | Some("zstd") => Compression::ZSTD(ZstdLevel::default()),
+        Some("snappy") => Compression::SNAPPY,
+        Some("gzip") => Compression::GZIP(GzipLevel::default()),
+        Some("lz4") => Compression::LZ4_RAW,
+        Some("brotli") => Compression::BROTLI(BrotliLevel::default()),
+        Some("uncompressed") => Compression::UNCOMPRESSED,
+        Some(other) => {
+            return Err(Error::new(
+                ErrorKind::DataInvalid,
+                format!("Unsupported parquet compression codec: {other}"),
+            )
+            .into());
+        }
+    };
+
+    Ok(compression)
+}
@@ -0,0 +1,140 @@
+//! Mapping of PostgreSQL attribute numbers to Iceberg field ids.
+//!
+//! PostgreSQL never reuses the number of a dropped attribute and Iceberg never
+//! reuses the id of a deleted field, but the two diverge once columns are
+//! dropped and added. The mapping of the live attributes is kept in the
+//! `pg-iceberg.field-ids` table property as comma-separated
+//! `attnum:field-id` pairs, which is written when the table is created and
+//! whenever `ALTER TABLE` evolves the schema, see `hooks::alter_table`.
+//!
+//! Reads and writes resolve columns through the mapping only, so a renamed or
+//! re-added column never reads the values of another column's field. Tables
+//! created without the property have field ids equal to attribute numbers.
+
+use crate::error::{IcebergError, IcebergResult};
+use iceberg_lite::spec::Schema;
+use iceberg_lite::table::Table;
+use pgrx::pg_sys;
+use std::collections::BTreeMap;
+use std::ffi::CStr;
+
+/// Table property holding the attribute number to field id mapping.
+pub const FIELD_IDS_PROPERTY: &str = "pg-iceberg.field-ids";
+
+/// The Iceberg field ids of the attributes of a relation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FieldIds {
+    by_attnum: BTreeMap<pg_sys::AttrNumber, i32>,
+}
+
+impl FieldIds {
+    /// Read the mapping from the properties of `table`.
+    pub fn from_table(table: &Table) -> IcebergResult<Self> {
+        let metadata = table.metadata();
+        match metadata.properties().get(FIELD_IDS_PROPERTY) {
+            Some(value) => Self::parse(value),
+            None => Ok(Self {
+                by_attnum: metadata
+                    .current_schema()
+                    .as_struct()
+                    .fields()
+                    .iter()
+                    .map(|field| (field.id as pg_sys::AttrNumber, field.id))
+                    .collect(),
+            }),
+        }
+    }
+
+    /// Map the live attributes of `tup_desc` to the top-level fields of
+    /// `schema` with the same name.
+    ///
+    /// # Safety
+    /// The caller must ensure that `tup_desc` is a valid pointer to a TupleDesc.
+    pub unsafe fn from_schema(
+        tup_desc: pg_sys::TupleDesc,
+        schema: &Schema,
+    ) -> IcebergResult<Self> {
+        unsafe {
+            let natts = (*tup_desc).natts as usize;
+            let attrs = std::slice::from_raw_parts((*tup_desc).attrs.as_ptr(), natts);
+
+            let mut by_attnum = BTreeMap::new();
+            for attr in attrs.iter().filter(|attr| !attr.attisdropped) {
+                let name =
+                    CStr::from_ptr(attr.attname.data.as_ptr()).to_string_lossy();
+                let field = schema
+                    .as_struct()
+                    .field_by_name(&name)
+                    .ok_or_else(|| IcebergError::ColumnNotFound(name.to_string()))?;
+                by_attnum.insert(attr.attnum, field.id);
+            }
+            Ok(Self { by_attnum })
+        }
+    }
+
+    fn parse(value: &str) -> IcebergResult<Self> {
+        let invalid = || IcebergError::InvalidFieldIds(value.to_string());
+
+        let mut by_attnum = BTreeMap::new();
+        for pair in value.split(',').filter(|pair| !pair.is_empty()) {
+            let (attnum, field_id) = pair.split_once(':').ok_or_else(invalid)?;
+            by_attnum.insert(
+                attnum.parse().map_err(|_| invalid())?,
+                field_id.parse().map_err(|_| invalid())?,
+            );
+        }
+        Ok(Self { by_attnum })
+    }
+
+    /// The value of the `pg-iceberg.field-ids` property.
+    pub fn to_property(&self) -> String {
+        self.by_attnum
+            .iter()
+            .map(|(attnum, field_id)| format!("{attnum}:{field_id}"))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    /// The field id of attribute `attnum`, `None` for dropped attributes.
+    pub fn field_id(&self, attnum: pg_sys::AttrNumber) -> Option<i32> {
+        self.by_attnum.get(&attnum).copied()
+    }
+
+    /// The number of the attribute stored in field `field_id`.
+    pub fn attnum(&self, field_id: i32) -> Option<pg_sys::AttrNumber> {
+        self.by_attnum
+            .iter()
+            .find_map(|(attnum, id)| (*id == field_id).then_some(*attnum))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_ids_property() {
+        let field_ids = FieldIds::parse("1:1,2:2,4:5").unwrap();
+
+        assert_eq!(field_ids.field_id(2), Some(2));
+        assert_eq!(field_ids.field_id(3), None);
+        assert_eq!(field_ids.field_id(4), Some(5));
+        assert_eq!(field_ids.attnum(5), Some(4));
+        assert_eq!(field_ids.attnum(4), None);
+        assert_eq!(field_ids.to_property(), "1:1,2:2,4:5");
+    }
+
+    #[test]
+    fn test_field_ids_property_empty() {
+        let field_ids = FieldIds::parse("").unwrap();
+
+        assert_eq!(field_ids, FieldIds::default());
+        assert_eq!(field_ids.to_property(), "");
+    }
+
+    #[test]
+    fn test_field_ids_property_invalid() {
+        assert!(FieldIds::parse("1:1,2").is_err());
+        assert!(FieldIds::parse("a:1").is_err());
+    }
+}
@@ -4,6 +4,7 @@
 //! Iceberg table metadata in PostgreSQL system catalogs.
 
 use std::collections::HashMap;
+use std::ffi::{CStr, CString};
 use std::fmt::Debug;
 
 use iceberg_lite::catalog::{
@@ -11,9 +12,16 @@ use iceberg_lite::catalog::{
     TableIdent,
 };
 use iceberg_lite::io::FileIO;
-use iceberg_lite::spec::TableMetadataBuilder;
+use iceberg_lite::spec::{TableMetadata, TableMetadataBuilder};
 use iceberg_lite::table::Table;
 use iceberg_lite::{Error, ErrorKind, Result};
+use pg_tam::pg_wrapper::PgWrapper;
+use pgrx::{FromDatum, pg_sys};
+
+use super::iceberg_metadata::{IcebergMetadata, IcebergMetadataError};
+
+/// Name of the catalog backed by the PostgreSQL system catalogs.
+pub const ICEBERG_CATALOG_NAME: &str = "PostgreSQL";
 
 /// PostgreSQL-based Iceberg Catalog implementation.
 ///
@@ -54,6 +62,280 @@ impl IcebergCatalog {
     pub fn file_io(&self) -> FileIO {
         self.file_io.clone()
     }
+
+    /// Resolve the OID of the schema backing a namespace.
+    fn namespace_oid(&self, namespace: &NamespaceIdent) -> Result<pg_sys::Oid> {
+        let nsp_name = to_cstring(validate_namespace(namespace)?)?;
+
+        let nsp_oid =
+            PgWrapper::get_namespace_oid(&nsp_name, true).map_err(catalog_error)?;
+        if nsp_oid == pg_sys::InvalidOid {
+            return Err(Error::new(
+                ErrorKind::NamespaceNotFound,
+                format!("Namespace {namespace} does not exist"),
+            ));
+        }
+
+        Ok(nsp_oid)
+    }
+
+    /// Resolve the OID of the relation backing a table identifier.
+    fn table_relid(&self, table: &TableIdent) -> Result<pg_sys::Oid> {
+        let nsp_oid = self.namespace_oid(table.namespace())?;
+        let rel_name = to_cstring(table.name())?;
+
+        let relid = PgWrapper::get_relname_relid(&rel_name, nsp_oid)
+            .map_err(catalog_error)?;
+        if relid == pg_sys::InvalidOid {
+            return Err(Error::new(
+                ErrorKind::TableNotFound,
+                format!("Table {table} does not exist"),
+            ));
+        }
+
+        Ok(relid)
+    }
+
+    /// Resolve the OID of the relation backing an Iceberg table.
+    ///
+    /// Fails with [`ErrorKind::TableNotFound`] if the relation is not an
+    /// Iceberg table.
+    fn iceberg_table_relid(&self, table: &TableIdent) -> Result<pg_sys::Oid> {
+        let relid = self.table_relid(table)?;
+
+        if !IcebergMetadata::exists(relid).map_err(catalog_error)? {
+            return Err(not_an_iceberg_table(table));
+        }
+
+        Ok(relid)
+    }
+
+    /// Load the table backing the relation `relid`, identified as `table`.
+    pub fn load_table_by_relid(
+        &self,
+        relid: pg_sys::Oid,
+        table: &TableIdent,
+    ) -> Result<Table> {
+        let record = IcebergMetadata::find_by_relid(relid)
+            .map_err(catalog_error)?
+            .ok_or_else(|| not_an_iceberg_table(table))?;
+        let metadata_location = record.metadata_location.ok_or_else(|| {
+            Error::new(
+                ErrorKind::DataInvalid,
+                format!("Table {table} has no metadata location"),
+            )
+        })?;
+
+        let metadata = TableMetadata::read_from(&self.file_io, &metadata_location)?;
+
+        Table::builder()
+            .file_io(self.file_io.clone())
+            .metadata_location(metadata_location)
+            .metadata(metadata)
+            .identifier(table.clone())
+            .build()
+    }
+
+    /// Commit `commit` to the table backing the relation `relid`.
+    ///
+    /// The commit's requirements are checked against the metadata currently
+    /// referenced by `lakehouse.iceberg_metadata`, and the new metadata
+    /// location is only stored if that reference did not change meanwhile.
+    ///
+    /// Unlike [`Catalog::update_table`] the relation is not looked up by the
+    /// commit's identifier, so this also works when the table was renamed
+    /// after the commit was built.
+    pub fn update_table_by_relid(
+        &self,
+        relid: pg_sys::Oid,
+        commit: TableCommit,
+    ) -> Result<Table> {
+        self.update_table_by_relid_with(relid, || Ok(commit))
+    }
+
+    /// Commit the commit `build` returns to the table backing the relation
+    /// `relid`, see [`Self::update_table_by_relid`].
+    ///
+    /// `build` runs once commits to the table are serialized, so that no other
+    /// commit lands between the table it is based on and the update.
+    pub fn update_table_by_relid_with<E: From<Error>>(
+        &self,
+        relid: pg_sys::Oid,
+        build: impl FnOnce() -> std::result::Result<TableCommit, E>,
+    ) -> std::result::Result<Table, E> {
+        // Serialize commits to the same table. The lock is self-conflicting
+        // but does not block readers or writers that have not reached their
+        // commit yet.
+        PgWrapper::lock_relation_oid(relid, pg_sys::ShareUpdateExclusiveLock as _)
+            .map_err(catalog_error)?;
+
+        let commit = build()?;
+        let current = self.load_table_by_relid(relid, commit.identifier())?;
+        let previous_metadata_location =
+            current.metadata_location_result()?.to_string();
+
+        // Check the requirements and apply the updates on top of the current
+        // metadata, then write it out as the next metadata version.
+        let staged = commit.apply(current)?;
+        let metadata_location = staged.metadata_location_result()?;
+        staged
+            .metadata()
+            .write_to(&self.file_io, metadata_location)?;
+
+        IcebergMetadata::new(relid)
+            .with_metadata_location(metadata_location)
+            .with_previous_metadata_location(previous_metadata_location.clone())
+            .with_default_spec_id(staged.metadata().default_partition_spec_id())
+            .compare_and_swap(&previous_metadata_location)
+            .map_err(|e| match e {
+                IcebergMetadataError::Conflict(_) => Error::new(
+                    ErrorKind::CatalogCommitConflicts,
+                    format!("Table {} was concurrently updated", staged.identifier()),
+                )
+                .with_retryable(true),
+                e => catalog_error(e),
+            })?;
+
+        // Make the new metadata location visible to later lookups in this
+        // transaction.
+        unsafe { pg_sys::CommandCounterIncrement() };
+
+        Ok(staged)
+    }
+}
+
+fn to_cstring(name: impl Into<Vec<u8>>) -> Result<CString> {
+    CString::new(name).map_err(|e| {
+        Error::new(ErrorKind::DataInvalid, "Identifier contains a nul byte")
+            .with_source(e)
+    })
+}
+
+fn not_an_iceberg_table(table: &TableIdent) -> Error {
+    Error::new(
+        ErrorKind::TableNotFound,
+        format!("Table {table} is not an Iceberg table"),
+    )
+}
+
+fn namespaces_are_schemas(operation: &str) -> Error {
+    Error::new(
+        ErrorKind::FeatureUnsupported,
+        format!("Namespaces are PostgreSQL schemas, use {operation} instead"),
+    )
+}
+
+/// Raise PostgreSQL's `insufficient_privilege` error unless the current user
+/// owns the relation, as `DROP TABLE` and `ALTER TABLE` require.
+fn check_table_owner(relid: pg_sys::Oid) {
+    unsafe {
+        if !pg_sys::object_ownercheck(
+            pg_sys::RelationRelationId,
+            relid,
+            pg_sys::GetUserId(),
+        ) {
+            pg_sys::aclcheck_error(
+                pg_sys::AclResult::ACLCHECK_NOT_OWNER,
+                pg_sys::get_relkind_objtype(pg_sys::get_rel_relkind(relid)),
+                pg_sys::get_rel_name(relid),
+            );
+        }
+    }
+}
+
+/// Raise PostgreSQL's `insufficient_privilege` error unless the current user
+/// may create objects in the schema, as `ALTER TABLE ... SET SCHEMA` requires.
+fn check_namespace_create(nsp_oid: pg_sys::Oid) {
+    unsafe {
+        let result = pg_sys::object_aclcheck(
+            pg_sys::NamespaceRelationId,
+            nsp_oid,
+            pg_sys::GetUserId(),
+            pg_sys::ACL_CREATE as _,
+        );
+        if result != pg_sys::AclResult::ACLCHECK_OK {
+            pg_sys::aclcheck_error(
+                result,
+                pg_sys::ObjectType::OBJECT_SCHEMA,
+                pg_sys::get_namespace_name(nsp_oid),
+            );
+        }
+    }
+}
+
+/// Whether a schema is visible as a namespace, i.e. it is not a system,
+/// TOAST or temporary schema.
+fn is_user_namespace(nsp_oid: pg_sys::Oid) -> bool {
+    unsafe {
+        !pg_sys::IsSystemNamespace(nsp_oid)
+            && !pg_sys::IsToastNamespace(nsp_oid)
+            && !pg_sys::isAnyTempNamespace(nsp_oid)
+    }
+}
+
+/// Names of the user schemas in `pg_namespace`, see [`is_user_namespace`].
+fn list_schema_names() -> Result<Vec<String>> {
+    let mut names = vec![];
+
+    unsafe {
+        let rel = PgWrapper::table_open(
+            pg_sys::NamespaceRelationId,
+            pg_sys::AccessShareLock as _,
+        )
+        .map_err(catalog_error)?;
+        let tup_desc = (*rel).rd_att;
+
+        let scan = PgWrapper::systable_beginscan(
+            rel,
+            pg_sys::InvalidOid,
+            false,
+            std::ptr::null_mut(),
+            0,
+            std::ptr::null_mut(),
+        )
+        .map_err(catalog_error)?;
+
+        while let Some(tuple) =
+            PgWrapper::systable_getnext(scan).map_err(catalog_error)?
+        {
+            let mut is_null = false;
+            let oid_datum = pg_sys::heap_getattr(
+                tuple,
+                pg_sys::Anum_pg_namespace_oid as _,
+                tup_desc,
+                &mut is_null,
+            );
+            let nsp_oid = pg_sys::Oid::from_datum(oid_datum, is_null)
+                .unwrap_or(pg_sys::InvalidOid);
+            if !is_user_namespace(nsp_oid) {
+                continue;
+            }
+
+            let name_datum = pg_sys::heap_getattr(
+                tuple,
+                pg_sys::Anum_pg_namespace_nspname as _,
+                tup_desc,
+                &mut is_null,
+            );
+            let name = CStr::from_ptr(name_datum.cast_mut_ptr::<std::ffi::c_char>());
+            names.push(name.to_string_lossy().into_owned());
+        }
+
+        PgWrapper::systable_endscan(scan).map_err(catalog_error)?;
+        PgWrapper::table_close(rel, pg_sys::AccessShareLock as _)
+            .map_err(catalog_error)?;
+    }
+
+    names.sort();
+    Ok(names)
+}
+
+fn catalog_error(e: impl std::error::Error + Send + Sync + 'static) -> Error {
+    Error::new(
+        ErrorKind::Unexpected,
+        "Failed to access the PostgreSQL catalog",
+    )
+    .with_source(e)
 }
 
 /// Checks if provided `NamespaceIdent` is valid.
@@ -84,9 +366,17 @@ pub(crate) fn validate_namespace(namespace: &NamespaceIdent) -> Result<String> {
 impl Catalog for IcebergCatalog {
     fn list_namespaces<'a>(
         &self,
-        _parent: Option<&'a NamespaceIdent>,
+        parent: Option<&'a NamespaceIdent>,
     ) -> Result<Vec<NamespaceIdent>> {
-        todo!("list_namespaces not yet implemented")
+        // Schemas are not nested
+        if parent.is_some() {
+            return Ok(vec![]);
+        }
+
+        Ok(list_schema_names()?
+            .into_iter()
+            .map(NamespaceIdent::new)
+            .collect())
     }
 
     fn create_namespace(
@@ -94,15 +384,20 @@ impl Catalog for IcebergCatalog {
         _namespace: &NamespaceIdent,
         _properties: HashMap<String, String>,
     ) -> Result<Namespace> {
-        todo!("create_namespace not yet implemented")
+        Err(namespaces_are_schemas("CREATE SCHEMA"))
     }
 
-    fn get_namespace(&self, _namespace: &NamespaceIdent) -> Result<Namespace> {
-        todo!("get_namespace not yet implemented")
+    fn get_namespace(&self, namespace: &NamespaceIdent) -> Result<Namespace> {
+        self.namespace_oid(namespace)?;
+        Ok(Namespace::new(namespace.clone()))
     }
 
-    fn namespace_exists(&self, _namespace: &NamespaceIdent) -> Result<bool> {
-        todo!("namespace_exists not yet implemented")
+    fn namespace_exists(&self, namespace: &NamespaceIdent) -> Result<bool> {
+        match self.namespace_oid(namespace) {
+            Ok(_) => Ok(true),
+            Err(e) if e.kind() == ErrorKind::NamespaceNotFound => Ok(false),
+            Err(e) => Err(e),
+        }
     }
 
     fn update_namespace(
@@ -110,15 +405,34 @@ impl Catalog for IcebergCatalog {
         _namespace: &NamespaceIdent,
         _properties: HashMap<String, String>,
     ) -> Result<()> {
-        todo!("update_namespace not yet implemented")
+        Err(namespaces_are_schemas("ALTER SCHEMA"))
     }
 
     fn drop_namespace(&self, _namespace: &NamespaceIdent) -> Result<()> {
-        todo!("drop_namespace not yet implemented")
+        Err(namespaces_are_schemas("DROP SCHEMA"))
     }
 
-    fn list_tables(&self, _namespace: &NamespaceIdent) -> Result<Vec<TableIdent>> {
-        todo!("list_tables not yet implemented")
+    fn list_tables(&self, namespace: &NamespaceIdent) -> Result<Vec<TableIdent>> {
+        let nsp_oid = self.namespace_oid(namespace)?;
+
+        let mut tables = vec![];
+        for record in IcebergMetadata::list().map_err(catalog_error)? {
+            if unsafe { pg_sys::get_rel_namespace(record.relid) } != nsp_oid {
+                continue;
+            }
+
+            let name = unsafe { pg_sys::get_rel_name(record.relid) };
+            if name.is_null() {
+                continue;
+            }
+            let name = unsafe { CStr::from_ptr(name) }
+                .to_string_lossy()
+                .into_owned();
+            tables.push(TableIdent::new(namespace.clone(), name));
+        }
+
+        tables.sort_by(|a, b| a.name().cmp(b.name()));
+        Ok(tables)
     }
 
     fn create_table(
@@ -156,20 +470,73 @@ impl Catalog for IcebergCatalog {
             .build()
     }
 
-    fn load_table(&self, _table: &TableIdent) -> Result<Table> {
-        todo!("load_table not yet implemented")
+    fn load_table(&self, table: &TableIdent) -> Result<Table> {
+        let relid = self.table_relid(table)?;
+        self.load_table_by_relid(relid, table)
     }
 
-    fn drop_table(&self, _table: &TableIdent) -> Result<()> {
-        todo!("drop_table not yet implemented")
+    fn drop_table(&self, table: &TableIdent) -> Result<()> {
+        let relid = self.iceberg_table_relid(table)?;
+        check_table_owner(relid);
+
+        // Files are removed at commit by the object access hook
+        PgWrapper::lock_relation_oid(relid, pg_sys::AccessExclusiveLock as _)
+            .map_err(catalog_error)?;
+        PgWrapper::drop_relation(relid, pg_sys::DropBehavior::DROP_RESTRICT)
+            .map_err(catalog_error)?;
+        unsafe { pg_sys::CommandCounterIncrement() };
+
+        Ok(())
     }
 
-    fn table_exists(&self, _table: &TableIdent) -> Result<bool> {
-        todo!("table_exists not yet implemented")
+    fn table_exists(&self, table: &TableIdent) -> Result<bool> {
+        match self.iceberg_table_relid(table) {
+            Ok(_) => Ok(true),
+            Err(e)
+                if matches!(
+                    e.kind(),
+                    ErrorKind::NamespaceNotFound | ErrorKind::TableNotFound
+                ) =>
+            {
+                Ok(false)
+            }
+            Err(e) => Err(e),
+        }
     }
 
-    fn rename_table(&self, _src: &TableIdent, _dest: &TableIdent) -> Result<()> {
-        todo!("rename_table not yet implemented")
+    fn rename_table(&self, src: &TableIdent, dest: &TableIdent) -> Result<()> {
+        let relid = self.iceberg_table_relid(src)?;
+        check_table_owner(relid);
+        let dest_nsp_oid = self.namespace_oid(dest.namespace())?;
+        let dest_name = to_cstring(dest.name())?;
+
+        let existing = PgWrapper::get_relname_relid(&dest_name, dest_nsp_oid)
+            .map_err(catalog_error)?;
+        if existing != pg_sys::InvalidOid {
+            return Err(Error::new(
+                ErrorKind::TableAlreadyExists,
+                format!("Table {dest} already exists"),
+            ));
+        }
+
+        // The table location is derived from the relation's file locator, so
+        // only the PostgreSQL catalog needs to change.
+        PgWrapper::lock_relation_oid(relid, pg_sys::AccessExclusiveLock as _)
+            .map_err(catalog_error)?;
+
+        if unsafe { pg_sys::get_rel_namespace(relid) } != dest_nsp_oid {
+            check_namespace_create(dest_nsp_oid);
+            PgWrapper::alter_relation_namespace(relid, dest_nsp_oid)
+                .map_err(catalog_error)?;
+            unsafe { pg_sys::CommandCounterIncrement() };
+        }
+
+        if src.name() != dest.name() {
+            PgWrapper::rename_relation(relid, &dest_name).map_err(catalog_error)?;
+            unsafe { pg_sys::CommandCounterIncrement() };
+        }
+
+        Ok(())
     }
 
     fn register_table(
@@ -177,11 +544,15 @@ impl Catalog for IcebergCatalog {
         _table: &TableIdent,
         _metadata_location: String,
     ) -> Result<Table> {
-        todo!("register_table not yet implemented")
+        Err(Error::new(
+            ErrorKind::FeatureUnsupported,
+            "Iceberg tables are backed by PostgreSQL relations, use CREATE TABLE ... USING iceberg instead",
+        ))
     }
 
-    fn update_table(&self, _commit: TableCommit) -> Result<Table> {
-        todo!("update_table not yet implemented")
+    fn update_table(&self, commit: TableCommit) -> Result<Table> {
+        let relid = self.table_relid(commit.identifier())?;
+        self.update_table_by_relid(relid, commit)
     }
 }
 
@@ -209,3 +580,185 @@ mod tests {
         assert_eq!(catalog.name(), "test_catalog");
     }
 }
+
+#[cfg(any(test, feature = "pg_test"))]
+#[pgrx::pg_schema]
+mod pg_tests {
+    use super::*;
+    use crate::storage::create_storage_context;
+    use pgrx::prelude::*;
+
+    fn catalog() -> IcebergCatalog {
+        let ctx = create_storage_context(pg_sys::DEFAULTTABLESPACE_OID).unwrap();
+        IcebergCatalog::new(ICEBERG_CATALOG_NAME, ctx.file_io)
+    }
+
+    fn table_ident(namespace: &str, name: &str) -> TableIdent {
+        TableIdent::new(NamespaceIdent::new(namespace.to_string()), name.to_string())
+    }
+
+    fn relid(name: &str) -> pg_sys::Oid {
+        Spi::get_one::<pg_sys::Oid>(&format!("SELECT '{name}'::regclass::oid"))
+            .unwrap()
+            .unwrap()
+    }
+
+    #[pg_test]
+    fn test_list_tables() {
+        Spi::run("CREATE SCHEMA catalog_list").unwrap();
+        Spi::run("CREATE TABLE catalog_list.t2 (id int) USING iceberg").unwrap();
+        Spi::run("CREATE TABLE catalog_list.t1 (id int) USING iceberg").unwrap();
+        Spi::run("CREATE TABLE catalog_list.heap (id int)").unwrap();
+
+        // Only Iceberg tables are listed, ordered by name
+        let tables = catalog()
+            .list_tables(&NamespaceIdent::new("catalog_list".to_string()))
+            .unwrap();
+        assert_eq!(
+            tables,
+            vec![
+                table_ident("catalog_list", "t1"),
+                table_ident("catalog_list", "t2")
+            ]
+        );
+
+        let err = catalog()
+            .list_tables(&NamespaceIdent::new("catalog_missing".to_string()))
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NamespaceNotFound);
+    }
+
+    #[pg_test]
+    fn test_load_table() {
+        Spi::run("CREATE SCHEMA catalog_load").unwrap();
+        Spi::run("CREATE TABLE catalog_load.t (id int) USING iceberg").unwrap();
+        Spi::run("CREATE TABLE catalog_load.heap (id int)").unwrap();
+
+        let ident = table_ident("catalog_load", "t");
+        let table = catalog().load_table(&ident).unwrap();
+        assert_eq!(table.identifier(), &ident);
+        assert_eq!(
+            table.metadata_location(),
+            IcebergMetadata::get(relid("catalog_load.t"))
+                .unwrap()
+                .metadata_location
+                .as_deref()
+        );
+
+        let err = catalog()
+            .load_table(&table_ident("catalog_load", "heap"))
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::TableNotFound);
+    }
+
+    #[pg_test]
+    fn test_rename_table() {
+        Spi::run("CREATE SCHEMA catalog_rename_src").unwrap();
+        Spi::run("CREATE SCHEMA catalog_rename_dest").unwrap();
+        Spi::run("CREATE TABLE catalog_rename_src.t (id int) USING iceberg").unwrap();
+        Spi::run("CREATE TABLE catalog_rename_dest.taken (id int)").unwrap();
+        let src = table_ident("catalog_rename_src", "t");
+        let relid = relid("catalog_rename_src.t");
+
+        let err = catalog()
+            .rename_table(&src, &table_ident("catalog_rename_dest", "taken"))
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::TableAlreadyExists);
+
+        // The relation moves to the other schema under its new name
+        let dest = table_ident("catalog_rename_dest", "renamed");
+        catalog().rename_table(&src, &dest).unwrap();
+        assert!(!catalog().table_exists(&src).unwrap());
+        assert!(catalog().table_exists(&dest).unwrap());
+        assert_eq!(
+            Spi::get_one::<pg_sys::Oid>(
+                "SELECT 'catalog_rename_dest.renamed'::regclass::oid"
+            )
+            .unwrap(),
+            Some(relid)
+        );
+        assert!(catalog().load_table(&dest).is_ok());
+    }
+
+    #[pg_test]
+    fn test_drop_table() {
+        Spi::run("CREATE SCHEMA catalog_drop").unwrap();
+        Spi::run("CREATE TABLE catalog_drop.t (id int) USING iceberg").unwrap();
+        Spi::run("CREATE TABLE catalog_drop.heap (id int)").unwrap();
+        let ident = table_ident("catalog_drop", "t");
+        let relid = relid("catalog_drop.t");
+
+        catalog().drop_table(&ident).unwrap();
+        assert!(!catalog().table_exists(&ident).unwrap());
+        assert!(!IcebergMetadata::exists(relid).unwrap());
+        assert_eq!(
+            Spi::get_one::<bool>("SELECT to_regclass('catalog_drop.t') IS NULL")
+                .unwrap(),
+            Some(true)
+        );
+
+        // Other relations are not Iceberg tables
+        let err = catalog()
+            .drop_table(&table_ident("catalog_drop", "heap"))
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::TableNotFound);
+    }
+
+    #[pg_test(error = "must be owner of table t")]
+    fn test_drop_table_not_owner() {
+        Spi::run("CREATE SCHEMA catalog_drop_owner").unwrap();
+        Spi::run("CREATE TABLE catalog_drop_owner.t (id int) USING iceberg").unwrap();
+        Spi::run("CREATE ROLE catalog_drop_user").unwrap();
+        Spi::run("SET ROLE catalog_drop_user").unwrap();
+
+        let _ = catalog().drop_table(&table_ident("catalog_drop_owner", "t"));
+    }
+
+    #[pg_test(error = "must be owner of table t")]
+    fn test_rename_table_not_owner() {
+        Spi::run("CREATE SCHEMA catalog_rename_owner").unwrap();
+        Spi::run("CREATE TABLE catalog_rename_owner.t (id int) USING iceberg")
+            .unwrap();
+        Spi::run("CREATE ROLE catalog_rename_user").unwrap();
+        Spi::run("SET ROLE catalog_rename_user").unwrap();
+
+        let _ = catalog().rename_table(
+            &table_ident("catalog_rename_owner", "t"),
+            &table_ident("catalog_rename_owner", "renamed"),
+        );
+    }
+
+    #[pg_test]
+    fn test_compare_and_swap_conflict() {
+        Spi::run("CREATE TABLE catalog_cas (id int) USING iceberg").unwrap();
+        let relid = relid("catalog_cas");
+        let current = IcebergMetadata::get(relid)
+            .unwrap()
+            .metadata_location
+            .unwrap();
+
+        // The metadata location changed since it was read
+        let err = IcebergMetadata::new(relid)
+            .with_metadata_location("v2.metadata.json")
+            .compare_and_swap("v0.metadata.json")
+            .unwrap_err();
+        assert!(matches!(err, IcebergMetadataError::Conflict(oid) if oid == relid));
+        assert_eq!(
+            IcebergMetadata::get(relid).unwrap().metadata_location,
+            Some(current.clone())
+        );
+
+        IcebergMetadata::new(relid)
+            .with_metadata_location("v2.metadata.json")
+            .with_previous_metadata_location(current.clone())
+            .compare_and_swap(&current)
+            .unwrap();
+        assert_eq!(
+            IcebergMetadata::get(relid)
+                .unwrap()
+                .metadata_location
+                .as_deref(),
+            Some("v2.metadata.json")
+        );
+    }
+}
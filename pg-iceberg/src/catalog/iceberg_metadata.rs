@@ -105,6 +105,9 @@ pub enum IcebergMetadataError {
 
     #[error("failed to read record: {0}")]
     ReadFailed(String),
+
+    #[error("record was concurrently updated for relid: {0}")]
+    Conflict(pg_sys::Oid),
 }
 
 impl From<IcebergMetadataError> for ErrorReport {
@@ -117,6 +120,9 @@ impl From<IcebergMetadataError> for ErrorReport {
             IcebergMetadataError::AlreadyExists(_) => {
                 PgSqlErrorCode::ERRCODE_UNIQUE_VIOLATION
             }
+            IcebergMetadataError::Conflict(_) => {
+                PgSqlErrorCode::ERRCODE_T_R_SERIALIZATION_FAILURE
+            }
             _ => PgSqlErrorCode::ERRCODE_INTERNAL_ERROR,
         };
         ErrorReport::new(code, error_message, "")
@@ -274,10 +280,63 @@ impl IcebergMetadata {
         Ok(Self::find_by_relid(relid)?.is_some())
     }
 
+    /// List all records of the `lakehouse.iceberg_metadata` table.
+    pub fn list() -> Result<Vec<Self>, IcebergMetadataError> {
+        let table_oid = get_iceberg_metadata_oid()?;
+
+        unsafe {
+            let rel = PgWrapper::table_open(table_oid, pg_sys::AccessShareLock as _)
+                .map_err(|e| IcebergMetadataError::ReadFailed(e.to_string()))?;
+
+            let scan = PgWrapper::systable_beginscan(
+                rel,
+                pg_sys::InvalidOid,
+                false,
+                std::ptr::null_mut(),
+                0,
+                std::ptr::null_mut(),
+            )
+            .map_err(|e| IcebergMetadataError::ReadFailed(e.to_string()))?;
+
+            let mut records = vec![];
+            while let Some(tuple) = PgWrapper::systable_getnext(scan)
+                .map_err(|e| IcebergMetadataError::ReadFailed(e.to_string()))?
+            {
+                records.push(Self::from_tuple(rel, tuple)?);
+            }
+
+            PgWrapper::systable_endscan(scan)
+                .map_err(|e| IcebergMetadataError::ReadFailed(e.to_string()))?;
+            PgWrapper::table_close(rel, pg_sys::AccessShareLock as _)
+                .map_err(|e| IcebergMetadataError::ReadFailed(e.to_string()))?;
+
+            Ok(records)
+        }
+    }
+
     /// Update this record in the `lakehouse.iceberg_metadata` table.
     ///
     /// Returns an error if the record does not exist.
     pub fn update(&self) -> Result<(), IcebergMetadataError> {
+        self.update_if(None)
+    }
+
+    /// Update this record if its current metadata location is still
+    /// `expected_metadata_location`.
+    ///
+    /// Returns [`IcebergMetadataError::Conflict`] if the metadata location was
+    /// changed in the meantime.
+    pub fn compare_and_swap(
+        &self,
+        expected_metadata_location: &str,
+    ) -> Result<(), IcebergMetadataError> {
+        self.update_if(Some(expected_metadata_location))
+    }
+
+    fn update_if(
+        &self,
+        expected_metadata_location: Option<&str>,
+    ) -> Result<(), IcebergMetadataError> {
         let table_oid = get_iceberg_metadata_oid()?;
         let index_oid = get_iceberg_metadata_pkey_oid()?;
 
@@ -322,6 +381,20 @@ impl IcebergMetadata {
                 }
             };
 
+            if let Some(expected) = expected_metadata_location {
+                let current = Self::from_tuple(rel, old_tuple)?;
+                if current.metadata_location.as_deref() != Some(expected) {
+                    PgWrapper::systable_endscan(scan).map_err(|e| {
+                        IcebergMetadataError::UpdateFailed(e.to_string())
+                    })?;
+                    PgWrapper::table_close(rel, pg_sys::RowExclusiveLock as _)
+                        .map_err(|e| {
+                            IcebergMetadataError::UpdateFailed(e.to_string())
+                        })?;
+                    return Err(IcebergMetadataError::Conflict(self.relid));
+                }
+            }
+
             // Prepare new values
             let tup_desc = (*rel).rd_att;
             let natts = (*tup_desc).natts as usize;
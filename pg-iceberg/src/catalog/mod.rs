@@ -1,15 +1,22 @@
+pub mod field_ids;
 pub mod iceberg_catalog;
 pub mod iceberg_metadata;
+pub mod partitioning;
 pub mod schema_mapping;
+pub mod sort_order;
 pub mod table_handler;
 
+use crate::error::{IcebergError, IcebergResult};
 use crate::get_iceberg_am_routine_ptr;
 use pg_tam::handles::RelationHandle;
 use pgrx::pg_sys;
 
+pub use field_ids::*;
 pub use iceberg_catalog::*;
 pub use iceberg_metadata::*;
+pub use partitioning::*;
 pub use schema_mapping::*;
+pub use sort_order::*;
 pub use table_handler::*;
 
 #[inline]
@@ -28,3 +35,43 @@ pub fn is_iceberg_table(rel: &RelationHandle) -> bool {
         rd_tableam as *const pg_sys::TableAmRoutine == get_iceberg_am_routine_ptr()
     }
 }
+
+/// Check that the current user holds the privileges `mode` on `rel`.
+pub fn check_table_privilege(
+    rel: &RelationHandle,
+    mode: pg_sys::AclMode,
+) -> IcebergResult<()> {
+    let result =
+        unsafe { pg_sys::pg_class_aclcheck(rel.oid(), pg_sys::GetUserId(), mode) };
+    if result != pg_sys::AclResult::ACLCHECK_OK {
+        return Err(IcebergError::PermissionDenied(rel.relation_name()));
+    }
+    Ok(())
+}
+
+/// Check that the current user may maintain `rel` like ANALYZE does: with the
+/// `MAINTAIN` privilege, or as owner of the table or database before
+/// PostgreSQL 17.
+pub fn check_table_maintain(rel: &RelationHandle) -> IcebergResult<()> {
+    #[cfg(feature = "pg17")]
+    {
+        check_table_privilege(rel, pg_sys::ACL_MAINTAIN as pg_sys::AclMode)
+    }
+
+    #[cfg(not(feature = "pg17"))]
+    {
+        let is_owner = unsafe {
+            let user = pg_sys::GetUserId();
+            pg_sys::object_ownercheck(pg_sys::RelationRelationId, rel.oid(), user)
+                || pg_sys::object_ownercheck(
+                    pg_sys::DatabaseRelationId,
+                    pg_sys::MyDatabaseId,
+                    user,
+                )
+        };
+        if !is_owner {
+            return Err(IcebergError::PermissionDenied(rel.relation_name()));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,180 @@
+//! Partition specs declared by the `partitioning` table option.
+//!
+//! The option lists comma-separated partition terms, applied in order:
+//!
+//! ```text
+//! WITH (partitioning = 'bucket(16, id), day(ts), region')
+//! ```
+//!
+//! A bare column name partitions by the column's value. The other terms apply
+//! an Iceberg transform to a column: `identity(col)`, `year(col)`,
+//! `month(col)`, `day(col)`, `hour(col)`, `void(col)`, `bucket(n, col)` and
+//! `truncate(width, col)`. Partition fields are named after their source
+//! column like other Iceberg engines do, e.g. `ts_day` or `id_bucket`.
+
+use crate::error::{IcebergError, IcebergResult};
+use iceberg_lite::spec::{PartitionSpec, Schema, Transform, UnboundPartitionSpec};
+use std::str::FromStr;
+
+/// Split `value` at the commas that are not enclosed in parentheses.
+pub(crate) fn split_terms(value: &str) -> Vec<&str> {
+    let mut terms = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, c) in value.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                terms.push(value[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    terms.push(value[start..].trim());
+    terms
+}
+
+/// The column name of a term, without the quotes of a quoted identifier.
+pub(crate) fn column_name(term: &str) -> &str {
+    let term = term.trim();
+    term.strip_prefix('"')
+        .and_then(|term| term.strip_suffix('"'))
+        .unwrap_or(term)
+}
+
+/// Parse a partition term into its source column and transform.
+fn parse_term(term: &str) -> Option<(&str, Transform)> {
+    let Some((name, args)) = term.split_once('(') else {
+        return Some((column_name(term), Transform::Identity));
+    };
+    let name = name.trim().to_ascii_lowercase();
+    let args: Vec<_> = args.strip_suffix(')')?.split(',').map(str::trim).collect();
+    let transform = match (name.as_str(), &args[..]) {
+        ("bucket" | "truncate", [param, _]) => {
+            Transform::from_str(&format!("{name}[{param}]")).ok()?
+        }
+        ("identity" | "year" | "month" | "day" | "hour" | "void", [_]) => {
+            Transform::from_str(&name).ok()?
+        }
+        _ => return None,
+    };
+    Some((column_name(args.last()?), transform))
+}
+
+/// The name of the partition field of `transform` applied to `column`.
+fn partition_field_name(column: &str, transform: &Transform) -> String {
+    match transform {
+        Transform::Identity => column.to_string(),
+        Transform::Bucket(_) => format!("{column}_bucket"),
+        Transform::Truncate(_) => format!("{column}_trunc"),
+        Transform::Year => format!("{column}_year"),
+        Transform::Month => format!("{column}_month"),
+        Transform::Day => format!("{column}_day"),
+        Transform::Hour => format!("{column}_hour"),
+        _ => format!("{column}_null"),
+    }
+}
+
+/// Parse the `partitioning` option into a partition spec of `schema`.
+///
+/// The terms are validated against the schema: their columns must exist and
+/// their transforms must apply to the column types.
+pub fn parse_partitioning(
+    value: &str,
+    schema: &Schema,
+) -> IcebergResult<UnboundPartitionSpec> {
+    let invalid =
+        |reason: String| IcebergError::InvalidPartitioning(value.to_string(), reason);
+
+    let mut builder = PartitionSpec::builder(schema.clone());
+    for term in split_terms(value)
+        .into_iter()
+        .filter(|term| !term.is_empty())
+    {
+        let (column, transform) = parse_term(term)
+            .ok_or_else(|| invalid(format!("invalid partition term \"{term}\"")))?;
+        let name = partition_field_name(column, &transform);
+        builder = builder
+            .add_partition_field(column, name, transform)
+            .map_err(|e| invalid(e.message().to_string()))?;
+    }
+
+    let spec = builder
+        .build()
+        .map_err(|e| invalid(e.message().to_string()))?;
+    Ok(spec.into_unbound())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iceberg_lite::spec::{NestedField, PrimitiveType, Type};
+
+    fn schema() -> Schema {
+        Schema::builder()
+            .with_fields(vec![
+                NestedField::required(1, "id", Type::Primitive(PrimitiveType::Int))
+                    .into(),
+                NestedField::optional(
+                    2,
+                    "ts",
+                    Type::Primitive(PrimitiveType::Timestamp),
+                )
+                .into(),
+                NestedField::optional(
+                    3,
+                    "region",
+                    Type::Primitive(PrimitiveType::String),
+                )
+                .into(),
+            ])
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_split_terms() {
+        assert_eq!(
+            split_terms("bucket(16, id), day(ts) ,region"),
+            vec!["bucket(16, id)", "day(ts)", "region"]
+        );
+        assert_eq!(split_terms(""), vec![""]);
+    }
+
+    #[test]
+    fn test_parse_partitioning() {
+        let spec =
+            parse_partitioning("bucket(16, id), DAY(ts), \"region\"", &schema())
+                .unwrap();
+
+        let fields: Vec<_> = spec
+            .fields()
+            .iter()
+            .map(|field| (field.source_id, field.name.as_str(), field.transform))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                (1, "id_bucket", Transform::Bucket(16)),
+                (2, "ts_day", Transform::Day),
+                (3, "region", Transform::Identity),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_partitioning_invalid() {
+        // Unknown column
+        assert!(parse_partitioning("day(created)", &schema()).is_err());
+        // Transform not applicable to the column type
+        assert!(parse_partitioning("day(id)", &schema()).is_err());
+        // Malformed terms
+        assert!(parse_partitioning("bucket(id)", &schema()).is_err());
+        assert!(parse_partitioning("bucket(x, id)", &schema()).is_err());
+        assert!(parse_partitioning("week(ts)", &schema()).is_err());
+        // Redundant partitions
+        assert!(parse_partitioning("day(ts), day(ts)", &schema()).is_err());
+    }
+}
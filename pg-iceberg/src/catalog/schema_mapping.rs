@@ -94,7 +94,9 @@ pub fn pg_type_to_iceberg_type(type_oid: pg_sys::Oid, type_mod: i32) -> IcebergR
 /// * `tup_desc` - A pointer to the PostgreSQL TupleDesc
 ///
 /// # Returns
-/// An Iceberg Schema with fields corresponding to the TupleDesc attributes.
+/// An Iceberg Schema with fields corresponding to the live TupleDesc
+/// attributes, numbered in order as `create_table` assigns field ids. The
+/// attributes are mapped to their fields by `FieldIds`.
 pub unsafe fn tuple_desc_to_schema(tup_desc: pg_sys::TupleDesc) -> IcebergResult<Schema> {
     unsafe {
         let natts = (*tup_desc).natts as usize;
@@ -102,7 +104,7 @@ pub unsafe fn tuple_desc_to_schema(tup_desc: pg_sys::TupleDesc) -> IcebergResult
 
         let mut fields = Vec::with_capacity(natts);
 
-        for (i, attr) in attrs.iter().enumerate() {
+        for attr in attrs {
             // Skip dropped columns
             if attr.attisdropped {
                 continue;
@@ -115,8 +117,8 @@ pub unsafe fn tuple_desc_to_schema(tup_desc: pg_sys::TupleDesc) -> IcebergResult
             // Get Iceberg type from PostgreSQL type
             let iceberg_type = pg_type_to_iceberg_type(attr.atttypid, attr.atttypmod)?;
 
-            // Field ID is 1-based index (Iceberg requires unique positive field IDs)
-            let field_id = (i + 1) as i32;
+            // Field IDs are 1-based (Iceberg requires unique positive field IDs)
+            let field_id = (fields.len() + 1) as i32;
 
             // Create NestedField based on nullability
             let field = if attr.attnotnull {
@@ -0,0 +1,178 @@
+//! Sort orders declared by the `sort_order` table option.
+//!
+//! The option lists comma-separated sort terms like an `ORDER BY` clause:
+//!
+//! ```text
+//! WITH (sort_order = 'ts DESC NULLS LAST, id')
+//! ```
+//!
+//! Each term is a column, optionally followed by `ASC` or `DESC` and by
+//! `NULLS FIRST` or `NULLS LAST`. As in PostgreSQL, nulls sort last in
+//! ascending and first in descending order unless specified otherwise.
+
+use super::partitioning::{column_name, split_terms};
+use crate::error::{IcebergError, IcebergResult};
+use iceberg_lite::spec::{
+    NullOrder, Schema, SortDirection, SortField, SortOrder, Transform,
+};
+
+/// A sort term of the `sort_order` option.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SortTerm {
+    pub column: String,
+    pub direction: SortDirection,
+    pub null_order: NullOrder,
+}
+
+/// Parse a sort term, `None` if it is malformed.
+fn parse_term(term: &str) -> Option<SortTerm> {
+    // The column may be a quoted identifier containing spaces
+    let (column, rest) = match term.strip_prefix('"') {
+        Some(quoted) => {
+            let end = quoted.find('"')?;
+            (&quoted[..end], &quoted[end + 1..])
+        }
+        None => term.split_once(char::is_whitespace).unwrap_or((term, "")),
+    };
+    let column = column_name(column);
+    if column.is_empty() {
+        return None;
+    }
+
+    let keywords: Vec<_> = rest
+        .split_whitespace()
+        .map(str::to_ascii_uppercase)
+        .collect();
+    let keywords: Vec<_> = keywords.iter().map(String::as_str).collect();
+    let (direction, nulls) = match keywords[..] {
+        ["ASC", ref nulls @ ..] => (SortDirection::Ascending, nulls),
+        ["DESC", ref nulls @ ..] => (SortDirection::Descending, nulls),
+        ref nulls => (SortDirection::Ascending, nulls),
+    };
+    let null_order = match (nulls, direction) {
+        (["NULLS", "FIRST"], _) => NullOrder::First,
+        (["NULLS", "LAST"], _) => NullOrder::Last,
+        ([], SortDirection::Ascending) => NullOrder::Last,
+        ([], SortDirection::Descending) => NullOrder::First,
+        _ => return None,
+    };
+
+    Some(SortTerm {
+        column: column.to_string(),
+        direction,
+        null_order,
+    })
+}
+
+/// Parse the terms of the `sort_order` option.
+pub fn parse_sort_terms(value: &str) -> IcebergResult<Vec<SortTerm>> {
+    split_terms(value)
+        .into_iter()
+        .filter(|term| !term.is_empty())
+        .map(|term| {
+            parse_term(term).ok_or_else(|| {
+                IcebergError::InvalidSortOrder(
+                    value.to_string(),
+                    format!("invalid sort term \"{term}\""),
+                )
+            })
+        })
+        .collect()
+}
+
+/// Parse the `sort_order` option into a sort order of `schema`.
+///
+/// The terms are validated against the schema: their columns must exist and
+/// be of a primitive type.
+pub fn parse_sort_order(value: &str, schema: &Schema) -> IcebergResult<SortOrder> {
+    let invalid =
+        |reason: String| IcebergError::InvalidSortOrder(value.to_string(), reason);
+
+    let mut builder = SortOrder::builder();
+    for term in parse_sort_terms(value)? {
+        let field = schema.field_by_name(&term.column).ok_or_else(|| {
+            invalid(format!("column \"{}\" does not exist", term.column))
+        })?;
+        builder.with_sort_field(
+            SortField::builder()
+                .source_id(field.id)
+                .transform(Transform::Identity)
+                .direction(term.direction)
+                .null_order(term.null_order)
+                .build(),
+        );
+    }
+
+    builder
+        .build(schema)
+        .map_err(|e| invalid(e.message().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iceberg_lite::spec::{NestedField, PrimitiveType, StructType, Type};
+
+    fn schema() -> Schema {
+        Schema::builder()
+            .with_fields(vec![
+                NestedField::required(1, "id", Type::Primitive(PrimitiveType::Int))
+                    .into(),
+                NestedField::optional(
+                    2,
+                    "ts",
+                    Type::Primitive(PrimitiveType::Timestamp),
+                )
+                .into(),
+                NestedField::optional(
+                    3,
+                    "attrs",
+                    Type::Struct(StructType::new(vec![])),
+                )
+                .into(),
+            ])
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_parse_sort_order() {
+        let sort_order =
+            parse_sort_order("ts DESC NULLS LAST, id, \"id\" desc", &schema())
+                .unwrap();
+
+        let fields: Vec<_> = sort_order
+            .fields
+            .iter()
+            .map(|field| (field.source_id, field.direction, field.null_order))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                (2, SortDirection::Descending, NullOrder::Last),
+                (1, SortDirection::Ascending, NullOrder::Last),
+                (1, SortDirection::Descending, NullOrder::First),
+            ]
+        );
+        assert_eq!(sort_order.order_id, 1);
+    }
+
+    #[test]
+    fn test_parse_sort_order_empty() {
+        let sort_order = parse_sort_order("", &schema()).unwrap();
+
+        assert!(sort_order.is_unsorted());
+    }
+
+    #[test]
+    fn test_parse_sort_order_invalid() {
+        // Unknown column
+        assert!(parse_sort_order("created", &schema()).is_err());
+        // Column of a nested type
+        assert!(parse_sort_order("attrs", &schema()).is_err());
+        // Malformed terms
+        assert!(parse_sort_order("id ASC DESC", &schema()).is_err());
+        assert!(parse_sort_order("id NULLS", &schema()).is_err());
+        assert!(parse_sort_order("day(ts)", &schema()).is_err());
+    }
+}
@@ -1,16 +1,27 @@
+use super::field_ids::{FIELD_IDS_PROPERTY, FieldIds};
+use super::iceberg_metadata::IcebergMetadata;
+use super::partitioning::parse_partitioning;
 use super::schema_mapping::tuple_desc_to_schema;
+use super::sort_order::{parse_sort_order, parse_sort_terms};
+use crate::access::commit::{stage_transaction, staged_table};
 use crate::access::pending_deletes::register_table_pending_delete;
 use crate::error::{IcebergError, IcebergResult};
 use crate::hooks::table_option_cache::IcebergTableOptionCache;
+use crate::hooks::table_options::{OPT_FORMAT_VERSION, OPT_SORT_ORDER};
 use crate::storage::create_storage_context;
-use iceberg_lite::catalog::{Catalog, NamespaceIdent, TableCreation};
-use iceberg_lite::spec::{FormatVersion, SortOrder, UnboundPartitionSpec};
+use iceberg_lite::catalog::{Catalog, NamespaceIdent, TableCreation, TableIdent};
+use iceberg_lite::io::FileIO;
+use iceberg_lite::spec::{
+    FormatVersion, SortDirection, SortOrder, TableMetadata, UnboundPartitionSpec,
+};
+use iceberg_lite::table::Table;
+use iceberg_lite::transaction::{ApplyTransactionAction, Transaction};
 use pg_tam::handles::RelationHandle;
 use pg_tam::option::AmCache;
 use pg_tam::pg_wrapper::PgWrapper;
 use pgrx::pg_sys;
 
-use super::IcebergCatalog;
+use super::{IcebergCatalog, ICEBERG_CATALOG_NAME};
 
 fn get_tablespace_version_directory() -> String {
     let major_version = pg_sys::PG_MAJORVERSION.to_string_lossy();
@@ -83,18 +94,25 @@ pub fn init_table_storage_metadata(rel: &RelationHandle) -> IcebergResult<String
     let nsp_name = PgWrapper::get_namespace_name(rel_namespace)?
         .ok_or(IcebergError::NamespaceNull)?;
 
-    let schema = unsafe {
+    let (schema, field_ids) = unsafe {
         let tup_desc = (*rel.as_raw()).rd_att;
-        tuple_desc_to_schema(tup_desc)?
+        let schema = tuple_desc_to_schema(tup_desc)?;
+        let field_ids = FieldIds::from_schema(tup_desc, &schema)?;
+        (schema, field_ids)
     };
 
     // Get table options from rd_amcache (loads from catalog if not cached)
     let table_option = AmCache::get::<IcebergTableOptionCache>(rel)?;
-    let properties = table_option.to_properties();
-    let format_version = match table_option.format_version {
-        1 => FormatVersion::V1,
-        3 => FormatVersion::V3,
-        _ => FormatVersion::V2,
+    let mut properties = table_option.to_properties();
+    properties.insert(FIELD_IDS_PROPERTY.to_string(), field_ids.to_property());
+    let format_version = iceberg_format_version(table_option);
+    let partition_spec = match table_option.partitioning() {
+        Some(partitioning) => parse_partitioning(partitioning, &schema)?,
+        None => UnboundPartitionSpec::default(),
+    };
+    let sort_order = match table_option.sort_order() {
+        Some(sort_order) => parse_sort_order(sort_order, &schema)?,
+        None => SortOrder::unsorted_order(),
     };
 
     // Build TableCreation with all required fields
@@ -103,13 +121,13 @@ pub fn init_table_storage_metadata(rel: &RelationHandle) -> IcebergResult<String
         .location(location.clone())
         .schema(schema)
         .properties(properties)
-        .partition_spec(UnboundPartitionSpec::default()) // TODO: parse partition spec
-        .sort_order(SortOrder::unsorted_order()) // TODO: parse sort order
+        .partition_spec(partition_spec)
+        .sort_order(sort_order)
         .format_version(format_version)
         .build();
 
     // Create table in catalog and get the metadata location
-    let catalog = IcebergCatalog::new("PostgreSQL", ctx.file_io.clone());
+    let catalog = IcebergCatalog::new(ICEBERG_CATALOG_NAME, ctx.file_io.clone());
     let namespace = NamespaceIdent::new(nsp_name);
 
     let table = catalog.create_table(&namespace, creation)?;
@@ -122,3 +140,124 @@ pub fn init_table_storage_metadata(rel: &RelationHandle) -> IcebergResult<String
 
     Ok(metadata_location.to_string())
 }
+
+/// The Iceberg format version of the table options.
+fn iceberg_format_version(table_option: &IcebergTableOptionCache) -> FormatVersion {
+    match table_option.format_version {
+        1 => FormatVersion::V1,
+        3 => FormatVersion::V3,
+        _ => FormatVersion::V2,
+    }
+}
+
+/// Stage the changes of the table options of `rel` on its Iceberg table.
+///
+/// `changed` names the options set or reset by `ALTER TABLE`; the options
+/// are read from `rd_amcache`, which must have been invalidated. The table
+/// properties are set to the options' values, leaving other properties such
+/// as the field ids alone. A new format version upgrades the table and a new
+/// sort order replaces the default sort order.
+pub fn update_table_options(
+    rel: &RelationHandle,
+    changed: &[String],
+) -> IcebergResult<()> {
+    let table_option = AmCache::get::<IcebergTableOptionCache>(rel)?;
+    let table = load_iceberg_table(rel)?;
+    let metadata = table.metadata();
+    let mut tx = Transaction::new(&table);
+    let mut updated = false;
+
+    let properties: Vec<_> = table_option
+        .to_properties()
+        .into_iter()
+        .filter(|(key, value)| metadata.properties().get(key) != Some(value))
+        .collect();
+    if !properties.is_empty() {
+        let mut action = tx.update_table_properties();
+        for (key, value) in properties {
+            action = action.set(key, value);
+        }
+        tx = action.apply(tx)?;
+        updated = true;
+    }
+
+    let format_version = iceberg_format_version(table_option);
+    if changed.iter().any(|key| key == OPT_FORMAT_VERSION)
+        && format_version != metadata.format_version()
+    {
+        tx = tx
+            .upgrade_table_version()
+            .set_format_version(format_version)
+            .apply(tx)?;
+        updated = true;
+    }
+
+    if changed.iter().any(|key| key == OPT_SORT_ORDER) {
+        let value = table_option.sort_order().unwrap_or_default();
+        // Validate the terms against the schema like CREATE TABLE does
+        parse_sort_order(value, metadata.current_schema())?;
+
+        let mut action = tx.replace_sort_order();
+        for term in parse_sort_terms(value)? {
+            action = match term.direction {
+                SortDirection::Ascending => action.asc(&term.column, term.null_order),
+                SortDirection::Descending => {
+                    action.desc(&term.column, term.null_order)
+                }
+            };
+        }
+        tx = action.apply(tx)?;
+        updated = true;
+    }
+
+    if updated {
+        stage_transaction(rel.oid(), &table, tx)?;
+    }
+    Ok(())
+}
+
+/// Load the Iceberg table backing a relation.
+///
+/// Returns the table with the changes staged by the current transaction if
+/// there are any. Otherwise reads the current metadata location from
+/// `lakehouse.iceberg_metadata` and loads the table metadata from the
+/// relation's tablespace storage.
+pub fn load_iceberg_table(rel: &RelationHandle) -> IcebergResult<Table> {
+    if let Some(table) = staged_table(rel.oid()) {
+        return Ok(table);
+    }
+
+    let record = IcebergMetadata::get(rel.oid())?;
+    let metadata_location = record
+        .metadata_location
+        .ok_or(IcebergError::MetadataLocationNull)?;
+
+    let ctx = create_storage_context(rel.tablespace_oid())?;
+    let metadata = TableMetadata::read_from(&ctx.file_io, &metadata_location)?;
+
+    iceberg_table(rel, ctx.file_io, Some(metadata_location), metadata)
+}
+
+/// Build the Iceberg table of a relation from its metadata.
+pub fn iceberg_table(
+    rel: &RelationHandle,
+    file_io: FileIO,
+    metadata_location: Option<String>,
+    metadata: TableMetadata,
+) -> IcebergResult<Table> {
+    let nsp_name = PgWrapper::get_namespace_name(rel.namespace_oid())?
+        .ok_or(IcebergError::NamespaceNull)?;
+
+    let mut builder = Table::builder()
+        .file_io(file_io)
+        .metadata(metadata)
+        .identifier(TableIdent::new(
+            NamespaceIdent::new(nsp_name),
+            rel.relation_name(),
+        ));
+    if let Some(metadata_location) = metadata_location {
+        builder = builder.metadata_location(metadata_location);
+    }
+
+    Ok(builder.build()?)
+}
@@ -1,9 +1,11 @@
+use crate::catalog::IcebergMetadataError;
 use pg_tam::option::tablespace_cache::TablespaceCacheError;
 use pg_tam::option::TableOptionError;
 use pg_tam::pg_wrapper::PgWrapperError;
 use pg_tam::prelude::{CreateRuntimeError, TablespaceError};
 use pgrx::pg_sys::panic::ErrorReport;
 use pgrx::prelude::PgSqlErrorCode;
+use std::ffi::CStr;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -20,6 +22,9 @@ pub enum IcebergError {
     #[error("pg wrapper error: {0}")]
     PgWrapperError(#[from] PgWrapperError),
 
+    #[error("iceberg metadata error: {0}")]
+    MetadataError(#[from] IcebergMetadataError),
+
     #[error("tablespace options not found")]
     TablespaceNotFound,
 
@@ -77,8 +82,38 @@ pub enum IcebergError {
     #[error("{0}")]
     IoError(#[from] std::io::Error),
 
+    #[error("tuple identifier {0} does not identify an Iceberg row")]
+    InvalidTid(String),
+
+    #[error("invalid attribute to field id mapping \"{0}\"")]
+    InvalidFieldIds(String),
+
+    #[error("invalid partitioning \"{0}\": {1}")]
+    InvalidPartitioning(String, String),
+
+    #[error("invalid sort order \"{0}\": {1}")]
+    InvalidSortOrder(String, String),
+
+    #[error("invalid value for parameter {0:?}: {1}")]
+    InvalidTimeTravel(&'static CStr, String),
+
+    #[error("cannot modify table {0} while reading a past snapshot")]
+    TimeTravelModify(String),
+
+    #[error("could not serialize access due to concurrent update of table {0}")]
+    ConcurrentUpdate(String),
+
+    #[error("parallel scan of table {0} was not estimated")]
+    ParallelScanNotEstimated(String),
+
     #[error("feature not yet implemented: {0}")]
     NotImplemented(&'static str),
+
+    #[error("\"{0}\" is not an Iceberg table")]
+    NotIcebergTable(String),
+
+    #[error("permission denied for table {0}")]
+    PermissionDenied(String),
 }
 
 impl From<IcebergError> for ErrorReport {
@@ -89,7 +124,9 @@ impl From<IcebergError> for ErrorReport {
             | IcebergError::TableOptionError(_)
             | IcebergError::TablespaceNotFound => PgSqlErrorCode::ERRCODE_INVALID_PARAMETER_VALUE,
 
-            IcebergError::PgWrapperError(_) => PgSqlErrorCode::ERRCODE_INTERNAL_ERROR,
+            IcebergError::PgWrapperError(_) | IcebergError::MetadataError(_) => {
+                PgSqlErrorCode::ERRCODE_INTERNAL_ERROR
+            }
 
             IcebergError::NamespaceNull | IcebergError::MetadataLocationNull => {
                 PgSqlErrorCode::ERRCODE_UNDEFINED_OBJECT
@@ -118,7 +155,26 @@ impl From<IcebergError> for ErrorReport {
 
             IcebergError::IoError(_) => PgSqlErrorCode::ERRCODE_IO_ERROR,
 
+            IcebergError::InvalidTid(_) => PgSqlErrorCode::ERRCODE_INVALID_PARAMETER_VALUE,
+
+            IcebergError::InvalidFieldIds(_) => PgSqlErrorCode::ERRCODE_DATA_CORRUPTED,
+
+            IcebergError::InvalidPartitioning(_, _)
+            | IcebergError::InvalidSortOrder(_, _) => PgSqlErrorCode::ERRCODE_INVALID_PARAMETER_VALUE,
+
+            IcebergError::InvalidTimeTravel(_, _) => PgSqlErrorCode::ERRCODE_INVALID_PARAMETER_VALUE,
+
+            IcebergError::TimeTravelModify(_) => PgSqlErrorCode::ERRCODE_READ_ONLY_SQL_TRANSACTION,
+
+            IcebergError::ConcurrentUpdate(_) => PgSqlErrorCode::ERRCODE_T_R_SERIALIZATION_FAILURE,
+
+            IcebergError::ParallelScanNotEstimated(_) => PgSqlErrorCode::ERRCODE_INTERNAL_ERROR,
+
             IcebergError::NotImplemented(_) => PgSqlErrorCode::ERRCODE_FEATURE_NOT_SUPPORTED,
+
+            IcebergError::NotIcebergTable(_) => PgSqlErrorCode::ERRCODE_WRONG_OBJECT_TYPE,
+
+            IcebergError::PermissionDenied(_) => PgSqlErrorCode::ERRCODE_INSUFFICIENT_PRIVILEGE,
         };
         ErrorReport::new(error_code, format!("{value}"), "")
     }
@@ -0,0 +1,762 @@
+//! Metadata tables of Iceberg tables.
+//!
+//! Each function returns the rows of a metadata table of
+//! `iceberg_lite::inspect` as seen by the current transaction, so that tables
+//! can be inspected without external tools:
+//!
+//! ```sql
+//! SELECT snapshot_id, operation FROM lakehouse.snapshots('events'::regclass);
+//! ```
+//!
+//! As the metadata tables list the files and bounds of the column values,
+//! reading them requires the `SELECT` privilege on the table.
+//!
+//! The Arrow record batches of a metadata table are converted column by
+//! column through a [`MetadataRow`]: primitive columns into their PostgreSQL
+//! type, nested columns (maps, lists and structs) into `jsonb`. Exposing a new
+//! metadata table takes a function declaring its columns.
+
+use crate::access::convert::{arrow_value_to_cell, arrow_value_to_json};
+use crate::catalog::{check_table_privilege, is_iceberg_table, load_iceberg_table};
+use crate::error::{IcebergError, IcebergResult};
+use arrow_array::cast::AsArray;
+use arrow_array::{Array, RecordBatch};
+use iceberg_lite::inspect::MetadataTable;
+use iceberg_lite::scan::ArrowRecordBatchIterator;
+use pg_tam::diag::ReportableError;
+use pg_tam::handles::TableGuard;
+use pg_tam::prelude::Cell;
+use pgrx::JsonB;
+use pgrx::datum::TimestampWithTimeZone;
+use pgrx::prelude::*;
+
+/// Scan a metadata table of the Iceberg table `relid`.
+fn scan_metadata_table(
+    relid: pg_sys::Oid,
+    scan: impl FnOnce(&MetadataTable) -> iceberg_lite::Result<ArrowRecordBatchIterator>,
+) -> IcebergResult<Vec<RecordBatch>> {
+    let guard = TableGuard::open(relid, pg_sys::AccessShareLock as pg_sys::LOCKMODE)?;
+    let rel = guard.as_handle();
+    if !is_iceberg_table(&rel) {
+        return Err(IcebergError::NotIcebergTable(rel.relation_name()));
+    }
+    check_table_privilege(&rel, pg_sys::ACL_SELECT as pg_sys::AclMode)?;
+
+    let table = load_iceberg_table(&rel)?;
+    let batches = scan(&table.inspect())?;
+    Ok(batches.collect::<iceberg_lite::Result<_>>()?)
+}
+
+/// A row of the record batches of a metadata table.
+struct MetadataRow<'a> {
+    batch: &'a RecordBatch,
+    row: usize,
+}
+
+impl<'a> MetadataRow<'a> {
+    /// The rows of `batches`, in order.
+    fn rows(batches: &'a [RecordBatch]) -> impl Iterator<Item = MetadataRow<'a>> {
+        batches.iter().flat_map(|batch| {
+            (0..batch.num_rows()).map(|row| MetadataRow { batch, row })
+        })
+    }
+
+    /// Whether the metadata table has column `name`: the `partition` columns
+    /// are left out for unpartitioned tables.
+    fn has_column(&self, name: &str) -> bool {
+        self.batch.column_by_name(name).is_some()
+    }
+
+    fn column(&self, name: &str) -> IcebergResult<&'a dyn Array> {
+        self.batch
+            .column_by_name(name)
+            .map(|column| column.as_ref())
+            .ok_or_else(|| IcebergError::ColumnNotFound(name.to_string()))
+    }
+
+    /// The value of column `name` converted for PostgreSQL type `type_oid`.
+    fn cell(&self, name: &str, type_oid: pg_sys::Oid) -> IcebergResult<Option<Cell>> {
+        arrow_value_to_cell(self.column(name)?, self.row, type_oid, name)
+    }
+
+    fn mismatch(&self, name: &str) -> IcebergError {
+        let data_type = self
+            .column(name)
+            .map(|column| column.data_type().to_string());
+        IcebergError::IncompatibleColumnType(
+            name.to_string(),
+            data_type.unwrap_or_default(),
+        )
+    }
+
+    fn bool(&self, name: &str) -> IcebergResult<Option<bool>> {
+        match self.cell(name, pg_sys::BOOLOID)? {
+            Some(Cell::Bool(value)) => Ok(Some(value)),
+            None => Ok(None),
+            _ => Err(self.mismatch(name)),
+        }
+    }
+
+    fn int4(&self, name: &str) -> IcebergResult<Option<i32>> {
+        match self.cell(name, pg_sys::INT4OID)? {
+            Some(Cell::I32(value)) => Ok(Some(value)),
+            None => Ok(None),
+            _ => Err(self.mismatch(name)),
+        }
+    }
+
+    fn int8(&self, name: &str) -> IcebergResult<Option<i64>> {
+        match self.cell(name, pg_sys::INT8OID)? {
+            Some(Cell::I64(value)) => Ok(Some(value)),
+            None => Ok(None),
+            _ => Err(self.mismatch(name)),
+        }
+    }
+
+    fn text(&self, name: &str) -> IcebergResult<Option<String>> {
+        match self.cell(name, pg_sys::TEXTOID)? {
+            Some(Cell::String(value)) => Ok(Some(value)),
+            None => Ok(None),
+            _ => Err(self.mismatch(name)),
+        }
+    }
+
+    fn timestamptz(
+        &self,
+        name: &str,
+    ) -> IcebergResult<Option<TimestampWithTimeZone>> {
+        match self.cell(name, pg_sys::TIMESTAMPTZOID)? {
+            Some(Cell::Timestamptz(value)) => Ok(Some(value)),
+            None => Ok(None),
+            _ => Err(self.mismatch(name)),
+        }
+    }
+
+    fn bytea(&self, name: &str) -> IcebergResult<Option<Vec<u8>>> {
+        let column = self.column(name)?;
+        if column.is_null(self.row) {
+            return Ok(None);
+        }
+        let binary = column
+            .as_binary_opt::<i64>()
+            .ok_or_else(|| self.mismatch(name))?;
+        Ok(Some(binary.value(self.row).to_vec()))
+    }
+
+    /// The value of a column of a nested type.
+    fn jsonb(&self, name: &str) -> IcebergResult<Option<JsonB>> {
+        let column = self.column(name)?;
+        if column.is_null(self.row) {
+            return Ok(None);
+        }
+        Ok(Some(JsonB(arrow_value_to_json(column, self.row)?)))
+    }
+}
+
+/// The columns of the tables that list data and delete files.
+type FileRow = (
+    Option<i32>,
+    Option<String>,
+    Option<String>,
+    Option<i32>,
+    Option<JsonB>,
+    Option<i64>,
+    Option<i64>,
+    Option<JsonB>,
+    Option<JsonB>,
+    Option<JsonB>,
+    Option<JsonB>,
+    Option<JsonB>,
+    Option<JsonB>,
+    Option<Vec<u8>>,
+    Option<JsonB>,
+    Option<JsonB>,
+    Option<i32>,
+    Option<JsonB>,
+);
+
+/// The rows of a table that lists data and delete files.
+fn file_rows(
+    relid: pg_sys::Oid,
+    scan: impl FnOnce(&MetadataTable) -> iceberg_lite::Result<ArrowRecordBatchIterator>,
+) -> IcebergResult<Vec<FileRow>> {
+    let batches = scan_metadata_table(relid, scan)?;
+    MetadataRow::rows(&batches)
+        .map(|row| {
+            let partition = if row.has_column("partition") {
+                row.jsonb("partition")?
+            } else {
+                None
+            };
+            Ok((
+                row.int4("content")?,
+                row.text("file_path")?,
+                row.text("file_format")?,
+                row.int4("spec_id")?,
+                partition,
+                row.int8("record_count")?,
+                row.int8("file_size_in_bytes")?,
+                row.jsonb("column_sizes")?,
+                row.jsonb("value_counts")?,
+                row.jsonb("null_value_counts")?,
+                row.jsonb("nan_value_counts")?,
+                row.jsonb("lower_bounds")?,
+                row.jsonb("upper_bounds")?,
+                row.bytea("key_metadata")?,
+                row.jsonb("split_offsets")?,
+                row.jsonb("equality_ids")?,
+                row.int4("sort_order_id")?,
+                row.jsonb("readable_metrics")?,
+            ))
+        })
+        .collect()
+}
+
+/// The columns of the tables that list manifest entries.
+type EntryRow = (
+    Option<i32>,
+    Option<i64>,
+    Option<i64>,
+    Option<i64>,
+    Option<JsonB>,
+    Option<JsonB>,
+);
+
+/// The rows of a table that lists manifest entries.
+fn entry_rows(
+    relid: pg_sys::Oid,
+    scan: impl FnOnce(&MetadataTable) -> iceberg_lite::Result<ArrowRecordBatchIterator>,
+) -> IcebergResult<Vec<EntryRow>> {
+    let batches = scan_metadata_table(relid, scan)?;
+    MetadataRow::rows(&batches)
+        .map(|row| {
+            Ok((
+                row.int4("status")?,
+                row.int8("snapshot_id")?,
+                row.int8("sequence_number")?,
+                row.int8("file_sequence_number")?,
+                row.jsonb("data_file")?,
+                row.jsonb("readable_metrics")?,
+            ))
+        })
+        .collect()
+}
+
+/// The snapshots of an Iceberg table.
+#[pg_extern(schema = "lakehouse")]
+#[allow(clippy::type_complexity)]
+fn snapshots(
+    rel: PgRelation,
+) -> TableIterator<
+    'static,
+    (
+        name!(committed_at, Option<TimestampWithTimeZone>),
+        name!(snapshot_id, Option<i64>),
+        name!(parent_id, Option<i64>),
+        name!(operation, Option<String>),
+        name!(manifest_list, Option<String>),
+        name!(summary, Option<JsonB>),
+    ),
+> {
+    let rows = || -> IcebergResult<Vec<_>> {
+        let batches =
+            scan_metadata_table(rel.oid(), |table| table.snapshots().scan())?;
+        MetadataRow::rows(&batches)
+            .map(|row| {
+                Ok((
+                    row.timestamptz("committed_at")?,
+                    row.int8("snapshot_id")?,
+                    row.int8("parent_id")?,
+                    row.text("operation")?,
+                    row.text("manifest_list")?,
+                    row.jsonb("summary")?,
+                ))
+            })
+            .collect()
+    };
+    TableIterator::new(rows().report_unwrap().into_iter())
+}
+
+/// The manifests of the current snapshot of an Iceberg table.
+#[pg_extern(schema = "lakehouse")]
+#[allow(clippy::type_complexity)]
+fn manifests(
+    rel: PgRelation,
+) -> TableIterator<
+    'static,
+    (
+        name!(content, Option<i32>),
+        name!(path, Option<String>),
+        name!(length, Option<i64>),
+        name!(partition_spec_id, Option<i32>),
+        name!(added_snapshot_id, Option<i64>),
+        name!(added_data_files_count, Option<i32>),
+        name!(existing_data_files_count, Option<i32>),
+        name!(deleted_data_files_count, Option<i32>),
+        name!(added_delete_files_count, Option<i32>),
+        name!(existing_delete_files_count, Option<i32>),
+        name!(deleted_delete_files_count, Option<i32>),
+        name!(partition_summaries, Option<JsonB>),
+    ),
+> {
+    let rows = || -> IcebergResult<Vec<_>> {
+        let batches =
+            scan_metadata_table(rel.oid(), |table| table.manifests().scan())?;
+        MetadataRow::rows(&batches)
+            .map(|row| {
+                Ok((
+                    row.int4("content")?,
+                    row.text("path")?,
+                    row.int8("length")?,
+                    row.int4("partition_spec_id")?,
+                    row.int8("added_snapshot_id")?,
+                    row.int4("added_data_files_count")?,
+                    row.int4("existing_data_files_count")?,
+                    row.int4("deleted_data_files_count")?,
+                    row.int4("added_delete_files_count")?,
+                    row.int4("existing_delete_files_count")?,
+                    row.int4("deleted_delete_files_count")?,
+                    row.jsonb("partition_summaries")?,
+                ))
+            })
+            .collect()
+    };
+    TableIterator::new(rows().report_unwrap().into_iter())
+}
+
+/// The live data and delete files of the current snapshot of an Iceberg table.
+#[pg_extern(schema = "lakehouse")]
+#[allow(clippy::type_complexity)]
+fn files(
+    rel: PgRelation,
+) -> TableIterator<
+    'static,
+    (
+        name!(content, Option<i32>),
+        name!(file_path, Option<String>),
+        name!(file_format, Option<String>),
+        name!(spec_id, Option<i32>),
+        name!(partition, Option<JsonB>),
+        name!(record_count, Option<i64>),
+        name!(file_size_in_bytes, Option<i64>),
+        name!(column_sizes, Option<JsonB>),
+        name!(value_counts, Option<JsonB>),
+        name!(null_value_counts, Option<JsonB>),
+        name!(nan_value_counts, Option<JsonB>),
+        name!(lower_bounds, Option<JsonB>),
+        name!(upper_bounds, Option<JsonB>),
+        name!(key_metadata, Option<Vec<u8>>),
+        name!(split_offsets, Option<JsonB>),
+        name!(equality_ids, Option<JsonB>),
+        name!(sort_order_id, Option<i32>),
+        name!(readable_metrics, Option<JsonB>),
+    ),
+> {
+    let rows = file_rows(rel.oid(), |table| table.files().scan());
+    TableIterator::new(rows.report_unwrap().into_iter())
+}
+
+/// The live data files of the current snapshot of an Iceberg table.
+#[pg_extern(schema = "lakehouse")]
+#[allow(clippy::type_complexity)]
+fn data_files(
+    rel: PgRelation,
+) -> TableIterator<
+    'static,
+    (
+        name!(content, Option<i32>),
+        name!(file_path, Option<String>),
+        name!(file_format, Option<String>),
+        name!(spec_id, Option<i32>),
+        name!(partition, Option<JsonB>),
+        name!(record_count, Option<i64>),
+        name!(file_size_in_bytes, Option<i64>),
+        name!(column_sizes, Option<JsonB>),
+        name!(value_counts, Option<JsonB>),
+        name!(null_value_counts, Option<JsonB>),
+        name!(nan_value_counts, Option<JsonB>),
+        name!(lower_bounds, Option<JsonB>),
+        name!(upper_bounds, Option<JsonB>),
+        name!(key_metadata, Option<Vec<u8>>),
+        name!(split_offsets, Option<JsonB>),
+        name!(equality_ids, Option<JsonB>),
+        name!(sort_order_id, Option<i32>),
+        name!(readable_metrics, Option<JsonB>),
+    ),
+> {
+    let rows = file_rows(rel.oid(), |table| table.data_files().scan());
+    TableIterator::new(rows.report_unwrap().into_iter())
+}
+
+/// The live delete files of the current snapshot of an Iceberg table.
+#[pg_extern(schema = "lakehouse")]
+#[allow(clippy::type_complexity)]
+fn delete_files(
+    rel: PgRelation,
+) -> TableIterator<
+    'static,
+    (
+        name!(content, Option<i32>),
+        name!(file_path, Option<String>),
+        name!(file_format, Option<String>),
+        name!(spec_id, Option<i32>),
+        name!(partition, Option<JsonB>),
+        name!(record_count, Option<i64>),
+        name!(file_size_in_bytes, Option<i64>),
+        name!(column_sizes, Option<JsonB>),
+        name!(value_counts, Option<JsonB>),
+        name!(null_value_counts, Option<JsonB>),
+        name!(nan_value_counts, Option<JsonB>),
+        name!(lower_bounds, Option<JsonB>),
+        name!(upper_bounds, Option<JsonB>),
+        name!(key_metadata, Option<Vec<u8>>),
+        name!(split_offsets, Option<JsonB>),
+        name!(equality_ids, Option<JsonB>),
+        name!(sort_order_id, Option<i32>),
+        name!(readable_metrics, Option<JsonB>),
+    ),
+> {
+    let rows = file_rows(rel.oid(), |table| table.delete_files().scan());
+    TableIterator::new(rows.report_unwrap().into_iter())
+}
+
+/// The manifest entries of the current snapshot of an Iceberg table, deleted
+/// entries included.
+#[pg_extern(schema = "lakehouse")]
+#[allow(clippy::type_complexity)]
+fn entries(
+    rel: PgRelation,
+) -> TableIterator<
+    'static,
+    (
+        name!(status, Option<i32>),
+        name!(snapshot_id, Option<i64>),
+        name!(sequence_number, Option<i64>),
+        name!(file_sequence_number, Option<i64>),
+        name!(data_file, Option<JsonB>),
+        name!(readable_metrics, Option<JsonB>),
+    ),
+> {
+    let rows = entry_rows(rel.oid(), |table| table.entries().scan());
+    TableIterator::new(rows.report_unwrap().into_iter())
+}
+
+/// The snapshots that were current for an Iceberg table, in order.
+#[pg_extern(schema = "lakehouse")]
+#[allow(clippy::type_complexity)]
+fn history(
+    rel: PgRelation,
+) -> TableIterator<
+    'static,
+    (
+        name!(made_current_at, Option<TimestampWithTimeZone>),
+        name!(snapshot_id, Option<i64>),
+        name!(parent_id, Option<i64>),
+        name!(is_current_ancestor, Option<bool>),
+    ),
+> {
+    let rows = || -> IcebergResult<Vec<_>> {
+        let batches = scan_metadata_table(rel.oid(), |table| table.history().scan())?;
+        MetadataRow::rows(&batches)
+            .map(|row| {
+                Ok((
+                    row.timestamptz("made_current_at")?,
+                    row.int8("snapshot_id")?,
+                    row.int8("parent_id")?,
+                    row.bool("is_current_ancestor")?,
+                ))
+            })
+            .collect()
+    };
+    TableIterator::new(rows().report_unwrap().into_iter())
+}
+
+/// The branches and tags of an Iceberg table, the `type` column of the
+/// metadata table being named `ref_type`.
+#[pg_extern(schema = "lakehouse")]
+#[allow(clippy::type_complexity)]
+fn refs(
+    rel: PgRelation,
+) -> TableIterator<
+    'static,
+    (
+        name!(name, Option<String>),
+        name!(ref_type, Option<String>),
+        name!(snapshot_id, Option<i64>),
+        name!(max_reference_age_in_ms, Option<i64>),
+        name!(min_snapshots_to_keep, Option<i32>),
+        name!(max_snapshot_age_in_ms, Option<i64>),
+    ),
+> {
+    let rows = || -> IcebergResult<Vec<_>> {
+        let batches = scan_metadata_table(rel.oid(), |table| table.refs().scan())?;
+        MetadataRow::rows(&batches)
+            .map(|row| {
+                Ok((
+                    row.text("name")?,
+                    row.text("type")?,
+                    row.int8("snapshot_id")?,
+                    row.int8("max_reference_age_in_ms")?,
+                    row.int4("min_snapshots_to_keep")?,
+                    row.int8("max_snapshot_age_in_ms")?,
+                ))
+            })
+            .collect()
+    };
+    TableIterator::new(rows().report_unwrap().into_iter())
+}
+
+/// The live files of the current snapshot of an Iceberg table, aggregated by
+/// partition.
+#[pg_extern(schema = "lakehouse")]
+#[allow(clippy::type_complexity)]
+fn partitions(
+    rel: PgRelation,
+) -> TableIterator<
+    'static,
+    (
+        name!(partition, Option<JsonB>),
+        name!(spec_id, Option<i32>),
+        name!(record_count, Option<i64>),
+        name!(file_count, Option<i32>),
+        name!(total_data_file_size_in_bytes, Option<i64>),
+        name!(position_delete_record_count, Option<i64>),
+        name!(position_delete_file_count, Option<i32>),
+        name!(equality_delete_record_count, Option<i64>),
+        name!(equality_delete_file_count, Option<i32>),
+        name!(last_updated_at, Option<TimestampWithTimeZone>),
+        name!(last_updated_snapshot_id, Option<i64>),
+    ),
+> {
+    let rows = || -> IcebergResult<Vec<_>> {
+        let batches =
+            scan_metadata_table(rel.oid(), |table| table.partitions().scan())?;
+        MetadataRow::rows(&batches)
+            .map(|row| {
+                let (partition, spec_id) = if row.has_column("partition") {
+                    (row.jsonb("partition")?, row.int4("spec_id")?)
+                } else {
+                    (None, None)
+                };
+                Ok((
+                    partition,
+                    spec_id,
+                    row.int8("record_count")?,
+                    row.int4("file_count")?,
+                    row.int8("total_data_file_size_in_bytes")?,
+                    row.int8("position_delete_record_count")?,
+                    row.int4("position_delete_file_count")?,
+                    row.int8("equality_delete_record_count")?,
+                    row.int4("equality_delete_file_count")?,
+                    row.timestamptz("last_updated_at")?,
+                    row.int8("last_updated_snapshot_id")?,
+                ))
+            })
+            .collect()
+    };
+    TableIterator::new(rows().report_unwrap().into_iter())
+}
+
+/// The metadata files of an Iceberg table, the current one last.
+#[pg_extern(schema = "lakehouse")]
+#[allow(clippy::type_complexity)]
+fn metadata_log_entries(
+    rel: PgRelation,
+) -> TableIterator<
+    'static,
+    (
+        name!(timestamp, Option<TimestampWithTimeZone>),
+        name!(file, Option<String>),
+        name!(latest_snapshot_id, Option<i64>),
+        name!(latest_schema_id, Option<i32>),
+        name!(latest_sequence_number, Option<i64>),
+    ),
+> {
+    let rows = || -> IcebergResult<Vec<_>> {
+        let batches = scan_metadata_table(rel.oid(), |table| {
+            table.metadata_log_entries().scan()
+        })?;
+        MetadataRow::rows(&batches)
+            .map(|row| {
+                Ok((
+                    row.timestamptz("timestamp")?,
+                    row.text("file")?,
+                    row.int8("latest_snapshot_id")?,
+                    row.int4("latest_schema_id")?,
+                    row.int8("latest_sequence_number")?,
+                ))
+            })
+            .collect()
+    };
+    TableIterator::new(rows().report_unwrap().into_iter())
+}
+
+/// The manifests of all the snapshots of an Iceberg table, a manifest shared
+/// between snapshots being listed once for each of them.
+#[pg_extern(schema = "lakehouse")]
+#[allow(clippy::type_complexity)]
+fn all_manifests(
+    rel: PgRelation,
+) -> TableIterator<
+    'static,
+    (
+        name!(content, Option<i32>),
+        name!(path, Option<String>),
+        name!(length, Option<i64>),
+        name!(partition_spec_id, Option<i32>),
+        name!(added_snapshot_id, Option<i64>),
+        name!(added_data_files_count, Option<i32>),
+        name!(existing_data_files_count, Option<i32>),
+        name!(deleted_data_files_count, Option<i32>),
+        name!(added_delete_files_count, Option<i32>),
+        name!(existing_delete_files_count, Option<i32>),
+        name!(deleted_delete_files_count, Option<i32>),
+        name!(partition_summaries, Option<JsonB>),
+        name!(reference_snapshot_id, Option<i64>),
+    ),
+> {
+    let rows = || -> IcebergResult<Vec<_>> {
+        let batches =
+            scan_metadata_table(rel.oid(), |table| table.all_manifests().scan())?;
+        MetadataRow::rows(&batches)
+            .map(|row| {
+                Ok((
+                    row.int4("content")?,
+                    row.text("path")?,
+                    row.int8("length")?,
+                    row.int4("partition_spec_id")?,
+                    row.int8("added_snapshot_id")?,
+                    row.int4("added_data_files_count")?,
+                    row.int4("existing_data_files_count")?,
+                    row.int4("deleted_data_files_count")?,
+                    row.int4("added_delete_files_count")?,
+                    row.int4("existing_delete_files_count")?,
+                    row.int4("deleted_delete_files_count")?,
+                    row.jsonb("partition_summaries")?,
+                    row.int8("reference_snapshot_id")?,
+                ))
+            })
+            .collect()
+    };
+    TableIterator::new(rows().report_unwrap().into_iter())
+}
+
+/// The live data and delete files of the manifests of all the snapshots of an
+/// Iceberg table.
+#[pg_extern(schema = "lakehouse")]
+#[allow(clippy::type_complexity)]
+fn all_files(
+    rel: PgRelation,
+) -> TableIterator<
+    'static,
+    (
+        name!(content, Option<i32>),
+        name!(file_path, Option<String>),
+        name!(file_format, Option<String>),
+        name!(spec_id, Option<i32>),
+        name!(partition, Option<JsonB>),
+        name!(record_count, Option<i64>),
+        name!(file_size_in_bytes, Option<i64>),
+        name!(column_sizes, Option<JsonB>),
+        name!(value_counts, Option<JsonB>),
+        name!(null_value_counts, Option<JsonB>),
+        name!(nan_value_counts, Option<JsonB>),
+        name!(lower_bounds, Option<JsonB>),
+        name!(upper_bounds, Option<JsonB>),
+        name!(key_metadata, Option<Vec<u8>>),
+        name!(split_offsets, Option<JsonB>),
+        name!(equality_ids, Option<JsonB>),
+        name!(sort_order_id, Option<i32>),
+        name!(readable_metrics, Option<JsonB>),
+    ),
+> {
+    let rows = file_rows(rel.oid(), |table| table.all_files().scan());
+    TableIterator::new(rows.report_unwrap().into_iter())
+}
+
+/// The live data files of the manifests of all the snapshots of an Iceberg
+/// table.
+#[pg_extern(schema = "lakehouse")]
+#[allow(clippy::type_complexity)]
+fn all_data_files(
+    rel: PgRelation,
+) -> TableIterator<
+    'static,
+    (
+        name!(content, Option<i32>),
+        name!(file_path, Option<String>),
+        name!(file_format, Option<String>),
+        name!(spec_id, Option<i32>),
+        name!(partition, Option<JsonB>),
+        name!(record_count, Option<i64>),
+        name!(file_size_in_bytes, Option<i64>),
+        name!(column_sizes, Option<JsonB>),
+        name!(value_counts, Option<JsonB>),
+        name!(null_value_counts, Option<JsonB>),
+        name!(nan_value_counts, Option<JsonB>),
+        name!(lower_bounds, Option<JsonB>),
+        name!(upper_bounds, Option<JsonB>),
+        name!(key_metadata, Option<Vec<u8>>),
+        name!(split_offsets, Option<JsonB>),
+        name!(equality_ids, Option<JsonB>),
+        name!(sort_order_id, Option<i32>),
+        name!(readable_metrics, Option<JsonB>),
+    ),
+> {
+    let rows = file_rows(rel.oid(), |table| table.all_data_files().scan());
+    TableIterator::new(rows.report_unwrap().into_iter())
+}
+
+/// The live delete files of the manifests of all the snapshots of an Iceberg
+/// table.
+#[pg_extern(schema = "lakehouse")]
+#[allow(clippy::type_complexity)]
+fn all_delete_files(
+    rel: PgRelation,
+) -> TableIterator<
+    'static,
+    (
+        name!(content, Option<i32>),
+        name!(file_path, Option<String>),
+        name!(file_format, Option<String>),
+        name!(spec_id, Option<i32>),
+        name!(partition, Option<JsonB>),
+        name!(record_count, Option<i64>),
+        name!(file_size_in_bytes, Option<i64>),
+        name!(column_sizes, Option<JsonB>),
+        name!(value_counts, Option<JsonB>),
+        name!(null_value_counts, Option<JsonB>),
+        name!(nan_value_counts, Option<JsonB>),
+        name!(lower_bounds, Option<JsonB>),
+        name!(upper_bounds, Option<JsonB>),
+        name!(key_metadata, Option<Vec<u8>>),
+        name!(split_offsets, Option<JsonB>),
+        name!(equality_ids, Option<JsonB>),
+        name!(sort_order_id, Option<i32>),
+        name!(readable_metrics, Option<JsonB>),
+    ),
+> {
+    let rows = file_rows(rel.oid(), |table| table.all_delete_files().scan());
+    TableIterator::new(rows.report_unwrap().into_iter())
+}
+
+/// The manifest entries of all the snapshots of an Iceberg table, deleted
+/// entries included.
+#[pg_extern(schema = "lakehouse")]
+#[allow(clippy::type_complexity)]
+fn all_entries(
+    rel: PgRelation,
+) -> TableIterator<
+    'static,
+    (
+        name!(status, Option<i32>),
+        name!(snapshot_id, Option<i64>),
+        name!(sequence_number, Option<i64>),
+        name!(file_sequence_number, Option<i64>),
+        name!(data_file, Option<JsonB>),
+        name!(readable_metrics, Option<JsonB>),
+    ),
+> {
+    let rows = entry_rows(rel.oid(), |table| table.all_entries().scan());
+    TableIterator::new(rows.report_unwrap().into_iter())
+}
@@ -0,0 +1,4 @@
+//! SQL functions of the extension, created in the `lakehouse` schema.
+
+pub mod metadata_tables;
+pub mod statistics;
@@ -0,0 +1,69 @@
+//! Table statistics for other engines reading Iceberg tables.
+//!
+//! PostgreSQL keeps the statistics gathered by ANALYZE in its own catalog.
+//! Other engines read the statistics files registered in the table metadata
+//! instead, so [`compute_statistics`] writes the NDV sketches of the current
+//! snapshot to a Puffin file and registers it. Like other changes, the file is
+//! registered when the transaction commits and removed when it aborts.
+//!
+//! Like ANALYZE, computing statistics requires the `MAINTAIN` privilege on
+//! the table, ownership of the table or database before PostgreSQL 17.
+
+use crate::access::commit::stage_transaction;
+use crate::access::pending_deletes::register_files_pending_delete;
+use crate::catalog::{check_table_maintain, is_iceberg_table, load_iceberg_table};
+use crate::error::{IcebergError, IcebergResult};
+use iceberg_lite::puffin::write_ndv_statistics;
+use iceberg_lite::transaction::{ApplyTransactionAction, Transaction};
+use pg_tam::diag::ReportableError;
+use pg_tam::handles::TableGuard;
+use pgrx::prelude::*;
+use uuid::Uuid;
+
+/// Compute the number of distinct values of the columns of the current
+/// snapshot of an Iceberg table and register them as its statistics file.
+///
+/// Returns the location of the statistics file, NULL when the table has no
+/// snapshot.
+#[pg_extern(schema = "lakehouse")]
+fn compute_statistics(rel: PgRelation) -> Option<String> {
+    compute_table_statistics(rel.oid()).report_unwrap()
+}
+
+fn compute_table_statistics(relid: pg_sys::Oid) -> IcebergResult<Option<String>> {
+    // Conflicts with concurrent ANALYZE and DDL, like ANALYZE does
+    let guard = TableGuard::open(
+        relid,
+        pg_sys::ShareUpdateExclusiveLock as pg_sys::LOCKMODE,
+    )?;
+    let rel = guard.as_handle();
+    if !is_iceberg_table(&rel) {
+        return Err(IcebergError::NotIcebergTable(rel.relation_name()));
+    }
+    check_table_maintain(&rel)?;
+
+    let table = load_iceberg_table(&rel)?;
+    let Some(snapshot) = table.metadata().current_snapshot() else {
+        return Ok(None);
+    };
+    let snapshot_id = snapshot.snapshot_id();
+
+    let location = format!(
+        "{}/metadata/{}-{}.stats",
+        table.metadata().location(),
+        snapshot_id,
+        Uuid::now_v7()
+    );
+    let output_file = table.file_io().new_output(&location)?;
+    register_files_pending_delete(vec![location.clone()], table.file_io().clone());
+    let statistics = write_ndv_statistics(&table, snapshot_id, &output_file)?;
+
+    let tx = Transaction::new(&table);
+    let tx = tx
+        .update_statistics()
+        .set_statistics(statistics)
+        .apply(tx)?;
+    stage_transaction(relid, &table, tx)?;
+
+    Ok(Some(location))
+}
@@ -0,0 +1,388 @@
+//! Schema evolution of Iceberg tables.
+//!
+//! `ALTER TABLE` changes the columns of the relation in the PostgreSQL
+//! catalog. Afterwards the relation's attributes are compared with the current
+//! Iceberg schema and the differences are staged as a schema update, which is
+//! committed with the transaction, see `access::commit`:
+//!
+//! - Added columns are added as optional columns.
+//! - Dropped columns are deleted.
+//! - Renamed columns are renamed.
+//! - Type changes must widen the type, such as raising the precision of a
+//!   `numeric` column. PostgreSQL rewrites the table for type changes that
+//!   convert the stored values, such as `integer` to `bigint`, which Iceberg
+//!   tables cannot do, so they are refused.
+//! - `DROP NOT NULL` makes the column optional. Iceberg cannot make optional
+//!   columns required, so `SET NOT NULL` is only enforced by PostgreSQL.
+//!
+//! The attributes are matched with their fields by `FieldIds`, which are
+//! updated with the schema. Columns with a default are refused, as existing
+//! rows would read as NULL.
+//!
+//! `ALTER TABLE ... SET/RESET (...)` changes Iceberg table options. They are
+//! removed from the commands before PostgreSQL validates them as reloptions,
+//! afterwards they are persisted to `lakehouse.table_options` and staged on
+//! the Iceberg table, see `hooks::table_options`.
+
+use super::table_options::{alters_options, apply_alter_options, hide_alter_options};
+use crate::access::commit::stage_transaction;
+use crate::catalog::{
+    FIELD_IDS_PROPERTY, FieldIds, IcebergMetadata, is_iceberg_table,
+    load_iceberg_table, pg_type_to_iceberg_type,
+};
+use crate::error::{IcebergError, IcebergResult};
+use iceberg_lite::transaction::{ApplyTransactionAction, Transaction};
+use pg_tam::handles::{RelationHandle, TableGuard};
+use pg_tam::pg_wrapper::PgWrapper;
+use pg_tam::prelude::*;
+use pgrx::pg_sys;
+use std::ffi::CStr;
+
+struct IcebergAlterTableHook;
+
+/// The pointers stored in `list`.
+pub(crate) unsafe fn list_pointers<T>(list: *mut pg_sys::List) -> Vec<*mut T> {
+    unsafe {
+        if list.is_null() {
+            return Vec::new();
+        }
+        let cells =
+            std::slice::from_raw_parts((*list).elements, (*list).length as usize);
+        cells.iter().map(|cell| cell.ptr_value as *mut T).collect()
+    }
+}
+
+/// Check if an `ADD COLUMN` command gives existing rows a value.
+fn adds_column_with_default(cmd: &pg_sys::AlterTableCmd) -> bool {
+    if cmd.subtype != pg_sys::AlterTableType::AT_AddColumn || cmd.def.is_null() {
+        return false;
+    }
+
+    unsafe {
+        let def = cmd.def as *mut pg_sys::ColumnDef;
+        if !(*def).raw_default.is_null() || !(*def).cooked_default.is_null() {
+            return true;
+        }
+        list_pointers::<pg_sys::Constraint>((*def).constraints)
+            .into_iter()
+            .any(|constraint| {
+                matches!(
+                    (*constraint).contype,
+                    pg_sys::ConstrType::CONSTR_DEFAULT
+                        | pg_sys::ConstrType::CONSTR_IDENTITY
+                        | pg_sys::ConstrType::CONSTR_GENERATED
+                )
+            })
+    }
+}
+
+/// Check if an `ALTER COLUMN TYPE` command converts the stored values of the
+/// column, which makes PostgreSQL rewrite the table, and return the old and
+/// new type names if so.
+///
+/// Like `ATColumnChangeRequiresRewrite`, the values are kept when the
+/// simplified coercion of the column to the new type leaves the column as is,
+/// such as raising the precision of a `numeric` column.
+fn column_type_change_rewrites(
+    rel: &RelationHandle,
+    cmd: &pg_sys::AlterTableCmd,
+) -> Option<(String, String, String)> {
+    if cmd.subtype != pg_sys::AlterTableType::AT_AlterColumnType
+        || cmd.def.is_null()
+        || cmd.name.is_null()
+    {
+        return None;
+    }
+
+    unsafe {
+        let colname = CStr::from_ptr(cmd.name).to_string_lossy().to_string();
+        let tup_desc = (*rel.as_raw()).rd_att;
+        let natts = (*tup_desc).natts as usize;
+        let attrs = std::slice::from_raw_parts((*tup_desc).attrs.as_ptr(), natts);
+        // PostgreSQL reports unknown columns itself
+        let attr = attrs.iter().find(|attr| {
+            !attr.attisdropped
+                && CStr::from_ptr(attr.attname.data.as_ptr()).to_string_lossy()
+                    == colname
+        })?;
+
+        let def = cmd.def as *mut pg_sys::ColumnDef;
+        let mut target_type = pg_sys::InvalidOid;
+        let mut target_typmod = -1;
+        pg_sys::typenameTypeIdAndMod(
+            std::ptr::null_mut(),
+            (*def).typeName,
+            &mut target_type,
+            &mut target_typmod,
+        );
+        let type_names = || {
+            let name = |typid, typmod| {
+                let name = pg_sys::format_type_with_typemod(typid, typmod);
+                CStr::from_ptr(name).to_string_lossy().to_string()
+            };
+            (
+                colname.clone(),
+                name(attr.atttypid, attr.atttypmod),
+                name(target_type, target_typmod),
+            )
+        };
+
+        // A USING expression computes new values
+        if !(*def).raw_default.is_null() {
+            return Some(type_names());
+        }
+
+        let var = pg_sys::makeVar(
+            1,
+            attr.attnum,
+            attr.atttypid,
+            attr.atttypmod,
+            attr.attcollation,
+            0,
+        );
+        let transform = pg_sys::coerce_to_target_type(
+            std::ptr::null_mut(),
+            var as *mut pg_sys::Node,
+            attr.atttypid,
+            target_type,
+            target_typmod,
+            pg_sys::CoercionContext::COERCION_ASSIGNMENT,
+            pg_sys::CoercionForm::COERCE_IMPLICIT_CAST,
+            -1,
+        );
+        // PostgreSQL reports types without a coercion itself
+        if transform.is_null() {
+            return None;
+        }
+
+        let mut expr = pg_sys::expression_planner(transform as *mut pg_sys::Expr)
+            as *mut pg_sys::Node;
+        while (*expr).type_ == pg_sys::NodeTag::T_RelabelType {
+            expr = (*(expr as *mut pg_sys::RelabelType)).arg as *mut pg_sys::Node;
+        }
+        if (*expr).type_ == pg_sys::NodeTag::T_Var
+            && (*(expr as *mut pg_sys::Var)).varattno == attr.attnum
+        {
+            return None;
+        }
+        Some(type_names())
+    }
+}
+
+/// Open the relation of an `ALTER TABLE` statement if it is an Iceberg table.
+fn open_iceberg_table(
+    relation: *mut pg_sys::RangeVar,
+    lockmode: pg_sys::LOCKMODE,
+) -> Result<Option<TableGuard<'static>>, UtilityHookError> {
+    let oid =
+        PgWrapper::range_var_get_relid(relation, lockmode, true).map_err(|e| {
+            UtilityHookError::Message(format!(
+                "table: failed to get table OID - {}",
+                e
+            ))
+        })?;
+    if oid == pg_sys::InvalidOid {
+        return Ok(None);
+    }
+
+    let guard = TableGuard::open(oid, lockmode).map_err(|e| {
+        UtilityHookError::Message(format!("table: failed to open table - {}", e))
+    })?;
+    Ok(is_iceberg_table(&guard.as_handle()).then_some(guard))
+}
+
+/// Stage a schema update making the Iceberg schema match the attributes of
+/// `rel`, along with the field ids of its attributes.
+fn evolve_schema(rel: &RelationHandle) -> IcebergResult<()> {
+    // Nested statements of CREATE TABLE run before the table is created
+    if !IcebergMetadata::exists(rel.oid())? {
+        return Ok(());
+    }
+
+    let table = load_iceberg_table(rel)?;
+    let field_ids = FieldIds::from_table(&table)?;
+    let fields = table.metadata().current_schema().as_struct();
+    let tx = Transaction::new(&table);
+    let mut action = tx.update_schema();
+    let mut changed = false;
+    let tup_desc = unsafe { (*rel.as_raw()).rd_att };
+
+    unsafe {
+        let natts = (*tup_desc).natts as usize;
+        let attrs = std::slice::from_raw_parts((*tup_desc).attrs.as_ptr(), natts);
+
+        for (i, attr) in attrs.iter().enumerate() {
+            let name = CStr::from_ptr(attr.attname.data.as_ptr())
+                .to_string_lossy()
+                .to_string();
+            let field = field_ids
+                .field_id((i + 1) as pg_sys::AttrNumber)
+                .and_then(|field_id| fields.field_by_id(field_id));
+
+            let Some(field) = field else {
+                if attr.attisdropped {
+                    continue;
+                }
+                let field_type =
+                    pg_type_to_iceberg_type(attr.atttypid, attr.atttypmod)?;
+                action = action.add_column(&name, field_type);
+                changed = true;
+                continue;
+            };
+
+            if attr.attisdropped {
+                action = action.delete_column(&field.name);
+                changed = true;
+                continue;
+            }
+            if field.name != name {
+                action = action.rename_column(&field.name, &name);
+                changed = true;
+            }
+            let field_type = pg_type_to_iceberg_type(attr.atttypid, attr.atttypmod)?;
+            if *field.field_type != field_type {
+                let Some(primitive_type) = field_type.as_primitive_type() else {
+                    return Err(IcebergError::IncompatibleColumnType(
+                        name,
+                        field_type.to_string(),
+                    ));
+                };
+                action = action.update_column(&field.name, primitive_type.clone());
+                changed = true;
+            }
+            if field.required && !attr.attnotnull {
+                action = action.make_column_optional(&field.name);
+                changed = true;
+            }
+        }
+    }
+
+    if !changed {
+        return Ok(());
+    }
+    let tx = action.apply(tx)?;
+    let table = stage_transaction(rel.oid(), &table, tx)?;
+
+    // Added columns get their field ids when the schema update is staged
+    let new_field_ids = unsafe {
+        FieldIds::from_schema(tup_desc, table.metadata().current_schema())?
+    };
+    if new_field_ids != field_ids {
+        let tx = Transaction::new(&table);
+        let tx = tx
+            .update_table_properties()
+            .set(FIELD_IDS_PROPERTY.to_string(), new_field_ids.to_property())
+            .apply(tx)?;
+        stage_transaction(rel.oid(), &table, tx)?;
+    }
+    Ok(())
+}
+
+impl UtilityHook for IcebergAlterTableHook {
+    fn on_pre(&self, context: &mut UtilityNode) -> Result<(), UtilityHookError> {
+        let Some(stmt) = context
+            .is_a_mut::<pg_sys::AlterTableStmt>(pg_sys::NodeTag::T_AlterTableStmt)
+        else {
+            return Ok(());
+        };
+
+        let cmds = unsafe { list_pointers::<pg_sys::AlterTableCmd>(stmt.cmds) };
+        let alters_options = alters_options(stmt);
+        if !alters_options
+            && !cmds.iter().any(|cmd| unsafe {
+                adds_column_with_default(&**cmd)
+                    || (**cmd).subtype == pg_sys::AlterTableType::AT_AlterColumnType
+            })
+        {
+            return Ok(());
+        }
+
+        // Take the lock ALTER TABLE takes, to not upgrade it later
+        let lockmode = unsafe { pg_sys::AlterTableGetLockLevel(stmt.cmds) };
+        let Some(guard) = open_iceberg_table(stmt.relation, lockmode)? else {
+            return Ok(());
+        };
+
+        for cmd in cmds {
+            let cmd = unsafe { &*cmd };
+            if adds_column_with_default(cmd) {
+                let colname = unsafe {
+                    let def = cmd.def as *mut pg_sys::ColumnDef;
+                    CStr::from_ptr((*def).colname).to_string_lossy().to_string()
+                };
+                return Err(UtilityHookError::Message(format!(
+                    "table: cannot add column '{}' with a default value to an iceberg table",
+                    colname
+                )));
+            }
+            if let Some((colname, old_type, new_type)) =
+                column_type_change_rewrites(&guard.as_handle(), cmd)
+            {
+                return Err(UtilityHookError::Message(format!(
+                    "table: cannot change the type of column '{}' of an iceberg table from {} to {}, as it would rewrite the table",
+                    colname, old_type, new_type
+                )));
+            }
+        }
+
+        // Validate the options and hide them from PostgreSQL
+        if alters_options {
+            hide_alter_options(stmt)?;
+        }
+        Ok(())
+    }
+
+    fn on_post(&self, context: &mut UtilityNode) -> Result<(), UtilityHookError> {
+        let relation = if let Some(stmt) =
+            context.is_a::<pg_sys::AlterTableStmt>(pg_sys::NodeTag::T_AlterTableStmt)
+        {
+            if stmt.objtype != pg_sys::ObjectType::OBJECT_TABLE {
+                return Ok(());
+            }
+            stmt.relation
+        } else if let Some(stmt) =
+            context.is_a::<pg_sys::RenameStmt>(pg_sys::NodeTag::T_RenameStmt)
+        {
+            if stmt.renameType != pg_sys::ObjectType::OBJECT_COLUMN
+                || stmt.relationType != pg_sys::ObjectType::OBJECT_TABLE
+            {
+                return Ok(());
+            }
+            stmt.relation
+        } else {
+            return Ok(());
+        };
+
+        // ALTER TABLE already holds its lock on the table
+        let Some(guard) =
+            open_iceberg_table(relation, pg_sys::NoLock as pg_sys::LOCKMODE)?
+        else {
+            return Ok(());
+        };
+
+        if let Some(stmt) = context
+            .is_a_mut::<pg_sys::AlterTableStmt>(pg_sys::NodeTag::T_AlterTableStmt)
+            && alters_options(stmt)
+        {
+            apply_alter_options(&guard.as_handle(), stmt)?;
+        }
+
+        evolve_schema(&guard.as_handle()).map_err(|e| {
+            UtilityHookError::Message(format!(
+                "table: failed to evolve iceberg schema - {}",
+                e
+            ))
+        })
+    }
+}
+
+pub fn init_hook() {
+    register_utility_hook(
+        pg_sys::NodeTag::T_AlterTableStmt,
+        Box::new(IcebergAlterTableHook),
+    );
+    register_utility_hook(
+        pg_sys::NodeTag::T_RenameStmt,
+        Box::new(IcebergAlterTableHook),
+    );
+}
@@ -1,3 +1,4 @@
+pub mod alter_table;
 pub mod object_access;
 pub mod table_option_cache;
 pub mod table_options;
@@ -6,7 +7,12 @@ pub mod tablespace_options;
 pub fn init_hooks() {
     // Initialize transaction callback for pending delete cleanup
     pg_tam::access::pending_delete::init_xact_callback();
+    // Record the columns scans need, so that only those are read
+    pg_tam::access::projection::init_projection_hooks();
+    crate::access::commit::init_hook();
+    crate::access::tid::init_hook();
     tablespace_options::init_hook();
     table_options::init_hook();
+    alter_table::init_hook();
     object_access::init_hook();
 }
@@ -12,19 +12,26 @@
 //! |  IcebergTableOptionCache (Fixed Size, #[repr(C)])     | <- rd_amcache points here
 //! |-------------------------------------------------------|
 //! |  format_version: i32                                  |
+//! |  target_file_size: i64                                |
 //! |  compression_offset: u32  (relative to struct start)  |
 //! |  write_format_offset: u32                             |
+//! |  partitioning_offset: u32  (0 when not partitioned)   |
+//! |  sort_order_offset: u32    (0 when not sorted)        |
 //! +-------------------------------------------------------+
 //! |  Variable Data Area (u8 bytes)                        |
 //! |-------------------------------------------------------|
 //! |  "zstd\0"                                             |
 //! |  "parquet\0"                                          |
+//! |  "bucket(16, id), day(ts)\0"                          |
+//! |  "ts DESC NULLS LAST, id\0"                           |
 //! +-------------------------------------------------------+
 //! ```
 
 use super::table_options::{
     OPT_COMPRESSION_CODEC, OPT_COMPRESSION_CODEC_DEFAULT, OPT_FORMAT_VERSION,
-    OPT_FORMAT_VERSION_DEFAULT, OPT_WRITE_FORMAT, OPT_WRITE_FORMAT_DEFAULT,
+    OPT_FORMAT_VERSION_DEFAULT, OPT_PARTITIONING, OPT_SORT_ORDER,
+    OPT_TARGET_FILE_SIZE, OPT_TARGET_FILE_SIZE_DEFAULT, OPT_WRITE_FORMAT,
+    OPT_WRITE_FORMAT_DEFAULT,
 };
 use pg_tam::option::{AmCacheable, TableOptions, append_string, get_string_at_offset};
 use std::collections::HashMap;
@@ -37,8 +44,11 @@ use std::collections::HashMap;
 #[derive(Clone, Copy)]
 pub struct IcebergTableOptionCache {
     pub format_version: i32,
+    pub target_file_size: i64,
     compression_offset: u32,
     write_format_offset: u32,
+    partitioning_offset: u32,
+    sort_order_offset: u32,
 }
 
 // SAFETY: IcebergTableOptionCache is #[repr(C)] and contains only POD types.
@@ -51,21 +61,31 @@ unsafe impl AmCacheable for IcebergTableOptionCache {
         let format_version = opts
             .get_int(OPT_FORMAT_VERSION)
             .unwrap_or(OPT_FORMAT_VERSION_DEFAULT);
+        let target_file_size = opts
+            .get_long(OPT_TARGET_FILE_SIZE)
+            .unwrap_or(OPT_TARGET_FILE_SIZE_DEFAULT);
         let compression = opts
             .get_str(OPT_COMPRESSION_CODEC)
             .unwrap_or_else(|| OPT_COMPRESSION_CODEC_DEFAULT.to_string());
         let write_format = opts
             .get_str(OPT_WRITE_FORMAT)
             .unwrap_or_else(|| OPT_WRITE_FORMAT_DEFAULT.to_string());
+        let partitioning = opts.get_str(OPT_PARTITIONING).unwrap_or_default();
+        let sort_order = opts.get_str(OPT_SORT_ORDER).unwrap_or_default();
 
         let compression_offset = append_string(&mut data, header_size, &compression);
         let write_format_offset = append_string(&mut data, header_size, &write_format);
+        let partitioning_offset = append_string(&mut data, header_size, &partitioning);
+        let sort_order_offset = append_string(&mut data, header_size, &sort_order);
 
         (
             Self {
                 format_version,
+                target_file_size,
                 compression_offset,
                 write_format_offset,
+                partitioning_offset,
+                sort_order_offset,
             },
             data,
         )
@@ -83,8 +103,11 @@ unsafe impl AmCacheable for IcebergTableOptionCache {
         (
             Self {
                 format_version: OPT_FORMAT_VERSION_DEFAULT,
+                target_file_size: OPT_TARGET_FILE_SIZE_DEFAULT,
                 compression_offset,
                 write_format_offset,
+                partitioning_offset: 0,
+                sort_order_offset: 0,
             },
             data,
         )
@@ -100,6 +123,22 @@ impl IcebergTableOptionCache {
         unsafe { get_string_at_offset(self as *const _ as *const u8, self.write_format_offset) }
     }
 
+    /// The partition terms of the table, `None` when it is not partitioned.
+    pub fn partitioning(&self) -> Option<&str> {
+        let partitioning = unsafe {
+            get_string_at_offset(self as *const _ as *const u8, self.partitioning_offset)
+        };
+        (!partitioning.is_empty()).then_some(partitioning)
+    }
+
+    /// The sort terms of the table, `None` when it is not sorted.
+    pub fn sort_order(&self) -> Option<&str> {
+        let sort_order = unsafe {
+            get_string_at_offset(self as *const _ as *const u8, self.sort_order_offset)
+        };
+        (!sort_order.is_empty()).then_some(sort_order)
+    }
+
     /// Convert cached options to Iceberg table properties HashMap.
     ///
     /// This is the single source of truth for converting options to properties.
@@ -117,6 +156,11 @@ impl IcebergTableOptionCache {
             self.write_format().to_string(),
         );
 
+        properties.insert(
+            OPT_TARGET_FILE_SIZE.to_string(),
+            self.target_file_size.to_string(),
+        );
+
         properties
     }
 }
@@ -129,9 +173,12 @@ mod tests {
     fn test_default_options() {
         let (cache, data) = IcebergTableOptionCache::default_options();
         assert_eq!(cache.format_version, 2);
+        assert_eq!(cache.target_file_size, 512 * 1024 * 1024);
         // Verify offsets are non-zero (strings are stored)
         assert!(cache.compression_offset > 0);
         assert!(cache.write_format_offset > 0);
+        assert_eq!(cache.partitioning_offset, 0);
+        assert_eq!(cache.sort_order_offset, 0);
         // Verify data contains expected strings with null terminators
         assert!(data.len() > 0);
     }
@@ -1,6 +1,10 @@
+use super::alter_table::list_pointers;
 use crate::ICEBERG_AM_NAME;
 use crate::catalog::iceberg_metadata::IcebergMetadata;
-use crate::catalog::init_table_storage_metadata;
+use crate::catalog::{init_table_storage_metadata, update_table_options};
+use crate::error::IcebergResult;
+use pg_tam::handles::RelationHandle;
+use pg_tam::option::AmCache;
 use pg_tam::option::{OptionKind, StorageCategory, TamOptionDef};
 use pg_tam::pg_wrapper::PgWrapper;
 use pg_tam::prelude::*;
@@ -20,6 +24,14 @@ pub const OPT_FORMAT_VERSION_DEFAULT: i32 = 2;
 pub const OPT_COMPRESSION_CODEC: &str = "write.parquet.compression-codec";
 /// Default compression codec
 pub const OPT_COMPRESSION_CODEC_DEFAULT: &str = "zstd";
+/// Allowed compression codec values
+pub const OPT_COMPRESSION_CODEC_VALUES: &[&str] =
+    &["zstd", "snappy", "gzip", "lz4", "brotli", "uncompressed"];
+
+/// Target size of written data files in bytes
+pub const OPT_TARGET_FILE_SIZE: &str = "write.target-file-size-bytes";
+/// Default target file size (512 MB)
+pub const OPT_TARGET_FILE_SIZE_DEFAULT: i64 = 512 * 1024 * 1024;
 
 /// Default file format for writing (parquet, avro, orc)
 pub const OPT_WRITE_FORMAT: &str = "write.format.default";
@@ -28,6 +40,12 @@ pub const OPT_WRITE_FORMAT_DEFAULT: &str = "parquet";
 /// Allowed write format values
 pub const OPT_WRITE_FORMAT_VALUES: &[&str] = &["parquet", "avro", "orc"];
 
+/// Partition terms of the table, e.g. `bucket(16, id), day(ts)`
+pub const OPT_PARTITIONING: &str = "partitioning";
+
+/// Sort terms of the table, e.g. `ts DESC NULLS LAST, id`
+pub const OPT_SORT_ORDER: &str = "sort_order";
+
 // ============================================================================
 //  Option Definitions
 // ============================================================================
@@ -47,10 +65,21 @@ static ICEBERG_TABLE_OPTIONS: &[TamOptionDef] = &[
     TamOptionDef {
         name: OPT_COMPRESSION_CODEC,
         category: StorageCategory::Common,
-        kind: OptionKind::String {
-            default: Some(OPT_COMPRESSION_CODEC_DEFAULT),
+        kind: OptionKind::Enum {
+            default: OPT_COMPRESSION_CODEC_DEFAULT,
+            values: OPT_COMPRESSION_CODEC_VALUES,
         },
-        description: "Parquet compression codec (snappy, zstd)",
+        description: "Parquet compression codec (zstd, snappy, gzip, lz4, brotli, uncompressed)",
+    },
+    TamOptionDef {
+        name: OPT_TARGET_FILE_SIZE,
+        category: StorageCategory::Common,
+        kind: OptionKind::Long {
+            default: OPT_TARGET_FILE_SIZE_DEFAULT,
+            min: Some(1024 * 1024),
+            max: None,
+        },
+        description: "Target size of written data files in bytes",
     },
     TamOptionDef {
         name: OPT_WRITE_FORMAT,
@@ -61,6 +90,18 @@ static ICEBERG_TABLE_OPTIONS: &[TamOptionDef] = &[
         },
         description: "Default file format (parquet, avro, orc)",
     },
+    TamOptionDef {
+        name: OPT_PARTITIONING,
+        category: StorageCategory::Common,
+        kind: OptionKind::String { default: None },
+        description: "Partition terms of the table, e.g. 'bucket(16, id), day(ts)'",
+    },
+    TamOptionDef {
+        name: OPT_SORT_ORDER,
+        category: StorageCategory::Common,
+        kind: OptionKind::String { default: None },
+        description: "Sort terms of the table, e.g. 'ts DESC NULLS LAST, id'",
+    },
 ];
 
 struct IcebergTableHook;
@@ -172,6 +213,129 @@ impl UtilityHook for IcebergTableHook {
     }
 }
 
+/// The options set and the option names reset by an `ALTER TABLE` statement.
+///
+/// The options are removed from the commands' lists.
+fn extract_alter_options(
+    stmt: &mut pg_sys::AlterTableStmt,
+) -> Result<(Vec<(String, Option<String>)>, Vec<String>), UtilityHookError> {
+    let mut set = vec![];
+    let mut reset = vec![];
+
+    for cmd in unsafe { list_pointers::<pg_sys::AlterTableCmd>(stmt.cmds) } {
+        let (subtype, options) = unsafe {
+            (
+                (*cmd).subtype,
+                &mut *(&mut (*cmd).def as *mut *mut pg_sys::Node
+                    as *mut *mut pg_sys::List),
+            )
+        };
+        match subtype {
+            pg_sys::AlterTableType::AT_SetRelOptions => {
+                if let Some(opts) =
+                    TableOptions::extract_from_list(options, ICEBERG_TABLE_OPTIONS)
+                        .map_err(|e| {
+                            UtilityHookError::Message(format!(
+                                "table: option extraction failed - {}",
+                                e
+                            ))
+                        })?
+                {
+                    set.extend(opts.iter().map(|(k, v)| (k.clone(), v.clone())));
+                }
+            }
+            pg_sys::AlterTableType::AT_ResetRelOptions => {
+                reset.extend(TableOptions::extract_names_from_list(
+                    options,
+                    ICEBERG_TABLE_OPTIONS,
+                ));
+            }
+            _ => {}
+        }
+    }
+    Ok((set, reset))
+}
+
+/// Check if an `ALTER TABLE` statement sets or resets table options.
+pub(crate) fn alters_options(stmt: &pg_sys::AlterTableStmt) -> bool {
+    stmt.objtype == pg_sys::ObjectType::OBJECT_TABLE
+        && unsafe { list_pointers::<pg_sys::AlterTableCmd>(stmt.cmds) }
+            .into_iter()
+            .any(|cmd| {
+                matches!(
+                    unsafe { (*cmd).subtype },
+                    pg_sys::AlterTableType::AT_SetRelOptions
+                        | pg_sys::AlterTableType::AT_ResetRelOptions
+                )
+            })
+}
+
+/// Persist the changed options of `rel` and stage them on its Iceberg table.
+fn alter_table_options(
+    rel: &RelationHandle,
+    set: Vec<(String, Option<String>)>,
+    reset: Vec<String>,
+) -> IcebergResult<()> {
+    let mut options = TableOptions::load_from_catalog(rel.oid())?
+        .unwrap_or_else(|| TableOptions::new(vec![]));
+    let mut changed = reset;
+    for key in &changed {
+        options.remove(key);
+    }
+    for (key, value) in set {
+        options.set(&key, value);
+        changed.push(key);
+    }
+    options.update_catalog(rel.oid())?;
+
+    // Make the new options visible and have rd_amcache reload them
+    AmCache::invalidate(rel);
+    unsafe { pg_sys::CommandCounterIncrement() };
+
+    update_table_options(rel, &changed)
+}
+
+/// Validate the options an `ALTER TABLE` statement of an Iceberg table sets
+/// or resets and remove them from its commands, before PostgreSQL validates
+/// them as reloptions.
+pub(crate) fn hide_alter_options(
+    stmt: &mut pg_sys::AlterTableStmt,
+) -> Result<(), UtilityHookError> {
+    let (set, reset) = extract_alter_options(stmt)?;
+    if set
+        .iter()
+        .map(|(key, _)| key)
+        .chain(&reset)
+        .any(|key| key == OPT_PARTITIONING)
+    {
+        return Err(UtilityHookError::Message(format!(
+            "table: cannot change option '{}' of an iceberg table",
+            OPT_PARTITIONING
+        )));
+    }
+    Ok(())
+}
+
+/// Persist the options an `ALTER TABLE` statement of `rel` sets or resets to
+/// `lakehouse.table_options` and stage them on its Iceberg table.
+pub(crate) fn apply_alter_options(
+    rel: &RelationHandle,
+    stmt: &mut pg_sys::AlterTableStmt,
+) -> Result<(), UtilityHookError> {
+    // The statement is a copy taken before on_pre removed the options
+    let (set, reset) = extract_alter_options(stmt)?;
+    if set.is_empty() && reset.is_empty() {
+        return Ok(());
+    }
+
+    alter_table_options(rel, set, reset).map_err(|e| {
+        UtilityHookError::Message(format!(
+            "table: failed to alter iceberg table options - {}",
+            e
+        ))
+    })
+}
+
 pub fn init_hook() {
     register_utility_hook(pg_sys::NodeTag::T_CreateStmt, Box::new(IcebergTableHook));
 }
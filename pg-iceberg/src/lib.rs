@@ -6,6 +6,7 @@ use std::sync::OnceLock;
 mod access;
 pub mod catalog;
 pub mod error;
+mod functions;
 pub mod hooks;
 pub mod storage;
 pub mod wal;
@@ -68,6 +69,7 @@ extern "C-unwind" fn _PG_init() {
     setup_rustls_default_crypto_provider();
     hooks::init_hooks();
     wal::init_wal_rmgr();
+    access::time_travel::init_gucs();
 }
 
 // ============================================================================
@@ -1,19 +1,90 @@
-use iceberg_lite::Result;
-use iceberg_lite::io::{FileMetadata, FileRead, FileWrite, Storage};
+//! Object store storage implementation backed by `opendal`.
+//!
+//! Tables in distributed tablespaces live in an object store (S3, GCS, Azure
+//! Blob Storage). `ObjectStorage` maps Iceberg file paths such as
+//! `s3://bucket/path/to/file` onto an `opendal` operator rooted at the bucket
+//! and drives its async API from a single-threaded Tokio runtime, so all I/O
+//! stays blocking from the backend's point of view.
+//!
+//! Besides the cloud services, the `fs` and `memory` schemes are supported to
+//! exercise the implementation without an object store.
+
 use std::any::Any;
 use std::collections::HashMap;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::ops::Range;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use iceberg_lite::io::{FileMetadata, FileRead, FileWrite, Storage};
+use iceberg_lite::{Error, ErrorKind, Result};
+use opendal::services::{Azblob, Fs, Gcs, Memory, S3};
+use opendal::{Operator, Writer};
+use pg_tam::diag::create_async_runtime;
+use pg_tam::option::{
+    DEFAULT_S3_REGION, OPT_ACCESS_KEY_ID, OPT_ACCOUNT_KEY, OPT_ACCOUNT_NAME,
+    OPT_ALLOW_HTTP, OPT_BUCKET, OPT_CONTAINER, OPT_CREDENTIAL, OPT_CREDENTIAL_PATH,
+    OPT_ENDPOINT, OPT_REGION, OPT_SAS_TOKEN, OPT_SECRET_ACCESS_KEY,
+};
+use pg_tam::prelude::Runtime;
+
+/// Root directory of the `fs` scheme.
+const OPT_ROOT: &str = "root";
+
+/// Size of the parts uploaded by an `ObjectWriter`.
+///
+/// Files larger than this are written as multipart uploads. 8MB is above the
+/// minimum part size of all supported services.
+const WRITE_CHUNK_SIZE: usize = 8 * 1024 * 1024;
+
+/// An initialized connection to an object store.
+#[derive(Debug, Clone)]
+struct ObjectStore {
+    operator: Operator,
+    runtime: Arc<Runtime>,
+    /// URL prefix of the paths handled by `operator`, e.g. `s3://bucket/`.
+    root_url: String,
+}
+
+impl ObjectStore {
+    /// Path of `path` relative to the operator root.
+    fn relative_path<'a>(&self, path: &'a str) -> Result<&'a str> {
+        if let Some(relative) = path.strip_prefix(&self.root_url) {
+            return Ok(relative);
+        }
+        if !path.contains("://") {
+            return Ok(path);
+        }
+
+        Err(Error::new(
+            ErrorKind::DataInvalid,
+            format!("Path {path} is not located in {}", self.root_url),
+        ))
+    }
+}
 
 #[derive(Debug)]
 pub struct ObjectStorage {
     scheme: String,
+    store: Option<ObjectStore>,
 }
 
 impl ObjectStorage {
     pub fn new(scheme: impl Into<String>) -> Self {
         Self {
             scheme: scheme.into(),
+            store: None,
         }
     }
+
+    fn store(&self) -> Result<&ObjectStore> {
+        self.store.as_ref().ok_or_else(|| {
+            Error::new(
+                ErrorKind::Unexpected,
+                format!("Object storage for {} is not initialized", self.scheme),
+            )
+        })
+    }
 }
 
 impl Default for ObjectStorage {
@@ -23,33 +94,78 @@ impl Default for ObjectStorage {
 }
 
 impl Storage for ObjectStorage {
-    fn exists(&self, _path: &str) -> Result<bool> {
-        unimplemented!("ObjectStorage::exists")
+    fn exists(&self, path: &str) -> Result<bool> {
+        let store = self.store()?;
+        let path = store.relative_path(path)?;
+        Ok(store.runtime.block_on(store.operator.exists(path))?)
     }
 
-    fn delete(&self, _path: &str) -> Result<()> {
-        unimplemented!("ObjectStorage::delete")
+    fn delete(&self, path: &str) -> Result<()> {
+        let store = self.store()?;
+        let path = store.relative_path(path)?;
+        // Deleting a missing object succeeds
+        Ok(store.runtime.block_on(store.operator.delete(path))?)
     }
 
-    fn remove_dir_all(&self, _path: &str) -> Result<()> {
-        unimplemented!("ObjectStorage::remove_dir_all")
+    fn remove_dir_all(&self, path: &str) -> Result<()> {
+        let store = self.store()?;
+        let path = store.relative_path(path)?.trim_end_matches('/');
+        // Listing the root recursively would delete everything stored in it
+        if path.is_empty() {
+            return Err(Error::new(
+                ErrorKind::DataInvalid,
+                format!("Cannot remove the root {} of the storage", store.root_url),
+            ));
+        }
+        let prefix = format!("{path}/");
+
+        store.runtime.block_on(async {
+            let entries = store.operator.list_with(&prefix).recursive(true).await?;
+            let paths = entries
+                .into_iter()
+                .filter(|entry| entry.metadata().is_file())
+                .map(|entry| entry.path().to_string());
+            store.operator.delete_iter(paths).await
+        })?;
+
+        Ok(())
     }
 
-    fn metadata(&self, _path: &str) -> Result<FileMetadata> {
-        unimplemented!("ObjectStorage::metadata")
+    fn metadata(&self, path: &str) -> Result<FileMetadata> {
+        let store = self.store()?;
+        let path = store.relative_path(path)?;
+        let metadata = store.runtime.block_on(store.operator.stat(path))?;
+
+        Ok(FileMetadata {
+            size: metadata.content_length(),
+        })
     }
 
-    fn reader(&self, _path: &str) -> Result<Box<dyn FileRead>> {
-        Ok(Box::new(ObjectReader))
+    fn reader(&self, path: &str) -> Result<Box<dyn FileRead>> {
+        let store = self.store()?;
+        let reader = ObjectReader::open(store.clone(), store.relative_path(path)?)?;
+        Ok(Box::new(reader))
     }
 
-    fn writer(&self, _path: &str) -> Result<Box<dyn FileWrite>> {
-        Ok(Box::new(ObjectWriter))
+    fn writer(&self, path: &str) -> Result<Box<dyn FileWrite>> {
+        let store = self.store()?;
+        let writer = ObjectWriter::open(store.clone(), store.relative_path(path)?)?;
+        Ok(Box::new(writer))
     }
 
-    fn initialize(&mut self, _props: HashMap<String, String>) -> Result<()> {
-        // Props are used by the actual implementation to configure
-        // the connection to the object store (e.g., S3 credentials)
+    fn initialize(&mut self, props: HashMap<String, String>) -> Result<()> {
+        let (operator, root_url) = build_operator(&self.scheme, &props)?;
+        let runtime = create_async_runtime().map_err(|e| {
+            Error::new(ErrorKind::Unexpected, "Failed to create async runtime")
+                .with_source(e)
+        })?;
+
+        self.store = Some(ObjectStore {
+            operator,
+            runtime: Arc::new(runtime),
+            root_url,
+        });
+
         Ok(())
     }
 
@@ -62,48 +178,463 @@ impl Storage for ObjectStorage {
     }
 }
 
-pub struct ObjectReader;
+/// Build the operator for `scheme` from the tablespace storage properties,
+/// see `StorageOptions::to_props`.
+///
+/// Returns the operator and the URL prefix of the paths it serves.
+fn build_operator(
+    scheme: &str,
+    props: &HashMap<String, String>,
+) -> Result<(Operator, String)> {
+    let prop = |key: &str| props.get(key).map(String::as_str);
+    let required = |key: &str| {
+        prop(key).ok_or_else(|| {
+            Error::new(
+                ErrorKind::DataInvalid,
+                format!("Storage option {key} is required for {scheme}"),
+            )
+        })
+    };
+
+    let (operator, root_url) = match scheme {
+        "s3" => {
+            let bucket = required(OPT_BUCKET)?;
+            let allow_http = prop(OPT_ALLOW_HTTP) == Some("true");
+
+            let mut builder = S3::default()
+                .bucket(bucket)
+                .region(prop(OPT_REGION).unwrap_or(DEFAULT_S3_REGION));
+            if let Some(endpoint) = prop(OPT_ENDPOINT) {
+                builder = builder.endpoint(&s3_endpoint(endpoint, allow_http)?);
+            }
+            if let Some(access_key_id) = prop(OPT_ACCESS_KEY_ID) {
+                builder = builder.access_key_id(access_key_id);
+            }
+            if let Some(secret_access_key) = prop(OPT_SECRET_ACCESS_KEY) {
+                builder = builder.secret_access_key(secret_access_key);
+            }
+
+            (Operator::new(builder)?.finish(), format!("s3://{bucket}/"))
+        }
+        "gcs" => {
+            let bucket = required(OPT_BUCKET)?;
+
+            // Falls back to the environment's service account without a key
+            let mut builder = Gcs::default().bucket(bucket);
+            if let Some(endpoint) = prop(OPT_ENDPOINT) {
+                builder = builder.endpoint(endpoint);
+            }
+            if let Some(credential) = prop(OPT_CREDENTIAL) {
+                builder = builder.credential(credential);
+            }
+            if let Some(credential_path) = prop(OPT_CREDENTIAL_PATH) {
+                builder = builder.credential_path(credential_path);
+            }
+
+            (Operator::new(builder)?.finish(), format!("gs://{bucket}/"))
+        }
+        "azure" => {
+            let account = required(OPT_ACCOUNT_NAME)?;
+            let container = required(OPT_CONTAINER)?;
+            let endpoint =
+                prop(OPT_ENDPOINT).map(str::to_string).unwrap_or_else(|| {
+                    format!("https://{account}.blob.core.windows.net")
+                });
+
+            let mut builder = Azblob::default()
+                .account_name(account)
+                .container(container)
+                .endpoint(&endpoint);
+            if let Some(account_key) = prop(OPT_ACCOUNT_KEY) {
+                builder = builder.account_key(account_key);
+            }
+            if let Some(sas_token) = prop(OPT_SAS_TOKEN) {
+                builder = builder.sas_token(sas_token);
+            }
+
+            (
+                Operator::new(builder)?.finish(),
+                format!("az://{account}/{container}/"),
+            )
+        }
+        "fs" => {
+            let builder = Fs::default().root(prop(OPT_ROOT).unwrap_or("/"));
+            (Operator::new(builder)?.finish(), "file://".to_string())
+        }
+        "memory" => (
+            Operator::new(Memory::default())?.finish(),
+            "memory://".to_string(),
+        ),
+        _ => {
+            return Err(Error::new(
+                ErrorKind::FeatureUnsupported,
+                format!("Unsupported object storage scheme: {scheme}"),
+            ));
+        }
+    };
+
+    Ok((operator, root_url))
+}
+
+/// Add the URL scheme to an S3 endpoint given as `host[:port]`, rejecting
+/// plain HTTP endpoints unless `allow_http` is set.
+fn s3_endpoint(endpoint: &str, allow_http: bool) -> Result<String> {
+    if endpoint.starts_with("http://") && !allow_http {
+        return Err(Error::new(
+            ErrorKind::DataInvalid,
+            format!(
+                "Endpoint {endpoint} uses HTTP, set {OPT_ALLOW_HTTP} to allow it"
+            ),
+        ));
+    }
 
-impl std::io::Read for ObjectReader {
-    fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
-        unimplemented!("ObjectReader::read")
+    if endpoint.contains("://") {
+        Ok(endpoint.to_string())
+    } else if allow_http {
+        Ok(format!("http://{endpoint}"))
+    } else {
+        Ok(format!("https://{endpoint}"))
     }
 }
 
-impl std::io::Seek for ObjectReader {
-    fn seek(&mut self, _pos: std::io::SeekFrom) -> std::io::Result<u64> {
-        unimplemented!("ObjectReader::seek")
+/// Object reader issuing ranged reads against the object store.
+pub struct ObjectReader {
+    store: ObjectStore,
+    /// Path relative to the operator root
+    path: String,
+    /// Total object size in bytes
+    size: u64,
+    /// Current position of the `Read` cursor
+    position: u64,
+}
+
+impl ObjectReader {
+    fn open(store: ObjectStore, path: &str) -> Result<Self> {
+        let metadata = store.runtime.block_on(store.operator.stat(path))?;
+
+        Ok(Self {
+            size: metadata.content_length(),
+            store,
+            path: path.to_string(),
+            position: 0,
+        })
     }
 }
 
 impl FileRead for ObjectReader {
-    fn read_range(&self, _range: std::ops::Range<u64>) -> Result<bytes::Bytes> {
-        unimplemented!("ObjectReader::read_range")
+    fn read_range(&self, range: Range<u64>) -> Result<Bytes> {
+        if range.start > range.end || range.end > self.size {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "invalid range {}..{} for '{}' of size {}",
+                    range.start, range.end, self.path, self.size
+                ),
+            )
+            .into());
+        }
+        if range.is_empty() {
+            return Ok(Bytes::new());
+        }
+
+        let buffer = self
+            .store
+            .runtime
+            .block_on(self.store.operator.read_with(&self.path).range(range))?;
+        Ok(buffer.to_bytes())
     }
 
-    fn read_all(&self) -> Result<bytes::Bytes> {
-        unimplemented!("ObjectReader::read_all")
+    fn read_all(&self) -> Result<Bytes> {
+        self.read_range(0..self.size)
     }
 
-    fn try_clone(&self) -> std::io::Result<Box<dyn FileRead>> {
-        Ok(Box::new(ObjectReader))
+    fn try_clone(&self) -> io::Result<Box<dyn FileRead>> {
+        Ok(Box::new(Self {
+            store: self.store.clone(),
+            path: self.path.clone(),
+            size: self.size,
+            position: self.position,
+        }))
     }
 }
 
-pub struct ObjectWriter;
+impl Read for ObjectReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        // Seeking past the end is allowed, reads from there are at EOF
+        let start = self.size.min(self.position);
+        let end = self.size.min(start.saturating_add(buf.len() as u64));
+        let bytes = self.read_range(start..end).map_err(io::Error::other)?;
 
-impl std::io::Write for ObjectWriter {
-    fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
-        unimplemented!("ObjectWriter::write")
+        buf[..bytes.len()].copy_from_slice(&bytes);
+        self.position += bytes.len() as u64;
+        Ok(bytes.len())
     }
+}
 
-    fn flush(&mut self) -> std::io::Result<()> {
-        unimplemented!("ObjectWriter::flush")
+impl Seek for ObjectReader {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let position = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(offset) => self.size.checked_add_signed(offset),
+            SeekFrom::Current(offset) => self.position.checked_add_signed(offset),
+        };
+
+        match position {
+            Some(position) => {
+                self.position = position;
+                Ok(position)
+            }
+            None => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid seek to a negative position in '{}'", self.path),
+            )),
+        }
+    }
+}
+
+/// Object writer uploading the written bytes in parts of
+/// [`WRITE_CHUNK_SIZE`].
+///
+/// The object only becomes visible once the writer is closed. A writer that
+/// is dropped without being closed aborts the upload.
+pub struct ObjectWriter {
+    store: ObjectStore,
+    /// Path relative to the operator root
+    path: String,
+    /// Open upload, `None` once closed or aborted
+    writer: Option<Writer>,
+    /// Bytes not yet handed to the upload
+    buffer: Vec<u8>,
+}
+
+impl ObjectWriter {
+    fn open(store: ObjectStore, path: &str) -> Result<Self> {
+        let writer = store
+            .runtime
+            .block_on(store.operator.writer_with(path).chunk(WRITE_CHUNK_SIZE))?;
+
+        Ok(Self {
+            store,
+            path: path.to_string(),
+            writer: Some(writer),
+            buffer: Vec::with_capacity(WRITE_CHUNK_SIZE),
+        })
+    }
+
+    fn writer(&mut self) -> io::Result<&mut Writer> {
+        self.writer.as_mut().ok_or_else(|| {
+            io::Error::other(format!("writer for '{}' is closed", self.path))
+        })
+    }
+
+    /// Hand the buffered bytes to the upload.
+    fn upload_buffer(&mut self) -> io::Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+
+        let buffer =
+            std::mem::replace(&mut self.buffer, Vec::with_capacity(WRITE_CHUNK_SIZE));
+        let runtime = self.store.runtime.clone();
+        let writer = self.writer()?;
+        runtime
+            .block_on(writer.write(buffer))
+            .map_err(io::Error::other)
+    }
+}
+
+impl Write for ObjectWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writer()?;
+        self.buffer.extend_from_slice(buf);
+        if self.buffer.len() >= WRITE_CHUNK_SIZE {
+            self.upload_buffer()?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        // Parts can only be uploaded whole, the buffer is written on close
+        Ok(())
     }
 }
 
 impl FileWrite for ObjectWriter {
     fn close(&mut self) -> Result<()> {
+        if self.writer.is_none() {
+            return Ok(());
+        }
+
+        self.upload_buffer()?;
+        let mut writer = self.writer.take().expect("writer is open");
+        self.store.runtime.block_on(writer.close())?;
         Ok(())
     }
 }
+
+impl Drop for ObjectWriter {
+    fn drop(&mut self) {
+        if let Some(mut writer) = self.writer.take() {
+            // Best effort, an incomplete upload is never visible
+            let _ = self.store.runtime.block_on(writer.abort());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn memory_storage() -> ObjectStorage {
+        let mut storage = ObjectStorage::new("memory");
+        storage.initialize(HashMap::new()).unwrap();
+        storage
+    }
+
+    fn write_file(storage: &ObjectStorage, path: &str, content: &[u8]) {
+        let mut writer = storage.writer(path).unwrap();
+        writer.write_all(content).unwrap();
+        writer.close().unwrap();
+    }
+
+    #[test]
+    fn test_object_storage_read_write() {
+        let storage = memory_storage();
+        let path = "memory://table/data/file.parquet";
+
+        assert!(!storage.exists(path).unwrap());
+        write_file(&storage, path, b"hello iceberg");
+        assert!(storage.exists(path).unwrap());
+        assert_eq!(storage.metadata(path).unwrap().size, 13);
+
+        let mut reader = storage.reader(path).unwrap();
+        assert_eq!(reader.read_all().unwrap().as_ref(), b"hello iceberg");
+        assert_eq!(reader.read_range(6..13).unwrap().as_ref(), b"iceberg");
+
+        reader.seek(SeekFrom::End(-7)).unwrap();
+        let mut content = String::new();
+        reader.read_to_string(&mut content).unwrap();
+        assert_eq!(content, "iceberg");
+
+        assert!(reader.read_range(6..14).is_err());
+
+        // Reads after seeking past the end are at EOF
+        reader.seek(SeekFrom::Start(20)).unwrap();
+        assert_eq!(reader.read(&mut [0; 4]).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_object_storage_large_write() {
+        let storage = memory_storage();
+        let path = "memory://table/data/large.parquet";
+        let content: Vec<u8> =
+            (0..WRITE_CHUNK_SIZE * 2 + 17).map(|i| i as u8).collect();
+
+        write_file(&storage, path, &content);
+
+        let reader = storage.reader(path).unwrap();
+        assert_eq!(reader.read_all().unwrap().as_ref(), content.as_slice());
+    }
+
+    #[test]
+    fn test_object_storage_unclosed_writer() {
+        let storage = memory_storage();
+        let path = "memory://table/data/aborted.parquet";
+
+        let mut writer = storage.writer(path).unwrap();
+        writer.write_all(b"partial").unwrap();
+        drop(writer);
+
+        assert!(!storage.exists(path).unwrap());
+    }
+
+    #[test]
+    fn test_object_storage_delete() {
+        let storage = memory_storage();
+        write_file(&storage, "memory://table/metadata/v1.metadata.json", b"{}");
+        write_file(&storage, "memory://table/data/a.parquet", b"a");
+        write_file(&storage, "memory://table/data/b.parquet", b"b");
+        write_file(&storage, "memory://table_2/data/c.parquet", b"c");
+
+        storage.delete("memory://table/data/a.parquet").unwrap();
+        assert!(!storage.exists("memory://table/data/a.parquet").unwrap());
+        // Deleting a missing file succeeds
+        storage.delete("memory://table/data/a.parquet").unwrap();
+
+        storage.remove_dir_all("memory://table").unwrap();
+        assert!(!storage.exists("memory://table/data/b.parquet").unwrap());
+        assert!(
+            !storage
+                .exists("memory://table/metadata/v1.metadata.json")
+                .unwrap()
+        );
+        assert!(storage.exists("memory://table_2/data/c.parquet").unwrap());
+
+        // The root itself is never removed
+        assert!(storage.remove_dir_all("memory://").is_err());
+        assert!(storage.remove_dir_all("memory:///").is_err());
+        assert!(storage.exists("memory://table_2/data/c.parquet").unwrap());
+    }
+
+    #[test]
+    fn test_object_storage_fs() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut storage = ObjectStorage::new("fs");
+        storage.initialize(HashMap::new()).unwrap();
+
+        let path = format!("file://{}/data/file.parquet", dir.path().display());
+        write_file(&storage, &path, b"on disk");
+        assert_eq!(
+            std::fs::read(dir.path().join("data/file.parquet")).unwrap(),
+            b"on disk"
+        );
+
+        storage
+            .remove_dir_all(&format!("file://{}/data", dir.path().display()))
+            .unwrap();
+        assert!(!storage.exists(&path).unwrap());
+    }
+
+    #[test]
+    fn test_object_storage_paths() {
+        let mut storage = ObjectStorage::new("s3");
+        let props = HashMap::from([
+            (OPT_BUCKET.to_string(), "warehouse".to_string()),
+            (OPT_ENDPOINT.to_string(), "localhost:9000".to_string()),
+            (OPT_ALLOW_HTTP.to_string(), "true".to_string()),
+        ]);
+        storage.initialize(props).unwrap();
+
+        let store = storage.store().unwrap();
+        assert_eq!(
+            store.relative_path("s3://warehouse/base/1/2/3").unwrap(),
+            "base/1/2/3"
+        );
+        assert!(store.relative_path("s3://other/base/1").is_err());
+
+        assert!(ObjectStorage::new("s3").exists("s3://warehouse/a").is_err());
+        assert!(s3_endpoint("http://localhost:9000", false).is_err());
+        assert_eq!(
+            s3_endpoint("localhost:9000", false).unwrap(),
+            "https://localhost:9000"
+        );
+    }
+
+    #[test]
+    fn test_object_storage_azure() {
+        let mut props = HashMap::from([
+            (OPT_ACCOUNT_NAME.to_string(), "account".to_string()),
+            (
+                OPT_SAS_TOKEN.to_string(),
+                "sv=2024-01-01&sig=secret".to_string(),
+            ),
+        ]);
+
+        let err = build_operator("azure", &props).unwrap_err();
+        assert!(err.to_string().contains(OPT_CONTAINER));
+
+        props.insert(OPT_CONTAINER.to_string(), "warehouse".to_string());
+        let (_, root_url) = build_operator("azure", &props).unwrap();
+        assert_eq!(root_url, "az://account/warehouse/");
+    }
+}
@@ -0,0 +1,88 @@
+-- table_alter_test.sql
+-- Test ALTER TABLE column changes of iceberg tables
+DROP EXTENSION IF EXISTS pg_iceberg CASCADE;
+CREATE EXTENSION pg_iceberg;
+
+CREATE TABLE test_alter (id int NOT NULL, name text, price numeric(10, 2)) USING iceberg;
+INSERT INTO test_alter VALUES (1, 'one', 1.10), (2, 'two', 2.20), (3, 'three', 3.30);
+
+--
+-- Test 0: ADD COLUMN reads as NULL for existing rows
+--
+ALTER TABLE test_alter ADD COLUMN note text;
+INSERT INTO test_alter VALUES (4, 'four', 4.40, 'added');
+SELECT * FROM test_alter ORDER BY id;
+
+--
+-- Test 1: DROP COLUMN
+--
+ALTER TABLE test_alter DROP COLUMN name;
+SELECT * FROM test_alter ORDER BY id;
+
+--
+-- Test 2: A re-added column does not read the dropped column's values
+--
+ALTER TABLE test_alter ADD COLUMN name text;
+INSERT INTO test_alter VALUES (5, 5.50, 'added', 'five');
+SELECT * FROM test_alter ORDER BY id;
+
+--
+-- Test 3: RENAME COLUMN keeps the values
+--
+ALTER TABLE test_alter RENAME COLUMN note TO comment;
+SELECT id, comment FROM test_alter WHERE comment IS NOT NULL ORDER BY id;
+
+--
+-- Test 4: Widening the precision of a numeric column
+--
+ALTER TABLE test_alter ALTER COLUMN price TYPE numeric(12, 2);
+INSERT INTO test_alter VALUES (6, 1234567890.12, NULL, 'six');
+SELECT id, price FROM test_alter ORDER BY id;
+
+--
+-- Test 5: DROP NOT NULL
+--
+ALTER TABLE test_alter ALTER COLUMN id DROP NOT NULL;
+INSERT INTO test_alter (price) VALUES (7.70);
+SELECT count(*) FROM test_alter WHERE id IS NULL;
+
+--
+-- Test 6: Column changes of an aborted transaction are discarded
+--
+BEGIN;
+ALTER TABLE test_alter ADD COLUMN tmp int;
+ALTER TABLE test_alter DROP COLUMN comment;
+INSERT INTO test_alter VALUES (8, 8.80, 'eight', 8);
+ROLLBACK;
+SELECT * FROM test_alter ORDER BY id;
+
+--
+-- Test 7: Several changes in one statement
+--
+ALTER TABLE test_alter DROP COLUMN comment, ADD COLUMN comment varchar(20);
+INSERT INTO test_alter VALUES (9, 9.90, 'nine', 'new comment');
+SELECT * FROM test_alter ORDER BY id;
+
+--
+-- Test 8: A column renamed to the name of a dropped column keeps its values
+--
+ALTER TABLE test_alter DROP COLUMN name;
+ALTER TABLE test_alter RENAME COLUMN comment TO name;
+SELECT * FROM test_alter ORDER BY id;
+
+--
+-- Test 9: ADD COLUMN with a default is refused
+--
+ALTER TABLE test_alter ADD COLUMN flag boolean DEFAULT true;
+
+--
+-- Test 10: Type changes that rewrite the table are refused
+--
+ALTER TABLE test_alter ALTER COLUMN id TYPE bigint;
+ALTER TABLE test_alter ALTER COLUMN price TYPE numeric(12, 3);
+SELECT attname, format_type(atttypid, atttypmod) FROM pg_attribute
+WHERE attrelid = 'test_alter'::regclass AND attname IN ('id', 'price')
+ORDER BY attnum;
+
+-- Cleanup
+DROP TABLE test_alter;
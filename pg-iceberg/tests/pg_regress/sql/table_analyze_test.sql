@@ -0,0 +1,73 @@
+-- table_analyze_test.sql
+-- Test ANALYZE of iceberg tables
+DROP EXTENSION IF EXISTS pg_iceberg CASCADE;
+CREATE EXTENSION pg_iceberg;
+
+--
+-- Test 0: ANALYZE collects statistics of all columns
+--
+CREATE TABLE test_analyze (id int, grp int, name text, note text) USING iceberg;
+
+INSERT INTO test_analyze
+SELECT i, i % 3, 'row ' || i, CASE WHEN i % 4 <> 0 THEN 'note ' || i END
+FROM generate_series(1, 600) i;
+INSERT INTO test_analyze
+SELECT i, i % 3, 'row ' || i, CASE WHEN i % 4 <> 0 THEN 'note ' || i END
+FROM generate_series(601, 1200) i;
+
+ANALYZE test_analyze;
+
+SELECT reltuples FROM pg_class WHERE relname = 'test_analyze';
+SELECT attname, null_frac, n_distinct
+FROM pg_stats WHERE tablename = 'test_analyze' ORDER BY attname;
+SELECT most_common_vals FROM pg_stats
+WHERE tablename = 'test_analyze' AND attname = 'grp';
+SELECT histogram_bounds IS NOT NULL AS has_histogram FROM pg_stats
+WHERE tablename = 'test_analyze' AND attname = 'id';
+
+--
+-- Test 1: Deleted rows are not counted
+--
+DELETE FROM test_analyze WHERE grp = 0;
+
+ANALYZE test_analyze;
+
+SELECT reltuples FROM pg_class WHERE relname = 'test_analyze';
+SELECT attname, null_frac, n_distinct
+FROM pg_stats WHERE tablename = 'test_analyze' ORDER BY attname;
+
+--
+-- Test 2: ANALYZE of selected columns and of an empty table
+--
+CREATE TABLE test_analyze_empty (id int, name text) USING iceberg;
+
+ANALYZE test_analyze_empty;
+ANALYZE test_analyze (grp);
+
+SELECT reltuples FROM pg_class WHERE relname = 'test_analyze_empty';
+SELECT count(*) AS stats_count FROM pg_stats WHERE tablename = 'test_analyze_empty';
+
+--
+-- Test 3: The sample is drawn from all data files, sized by SET STATISTICS
+--
+CREATE TABLE test_analyze_files (id int, part text) USING iceberg;
+
+INSERT INTO test_analyze_files SELECT i, 'first' FROM generate_series(1, 3000) i;
+INSERT INTO test_analyze_files SELECT i, 'second' FROM generate_series(3001, 6000) i;
+ALTER TABLE test_analyze_files ALTER COLUMN id SET STATISTICS 1;
+ALTER TABLE test_analyze_files ALTER COLUMN part SET STATISTICS 1;
+
+-- All rows are counted, the sample of 300 rows holds rows of both files
+ANALYZE test_analyze_files;
+
+SELECT reltuples FROM pg_class WHERE relname = 'test_analyze_files';
+SELECT n_distinct FROM pg_stats
+WHERE tablename = 'test_analyze_files' AND attname = 'part';
+SELECT (histogram_bounds::text::int[])[1] <= 3000 AS has_first,
+       (histogram_bounds::text::int[])[2] > 3000 AS has_second
+FROM pg_stats WHERE tablename = 'test_analyze_files' AND attname = 'id';
+
+-- Cleanup
+DROP TABLE test_analyze;
+DROP TABLE test_analyze_empty;
+DROP TABLE test_analyze_files;
@@ -0,0 +1,107 @@
+-- table_delete_test.sql
+-- Test DELETE from iceberg tables
+DROP EXTENSION IF EXISTS pg_iceberg CASCADE;
+CREATE EXTENSION pg_iceberg;
+
+--
+-- Test 0: DELETE with a WHERE clause
+--
+CREATE TABLE test_delete_basic (id int, name text) USING iceberg;
+
+INSERT INTO test_delete_basic SELECT i, 'row ' || i FROM generate_series(1, 10) i;
+
+DELETE FROM test_delete_basic WHERE id % 3 = 0;
+SELECT * FROM test_delete_basic ORDER BY id;
+
+-- Deleting rows that no longer exist is a no-op
+DELETE FROM test_delete_basic WHERE id = 3;
+SELECT count(*) AS row_count FROM test_delete_basic;
+
+DROP TABLE test_delete_basic;
+
+
+--
+-- Test 1: DELETE across several data files
+--
+CREATE TABLE test_delete_files (id int, name text) USING iceberg;
+
+INSERT INTO test_delete_files VALUES (1, 'one'), (2, 'two');
+INSERT INTO test_delete_files VALUES (3, 'three'), (4, 'four');
+INSERT INTO test_delete_files VALUES (5, 'five');
+
+DELETE FROM test_delete_files WHERE id IN (2, 3, 5);
+SELECT * FROM test_delete_files ORDER BY id;
+
+-- Rows inserted after a DELETE are visible
+INSERT INTO test_delete_files VALUES (6, 'six');
+DELETE FROM test_delete_files WHERE id = 1;
+SELECT * FROM test_delete_files ORDER BY id;
+
+DROP TABLE test_delete_files;
+
+
+--
+-- Test 2: DELETE within a transaction
+--
+CREATE TABLE test_delete_xact (id int) USING iceberg;
+INSERT INTO test_delete_xact SELECT generate_series(1, 5);
+
+BEGIN;
+DELETE FROM test_delete_xact WHERE id = 1;
+-- Later statements see the deleted rows gone
+SELECT * FROM test_delete_xact ORDER BY id;
+DELETE FROM test_delete_xact WHERE id = 2;
+INSERT INTO test_delete_xact VALUES (6);
+DELETE FROM test_delete_xact WHERE id = 6;
+SELECT * FROM test_delete_xact ORDER BY id;
+COMMIT;
+
+SELECT * FROM test_delete_xact ORDER BY id;
+
+-- Rolled back deletes are not applied
+BEGIN;
+DELETE FROM test_delete_xact;
+SELECT count(*) AS row_count FROM test_delete_xact;
+ROLLBACK;
+
+SELECT * FROM test_delete_xact ORDER BY id;
+
+DROP TABLE test_delete_xact;
+
+
+--
+-- Test 3: DELETE and savepoints
+--
+CREATE TABLE test_delete_savepoint (id int) USING iceberg;
+INSERT INTO test_delete_savepoint SELECT generate_series(1, 5);
+
+BEGIN;
+DELETE FROM test_delete_savepoint WHERE id = 1;
+SAVEPOINT s1;
+DELETE FROM test_delete_savepoint WHERE id = 2;
+SELECT * FROM test_delete_savepoint ORDER BY id;
+ROLLBACK TO SAVEPOINT s1;
+SELECT * FROM test_delete_savepoint ORDER BY id;
+SAVEPOINT s2;
+DELETE FROM test_delete_savepoint WHERE id = 3;
+RELEASE SAVEPOINT s2;
+COMMIT;
+
+SELECT * FROM test_delete_savepoint ORDER BY id;
+
+DROP TABLE test_delete_savepoint;
+
+
+--
+-- Test 4: DELETE of all rows
+--
+CREATE TABLE test_delete_all (id int, name text) USING iceberg;
+INSERT INTO test_delete_all SELECT i, 'row ' || i FROM generate_series(1, 100) i;
+
+DELETE FROM test_delete_all;
+SELECT count(*) AS row_count FROM test_delete_all;
+
+INSERT INTO test_delete_all VALUES (1, 'again');
+SELECT * FROM test_delete_all;
+
+DROP TABLE test_delete_all;
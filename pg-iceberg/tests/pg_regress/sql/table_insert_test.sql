@@ -0,0 +1,127 @@
+-- table_insert_test.sql
+-- Test INSERT and COPY into iceberg tables
+DROP EXTENSION IF EXISTS pg_iceberg CASCADE;
+CREATE EXTENSION pg_iceberg;
+
+--
+-- Test 0: Single and multi-row INSERT
+--
+CREATE TABLE test_insert_basic (id int, name text) USING iceberg;
+
+INSERT INTO test_insert_basic VALUES (1, 'one');
+INSERT INTO test_insert_basic VALUES (2, 'two'), (3, NULL);
+
+SELECT * FROM test_insert_basic ORDER BY id;
+SELECT count(*) AS row_count FROM test_insert_basic;
+
+DROP TABLE test_insert_basic;
+
+
+--
+-- Test 1: INSERT covering all supported column types
+--
+CREATE TABLE test_insert_types (
+    c_bool boolean,
+    c_int2 smallint,
+    c_int4 integer,
+    c_int8 bigint,
+    c_float4 real,
+    c_float8 double precision,
+    c_numeric numeric(10, 2),
+    c_text text,
+    c_varchar varchar(32),
+    c_date date,
+    c_time time,
+    c_timestamp timestamp,
+    c_timestamptz timestamptz,
+    c_bytea bytea,
+    c_uuid uuid,
+    c_jsonb jsonb
+) USING iceberg;
+
+INSERT INTO test_insert_types VALUES (
+    true, 1, 2, 3, 1.5, 2.25, 12345.67, 'text', 'varchar',
+    '2024-01-15', '12:34:56', '2024-01-15 12:34:56',
+    '2024-01-15 12:34:56+00', '\xdeadbeef',
+    'a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11', '{"a": 1}'
+);
+INSERT INTO test_insert_types (c_int4) VALUES (4);
+
+SELECT c_bool, c_int2, c_int4, c_int8, c_float4, c_float8, c_numeric
+FROM test_insert_types ORDER BY c_int4;
+SELECT c_text, c_varchar, c_date, c_time, c_timestamp
+FROM test_insert_types ORDER BY c_int4;
+SELECT c_timestamptz AT TIME ZONE 'UTC' AS c_timestamptz_utc, c_bytea, c_uuid, c_jsonb
+FROM test_insert_types ORDER BY c_int4;
+
+DROP TABLE test_insert_types;
+
+
+--
+-- Test 2: COPY FROM into a table with non-default writer options
+--
+CREATE TABLE test_insert_copy (id int, value numeric(12, 3)) USING iceberg WITH (
+    "write.parquet.compression-codec" = 'snappy',
+    "write.target-file-size-bytes" = 1048576
+);
+
+COPY test_insert_copy FROM STDIN;
+1	1.5
+2	-2.0005
+3	\N
+\.
+
+SELECT * FROM test_insert_copy ORDER BY id;
+
+INSERT INTO test_insert_copy SELECT g, g * 0.5 FROM generate_series(4, 10000) g;
+SELECT count(*) AS row_count, sum(value) AS value_sum FROM test_insert_copy;
+
+DROP TABLE test_insert_copy;
+
+-- Target file sizes of 2 GiB and more
+CREATE TABLE test_insert_large_files (id int) USING iceberg WITH (
+    "write.target-file-size-bytes" = 4294967296
+);
+INSERT INTO test_insert_large_files VALUES (1);
+SELECT * FROM test_insert_large_files;
+
+DROP TABLE test_insert_large_files;
+
+
+--
+-- Test 3: Rows of an aborted transaction are not visible
+--
+CREATE TABLE test_insert_abort (id int) USING iceberg;
+
+BEGIN;
+INSERT INTO test_insert_abort VALUES (1);
+ROLLBACK;
+
+SELECT count(*) AS row_count FROM test_insert_abort;
+
+DROP TABLE test_insert_abort;
+
+
+--
+-- Test 4: Changes are visible within their transaction and follow savepoints
+--
+CREATE TABLE test_insert_xact (id int) USING iceberg;
+
+BEGIN;
+INSERT INTO test_insert_xact VALUES (1);
+INSERT INTO test_insert_xact VALUES (2);
+SELECT * FROM test_insert_xact ORDER BY id;
+
+SAVEPOINT s1;
+INSERT INTO test_insert_xact VALUES (3);
+ROLLBACK TO SAVEPOINT s1;
+
+SAVEPOINT s2;
+INSERT INTO test_insert_xact VALUES (4);
+RELEASE SAVEPOINT s2;
+SELECT * FROM test_insert_xact ORDER BY id;
+COMMIT;
+
+SELECT * FROM test_insert_xact ORDER BY id;
+
+DROP TABLE test_insert_xact;
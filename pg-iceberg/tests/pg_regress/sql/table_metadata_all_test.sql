@@ -0,0 +1,73 @@
+-- table_metadata_all_test.sql
+-- Test the metadata tables of all the snapshots of iceberg tables
+DROP EXTENSION IF EXISTS pg_iceberg CASCADE;
+CREATE EXTENSION pg_iceberg;
+
+--
+-- Test 0: A table without snapshot
+--
+CREATE TABLE test_all (id int, name text) USING iceberg;
+SELECT count(*) FROM lakehouse.all_manifests('test_all'::regclass);
+SELECT count(*) FROM lakehouse.all_files('test_all'::regclass);
+SELECT count(*) FROM lakehouse.all_data_files('test_all'::regclass);
+SELECT count(*) FROM lakehouse.all_delete_files('test_all'::regclass);
+SELECT count(*) FROM lakehouse.all_entries('test_all'::regclass);
+
+--
+-- Test 1: Manifests of all snapshots
+--
+INSERT INTO test_all SELECT i, 'row ' || i FROM generate_series(1, 10) i;
+INSERT INTO test_all VALUES (11, 'row 11');
+DELETE FROM test_all WHERE id <= 5;
+
+-- Manifests carried over by later snapshots are listed for each of them
+SELECT count(*) AS manifests,
+       count(DISTINCT path) AS paths,
+       count(DISTINCT reference_snapshot_id) AS snapshots
+FROM lakehouse.all_manifests('test_all'::regclass);
+
+SELECT s.operation, m.content, count(*) AS manifests
+FROM lakehouse.all_manifests('test_all'::regclass) m
+JOIN lakehouse.snapshots('test_all'::regclass) s
+    ON m.reference_snapshot_id = s.snapshot_id
+GROUP BY 1, 2
+ORDER BY 1, 2;
+
+--
+-- Test 2: Files and entries of all snapshots
+--
+-- Manifests shared between snapshots are read once
+SELECT content, count(*) AS files, sum(record_count) AS records
+FROM lakehouse.all_files('test_all'::regclass)
+GROUP BY content
+ORDER BY content;
+
+-- Data and delete files are also listed apart
+SELECT content, count(*) AS files, sum(record_count) AS records
+FROM lakehouse.all_data_files('test_all'::regclass)
+GROUP BY content;
+SELECT content, count(*) AS files, sum(record_count) AS records
+FROM lakehouse.all_delete_files('test_all'::regclass)
+GROUP BY content;
+
+SELECT status, data_file->>'content' AS content, count(*) AS entries
+FROM lakehouse.all_entries('test_all'::regclass)
+GROUP BY 1, 2
+ORDER BY 1, 2;
+
+-- The files of the current snapshot are among them
+SELECT count(*) AS missing
+FROM (
+    SELECT file_path FROM lakehouse.files('test_all'::regclass)
+    EXCEPT
+    SELECT file_path FROM lakehouse.all_files('test_all'::regclass)
+) f;
+
+DROP TABLE test_all;
+
+--
+-- Test 3: Tables of other access methods
+--
+CREATE TABLE test_all_heap (id int);
+SELECT count(*) FROM lakehouse.all_manifests('test_all_heap'::regclass);
+DROP TABLE test_all_heap;
@@ -0,0 +1,104 @@
+-- table_metadata_inspect_test.sql
+-- Test the files, entries, history, refs, partitions and metadata log entries of iceberg tables
+DROP EXTENSION IF EXISTS pg_iceberg CASCADE;
+CREATE EXTENSION pg_iceberg;
+
+--
+-- Test 0: A table without snapshot
+--
+CREATE TABLE test_inspect (
+    id int,
+    grp int
+) USING iceberg WITH (
+    partitioning = 'grp'
+);
+SELECT count(*) FROM lakehouse.files('test_inspect'::regclass);
+SELECT count(*) FROM lakehouse.entries('test_inspect'::regclass);
+SELECT count(*) FROM lakehouse.history('test_inspect'::regclass);
+SELECT count(*) FROM lakehouse.refs('test_inspect'::regclass);
+SELECT count(*) FROM lakehouse.partitions('test_inspect'::regclass);
+
+--
+-- Test 1: Files
+--
+INSERT INTO test_inspect SELECT i, i % 3 + 1 FROM generate_series(1, 9) i;
+
+SELECT content, file_format, spec_id, partition, record_count
+FROM lakehouse.files('test_inspect'::regclass)
+ORDER BY partition->>'grp';
+
+-- Readable metrics of each column
+SELECT DISTINCT jsonb_object_keys(readable_metrics) AS column_name
+FROM lakehouse.files('test_inspect'::regclass)
+ORDER BY column_name;
+
+--
+-- Test 2: Entries and partitions with deletes
+--
+DELETE FROM test_inspect WHERE id = 1;
+
+SELECT data_file->>'content' AS content, count(*) AS entries,
+       bool_and(snapshot_id IS NOT NULL) AS has_snapshot
+FROM lakehouse.entries('test_inspect'::regclass)
+GROUP BY 1
+ORDER BY 1;
+
+SELECT partition, spec_id, record_count, file_count,
+       position_delete_record_count, position_delete_file_count
+FROM lakehouse.partitions('test_inspect'::regclass)
+ORDER BY partition->>'grp';
+
+-- Data and delete files are also listed apart
+SELECT content, count(*) AS files, sum(record_count) AS records
+FROM lakehouse.data_files('test_inspect'::regclass)
+GROUP BY content;
+SELECT content, count(*) AS files, sum(record_count) AS records
+FROM lakehouse.delete_files('test_inspect'::regclass)
+GROUP BY content;
+
+-- The data and delete files fill the pages the table's size is reported in
+ANALYZE test_inspect;
+SELECT c.relpages = ceil(sum(f.file_size_in_bytes) / 8192.0) AS pages_match
+FROM pg_class c, lakehouse.files('test_inspect'::regclass) f
+WHERE c.relname = 'test_inspect'
+GROUP BY c.relpages;
+
+--
+-- Test 3: History, refs and metadata log entries
+--
+SELECT count(*) AS snapshots, bool_and(is_current_ancestor) AS ancestors
+FROM lakehouse.history('test_inspect'::regclass);
+
+SELECT name, ref_type, min_snapshots_to_keep
+FROM lakehouse.refs('test_inspect'::regclass);
+
+-- The current metadata file comes last, with the current snapshot
+SELECT e.latest_snapshot_id = r.snapshot_id AS current
+FROM lakehouse.metadata_log_entries('test_inspect'::regclass) e,
+     lakehouse.refs('test_inspect'::regclass) r
+WHERE r.name = 'main'
+ORDER BY e.timestamp DESC
+LIMIT 1;
+
+DROP TABLE test_inspect;
+
+--
+-- Test 4: Unpartitioned tables
+--
+CREATE TABLE test_inspect_plain (id int) USING iceberg;
+INSERT INTO test_inspect_plain VALUES (1), (2);
+
+SELECT partition IS NULL AS no_partition, record_count
+FROM lakehouse.files('test_inspect_plain'::regclass);
+SELECT partition IS NULL AS no_partition, spec_id, record_count, file_count
+FROM lakehouse.partitions('test_inspect_plain'::regclass);
+
+DROP TABLE test_inspect_plain;
+
+--
+-- Test 5: Tables of other access methods
+--
+CREATE TABLE test_inspect_heap (id int);
+SELECT count(*) FROM lakehouse.files('test_inspect_heap'::regclass);
+SELECT count(*) FROM lakehouse.data_files('test_inspect_heap'::regclass);
+DROP TABLE test_inspect_heap;
@@ -0,0 +1,108 @@
+-- table_metadata_tables_test.sql
+-- Test the metadata tables of iceberg tables
+DROP EXTENSION IF EXISTS pg_iceberg CASCADE;
+CREATE EXTENSION pg_iceberg;
+
+--
+-- Test 0: A table without snapshot
+--
+CREATE TABLE test_metadata (id int, name text) USING iceberg;
+SELECT count(*) FROM lakehouse.snapshots('test_metadata'::regclass);
+SELECT count(*) FROM lakehouse.manifests('test_metadata'::regclass);
+
+--
+-- Test 1: Snapshots
+--
+INSERT INTO test_metadata SELECT i, 'row ' || i FROM generate_series(1, 10) i;
+INSERT INTO test_metadata VALUES (11, 'row 11');
+DELETE FROM test_metadata WHERE id <= 5;
+
+SELECT operation,
+       parent_id IS NULL AS first,
+       summary->>'added-records' AS added_records,
+       summary->>'added-position-deletes' AS added_deletes,
+       manifest_list LIKE '%/metadata/snap-%.avro' AS manifest_list
+FROM lakehouse.snapshots('test_metadata'::regclass)
+ORDER BY committed_at;
+
+-- Each snapshot but the first has the previous one as parent
+SELECT count(*) AS parents
+FROM lakehouse.snapshots('test_metadata'::regclass) s
+JOIN lakehouse.snapshots('test_metadata'::regclass) p
+    ON s.parent_id = p.snapshot_id AND s.committed_at >= p.committed_at;
+
+--
+-- Test 2: Manifests of the current snapshot
+--
+SELECT content, count(*) AS manifests, bool_and(path LIKE '%/metadata/%.avro') AS in_metadata
+FROM lakehouse.manifests('test_metadata'::regclass)
+GROUP BY content
+ORDER BY content;
+
+SELECT DISTINCT partition_summaries
+FROM lakehouse.manifests('test_metadata'::regclass);
+
+-- Data manifests count data files, delete manifests delete files
+SELECT content,
+       sum(added_data_files_count) AS added_data_files,
+       sum(added_delete_files_count) AS added_delete_files
+FROM lakehouse.manifests('test_metadata'::regclass)
+GROUP BY content
+ORDER BY content;
+
+-- Changes of the current transaction are visible
+BEGIN;
+INSERT INTO test_metadata VALUES (12, 'row 12');
+SELECT count(*) FROM lakehouse.snapshots('test_metadata'::regclass);
+SELECT count(*) FROM lakehouse.manifests('test_metadata'::regclass);
+ROLLBACK;
+SELECT count(*) FROM lakehouse.snapshots('test_metadata'::regclass);
+
+DROP TABLE test_metadata;
+
+--
+-- Test 3: Partition summaries
+--
+CREATE TABLE test_metadata_partitioned (
+    id int,
+    grp int
+) USING iceberg WITH (
+    partitioning = 'grp'
+);
+INSERT INTO test_metadata_partitioned SELECT i, i % 3 + 1 FROM generate_series(1, 9) i;
+
+SELECT content, partition_summaries
+FROM lakehouse.manifests('test_metadata_partitioned'::regclass);
+
+DROP TABLE test_metadata_partitioned;
+
+--
+-- Test 4: Tables of other access methods
+--
+CREATE TABLE test_metadata_heap (id int);
+SELECT count(*) FROM lakehouse.snapshots('test_metadata_heap'::regclass);
+DROP TABLE test_metadata_heap;
+
+--
+-- Test 5: Reading metadata tables requires the SELECT privilege on the table
+--
+CREATE TABLE test_metadata_private (id int) USING iceberg;
+INSERT INTO test_metadata_private VALUES (1);
+CREATE ROLE test_metadata_user;
+GRANT USAGE ON SCHEMA lakehouse TO test_metadata_user;
+
+SET ROLE test_metadata_user;
+SELECT count(*) FROM lakehouse.snapshots('test_metadata_private'::regclass);
+SELECT count(*) FROM lakehouse.manifests('test_metadata_private'::regclass);
+SELECT count(*) FROM lakehouse.files('test_metadata_private'::regclass);
+SELECT count(*) FROM lakehouse.all_entries('test_metadata_private'::regclass);
+RESET ROLE;
+
+GRANT SELECT ON test_metadata_private TO test_metadata_user;
+SET ROLE test_metadata_user;
+SELECT count(*) FROM lakehouse.snapshots('test_metadata_private'::regclass);
+RESET ROLE;
+
+DROP TABLE test_metadata_private;
+DROP OWNED BY test_metadata_user;
+DROP ROLE test_metadata_user;
@@ -0,0 +1,130 @@
+-- table_option_alter_test.sql
+-- Test ALTER TABLE SET/RESET of Iceberg table options
+DROP EXTENSION IF EXISTS pg_iceberg CASCADE;
+CREATE EXTENSION pg_iceberg;
+
+--
+-- Test 0: Set options of a table created without options
+--
+CREATE TABLE test_alter_opts (
+    id int,
+    name text
+) USING iceberg;
+
+INSERT INTO test_alter_opts VALUES (1, 'one'), (2, 'two');
+
+ALTER TABLE test_alter_opts SET ("write.parquet.compression-codec" = 'snappy');
+SELECT options FROM lakehouse.table_options
+WHERE relid = 'test_alter_opts'::regclass;
+
+-- Writes use the new codec, existing data files stay readable
+INSERT INTO test_alter_opts VALUES (3, 'three');
+SELECT * FROM test_alter_opts ORDER BY id;
+
+ALTER TABLE test_alter_opts SET (
+    "write.parquet.compression-codec" = 'gzip',
+    "write.target-file-size-bytes" = 4294967296
+);
+SELECT options FROM lakehouse.table_options
+WHERE relid = 'test_alter_opts'::regclass;
+
+INSERT INTO test_alter_opts VALUES (4, 'four');
+SELECT * FROM test_alter_opts ORDER BY id;
+
+--
+-- Test 1: Reset options to their defaults
+--
+ALTER TABLE test_alter_opts RESET ("write.parquet.compression-codec");
+SELECT options FROM lakehouse.table_options
+WHERE relid = 'test_alter_opts'::regclass;
+
+-- Options and regular storage parameters in one statement
+ALTER TABLE test_alter_opts
+    RESET ("write.target-file-size-bytes"),
+    SET (autovacuum_enabled = false);
+SELECT options FROM lakehouse.table_options
+WHERE relid = 'test_alter_opts'::regclass;
+SELECT reloptions FROM pg_class WHERE oid = 'test_alter_opts'::regclass;
+
+INSERT INTO test_alter_opts VALUES (5, 'five');
+SELECT count(*) FROM test_alter_opts;
+
+-- Rolled back changes leave the options alone
+BEGIN;
+ALTER TABLE test_alter_opts SET ("write.parquet.compression-codec" = 'lz4');
+INSERT INTO test_alter_opts VALUES (6, 'six');
+ROLLBACK;
+SELECT options FROM lakehouse.table_options
+WHERE relid = 'test_alter_opts'::regclass;
+SELECT count(*) FROM test_alter_opts;
+
+DROP TABLE test_alter_opts;
+
+
+--
+-- Test 2: Upgrade the format version
+--
+CREATE TABLE test_alter_format (
+    id int
+) USING iceberg WITH (
+    "format-version" = 1
+);
+
+INSERT INTO test_alter_format VALUES (1), (2);
+ALTER TABLE test_alter_format SET ("format-version" = 2);
+SELECT options FROM lakehouse.table_options
+WHERE relid = 'test_alter_format'::regclass;
+
+-- Version 2 tables support row-level deletes
+DELETE FROM test_alter_format WHERE id = 1;
+SELECT * FROM test_alter_format;
+
+DROP TABLE test_alter_format;
+
+
+--
+-- Test 3: Replace the sort order
+--
+CREATE TABLE test_alter_sort (
+    id int,
+    score int
+) USING iceberg;
+
+ALTER TABLE test_alter_sort SET (sort_order = 'score DESC');
+INSERT INTO test_alter_sort VALUES (1, 20), (2, 40), (3, 10), (4, 30);
+
+-- A single data file is scanned in the order it was written
+SELECT * FROM test_alter_sort;
+
+DROP TABLE test_alter_sort;
+
+
+--
+-- Test 4: Invalid changes
+--
+CREATE TABLE test_alter_invalid (
+    id int,
+    ts timestamp
+) USING iceberg WITH (
+    partitioning = 'day(ts)'
+);
+
+-- Invalid value
+ALTER TABLE test_alter_invalid SET ("write.parquet.compression-codec" = 'none');
+
+-- The partitioning cannot be changed
+ALTER TABLE test_alter_invalid SET (partitioning = 'id');
+ALTER TABLE test_alter_invalid RESET (partitioning);
+
+-- Unknown sort column
+ALTER TABLE test_alter_invalid SET (sort_order = 'created');
+
+SELECT options FROM lakehouse.table_options
+WHERE relid = 'test_alter_invalid'::regclass;
+
+DROP TABLE test_alter_invalid;
+
+-- Iceberg options are not storage parameters of other tables
+CREATE TABLE test_alter_heap (id int);
+ALTER TABLE test_alter_heap SET ("write.parquet.compression-codec" = 'snappy');
+DROP TABLE test_alter_heap;
@@ -0,0 +1,78 @@
+-- table_parallel_scan_test.sql
+-- Test parallel sequential scans of iceberg tables
+DROP EXTENSION IF EXISTS pg_iceberg CASCADE;
+CREATE EXTENSION pg_iceberg;
+
+SET parallel_setup_cost = 0;
+SET parallel_tuple_cost = 0;
+SET min_parallel_table_scan_size = 0;
+SET max_parallel_workers_per_gather = 2;
+
+-- The plan with the number of planned workers, which depends on the table size
+CREATE FUNCTION explain_parallel(query text) RETURNS SETOF text
+LANGUAGE plpgsql AS $$
+DECLARE
+    line text;
+BEGIN
+    FOR line IN EXECUTE 'EXPLAIN (COSTS OFF) ' || query LOOP
+        RETURN NEXT regexp_replace(line, 'Workers Planned: \d+', 'Workers Planned: N');
+    END LOOP;
+END;
+$$;
+
+--
+-- Test 0: Rows of several data files are each returned once
+--
+CREATE TABLE test_parallel (id int, grp int, name text) USING iceberg;
+
+INSERT INTO test_parallel SELECT i, i % 3, 'row ' || i FROM generate_series(1, 1000) i;
+INSERT INTO test_parallel SELECT i, i % 3, 'row ' || i FROM generate_series(1001, 2000) i;
+INSERT INTO test_parallel SELECT i, i % 3, 'row ' || i FROM generate_series(2001, 3000) i;
+INSERT INTO test_parallel SELECT i, i % 3, 'row ' || i FROM generate_series(3001, 4000) i;
+
+SELECT explain_parallel('SELECT count(*) FROM test_parallel');
+SELECT count(*) AS row_count, count(DISTINCT id) AS distinct_ids, sum(id) AS id_sum
+FROM test_parallel;
+SELECT grp, count(*) AS row_count, min(id) AS min_id, max(id) AS max_id
+FROM test_parallel GROUP BY grp ORDER BY grp;
+SELECT id, name FROM test_parallel WHERE id % 997 = 0 ORDER BY id;
+SELECT count(*) AS matching FROM test_parallel WHERE name LIKE 'row 1%';
+
+--
+-- Test 1: Deleted rows are not returned
+--
+DELETE FROM test_parallel WHERE id % 10 = 0;
+
+SELECT count(*) AS row_count, sum(id) AS id_sum FROM test_parallel;
+
+--
+-- Test 2: Changes staged by the transaction are seen by the workers
+--
+BEGIN;
+INSERT INTO test_parallel SELECT i, i % 3, 'row ' || i FROM generate_series(4001, 5000) i;
+SELECT count(*) AS row_count, sum(id) AS id_sum FROM test_parallel;
+DELETE FROM test_parallel WHERE id > 4500;
+SELECT count(*) AS row_count, max(id) AS max_id FROM test_parallel;
+ROLLBACK;
+
+SELECT count(*) AS row_count, max(id) AS max_id FROM test_parallel;
+
+--
+-- Test 3: Parallel scans joined with another table
+--
+CREATE TABLE test_parallel_groups (grp int, label text) USING iceberg;
+INSERT INTO test_parallel_groups VALUES (0, 'zero'), (1, 'one'), (2, 'two');
+
+SELECT g.label, count(*) AS row_count
+FROM test_parallel_groups g JOIN test_parallel p ON p.grp = g.grp
+GROUP BY g.label ORDER BY g.label;
+
+-- Cleanup
+DROP TABLE test_parallel;
+DROP TABLE test_parallel_groups;
+DROP FUNCTION explain_parallel(text);
+
+RESET parallel_setup_cost;
+RESET parallel_tuple_cost;
+RESET min_parallel_table_scan_size;
+RESET max_parallel_workers_per_gather;
@@ -0,0 +1,111 @@
+-- table_partition_test.sql
+-- Test partitioned iceberg tables
+DROP EXTENSION IF EXISTS pg_iceberg CASCADE;
+CREATE EXTENSION pg_iceberg;
+
+--
+-- Test 0: Identity partitioning
+--
+CREATE TABLE test_part_identity (
+    id int,
+    region text,
+    amount numeric(10, 2)
+) USING iceberg WITH (
+    partitioning = 'region'
+);
+
+INSERT INTO test_part_identity VALUES
+    (1, 'us', 10.50), (2, 'eu', 20.00), (3, 'us', 30.25),
+    (4, 'apac', 40.00), (5, 'eu', 50.75), (6, NULL, 60.00);
+
+SELECT * FROM test_part_identity ORDER BY id;
+SELECT region, count(*), sum(amount) FROM test_part_identity
+GROUP BY region ORDER BY region;
+SELECT * FROM test_part_identity WHERE region = 'eu' ORDER BY id;
+SELECT * FROM test_part_identity WHERE region IS NULL;
+
+DROP TABLE test_part_identity;
+
+
+--
+-- Test 1: Bucket and time transforms
+--
+CREATE TABLE test_part_transforms (
+    id int,
+    ts timestamp,
+    name text
+) USING iceberg WITH (
+    partitioning = 'bucket(4, id), day(ts)'
+);
+
+INSERT INTO test_part_transforms
+SELECT i, '2024-01-01 00:00:00'::timestamp + (i || ' hours')::interval, 'row ' || i
+FROM generate_series(1, 50) i;
+
+SELECT count(*), min(id), max(id) FROM test_part_transforms;
+SELECT ts::date AS day, count(*) FROM test_part_transforms
+GROUP BY ts::date ORDER BY day;
+SELECT * FROM test_part_transforms
+WHERE ts >= '2024-01-02' AND ts < '2024-01-02 04:00' ORDER BY id;
+SELECT * FROM test_part_transforms WHERE id = 42;
+
+DROP TABLE test_part_transforms;
+
+
+--
+-- Test 2: DELETE and UPDATE across partitions
+--
+CREATE TABLE test_part_modify (
+    id int,
+    category text,
+    value int
+) USING iceberg WITH (
+    partitioning = 'truncate(1, category), bucket(2, id)'
+);
+
+INSERT INTO test_part_modify
+SELECT i, (ARRAY['apple', 'banana', 'cherry'])[i % 3 + 1], i * 10
+FROM generate_series(1, 12) i;
+
+DELETE FROM test_part_modify WHERE id % 4 = 0;
+SELECT * FROM test_part_modify ORDER BY id;
+
+-- Updated rows move to the partition of their new values
+UPDATE test_part_modify SET category = 'avocado', value = value + 1
+WHERE category = 'banana';
+SELECT * FROM test_part_modify ORDER BY id;
+
+DELETE FROM test_part_modify WHERE category = 'avocado';
+SELECT category, count(*) FROM test_part_modify GROUP BY category ORDER BY category;
+
+DROP TABLE test_part_modify;
+
+
+--
+-- Test 3: Invalid partitioning
+--
+-- Unknown column
+CREATE TABLE test_part_invalid (
+    id int,
+    ts timestamp
+) USING iceberg WITH (
+    partitioning = 'day(created)'
+);
+
+-- Transform not applicable to the column type
+CREATE TABLE test_part_invalid (
+    id int,
+    ts timestamp
+) USING iceberg WITH (
+    partitioning = 'day(id)'
+);
+
+-- Malformed term
+CREATE TABLE test_part_invalid (
+    id int,
+    ts timestamp
+) USING iceberg WITH (
+    partitioning = 'bucket(id)'
+);
+
+SELECT count(*) AS invalid_tables FROM pg_class WHERE relname = 'test_part_invalid';
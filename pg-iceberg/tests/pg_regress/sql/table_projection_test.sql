@@ -0,0 +1,67 @@
+-- table_projection_test.sql
+-- Test scans reading only the referenced columns of iceberg tables
+DROP EXTENSION IF EXISTS pg_iceberg CASCADE;
+CREATE EXTENSION pg_iceberg;
+
+--
+-- Test 0: Queries referencing a subset of the columns
+--
+CREATE TABLE test_projection (id int, name text, score float8, note text, flag bool) USING iceberg;
+
+INSERT INTO test_projection
+SELECT i, 'row ' || i, i * 1.5, 'note ' || i, i % 2 = 0 FROM generate_series(1, 8) i;
+
+SELECT name FROM test_projection ORDER BY id;
+SELECT id, note FROM test_projection WHERE score > 6 ORDER BY id;
+SELECT count(*) AS row_count FROM test_projection;
+SELECT count(*) AS flagged FROM test_projection WHERE flag;
+SELECT flag, sum(score) AS total FROM test_projection GROUP BY flag ORDER BY flag;
+SELECT max(note) AS max_note FROM test_projection;
+
+-- Expressions and whole-row references
+SELECT upper(name) || '/' || note AS label FROM test_projection WHERE id < 3 ORDER BY id;
+SELECT t FROM test_projection t WHERE id = 1;
+SELECT row_to_json(t) AS json FROM test_projection t WHERE id = 2;
+
+--
+-- Test 1: The same table scanned with different columns
+--
+SELECT a.id, a.name, b.note
+FROM test_projection a JOIN test_projection b ON a.id = b.id + 1
+WHERE b.flag
+ORDER BY a.id;
+
+SELECT id, (SELECT max(score) FROM test_projection) AS max_score
+FROM test_projection WHERE id <= 2 ORDER BY id;
+
+SELECT id FROM test_projection
+WHERE score > (SELECT avg(score) FROM test_projection WHERE flag)
+ORDER BY id;
+
+-- A cursor reading its rows after another scan of the table
+BEGIN;
+DECLARE c CURSOR FOR SELECT id, note FROM test_projection ORDER BY id;
+FETCH 2 FROM c;
+SELECT name FROM test_projection WHERE id = 8;
+FETCH 2 FROM c;
+CLOSE c;
+COMMIT;
+
+-- Views and subqueries
+CREATE VIEW test_projection_view AS SELECT id, score FROM test_projection WHERE flag;
+SELECT score FROM test_projection_view ORDER BY id;
+SELECT * FROM (SELECT id, name, note FROM test_projection) s WHERE s.id = 5;
+DROP VIEW test_projection_view;
+
+--
+-- Test 2: Modifications keep the columns that are not referenced
+--
+UPDATE test_projection SET score = score + 100 WHERE name = 'row 3';
+DELETE FROM test_projection WHERE note = 'note 4';
+SELECT * FROM test_projection ORDER BY id;
+
+UPDATE test_projection SET note = 'changed' WHERE id = 5 RETURNING *;
+DELETE FROM test_projection WHERE id = 6 RETURNING *;
+SELECT * FROM test_projection ORDER BY id;
+
+DROP TABLE test_projection;
@@ -0,0 +1,99 @@
+-- table_scan_test.sql
+-- Test sequential scans of iceberg tables
+DROP EXTENSION IF EXISTS pg_iceberg CASCADE;
+CREATE EXTENSION pg_iceberg;
+
+--
+-- Test 0: Scan a newly created table without snapshots
+--
+CREATE TABLE test_scan_empty (id int, name text) USING iceberg;
+
+SELECT * FROM test_scan_empty;
+SELECT count(*) AS row_count FROM test_scan_empty;
+
+DROP TABLE test_scan_empty;
+
+
+--
+-- Test 1: Scan a table covering all supported column types
+--
+CREATE TABLE test_scan_types (
+    c_bool boolean,
+    c_int2 smallint,
+    c_int4 integer,
+    c_int8 bigint,
+    c_float4 real,
+    c_float8 double precision,
+    c_numeric numeric(10, 2),
+    c_text text,
+    c_varchar varchar(32),
+    c_date date,
+    c_time time,
+    c_timestamp timestamp,
+    c_timestamptz timestamptz,
+    c_bytea bytea,
+    c_uuid uuid,
+    c_jsonb jsonb
+) USING iceberg;
+
+SELECT * FROM test_scan_types;
+SELECT c_int4, c_text FROM test_scan_types WHERE c_int4 > 0;
+
+DROP TABLE test_scan_types;
+
+
+--
+-- Test 2: Rescan through a nested loop join
+--
+CREATE TABLE test_scan_outer (id int) USING iceberg;
+CREATE TABLE test_scan_inner (id int) USING iceberg;
+
+SET enable_hashjoin = off;
+SET enable_mergejoin = off;
+SELECT o.id, i.id FROM test_scan_outer o JOIN test_scan_inner i ON o.id = i.id;
+RESET enable_hashjoin;
+RESET enable_mergejoin;
+
+DROP TABLE test_scan_outer;
+DROP TABLE test_scan_inner;
+
+
+--
+-- Test 3: Quals prune the data files read
+--
+-- Each insert writes a data file: ids 1-100, 101-200 and 201-300.
+CREATE TABLE test_scan_prune (id int, name text) USING iceberg;
+INSERT INTO test_scan_prune SELECT i, 'row ' || i FROM generate_series(1, 100) i;
+INSERT INTO test_scan_prune SELECT i, 'row ' || i FROM generate_series(101, 200) i;
+INSERT INTO test_scan_prune SELECT i, 'row ' || i FROM generate_series(201, 300) i;
+
+SET max_parallel_workers_per_gather = 0;
+
+-- Only the last file is read, the filter removes its rows up to 250
+EXPLAIN (ANALYZE, COSTS OFF, TIMING OFF, SUMMARY OFF)
+SELECT * FROM test_scan_prune WHERE id > 250;
+
+-- The constant may be on the left
+EXPLAIN (ANALYZE, COSTS OFF, TIMING OFF, SUMMARY OFF)
+SELECT * FROM test_scan_prune WHERE 150 >= id;
+
+-- Only the files holding a listed value are read
+EXPLAIN (ANALYZE, COSTS OFF, TIMING OFF, SUMMARY OFF)
+SELECT * FROM test_scan_prune WHERE id IN (5, 150);
+
+-- No file has null ids
+EXPLAIN (ANALYZE, COSTS OFF, TIMING OFF, SUMMARY OFF)
+SELECT * FROM test_scan_prune WHERE id IS NULL;
+
+-- Quals on expressions read all files
+EXPLAIN (ANALYZE, COSTS OFF, TIMING OFF, SUMMARY OFF)
+SELECT * FROM test_scan_prune WHERE id + 0 > 250;
+
+-- Rows of pruned scans keep their TIDs
+DELETE FROM test_scan_prune WHERE id > 295;
+UPDATE test_scan_prune SET name = 'updated' WHERE id = 290;
+SELECT * FROM test_scan_prune WHERE id > 285 ORDER BY id;
+
+RESET max_parallel_workers_per_gather;
+
+DROP TABLE test_scan_prune;
@@ -0,0 +1,86 @@
+-- table_size_test.sql
+-- Test the size estimates of iceberg tables
+DROP EXTENSION IF EXISTS pg_iceberg CASCADE;
+CREATE EXTENSION pg_iceberg;
+
+-- The rows and pages the planner estimates for a sequential scan of a table.
+-- Without quals, the scan costs a page per page and 0.01 per row.
+CREATE FUNCTION scan_estimate(rel regclass, OUT estimated_rows bigint,
+                              OUT estimated_pages bigint)
+LANGUAGE plpgsql AS $$
+DECLARE
+    plan json;
+BEGIN
+    EXECUTE format('EXPLAIN (FORMAT JSON) SELECT * FROM %s', rel) INTO plan;
+    estimated_rows := (plan -> 0 -> 'Plan' ->> 'Plan Rows')::bigint;
+    estimated_pages := round((plan -> 0 -> 'Plan' ->> 'Total Cost')::numeric
+                             - (estimated_rows * 0.01));
+END;
+$$;
+
+SET seq_page_cost = 1;
+SET cpu_tuple_cost = 0.01;
+
+--
+-- Test 0: A table without snapshot is empty
+--
+CREATE TABLE test_size (id int, name text) USING iceberg;
+
+-- The planner estimates at least one row
+SELECT * FROM scan_estimate('test_size');
+
+-- pg_relation_size measures the relation's local files, which hold no data
+SELECT pg_relation_size('test_size') AS size,
+       pg_total_relation_size('test_size') AS total_size;
+
+--
+-- Test 1: INSERT adds its rows and data file
+--
+INSERT INTO test_size SELECT i, 'row ' || i FROM generate_series(1, 2000) i;
+
+SELECT estimated_rows, estimated_pages > 0 AS has_pages
+FROM scan_estimate('test_size');
+
+-- The estimate follows the metadata location of every commit
+INSERT INTO test_size SELECT i, 'row ' || i FROM generate_series(2001, 3000) i;
+
+SELECT estimated_rows, estimated_pages > 0 AS has_pages
+FROM scan_estimate('test_size');
+
+SELECT pg_relation_size('test_size') AS size,
+       pg_total_relation_size('test_size') AS total_size;
+
+--
+-- Test 2: DELETE subtracts its rows and adds its delete file
+--
+DELETE FROM test_size WHERE id % 3 = 0;
+
+SELECT estimated_rows, estimated_pages > 0 AS has_pages
+FROM scan_estimate('test_size');
+
+--
+-- Test 3: Changes staged by the transaction are included
+--
+BEGIN;
+INSERT INTO test_size SELECT i, 'row ' || i FROM generate_series(3001, 4000) i;
+SELECT estimated_rows FROM scan_estimate('test_size');
+ROLLBACK;
+
+SELECT estimated_rows FROM scan_estimate('test_size');
+
+--
+-- Test 4: TRUNCATE removes all rows and files
+--
+TRUNCATE test_size;
+
+SELECT estimated_rows, estimated_pages FROM scan_estimate('test_size');
+
+SELECT pg_relation_size('test_size') AS size,
+       pg_total_relation_size('test_size') AS total_size;
+
+-- Cleanup
+DROP TABLE test_size;
+DROP FUNCTION scan_estimate(regclass);
+
+RESET seq_page_cost;
+RESET cpu_tuple_cost;
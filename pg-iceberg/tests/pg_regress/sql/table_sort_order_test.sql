@@ -0,0 +1,91 @@
+-- table_sort_order_test.sql
+-- Test sorted iceberg tables
+DROP EXTENSION IF EXISTS pg_iceberg CASCADE;
+CREATE EXTENSION pg_iceberg;
+
+--
+-- Test 0: Rows are written in the sort order
+--
+CREATE TABLE test_sort_basic (
+    id int,
+    score int,
+    name text
+) USING iceberg WITH (
+    sort_order = 'score DESC NULLS LAST, id'
+);
+
+INSERT INTO test_sort_basic VALUES
+    (1, 50, 'a'), (2, NULL, 'b'), (3, 80, 'c'), (4, 50, 'd'),
+    (5, 10, 'e'), (6, NULL, 'f'), (7, 80, 'g');
+
+-- A single data file is scanned in the order it was written
+SELECT * FROM test_sort_basic;
+SELECT count(*), sum(score) FROM test_sort_basic;
+
+DELETE FROM test_sort_basic WHERE score IS NULL;
+UPDATE test_sort_basic SET score = score + 1 WHERE id = 5;
+SELECT * FROM test_sort_basic ORDER BY id;
+
+DROP TABLE test_sort_basic;
+
+
+--
+-- Test 1: Sorted and partitioned table
+--
+CREATE TABLE test_sort_partitioned (
+    id int,
+    region text
+) USING iceberg WITH (
+    partitioning = 'region',
+    sort_order = 'id DESC'
+);
+
+INSERT INTO test_sort_partitioned
+SELECT i, (ARRAY['us', 'eu'])[i % 2 + 1] FROM generate_series(1, 10) i;
+
+-- Each partition is written into a data file of its own
+SELECT * FROM test_sort_partitioned WHERE region = 'eu';
+SELECT * FROM test_sort_partitioned WHERE region = 'us';
+
+DROP TABLE test_sort_partitioned;
+
+
+--
+-- Test 2: Quoted column names
+--
+CREATE TABLE test_sort_quoted (
+    id int,
+    "Event Time" timestamp
+) USING iceberg WITH (
+    sort_order = '"Event Time" ASC NULLS FIRST'
+);
+
+INSERT INTO test_sort_quoted VALUES
+    (1, '2024-03-01 10:00'), (2, NULL), (3, '2024-01-15 08:30'),
+    (4, '2024-02-20 12:00');
+
+SELECT * FROM test_sort_quoted;
+
+DROP TABLE test_sort_quoted;
+
+
+--
+-- Test 3: Invalid sort orders
+--
+-- Unknown column
+CREATE TABLE test_sort_invalid (
+    id int,
+    ts timestamp
+) USING iceberg WITH (
+    sort_order = 'created DESC'
+);
+
+-- Malformed term
+CREATE TABLE test_sort_invalid (
+    id int,
+    ts timestamp
+) USING iceberg WITH (
+    sort_order = 'id ASC DESC'
+);
+
+SELECT count(*) AS invalid_tables FROM pg_class WHERE relname = 'test_sort_invalid';
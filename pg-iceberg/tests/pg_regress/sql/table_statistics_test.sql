@@ -0,0 +1,54 @@
+-- table_statistics_test.sql
+-- Test statistics files of iceberg tables
+DROP EXTENSION IF EXISTS pg_iceberg CASCADE;
+CREATE EXTENSION pg_iceberg;
+
+--
+-- Test 0: A table without snapshot has no statistics to compute
+--
+CREATE TABLE test_statistics (id int, grp int, name text) USING iceberg;
+SELECT lakehouse.compute_statistics('test_statistics'::regclass) IS NULL AS no_snapshot;
+
+--
+-- Test 1: Statistics are written to the metadata directory of the table
+--
+INSERT INTO test_statistics
+SELECT i, i % 3, 'row ' || i FROM generate_series(1, 1000) i;
+SELECT lakehouse.compute_statistics('test_statistics'::regclass) LIKE '%/metadata/%.stats'
+    AS written;
+
+--
+-- Test 2: Statistics of rows changed in the same transaction
+--
+BEGIN;
+DELETE FROM test_statistics WHERE grp = 0;
+SELECT lakehouse.compute_statistics('test_statistics'::regclass) LIKE '%/metadata/%.stats'
+    AS written;
+SELECT count(*) FROM test_statistics;
+COMMIT;
+SELECT count(*) FROM test_statistics;
+
+--
+-- Test 3: Statistics computed in an aborted transaction are discarded
+--
+BEGIN;
+SELECT lakehouse.compute_statistics('test_statistics'::regclass) IS NOT NULL AS written;
+ROLLBACK;
+SELECT count(*) FROM test_statistics;
+
+--
+-- Test 4: Computing statistics requires the MAINTAIN privilege on the table,
+-- before PostgreSQL 17 ownership of the table
+--
+CREATE ROLE test_statistics_user;
+GRANT USAGE ON SCHEMA lakehouse TO test_statistics_user;
+
+SET ROLE test_statistics_user;
+SELECT lakehouse.compute_statistics('test_statistics'::regclass);
+RESET ROLE;
+
+DROP OWNED BY test_statistics_user;
+DROP ROLE test_statistics_user;
+
+-- Cleanup
+DROP TABLE test_statistics;
@@ -0,0 +1,41 @@
+-- table_tid_test.sql
+-- Test tuple identifiers of iceberg table rows
+DROP EXTENSION IF EXISTS pg_iceberg CASCADE;
+CREATE EXTENSION pg_iceberg;
+
+--
+-- Test 0: ctid of scanned rows
+--
+CREATE TABLE test_tid (id int, name text) USING iceberg;
+INSERT INTO test_tid VALUES (1, 'one'), (2, 'two'), (3, 'three');
+
+SELECT ctid, * FROM test_tid ORDER BY id;
+
+
+--
+-- Test 1: TID scans
+--
+SELECT * FROM test_tid WHERE ctid = '(0,2)';
+SELECT * FROM test_tid WHERE ctid IN ('(0,1)', '(0,3)') ORDER BY id;
+
+-- TIDs that do not identify a row return nothing
+SELECT * FROM test_tid WHERE ctid = '(0,4)';
+SELECT * FROM test_tid WHERE ctid = '(5,1)';
+
+-- Deleted rows are not returned
+DELETE FROM test_tid WHERE id = 2;
+SELECT * FROM test_tid WHERE ctid = '(0,2)';
+SELECT ctid, * FROM test_tid ORDER BY id;
+
+-- TIDs of an earlier snapshot identify no row once the table changed
+INSERT INTO test_tid VALUES (4, 'four');
+SELECT * FROM test_tid WHERE ctid = '(0,1)';
+
+
+--
+-- Test 2: DELETE ... RETURNING fetches the deleted rows
+--
+DELETE FROM test_tid WHERE id = 3 RETURNING *;
+SELECT * FROM test_tid ORDER BY id;
+
+DROP TABLE test_tid;
@@ -0,0 +1,118 @@
+-- table_time_travel_test.sql
+-- Test reading iceberg tables as of a past snapshot
+DROP EXTENSION IF EXISTS pg_iceberg CASCADE;
+CREATE EXTENSION pg_iceberg;
+
+--
+-- Test 0: Read the snapshot that was current at a timestamp
+--
+CREATE TABLE test_time_travel (
+    id int,
+    name text
+) USING iceberg;
+
+INSERT INTO test_time_travel VALUES (1, 'one'), (2, 'two');
+
+SELECT pg_sleep(0.01);
+SELECT clock_timestamp() AS after_first_insert \gset
+SELECT pg_sleep(0.01);
+
+DELETE FROM test_time_travel WHERE id = 1;
+INSERT INTO test_time_travel VALUES (3, 'three');
+
+-- The current snapshot
+SELECT * FROM test_time_travel ORDER BY id;
+
+SET iceberg.as_of_timestamp = :'after_first_insert';
+SELECT * FROM test_time_travel ORDER BY id;
+SELECT count(*) FROM test_time_travel WHERE id = 1;
+
+-- The table had no snapshot yet
+SET iceberg.as_of_timestamp = '2000-01-01 00:00:00+00';
+SELECT * FROM test_time_travel;
+
+RESET iceberg.as_of_timestamp;
+SELECT * FROM test_time_travel ORDER BY id;
+
+
+--
+-- Test 1: Read a past snapshot with its schema
+--
+ALTER TABLE test_time_travel ADD COLUMN score int;
+ALTER TABLE test_time_travel RENAME COLUMN name TO label;
+INSERT INTO test_time_travel VALUES (4, 'four', 40);
+
+-- Added columns read as NULL, renamed columns read their field
+BEGIN;
+SET LOCAL iceberg.as_of_timestamp = :'after_first_insert';
+SELECT * FROM test_time_travel ORDER BY id;
+SELECT label FROM test_time_travel WHERE score IS NULL ORDER BY id;
+COMMIT;
+
+-- SET LOCAL ends with the transaction
+SELECT * FROM test_time_travel ORDER BY id;
+
+
+--
+-- Test 2: Read the snapshot of a branch
+--
+SET iceberg.ref = 'main';
+SELECT * FROM test_time_travel ORDER BY id;
+RESET iceberg.ref;
+
+
+--
+-- Test 3: Tables cannot be modified while reading a past snapshot
+--
+SET iceberg.as_of_timestamp = :'after_first_insert';
+INSERT INTO test_time_travel VALUES (5, 'five', 50);
+DELETE FROM test_time_travel WHERE id = 2;
+UPDATE test_time_travel SET score = 0 WHERE id = 2;
+RESET iceberg.as_of_timestamp;
+
+SELECT * FROM test_time_travel ORDER BY id;
+
+
+--
+-- Test 4: Invalid settings
+--
+SET iceberg.snapshot_id = 'latest';
+SELECT * FROM test_time_travel;
+
+SET iceberg.snapshot_id = '1';
+SELECT * FROM test_time_travel;
+RESET iceberg.snapshot_id;
+
+SET iceberg.ref = 'missing';
+SELECT * FROM test_time_travel;
+
+SET iceberg.snapshot_id = '1';
+SELECT * FROM test_time_travel;
+RESET iceberg.snapshot_id;
+RESET iceberg.ref;
+
+SET iceberg.as_of_timestamp = 'infinity';
+SELECT * FROM test_time_travel;
+RESET iceberg.as_of_timestamp;
+
+-- Other tables are not affected
+CREATE TABLE test_time_travel_heap (id int);
+INSERT INTO test_time_travel_heap VALUES (1);
+SET iceberg.snapshot_id = '1';
+SELECT * FROM test_time_travel_heap;
+RESET iceberg.snapshot_id;
+
+
+--
+-- Test 5: ANALYZE samples the current snapshot
+--
+SET iceberg.as_of_timestamp = :'after_first_insert';
+ANALYZE test_time_travel;
+RESET iceberg.as_of_timestamp;
+
+SELECT reltuples FROM pg_class WHERE relname = 'test_time_travel';
+SELECT histogram_bounds FROM pg_stats
+WHERE tablename = 'test_time_travel' AND attname = 'id';
+
+DROP TABLE test_time_travel_heap;
+DROP TABLE test_time_travel;
@@ -0,0 +1,77 @@
+-- table_truncate_test.sql
+-- Test TRUNCATE of iceberg tables
+DROP EXTENSION IF EXISTS pg_iceberg CASCADE;
+CREATE EXTENSION pg_iceberg;
+
+--
+-- Test 0: TRUNCATE removes all rows
+--
+CREATE TABLE test_truncate (id int, name text) USING iceberg;
+INSERT INTO test_truncate SELECT i, 'row ' || i FROM generate_series(1, 100) i;
+INSERT INTO test_truncate SELECT i, 'row ' || i FROM generate_series(101, 200) i;
+DELETE FROM test_truncate WHERE id % 2 = 0;
+SELECT count(*) FROM test_truncate;
+TRUNCATE test_truncate;
+SELECT count(*) FROM test_truncate;
+
+--
+-- Test 1: Rows inserted after TRUNCATE are read
+--
+INSERT INTO test_truncate VALUES (1, 'one'), (2, 'two');
+SELECT * FROM test_truncate ORDER BY id;
+
+--
+-- Test 2: TRUNCATE in an aborted transaction keeps the rows
+--
+BEGIN;
+TRUNCATE test_truncate;
+SELECT count(*) FROM test_truncate;
+ROLLBACK;
+SELECT * FROM test_truncate ORDER BY id;
+
+--
+-- Test 3: TRUNCATE removes rows inserted in the same transaction
+--
+BEGIN;
+INSERT INTO test_truncate VALUES (3, 'three');
+TRUNCATE test_truncate;
+INSERT INTO test_truncate VALUES (4, 'four');
+SELECT * FROM test_truncate ORDER BY id;
+COMMIT;
+SELECT * FROM test_truncate ORDER BY id;
+
+--
+-- Test 4: TRUNCATE rolled back to a savepoint keeps the rows
+--
+BEGIN;
+INSERT INTO test_truncate VALUES (5, 'five');
+SAVEPOINT s1;
+TRUNCATE test_truncate;
+SELECT count(*) FROM test_truncate;
+ROLLBACK TO SAVEPOINT s1;
+SELECT * FROM test_truncate ORDER BY id;
+COMMIT;
+SELECT * FROM test_truncate ORDER BY id;
+
+--
+-- Test 5: TRUNCATE of a table created in the same transaction
+--
+BEGIN;
+CREATE TABLE test_truncate_new (id int) USING iceberg;
+INSERT INTO test_truncate_new SELECT generate_series(1, 10);
+TRUNCATE test_truncate_new;
+INSERT INTO test_truncate_new VALUES (42);
+COMMIT;
+SELECT * FROM test_truncate_new;
+
+--
+-- Test 6: TRUNCATE of an empty table
+--
+CREATE TABLE test_truncate_empty (id int) USING iceberg;
+TRUNCATE test_truncate_empty;
+SELECT count(*) FROM test_truncate_empty;
+
+-- Cleanup
+DROP TABLE test_truncate;
+DROP TABLE test_truncate_new;
+DROP TABLE test_truncate_empty;
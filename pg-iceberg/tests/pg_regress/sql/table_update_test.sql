@@ -0,0 +1,92 @@
+-- table_update_test.sql
+-- Test UPDATE of iceberg tables
+DROP EXTENSION IF EXISTS pg_iceberg CASCADE;
+CREATE EXTENSION pg_iceberg;
+
+--
+-- Test 0: UPDATE with a WHERE clause
+--
+CREATE TABLE test_update_basic (id int, name text, score float8) USING iceberg;
+
+INSERT INTO test_update_basic
+SELECT i, 'row ' || i, i * 1.5 FROM generate_series(1, 6) i;
+
+UPDATE test_update_basic SET name = 'updated' WHERE id % 2 = 0;
+SELECT * FROM test_update_basic ORDER BY id;
+
+-- Expressions referencing the old row
+UPDATE test_update_basic SET score = score * 2, id = id + 10 WHERE id < 3;
+SELECT * FROM test_update_basic ORDER BY id;
+
+-- Updating a column to NULL
+UPDATE test_update_basic SET name = NULL WHERE id = 3;
+SELECT * FROM test_update_basic WHERE id = 3;
+
+-- Updating no rows
+UPDATE test_update_basic SET name = 'none' WHERE id > 100;
+SELECT count(*) AS row_count FROM test_update_basic;
+
+DROP TABLE test_update_basic;
+
+
+--
+-- Test 1: UPDATE ... RETURNING and updating all rows
+--
+CREATE TABLE test_update_all (id int, name text) USING iceberg;
+INSERT INTO test_update_all VALUES (1, 'one'), (2, 'two');
+INSERT INTO test_update_all VALUES (3, 'three');
+
+WITH updated AS (
+    UPDATE test_update_all SET name = upper(name) RETURNING *
+)
+SELECT * FROM updated ORDER BY id;
+SELECT * FROM test_update_all ORDER BY id;
+
+-- Updated rows can be updated again
+UPDATE test_update_all SET name = name || '!' WHERE id = 2;
+UPDATE test_update_all SET name = name || '?' WHERE id = 2;
+SELECT * FROM test_update_all ORDER BY id;
+
+DROP TABLE test_update_all;
+
+
+--
+-- Test 2: UPDATE within transactions and savepoints
+--
+CREATE TABLE test_update_xact (id int, val int) USING iceberg;
+INSERT INTO test_update_xact SELECT i, 0 FROM generate_series(1, 3) i;
+
+BEGIN;
+UPDATE test_update_xact SET val = 1 WHERE id = 1;
+SELECT * FROM test_update_xact ORDER BY id;
+SAVEPOINT s1;
+UPDATE test_update_xact SET val = 2;
+SELECT * FROM test_update_xact ORDER BY id;
+ROLLBACK TO SAVEPOINT s1;
+UPDATE test_update_xact SET val = val + 10 WHERE id = 1;
+COMMIT;
+
+SELECT * FROM test_update_xact ORDER BY id;
+
+BEGIN;
+UPDATE test_update_xact SET val = -1;
+ROLLBACK;
+
+SELECT * FROM test_update_xact ORDER BY id;
+
+DROP TABLE test_update_xact;
+
+
+--
+-- Test 3: rows joined more than once are updated once
+--
+CREATE TABLE test_update_join (id int, val int) USING iceberg;
+INSERT INTO test_update_join VALUES (1, 0), (2, 0);
+
+UPDATE test_update_join t SET val = t.val + s.delta
+FROM (VALUES (1, 5), (1, 7), (2, 1)) AS s(id, delta)
+WHERE t.id = s.id;
+SELECT id, val > 0 AS updated FROM test_update_join ORDER BY id;
+SELECT count(*) AS row_count FROM test_update_join;
+
+DROP TABLE test_update_join;
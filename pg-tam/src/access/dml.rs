@@ -383,9 +383,13 @@ where
         let snapshot_handle = SnapshotHandle::from_raw(snapshot);
         let mut tmfd_rust = TM_FailureData::default();
 
-        // Note: tuple_lock might modify the row (e.g. stores current version), so passing mut ref is correct
-        // But for consistency with update_from_slot, we first fill it
-        (*session).row_buffer.update_from_slot(slot);
+        // The slot is usually empty, it receives the locked row version
+        let is_empty = ((*slot).tts_flags & pg_sys::TTS_FLAG_EMPTY as u16) != 0;
+        if is_empty {
+            (*session).row_buffer.clear();
+        } else {
+            (*session).row_buffer.update_from_slot(slot);
+        }
 
         let state = (*session)
             .state
@@ -405,6 +409,11 @@ where
             )
             .report_unwrap();
 
+        if result == pg_sys::TM_Result::TM_Ok {
+            (*session).row_buffer.store_in_slot(slot);
+            (*slot).tts_tableOid = (*rel).rd_id;
+            (*slot).tts_tid = tid.to_pg_sys();
+        }
         tmfd_rust.write_to_ptr(tmfd);
 
         result
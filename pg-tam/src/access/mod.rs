@@ -5,6 +5,7 @@
 //! - `ddl`: DDL operations
 //! - `dml`: Data modification (INSERT/UPDATE/DELETE)
 //! - `index`: Index access
+//! - `projection`: Columns referenced by the scans of running queries
 //! - `relation`: Relation-level operations
 //! - `scan`: Scan operations
 
@@ -12,5 +13,6 @@ pub mod ddl;
 pub mod dml;
 pub mod index;
 pub mod pending_delete;
+pub mod projection;
 pub mod relation;
 pub mod scan;
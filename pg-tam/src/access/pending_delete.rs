@@ -37,12 +37,24 @@
 //! │  (e.g. Delete dropped table)    (e.g. Cleanup failed create)  │
 //! └──────────────────────────────────────────────────────────────┘
 //! ```
+//!
+//! # Transaction Hooks
+//!
+//! Access methods that defer work until the end of a transaction (e.g.
+//! publishing a new table snapshot) can register an [`XactHook`] with
+//! [`register_xact_hook`]. Hooks are driven by the same transaction callbacks
+//! and additionally see the pre-commit event, where the transaction is still
+//! open and may be aborted by returning an error.
 
 use std::cell::RefCell;
 use std::fmt::Debug;
+use std::sync::{Arc, RwLock};
+
+use pgrx::pg_sys::panic::ErrorReport;
+use pgrx::prelude::*;
+use thiserror::Error;
 
-use pgrx::pg_guard;
-use pgrx::pg_sys;
+use crate::diag::{ReportableError, report_warning};
 
 /// A pending delete entry that can be executed when a transaction aborts or commits.
 ///
@@ -72,6 +84,87 @@ pub trait PendingDelete: Debug + Send {
     }
 }
 
+/// Transaction events passed to an [`XactHook`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum XactEvent {
+    /// The top-level transaction is about to commit. The transaction is still
+    /// open, an error aborts it.
+    PreCommit,
+    /// The top-level transaction is about to be prepared for two-phase commit.
+    /// An error aborts it.
+    PrePrepare,
+    /// The top-level transaction has committed.
+    Commit,
+    /// The top-level transaction has aborted.
+    Abort,
+    /// The subtransaction at the given nesting level has committed
+    /// (`RELEASE SAVEPOINT`).
+    SubCommit(i32),
+    /// The subtransaction at the given nesting level has aborted
+    /// (`ROLLBACK TO SAVEPOINT`).
+    SubAbort(i32),
+}
+
+/// Error type for transaction hooks
+#[derive(Error, Debug)]
+pub enum XactHookError {
+    #[error("{0}")]
+    Message(String),
+
+    #[error("{0}")]
+    SerializationFailure(String),
+}
+
+impl From<XactHookError> for ErrorReport {
+    fn from(value: XactHookError) -> Self {
+        let error_message = format!("{value}");
+        let code = match &value {
+            XactHookError::Message(_) => PgSqlErrorCode::ERRCODE_INTERNAL_ERROR,
+            XactHookError::SerializationFailure(_) => {
+                PgSqlErrorCode::ERRCODE_T_R_SERIALIZATION_FAILURE
+            }
+        };
+        ErrorReport::new(code, error_message, "")
+    }
+}
+
+/// A hook notified of transaction events.
+///
+/// Errors returned for [`XactEvent::PreCommit`] and [`XactEvent::PrePrepare`]
+/// abort the transaction. Errors for the other events are reported as
+/// warnings, since the transaction outcome can no longer change.
+pub trait XactHook {
+    fn on_event(&self, event: XactEvent) -> Result<(), XactHookError>;
+}
+
+static XACT_HOOKS: RwLock<Vec<Arc<dyn XactHook + Send + Sync>>> =
+    RwLock::new(Vec::new());
+
+/// Register a transaction hook.
+///
+/// This should be called during extension initialization (`_PG_init`),
+/// together with [`init_xact_callback`] which drives the hooks.
+pub fn register_xact_hook(hook: Box<dyn XactHook + Send + Sync>) {
+    XACT_HOOKS.write().unwrap().push(Arc::from(hook));
+}
+
+fn call_xact_hooks(event: XactEvent) {
+    let hooks = XACT_HOOKS.read().unwrap().clone();
+
+    for hook in hooks {
+        match event {
+            XactEvent::PreCommit | XactEvent::PrePrepare => {
+                hook.on_event(event).report_unwrap();
+            }
+            _ => {
+                if let Err(e) = hook.on_event(event) {
+                    report_warning(&format!("transaction hook failed: {e}"));
+                }
+            }
+        }
+    }
+}
+
 /// Internal entry wrapper to track transaction nesting level.
 struct PendingDeleteEntry {
     inner: Box<dyn PendingDelete>,
@@ -159,10 +252,17 @@ unsafe extern "C-unwind" fn xact_callback(
     let (is_commit, is_abort) = match event {
         XACT_EVENT_COMMIT | XACT_EVENT_PARALLEL_COMMIT => (true, false),
         XACT_EVENT_ABORT | XACT_EVENT_PARALLEL_ABORT => (false, true),
-        // Pre-commit and pre-prepare events - don't process yet
-        XACT_EVENT_PRE_COMMIT
-        | XACT_EVENT_PRE_PREPARE
-        | XACT_EVENT_PARALLEL_PRE_COMMIT => {
+        // Pre-commit and pre-prepare events - only hooks run here, pending
+        // deletes are processed once the outcome is known
+        XACT_EVENT_PRE_COMMIT => {
+            call_xact_hooks(XactEvent::PreCommit);
+            return;
+        }
+        XACT_EVENT_PRE_PREPARE => {
+            call_xact_hooks(XactEvent::PrePrepare);
+            return;
+        }
+        XACT_EVENT_PARALLEL_PRE_COMMIT => {
             return;
         }
         // Prepare for 2PC - don't process
@@ -199,6 +299,14 @@ unsafe extern "C-unwind" fn xact_callback(
             entry.inner.execute();
         }
     }
+
+    // Hooks track work of the leader backend, parallel worker events are not
+    // forwarded
+    if event == XACT_EVENT_COMMIT {
+        call_xact_hooks(XactEvent::Commit);
+    } else if event == XACT_EVENT_ABORT {
+        call_xact_hooks(XactEvent::Abort);
+    }
 }
 
 /// PostgreSQL subtransaction callback.
@@ -256,4 +364,14 @@ unsafe extern "C-unwind" fn subxact_callback(
             _ => {}
         }
     });
+
+    match event {
+        SUBXACT_EVENT_COMMIT_SUB => {
+            call_xact_hooks(XactEvent::SubCommit(current_nest_level))
+        }
+        SUBXACT_EVENT_ABORT_SUB => {
+            call_xact_hooks(XactEvent::SubAbort(current_nest_level))
+        }
+        _ => {}
+    }
 }
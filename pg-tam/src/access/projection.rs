@@ -0,0 +1,568 @@
+//! Columns and quals of the scans of running queries.
+//!
+//! The table access method API does not tell a scan which columns the query
+//! needs, and sequential scans get no scan keys for their quals. This module
+//! records both from the plans of running queries, so that columnar access
+//! methods can read only those columns and skip data that cannot match:
+//!
+//! - At `ExecutorStart` the scan nodes of the plan are collected per relation.
+//!   A scan needs the columns its range table entry selects, see
+//!   `RTEPermissionInfo.selectedCols`, plus those referenced by its quals and
+//!   target list. Physical target lists, which the planner uses when the scan
+//!   need not project, list every column and are ignored.
+//! - The quals of a sequential scan comparing a column with a constant are
+//!   translated into scan keys, see [`scan_plan_keys`]. The executor still
+//!   checks every qual, the keys only tell which rows the scan may skip.
+//! - At `ExecutorEnd` the query is forgotten. Queries that fail before are
+//!   forgotten when their (sub)transaction aborts.
+//!
+//! [`scan_projection`] looks up the columns by the scan's snapshot, which is
+//! the executor's snapshot for scans of plan nodes. Parallel scans, which
+//! always scan plan nodes, restore their own copy of the snapshot and are
+//! looked up by relation only. Scans that match no running query must read all
+//! columns.
+
+use std::cell::RefCell;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::OnceLock;
+
+use pgrx::prelude::*;
+
+use crate::access::pending_delete::{
+    XactEvent, XactHook, XactHookError, register_xact_hook,
+};
+use crate::handles::{ParallelTableScanDescHandle, SnapshotHandle};
+
+/// The attributes a query's scans of a relation need, `None` for all.
+type Attributes = Option<BTreeSet<pg_sys::AttrNumber>>;
+
+/// What a query's scans of a relation need.
+struct RelationScans {
+    attributes: Attributes,
+    /// The scan keys of the relation's only sequential scan, `None` when it
+    /// is scanned by other or more nodes.
+    keys: Option<Vec<pg_sys::ScanKeyData>>,
+}
+
+/// A running query and what its scans need per relation.
+struct QueryProjection {
+    query_desc: *mut pg_sys::QueryDesc,
+    snapshot: pg_sys::Snapshot,
+    nest_level: i32,
+    relations: HashMap<pg_sys::Oid, RelationScans>,
+}
+
+thread_local! {
+    static RUNNING_QUERIES: RefCell<Vec<QueryProjection>> =
+        const { RefCell::new(Vec::new()) };
+}
+
+static PREV_EXECUTOR_START: OnceLock<pg_sys::ExecutorStart_hook_type> =
+    OnceLock::new();
+static PREV_EXECUTOR_END: OnceLock<pg_sys::ExecutorEnd_hook_type> = OnceLock::new();
+
+/// The attributes a scan of `relid` with `snapshot`, and `pscan` for parallel
+/// scans, needs to read, `None` when it must read all of them.
+pub fn scan_projection(
+    snapshot: &SnapshotHandle,
+    pscan: Option<&ParallelTableScanDescHandle>,
+    relid: pg_sys::Oid,
+) -> Option<BTreeSet<pg_sys::AttrNumber>> {
+    let snapshot = snapshot.as_raw();
+    if snapshot.is_null() {
+        return None;
+    }
+
+    RUNNING_QUERIES.with(|queries| {
+        let mut projection: Option<BTreeSet<_>> = None;
+        for query in queries.borrow().iter() {
+            if pscan.is_none() && query.snapshot != snapshot {
+                continue;
+            }
+            match query.relations.get(&relid).map(|scans| &scans.attributes) {
+                None => continue,
+                Some(None) => return None,
+                Some(Some(attributes)) => projection
+                    .get_or_insert_with(BTreeSet::new)
+                    .extend(attributes),
+            }
+        }
+        projection
+    })
+}
+
+/// The scan keys equivalent to quals of the sequential scan of `relid` with
+/// `snapshot`, and `pscan` for parallel scans, that compare a column with a
+/// constant.
+///
+/// The keys are only known when a single plan node scans the relation, the
+/// returned rows need not satisfy them. Their arguments point into the plan,
+/// which lives until the query ends.
+pub fn scan_plan_keys(
+    snapshot: &SnapshotHandle,
+    pscan: Option<&ParallelTableScanDescHandle>,
+    relid: pg_sys::Oid,
+) -> Vec<pg_sys::ScanKeyData> {
+    let snapshot = snapshot.as_raw();
+    if snapshot.is_null() {
+        return Vec::new();
+    }
+
+    RUNNING_QUERIES.with(|queries| {
+        let queries = queries.borrow();
+        let mut scans = queries
+            .iter()
+            .filter(|query| pscan.is_some() || query.snapshot == snapshot)
+            .filter_map(|query| query.relations.get(&relid));
+        match (scans.next(), scans.next()) {
+            (Some(scans), None) => scans.keys.clone().unwrap_or_default(),
+            _ => Vec::new(),
+        }
+    })
+}
+
+/// Install the executor hooks recording the columns referenced by scans.
+///
+/// Safe to call multiple times - will only install once.
+pub fn init_projection_hooks() {
+    PREV_EXECUTOR_START.get_or_init(|| unsafe {
+        let prev = pg_sys::ExecutorStart_hook;
+        pg_sys::ExecutorStart_hook = Some(executor_start);
+        register_xact_hook(Box::new(ProjectionHook));
+        prev
+    });
+    PREV_EXECUTOR_END.get_or_init(|| unsafe {
+        let prev = pg_sys::ExecutorEnd_hook;
+        pg_sys::ExecutorEnd_hook = Some(executor_end);
+        prev
+    });
+}
+
+#[pg_guard]
+unsafe extern "C-unwind" fn executor_start(
+    query_desc: *mut pg_sys::QueryDesc,
+    eflags: ::core::ffi::c_int,
+) {
+    unsafe {
+        match PREV_EXECUTOR_START.get() {
+            Some(Some(prev)) => prev(query_desc, eflags),
+            _ => pg_sys::standard_ExecutorStart(query_desc, eflags),
+        }
+
+        let estate = (*query_desc).estate;
+        let stmt = (*query_desc).plannedstmt;
+        if estate.is_null() || stmt.is_null() {
+            return;
+        }
+
+        let mut relations = HashMap::new();
+        collect_scans(stmt, (*stmt).planTree, &mut relations);
+        for subplan in list_pointers::<pg_sys::Plan>((*stmt).subPlans) {
+            collect_scans(stmt, subplan, &mut relations);
+        }
+        if relations.is_empty() {
+            return;
+        }
+
+        let query = QueryProjection {
+            query_desc,
+            snapshot: (*estate).es_snapshot,
+            nest_level: pg_sys::GetCurrentTransactionNestLevel(),
+            relations,
+        };
+        RUNNING_QUERIES.with(|queries| queries.borrow_mut().push(query));
+    }
+}
+
+#[pg_guard]
+unsafe extern "C-unwind" fn executor_end(query_desc: *mut pg_sys::QueryDesc) {
+    unsafe {
+        RUNNING_QUERIES.with(|queries| {
+            queries
+                .borrow_mut()
+                .retain(|query| query.query_desc != query_desc)
+        });
+
+        match PREV_EXECUTOR_END.get() {
+            Some(Some(prev)) => prev(query_desc),
+            _ => pg_sys::standard_ExecutorEnd(query_desc),
+        }
+    }
+}
+
+struct ProjectionHook;
+
+impl XactHook for ProjectionHook {
+    fn on_event(&self, event: XactEvent) -> Result<(), XactHookError> {
+        RUNNING_QUERIES.with(|queries| match event {
+            XactEvent::Commit | XactEvent::Abort => queries.borrow_mut().clear(),
+            XactEvent::SubAbort(nest_level) => queries
+                .borrow_mut()
+                .retain(|query| query.nest_level < nest_level),
+            _ => {}
+        });
+        Ok(())
+    }
+}
+
+/// Collect the attributes needed by the scan nodes of `plan` and its children.
+unsafe fn collect_scans(
+    stmt: *mut pg_sys::PlannedStmt,
+    plan: *mut pg_sys::Plan,
+    relations: &mut HashMap<pg_sys::Oid, RelationScans>,
+) {
+    unsafe {
+        if plan.is_null() {
+            return;
+        }
+
+        match (*plan).type_ {
+            pg_sys::NodeTag::T_SeqScan
+            | pg_sys::NodeTag::T_SampleScan
+            | pg_sys::NodeTag::T_TidRangeScan
+            | pg_sys::NodeTag::T_BitmapHeapScan => {
+                let scan = plan as *mut pg_sys::Scan;
+                let rte = list_nth_pointer::<pg_sys::RangeTblEntry>(
+                    (*stmt).rtable,
+                    (*scan).scanrelid as usize - 1,
+                );
+                let keys = ((*plan).type_ == pg_sys::NodeTag::T_SeqScan)
+                    .then(|| scan_keys(scan));
+                let attributes = scan_attributes(stmt, scan, rte);
+                match relations.get_mut(&(*rte).relid) {
+                    None => {
+                        relations
+                            .insert((*rte).relid, RelationScans { attributes, keys });
+                    }
+                    Some(needed) => {
+                        match (needed.attributes.as_mut(), attributes) {
+                            (Some(needed), Some(attributes)) => {
+                                needed.extend(attributes)
+                            }
+                            _ => needed.attributes = None,
+                        }
+                        // Which of the scans a scan key belongs to is unknown
+                        needed.keys = None;
+                    }
+                }
+            }
+            pg_sys::NodeTag::T_Append => {
+                let append = plan as *mut pg_sys::Append;
+                for child in list_pointers::<pg_sys::Plan>((*append).appendplans) {
+                    collect_scans(stmt, child, relations);
+                }
+            }
+            pg_sys::NodeTag::T_MergeAppend => {
+                let merge_append = plan as *mut pg_sys::MergeAppend;
+                for child in list_pointers::<pg_sys::Plan>((*merge_append).mergeplans)
+                {
+                    collect_scans(stmt, child, relations);
+                }
+            }
+            pg_sys::NodeTag::T_SubqueryScan => {
+                let subquery_scan = plan as *mut pg_sys::SubqueryScan;
+                collect_scans(stmt, (*subquery_scan).subplan, relations);
+            }
+            pg_sys::NodeTag::T_CustomScan => {
+                let custom_scan = plan as *mut pg_sys::CustomScan;
+                for child in
+                    list_pointers::<pg_sys::Plan>((*custom_scan).custom_plans)
+                {
+                    collect_scans(stmt, child, relations);
+                }
+            }
+            _ => {}
+        }
+
+        collect_scans(stmt, (*plan).lefttree, relations);
+        collect_scans(stmt, (*plan).righttree, relations);
+    }
+}
+
+/// The attributes the scan node `scan` of `rte` needs, `None` for all.
+unsafe fn scan_attributes(
+    stmt: *mut pg_sys::PlannedStmt,
+    scan: *mut pg_sys::Scan,
+    rte: *mut pg_sys::RangeTblEntry,
+) -> Attributes {
+    unsafe {
+        // Without permission info, e.g. for inheritance children, the
+        // columns the query selects are unknown
+        if (*rte).perminfoindex == 0 {
+            return None;
+        }
+        let perminfo = list_nth_pointer::<pg_sys::RTEPermissionInfo>(
+            (*stmt).permInfos,
+            (*rte).perminfoindex as usize - 1,
+        );
+
+        let mut varattnos = pg_sys::bms_copy((*perminfo).selectedCols);
+        let plan = &mut (*scan).plan;
+        pg_sys::pull_varattnos(plan.qual.cast(), (*scan).scanrelid, &mut varattnos);
+        if !is_physical_tlist(plan.targetlist) {
+            pg_sys::pull_varattnos(
+                plan.targetlist.cast(),
+                (*scan).scanrelid,
+                &mut varattnos,
+            );
+        }
+
+        let mut attributes = BTreeSet::new();
+        let mut member = -1;
+        loop {
+            member = pg_sys::bms_next_member(varattnos, member);
+            if member < 0 {
+                break;
+            }
+            let attno = member + pg_sys::FirstLowInvalidHeapAttributeNumber;
+            match attno {
+                // A whole-row reference needs all columns
+                0 => return None,
+                // System columns are not stored
+                _ if attno < 0 => {}
+                _ => {
+                    attributes.insert(attno as pg_sys::AttrNumber);
+                }
+            }
+        }
+
+        Some(attributes)
+    }
+}
+
+/// The scan keys equivalent to the quals of `scan` that compare a column with
+/// a constant, see [`qual_to_scan_key`].
+unsafe fn scan_keys(scan: *mut pg_sys::Scan) -> Vec<pg_sys::ScanKeyData> {
+    unsafe {
+        list_pointers::<pg_sys::Node>((*scan).plan.qual)
+            .into_iter()
+            .filter_map(|qual| qual_to_scan_key(qual, (*scan).scanrelid))
+            .collect()
+    }
+}
+
+/// Translate `qual` of a scan of the range table entry `scanrelid` into a
+/// scan key. Translated are:
+///
+/// - `column op constant` and `constant op column`, where `op` is an operator
+///   of the btree operator family of the column type's default operator class;
+/// - `column op ANY (constant array)` as a `SK_SEARCHARRAY` key;
+/// - `column IS [NOT] NULL` as a `SK_SEARCHNULL` or `SK_SEARCHNOTNULL` key.
+unsafe fn qual_to_scan_key(
+    qual: *mut pg_sys::Node,
+    scanrelid: pg_sys::Index,
+) -> Option<pg_sys::ScanKeyData> {
+    unsafe {
+        match (*qual).type_ {
+            pg_sys::NodeTag::T_OpExpr => {
+                let op = qual as *mut pg_sys::OpExpr;
+                let args = list_pointers::<pg_sys::Node>((*op).args);
+                let [left, right] = args[..] else {
+                    return None;
+                };
+
+                if let (Some(var), Some(constant)) =
+                    (scan_var(left, scanrelid), plan_const(right))
+                {
+                    return btree_scan_key(
+                        var,
+                        constant,
+                        (*op).opno,
+                        (*op).inputcollid,
+                        0,
+                    );
+                }
+                // The constant is on the left, the commutator has the column
+                // on its left side
+                let (var, constant) =
+                    (scan_var(right, scanrelid)?, plan_const(left)?);
+                let commutator = pg_sys::get_commutator((*op).opno);
+                if commutator == pg_sys::InvalidOid {
+                    return None;
+                }
+                btree_scan_key(var, constant, commutator, (*op).inputcollid, 0)
+            }
+            pg_sys::NodeTag::T_ScalarArrayOpExpr => {
+                let op = qual as *mut pg_sys::ScalarArrayOpExpr;
+                if !(*op).useOr {
+                    return None;
+                }
+                let args = list_pointers::<pg_sys::Node>((*op).args);
+                let [left, right] = args[..] else {
+                    return None;
+                };
+
+                btree_scan_key(
+                    scan_var(left, scanrelid)?,
+                    plan_const(right)?,
+                    (*op).opno,
+                    (*op).inputcollid,
+                    pg_sys::SK_SEARCHARRAY as i32,
+                )
+            }
+            pg_sys::NodeTag::T_NullTest => {
+                let test = qual as *mut pg_sys::NullTest;
+                if (*test).argisrow {
+                    return None;
+                }
+                let var = scan_var((*test).arg as *mut pg_sys::Node, scanrelid)?;
+                let flags = match (*test).nulltesttype {
+                    pg_sys::NullTestType::IS_NULL => pg_sys::SK_SEARCHNULL,
+                    _ => pg_sys::SK_SEARCHNOTNULL,
+                };
+
+                let mut key: pg_sys::ScanKeyData = std::mem::zeroed();
+                pg_sys::ScanKeyEntryInitialize(
+                    &mut key,
+                    (pg_sys::SK_ISNULL | flags) as i32,
+                    (*var).varattno,
+                    pg_sys::InvalidStrategy as pg_sys::StrategyNumber,
+                    pg_sys::InvalidOid,
+                    pg_sys::InvalidOid,
+                    pg_sys::InvalidOid,
+                    pg_sys::Datum::from(0),
+                );
+                Some(key)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The scan key comparing `var` with `constant` by the operator `opno`, if it
+/// belongs to the btree operator family of the column's type.
+unsafe fn btree_scan_key(
+    var: *mut pg_sys::Var,
+    constant: *mut pg_sys::Const,
+    opno: pg_sys::Oid,
+    collation: pg_sys::Oid,
+    flags: i32,
+) -> Option<pg_sys::ScanKeyData> {
+    unsafe {
+        let opclass = pg_sys::GetDefaultOpClass((*var).vartype, pg_sys::BTREE_AM_OID);
+        if opclass == pg_sys::InvalidOid {
+            return None;
+        }
+        let opfamily = pg_sys::get_opclass_family(opclass);
+        if !pg_sys::op_in_opfamily(opno, opfamily) {
+            return None;
+        }
+
+        let mut strategy = 0;
+        let mut lefttype = pg_sys::InvalidOid;
+        let mut righttype = pg_sys::InvalidOid;
+        pg_sys::get_op_opfamily_properties(
+            opno,
+            opfamily,
+            false,
+            &mut strategy,
+            &mut lefttype,
+            &mut righttype,
+        );
+        // Like index scan keys, the subtype is only set for cross-type
+        // operators
+        let subtype = if lefttype == righttype {
+            pg_sys::InvalidOid
+        } else {
+            righttype
+        };
+        let flags = if (*constant).constisnull {
+            flags | pg_sys::SK_ISNULL as i32
+        } else {
+            flags
+        };
+
+        let mut key: pg_sys::ScanKeyData = std::mem::zeroed();
+        pg_sys::ScanKeyEntryInitialize(
+            &mut key,
+            flags,
+            (*var).varattno,
+            strategy as pg_sys::StrategyNumber,
+            subtype,
+            collation,
+            pg_sys::get_opcode(opno),
+            (*constant).constvalue,
+        );
+        Some(key)
+    }
+}
+
+/// `node` as a column of the relation scanned as `scanrelid`, looking through
+/// binary compatible casts.
+unsafe fn scan_var(
+    node: *mut pg_sys::Node,
+    scanrelid: pg_sys::Index,
+) -> Option<*mut pg_sys::Var> {
+    unsafe {
+        let node = strip_relabel(node);
+        if (*node).type_ != pg_sys::NodeTag::T_Var {
+            return None;
+        }
+
+        let var = node as *mut pg_sys::Var;
+        ((*var).varno as pg_sys::Index == scanrelid
+            && (*var).varlevelsup == 0
+            && (*var).varattno > 0)
+            .then_some(var)
+    }
+}
+
+/// `node` as a constant, looking through binary compatible casts.
+unsafe fn plan_const(node: *mut pg_sys::Node) -> Option<*mut pg_sys::Const> {
+    unsafe {
+        let node = strip_relabel(node);
+        ((*node).type_ == pg_sys::NodeTag::T_Const)
+            .then_some(node as *mut pg_sys::Const)
+    }
+}
+
+/// `node` without the binary compatible casts around it.
+unsafe fn strip_relabel(mut node: *mut pg_sys::Node) -> *mut pg_sys::Node {
+    unsafe {
+        while (*node).type_ == pg_sys::NodeTag::T_RelabelType {
+            node = (*(node as *mut pg_sys::RelabelType)).arg as *mut pg_sys::Node;
+        }
+        node
+    }
+}
+
+/// Whether `tlist` may be a physical target list, listing the attributes in
+/// order with NULL constants for dropped ones.
+unsafe fn is_physical_tlist(tlist: *mut pg_sys::List) -> bool {
+    unsafe {
+        let entries = list_pointers::<pg_sys::TargetEntry>(tlist);
+        !entries.is_empty()
+            && entries.iter().all(|&entry| {
+                let expr = (*entry).expr as *mut pg_sys::Node;
+                match (*expr).type_ {
+                    pg_sys::NodeTag::T_Var => {
+                        (*(expr as *mut pg_sys::Var)).varattno == (*entry).resno
+                    }
+                    pg_sys::NodeTag::T_Const => {
+                        (*(expr as *mut pg_sys::Const)).constisnull
+                    }
+                    _ => false,
+                }
+            })
+    }
+}
+
+/// The pointers stored in `list`.
+unsafe fn list_pointers<T>(list: *mut pg_sys::List) -> Vec<*mut T> {
+    unsafe {
+        if list.is_null() {
+            return Vec::new();
+        }
+        let cells =
+            std::slice::from_raw_parts((*list).elements, (*list).length as usize);
+        cells.iter().map(|cell| cell.ptr_value as *mut T).collect()
+    }
+}
+
+/// The pointer at `index` of `list`, which must be that long.
+unsafe fn list_nth_pointer<T>(list: *mut pg_sys::List, index: usize) -> *mut T {
+    unsafe {
+        debug_assert!(!list.is_null() && index < (*list).length as usize);
+        (*(*list).elements.add(index)).ptr_value as *mut T
+    }
+}
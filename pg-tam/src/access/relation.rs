@@ -160,6 +160,9 @@ where
             pg_sys::ExecStoreVirtualTuple(slot);
         });
 
+        (*slot).tts_tableOid = rel_handle.oid();
+        (*slot).tts_tid = row.tid.unwrap_or(tid).to_pg_sys();
+
         true
     }
 }
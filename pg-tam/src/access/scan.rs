@@ -68,6 +68,13 @@ impl<T> ScanState<T> {
 
                 pg_sys::ExecStoreVirtualTuple(slot);
             });
+
+            (*slot).tts_tid = self
+                .row
+                .tid
+                .take()
+                .unwrap_or(ItemPointer::INVALID)
+                .to_pg_sys();
         }
     }
 }
@@ -188,6 +195,7 @@ pub extern "C-unwind" fn scan_rescan<E, T>(
             let key_handle = if key.is_null() {
                 None
             } else {
+                (*custom_scan).base.rs_key = key;
                 Some(ScanKeyHandle::from_raw(key, nkeys))
             };
 
@@ -220,21 +228,92 @@ where
         pg_sys::ExecClearTuple(slot);
 
         let state = &mut *(*custom_scan).am_state;
+        let keys = ScanKeyHandle::from_raw(
+            (*custom_scan).base.rs_key,
+            (*custom_scan).base.rs_nkeys,
+        );
 
-        pg_sys::MemoryContextReset(state.tmp_ctx);
+        loop {
+            pg_sys::MemoryContextReset(state.tmp_ctx);
 
-        let direction_handle = ScanDirection::from_raw(direction);
-        let found = state
-            .am_instance
-            .scan_getnextslot(direction_handle, &mut state.row)
-            .report_unwrap();
-
-        if !found {
-            return false;
+            let direction_handle = ScanDirection::from_raw(direction);
+            let found = state
+                .am_instance
+                .scan_getnextslot(direction_handle, &mut state.row)
+                .report_unwrap();
+
+            if !found {
+                return false;
+            }
+
+            state.row_to_slot(slot);
+            if slot_satisfies_keys(slot, keys.keys(), state.tmp_ctx) {
+                return true;
+            }
+            pg_sys::ExecClearTuple(slot);
         }
+    }
+}
 
-        state.row_to_slot(slot);
-        true
+/// Whether the row stored in `slot` satisfies all scan `keys`, the
+/// counterpart of heap's `HeapKeyTest`. Access methods may push keys down to
+/// their storage, rows are checked regardless so that keys they cannot
+/// evaluate exactly are still honored.
+unsafe fn slot_satisfies_keys(
+    slot: *mut pg_sys::TupleTableSlot,
+    keys: &[pg_sys::ScanKeyData],
+    tmp_ctx: pg_sys::MemoryContext,
+) -> bool {
+    unsafe {
+        let natts = (*(*slot).tts_tupleDescriptor).natts;
+
+        keys.iter().all(|key| {
+            let flags = key.sk_flags as u32;
+            let attno = key.sk_attno as i32;
+            if attno < 1 || attno > natts {
+                return false;
+            }
+            let is_null = *(*slot).tts_isnull.add(attno as usize - 1);
+            let value = *(*slot).tts_values.add(attno as usize - 1);
+
+            if (flags & pg_sys::SK_SEARCHNULL) != 0 {
+                return is_null;
+            }
+            if (flags & pg_sys::SK_SEARCHNOTNULL) != 0 {
+                return !is_null;
+            }
+            if is_null || (flags & pg_sys::SK_ISNULL) != 0 {
+                return false;
+            }
+
+            PgMemoryContexts::For(tmp_ctx).switch_to(|_| {
+                if (flags & pg_sys::SK_SEARCHARRAY) != 0 {
+                    ScanKeyHandle::array_elements(key)
+                        .into_iter()
+                        .flatten()
+                        .any(|element| key_matches(key, value, element))
+                } else {
+                    key_matches(key, value, key.sk_argument)
+                }
+            })
+        })
+    }
+}
+
+/// Call the comparison function of `key` on `value` and `argument`.
+unsafe fn key_matches(
+    key: &pg_sys::ScanKeyData,
+    value: pg_sys::Datum,
+    argument: pg_sys::Datum,
+) -> bool {
+    unsafe {
+        let result = pg_sys::FunctionCall2Coll(
+            &key.sk_func as *const pg_sys::FmgrInfo as *mut pg_sys::FmgrInfo,
+            key.sk_collation,
+            value,
+            argument,
+        );
+        result.value() != 0
     }
 }
 
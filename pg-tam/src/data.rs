@@ -4,6 +4,7 @@
 //! including the `Cell` enum representing individual column values and
 //! the `Row` struct representing a complete table row.
 
+use pgrx::memcxt::PgMemoryContexts;
 use pgrx::prelude::{Date, Interval, Time, Timestamp, TimestampWithTimeZone};
 use pgrx::{
     datum::Uuid,
@@ -15,6 +16,8 @@ use std::ffi::CStr;
 use std::fmt;
 use std::mem;
 
+use crate::handles::ItemPointer;
+
 #[derive(Debug)]
 pub enum Cell {
     Bool(bool),
@@ -301,7 +304,10 @@ impl FromDatum for Cell {
                 PgOid::BuiltIn(PgBuiltInOids::NUMERICOID) => {
                     AnyNumeric::from_datum(datum, is_null).map(Cell::Numeric)
                 }
-                PgOid::BuiltIn(PgBuiltInOids::TEXTOID) => {
+                PgOid::BuiltIn(PgBuiltInOids::TEXTOID)
+                | PgOid::BuiltIn(PgBuiltInOids::VARCHAROID)
+                | PgOid::BuiltIn(PgBuiltInOids::BPCHAROID)
+                | PgOid::BuiltIn(PgBuiltInOids::JSONOID) => {
                     String::from_datum(datum, is_null).map(Cell::String)
                 }
                 PgOid::BuiltIn(PgBuiltInOids::DATEOID) => {
@@ -359,6 +365,8 @@ impl FromDatum for Cell {
 #[derive(Debug, Clone, Default)]
 pub struct Row {
     pub cells: Vec<Option<Cell>>,
+    /// Identifier of the row in the table, stored in the slot's `tts_tid`.
+    pub tid: Option<ItemPointer>,
 }
 
 impl Row {
@@ -370,7 +378,7 @@ impl Row {
     pub fn with_capacity(capacity: usize) -> Self {
         let mut cells = Vec::with_capacity(capacity);
         cells.resize_with(capacity, || None);
-        Self { cells }
+        Self { cells, tid: None }
     }
 
     pub fn push(&mut self, cell: Option<Cell>) {
@@ -404,6 +412,7 @@ impl Row {
 
     pub fn clear(&mut self) {
         self.cells.clear();
+        self.tid = None;
     }
 
     pub unsafe fn update_from_slot(&mut self, slot: *mut pg_sys::TupleTableSlot) {
@@ -429,6 +438,41 @@ impl Row {
                 };
                 self.cells[i] = cell;
             }
+
+            let tid = ItemPointer::from_raw(&mut (*slot).tts_tid);
+            self.tid = tid.is_valid().then_some(tid);
+        }
+    }
+
+    /// Store the row in `slot` as a virtual tuple. The cells are moved into
+    /// datums allocated in the slot's memory context.
+    pub unsafe fn store_in_slot(&mut self, slot: *mut pg_sys::TupleTableSlot) {
+        unsafe {
+            pg_sys::ExecClearTuple(slot);
+
+            let tup_desc = (*slot).tts_tupleDescriptor;
+            let natts = (*tup_desc).natts as usize;
+            let values = std::slice::from_raw_parts_mut((*slot).tts_values, natts);
+            let nulls = std::slice::from_raw_parts_mut((*slot).tts_isnull, natts);
+
+            PgMemoryContexts::For((*slot).tts_mcxt).switch_to(|_| {
+                for i in 0..natts {
+                    match self.cells.get_mut(i).and_then(Option::take) {
+                        Some(cell) => {
+                            values[i] = cell
+                                .into_datum()
+                                .expect("Failed to convert cell to datum");
+                            nulls[i] = false;
+                        }
+                        None => nulls[i] = true,
+                    }
+                }
+            });
+
+            pg_sys::ExecStoreVirtualTuple(slot);
+            if let Some(tid) = self.tid {
+                (*slot).tts_tid = tid.to_pg_sys();
+            }
         }
     }
 
@@ -27,6 +27,11 @@ impl<'a> RelationHandle<'a> {
         self.inner
     }
 
+    #[inline]
+    pub fn oid(&self) -> pg_sys::Oid {
+        unsafe { (*self.inner).rd_id }
+    }
+
     #[inline]
     pub fn tablespace_oid(&self) -> pg_sys::Oid {
         unsafe { (*(*self.inner).rd_rel).reltablespace }
@@ -147,6 +152,18 @@ pub struct ItemPointer {
 }
 
 impl ItemPointer {
+    /// The invalid item pointer, as set by `ItemPointerSetInvalid`.
+    pub const INVALID: Self = Self {
+        block_number: u32::MAX,
+        offset: 0,
+    };
+
+    /// Valid item pointers have a non-zero offset, see `ItemPointerIsValid`.
+    #[inline]
+    pub fn is_valid(&self) -> bool {
+        self.offset != 0
+    }
+
     #[inline]
     pub unsafe fn from_raw(ptr: pg_sys::ItemPointer) -> Self {
         unsafe {
@@ -406,6 +423,66 @@ impl<'a> ScanKeyHandle<'a> {
     pub fn nkeys(&self) -> i32 {
         self.nkeys
     }
+
+    /// The scan keys as a slice, empty when there are none.
+    #[inline]
+    pub fn keys(&self) -> &[pg_sys::ScanKeyData] {
+        if self.inner.is_null() || self.nkeys <= 0 {
+            return &[];
+        }
+        unsafe { std::slice::from_raw_parts(self.inner, self.nkeys as usize) }
+    }
+
+    /// The elements of the array argument of a `SK_SEARCHARRAY` key, `None`
+    /// for null elements.
+    ///
+    /// # Safety
+    /// `key.sk_argument` must be an array datum.
+    pub unsafe fn array_elements(
+        key: &pg_sys::ScanKeyData,
+    ) -> Vec<Option<pg_sys::Datum>> {
+        unsafe {
+            let array = pg_sys::pg_detoast_datum(
+                key.sk_argument.cast_mut_ptr::<pg_sys::varlena>(),
+            ) as *mut pg_sys::ArrayType;
+            let elem_type = (*array).elemtype;
+
+            let mut typlen = 0;
+            let mut typbyval = false;
+            let mut typalign = 0;
+            pg_sys::get_typlenbyvalalign(
+                elem_type,
+                &mut typlen,
+                &mut typbyval,
+                &mut typalign,
+            );
+
+            let mut values = std::ptr::null_mut();
+            let mut nulls = std::ptr::null_mut();
+            let mut count = 0;
+            pg_sys::deconstruct_array(
+                array,
+                elem_type,
+                typlen as i32,
+                typbyval,
+                typalign,
+                &mut values,
+                &mut nulls,
+                &mut count,
+            );
+            if count <= 0 {
+                return Vec::new();
+            }
+
+            let values = std::slice::from_raw_parts(values, count as usize);
+            let nulls = std::slice::from_raw_parts(nulls, count as usize);
+            values
+                .iter()
+                .zip(nulls)
+                .map(|(value, is_null)| (!is_null).then_some(*value))
+                .collect()
+        }
+    }
 }
 
 /// Safe wrapper for PostgreSQL TBMIterateResult
@@ -472,6 +549,15 @@ impl<'a> ReadStreamHandle<'a> {
     pub fn as_raw(&self) -> *mut pg_sys::ReadStream {
         self.inner
     }
+
+    /// Take the number of the next block of the stream without reading it,
+    /// `None` at the end of the stream, see `read_stream_next_block`.
+    pub fn next_block(&self) -> Option<pg_sys::BlockNumber> {
+        let mut strategy = std::ptr::null_mut();
+        let block =
+            unsafe { pg_sys::read_stream_next_block(self.inner, &mut strategy) };
+        (block != pg_sys::InvalidBlockNumber).then_some(block)
+    }
 }
 
 /// Safe wrapper for PostgreSQL ParallelTableScanDesc
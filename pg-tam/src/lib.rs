@@ -63,8 +63,11 @@ pub mod catalog;
 /// The prelude includes all necessary imports to make pg_tam work
 pub mod prelude {
     pub use crate::access::pending_delete::{
-        PendingDelete, init_xact_callback, pending_delete_size,
-        register_pending_delete,
+        PendingDelete, XactEvent, XactHook, XactHookError, init_xact_callback,
+        pending_delete_size, register_pending_delete, register_xact_hook,
+    };
+    pub use crate::access::projection::{
+        init_projection_hooks, scan_plan_keys, scan_projection,
     };
     pub use crate::api::*;
     pub use crate::data::*;
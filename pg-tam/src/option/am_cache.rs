@@ -100,6 +100,21 @@ impl AmCache {
         }
     }
 
+    /// Invalidate the cached data of a relation, e.g. after its options changed.
+    ///
+    /// This invalidates the relcache entry of the relation in all backends, which
+    /// frees `rd_amcache`. In the current backend the invalidation is processed at
+    /// the next `CommandCounterIncrement`, after which `get` reloads the options
+    /// from the catalog.
+    pub fn invalidate(rel: &RelationHandle) {
+        let rel_ptr = rel.as_raw();
+        unsafe {
+            if !rel_ptr.is_null() {
+                pg_sys::CacheInvalidateRelcache(rel_ptr);
+            }
+        }
+    }
+
     #[cold]
     unsafe fn load_and_cache<'a, T: AmCacheable>(
         rel: pg_sys::Relation,
@@ -3,6 +3,7 @@
 //! This module provides types and utilities for handling options in:
 //! - `CREATE TABLESPACE` statements
 //! - `CREATE TABLE` statements (with custom access methods)
+//! - `ALTER TABLE ... SET/RESET` statements (with custom access methods)
 //!
 pub mod am_cache;
 mod storage_option;
@@ -14,7 +15,11 @@ pub mod utils;
 // Re-export commonly used types
 pub use am_cache::{AmCache, AmCacheable};
 pub use storage_option::{
-    OptionKind, StorageCategory, TamOptionDef, extract_and_remove_options,
+    DEFAULT_S3_REGION, OPT_ACCESS_KEY_ID, OPT_ACCOUNT_KEY, OPT_ACCOUNT_NAME,
+    OPT_ALLOW_HTTP, OPT_BUCKET, OPT_CONTAINER, OPT_CREDENTIAL, OPT_CREDENTIAL_PATH,
+    OPT_ENDPOINT, OPT_REGION, OPT_SAS_TOKEN, OPT_SECRET_ACCESS_KEY, OptionKind,
+    StorageCategory, TamOptionDef, extract_and_remove_option_names,
+    extract_and_remove_options,
 };
 pub use table_option::{TableOptionError, TableOptions};
 pub use tablespace_option::{TablespaceError, TablespaceOptions};
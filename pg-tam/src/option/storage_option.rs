@@ -16,6 +16,12 @@ pub const OPT_ENDPOINT: &str = "endpoint";
 pub const OPT_ALLOW_HTTP: &str = "allow_http";
 pub const OPT_ACCESS_KEY_ID: &str = "access_key_id";
 pub const OPT_SECRET_ACCESS_KEY: &str = "secret_access_key";
+pub const OPT_CREDENTIAL: &str = "credential";
+pub const OPT_CREDENTIAL_PATH: &str = "credential_path";
+pub const OPT_ACCOUNT_NAME: &str = "account_name";
+pub const OPT_CONTAINER: &str = "container";
+pub const OPT_ACCOUNT_KEY: &str = "account_key";
+pub const OPT_SAS_TOKEN: &str = "sas_token";
 // ============================================================================
 //  Default Value Constants
 // ============================================================================
@@ -45,6 +51,11 @@ pub enum OptionKind {
         min: Option<i32>,
         max: Option<i32>,
     },
+    Long {
+        default: i64,
+        min: Option<i64>,
+        max: Option<i64>,
+    },
     String {
         default: Option<&'static str>,
     },
@@ -113,6 +124,44 @@ static STANDARD_STORAGE_OPTIONS: &[TamOptionDef] = &[
         kind: OptionKind::String { default: None },
         description: "S3 Secret Access Key",
     },
+    // --- GCS ---
+    TamOptionDef {
+        name: OPT_CREDENTIAL,
+        category: StorageCategory::GCS,
+        kind: OptionKind::String { default: None },
+        description: "Base64 encoded GCS service account key",
+    },
+    TamOptionDef {
+        name: OPT_CREDENTIAL_PATH,
+        category: StorageCategory::GCS,
+        kind: OptionKind::String { default: None },
+        description: "Path of a GCS service account key file",
+    },
+    // --- Azure ---
+    TamOptionDef {
+        name: OPT_ACCOUNT_NAME,
+        category: StorageCategory::Azure,
+        kind: OptionKind::String { default: None },
+        description: "Azure Storage account name",
+    },
+    TamOptionDef {
+        name: OPT_CONTAINER,
+        category: StorageCategory::Azure,
+        kind: OptionKind::String { default: None },
+        description: "Azure Blob Storage container name",
+    },
+    TamOptionDef {
+        name: OPT_ACCOUNT_KEY,
+        category: StorageCategory::Azure,
+        kind: OptionKind::String { default: None },
+        description: "Azure Storage account key",
+    },
+    TamOptionDef {
+        name: OPT_SAS_TOKEN,
+        category: StorageCategory::Azure,
+        kind: OptionKind::String { default: None },
+        description: "Azure shared access signature token",
+    },
 ];
 
 // ============================================================================
@@ -202,6 +251,53 @@ pub unsafe fn extract_and_remove_options(
     }
 }
 
+/// Extract and remove the names of custom options from a list of DefElem.
+///
+/// This serves `ALTER TABLE ... RESET (...)`, whose options have no values to
+/// validate. Options not matching `valid_options` are left in the list.
+///
+/// # Arguments
+/// * `options_list_ptr` - Pointer to the options list pointer
+/// * `valid_options` - List of valid option definitions to match against
+pub unsafe fn extract_and_remove_option_names(
+    options_list_ptr: *mut *mut pg_sys::List,
+    valid_options: &[TamOptionDef],
+) -> Vec<String> {
+    unsafe {
+        let mut names = Vec::new();
+        let mut new_pg_opts: *mut pg_sys::List = std::ptr::null_mut();
+
+        if (*options_list_ptr).is_null() {
+            return names;
+        }
+
+        let cell = (*(*options_list_ptr)).elements;
+        let length = (*(*options_list_ptr)).length;
+
+        for i in 0..length {
+            let def_elem_ptr =
+                (*cell.add(i as usize)).ptr_value as *mut pg_sys::DefElem;
+            let def_name = CStr::from_ptr((*def_elem_ptr).defname)
+                .to_string_lossy()
+                .to_string();
+
+            if valid_options.iter().any(|opt| opt.name == def_name) {
+                if !names.contains(&def_name) {
+                    names.push(def_name);
+                }
+            } else {
+                new_pg_opts = pg_sys::lappend(
+                    new_pg_opts,
+                    def_elem_ptr as *mut std::ffi::c_void,
+                );
+            }
+        }
+
+        *options_list_ptr = new_pg_opts;
+        names
+    }
+}
+
 /// Legacy wrapper for tablespace options
 pub unsafe fn extract_and_remove_custom_options(
     stmt: *mut pg_sys::CreateTableSpaceStmt,
@@ -234,25 +330,21 @@ fn validate_option_value(
             let int_val = v
                 .parse::<i32>()
                 .map_err(|_| format!("invalid integer value \"{}\"", v))?;
-
-            if let Some(min_val) = min {
-                if int_val < *min_val {
-                    return Err(format!(
-                        "value {} is less than minimum {}",
-                        int_val, min_val
-                    ));
-                }
-            }
-            if let Some(max_val) = max {
-                if int_val > *max_val {
-                    return Err(format!(
-                        "value {} is greater than maximum {}",
-                        int_val, max_val
-                    ));
-                }
-            }
+            check_range(int_val, *min, *max)?;
             Ok(Some(int_val.to_string()))
         }
+        OptionKind::Long {
+            default: _,
+            min,
+            max,
+        } => {
+            let v = raw_val.ok_or("numeric option requires a value")?;
+            let long_val = v
+                .parse::<i64>()
+                .map_err(|_| format!("invalid integer value \"{}\"", v))?;
+            check_range(long_val, *min, *max)?;
+            Ok(Some(long_val.to_string()))
+        }
         OptionKind::String { default } => {
             if let Some(val) = raw_val {
                 Ok(Some(val))
@@ -275,6 +367,25 @@ fn validate_option_value(
     }
 }
 
+/// Check that a numeric option value lies within its bounds.
+fn check_range<T: PartialOrd + std::fmt::Display>(
+    val: T,
+    min: Option<T>,
+    max: Option<T>,
+) -> Result<(), String> {
+    if let Some(min_val) = min {
+        if val < min_val {
+            return Err(format!("value {} is less than minimum {}", val, min_val));
+        }
+    }
+    if let Some(max_val) = max {
+        if val > max_val {
+            return Err(format!("value {} is greater than maximum {}", val, max_val));
+        }
+    }
+    Ok(())
+}
+
 /// Parse a boolean value from a string, supporting various common formats.
 /// Returns `None` if the string is not a recognized boolean representation.
 pub fn parse_bool(s: &str) -> Option<bool> {
@@ -1,7 +1,8 @@
 //! TableOptions - Type-safe wrapper for table options.
 //!
 //! This module provides a high-level interface for extracting and persisting
-//! custom options from `CREATE TABLE` statements with custom access methods.
+//! custom options from `CREATE TABLE` statements with custom access methods,
+//! and for changing them with `ALTER TABLE ... SET/RESET`.
 
 use super::storage_option::{self, TamOptionDef};
 use crate::catalog;
@@ -68,6 +69,23 @@ impl TableOptions {
         self.get_str(key).and_then(|v| v.parse().ok())
     }
 
+    pub fn get_long(&self, key: &str) -> Option<i64> {
+        self.get_str(key).and_then(|v| v.parse().ok())
+    }
+
+    /// Set an option, replacing its previous value.
+    pub fn set(&mut self, key: &str, value: Option<String>) {
+        match self.options.iter_mut().find(|(k, _)| k == key) {
+            Some((_, v)) => *v = value,
+            None => self.options.push((key.to_string(), value)),
+        }
+    }
+
+    /// Remove an option, so that its default applies again.
+    pub fn remove(&mut self, key: &str) {
+        self.options.retain(|(k, _)| k != key);
+    }
+
     pub fn extract_from_stmt(
         stmt: &mut pg_sys::CreateStmt,
         valid_options: Option<&[TamOptionDef]>,
@@ -83,6 +101,51 @@ impl TableOptions {
         Ok((!opts.is_empty()).then(|| Self { options: opts }))
     }
 
+    /// Extract the options of an `ALTER TABLE ... SET (...)` command.
+    ///
+    /// `options` is the command's list of DefElem, from which the extracted
+    /// options are removed.
+    pub fn extract_from_list(
+        options: &mut *mut pg_sys::List,
+        valid_options: &[TamOptionDef],
+    ) -> Result<Option<Self>, TableOptionError> {
+        // SAFETY: We hold a mutable reference to the list, so it is safe to modify via FFI.
+        let opts = unsafe {
+            storage_option::extract_and_remove_options(options, valid_options)
+                .map_err(TableOptionError::InvalidOption)?
+        };
+
+        Ok((!opts.is_empty()).then(|| Self { options: opts }))
+    }
+
+    /// Extract the option names of an `ALTER TABLE ... RESET (...)` command.
+    ///
+    /// `options` is the command's list of DefElem, from which the extracted
+    /// options are removed.
+    pub fn extract_names_from_list(
+        options: &mut *mut pg_sys::List,
+        valid_options: &[TamOptionDef],
+    ) -> Vec<String> {
+        // SAFETY: We hold a mutable reference to the list, so it is safe to modify via FFI.
+        unsafe {
+            storage_option::extract_and_remove_option_names(options, valid_options)
+        }
+    }
+
+    /// The options as a `text[]` datum of `key=value` entries.
+    fn to_datum(&self) -> Option<pg_sys::Datum> {
+        let options_vec: Vec<String> = self
+            .options
+            .iter()
+            .map(|(k, v)| {
+                let val = v.as_ref().map(|s| s.as_str()).unwrap_or("");
+                format!("{}={}", k, val)
+            })
+            .collect();
+
+        options_vec.into_datum()
+    }
+
     pub fn persist_to_catalog(
         &self,
         relid: pg_sys::Oid,
@@ -101,17 +164,7 @@ impl TableOptions {
                 .map_err(|e| TableOptionError::PersistFailed(e.to_string()))?;
 
             let relid_datum = relid.into_datum().unwrap();
-
-            let options_vec: Vec<String> = self
-                .options
-                .iter()
-                .map(|(k, v)| {
-                    let val = v.as_ref().map(|s| s.as_str()).unwrap_or("");
-                    format!("{}={}", k, val)
-                })
-                .collect();
-
-            let options_datum = options_vec.into_datum();
+            let options_datum = self.to_datum();
 
             let mut values =
                 [relid_datum, options_datum.unwrap_or(pg_sys::Datum::from(0))];
@@ -135,6 +188,81 @@ impl TableOptions {
         Ok(())
     }
 
+    /// Replace the persisted options of `relid` with these options.
+    ///
+    /// Unlike `persist_to_catalog`, this updates the existing catalog entry of
+    /// the relation, and only inserts one for relations created without
+    /// options.
+    pub fn update_catalog(&self, relid: pg_sys::Oid) -> Result<(), TableOptionError> {
+        let table_oid = catalog::get_table_options_oid()
+            .map_err(|e| TableOptionError::PersistFailed(e.to_string()))?;
+        let index_oid = catalog::get_table_options_pkey_oid()
+            .map_err(|e| TableOptionError::PersistFailed(e.to_string()))?;
+
+        let updated = unsafe {
+            let rel = PgWrapper::table_open(table_oid, pg_sys::RowExclusiveLock as _)
+                .map_err(|e| TableOptionError::PersistFailed(e.to_string()))?;
+
+            let mut key: pg_sys::ScanKeyData = std::mem::zeroed();
+
+            PgWrapper::scan_key_init(
+                &mut key,
+                1, // relid column
+                pg_sys::BTEqualStrategyNumber as _,
+                pg_sys::Oid::from(pg_sys::F_OIDEQ),
+                relid.into_datum().unwrap(),
+            );
+
+            let scan = PgWrapper::systable_beginscan(
+                rel,
+                index_oid,
+                true,
+                std::ptr::null_mut(),
+                1,
+                &key as *const _ as *mut _,
+            )
+            .map_err(|e| TableOptionError::PersistFailed(e.to_string()))?;
+
+            let tuple = PgWrapper::systable_getnext(scan)
+                .map_err(|e| TableOptionError::PersistFailed(e.to_string()))?;
+
+            if let Some(tuple) = tuple {
+                let options_datum = self.to_datum();
+
+                // options is column 2
+                let mut values = [pg_sys::Datum::from(0), pg_sys::Datum::from(0)];
+                let mut nulls = [false, options_datum.is_none()];
+                let mut repls = [false, true];
+                values[1] = options_datum.unwrap_or(pg_sys::Datum::from(0));
+
+                let new_tuple = pg_sys::heap_modify_tuple(
+                    tuple,
+                    (*rel).rd_att,
+                    values.as_mut_ptr(),
+                    nulls.as_mut_ptr(),
+                    repls.as_mut_ptr(),
+                );
+
+                PgWrapper::catalog_tuple_update(rel, &mut (*tuple).t_self, new_tuple)
+                    .map_err(|e| TableOptionError::PersistFailed(e.to_string()))?;
+
+                pg_sys::heap_freetuple(new_tuple);
+            }
+
+            PgWrapper::systable_endscan(scan)
+                .map_err(|e| TableOptionError::PersistFailed(e.to_string()))?;
+            PgWrapper::table_close(rel, pg_sys::RowExclusiveLock as _)
+                .map_err(|e| TableOptionError::PersistFailed(e.to_string()))?;
+
+            tuple.is_some()
+        };
+
+        if !updated {
+            self.persist_to_catalog(relid)?;
+        }
+        Ok(())
+    }
+
     pub fn load_from_catalog(
         relid: pg_sys::Oid,
     ) -> Result<Option<Self>, TableOptionError> {
@@ -76,11 +76,16 @@ impl Default for S3Options {
 }
 
 /// Google Cloud Storage options
+///
+/// Without `credential` or `credential_path` the service account of the
+/// environment is used.
 #[derive(Debug, Clone)]
 pub struct GcsOptions {
     pub bucket: Option<String>,
     pub project_id: Option<String>,
-    // TODO: Add more GCS-specific options as needed
+    pub endpoint: Option<String>,
+    pub credential: Option<String>,
+    pub credential_path: Option<String>,
 }
 
 impl GcsOptions {
@@ -93,6 +98,21 @@ impl GcsOptions {
         if let Some(project_id) = &self.project_id {
             props.insert("project_id".to_string(), project_id.clone());
         }
+        if let Some(endpoint) = &self.endpoint {
+            props.insert(storage_option::OPT_ENDPOINT.to_string(), endpoint.clone());
+        }
+        if let Some(credential) = &self.credential {
+            props.insert(
+                storage_option::OPT_CREDENTIAL.to_string(),
+                credential.clone(),
+            );
+        }
+        if let Some(path) = &self.credential_path {
+            props.insert(
+                storage_option::OPT_CREDENTIAL_PATH.to_string(),
+                path.clone(),
+            );
+        }
         props
     }
 }
@@ -102,16 +122,23 @@ impl Default for GcsOptions {
         Self {
             bucket: None,
             project_id: None,
+            endpoint: None,
+            credential: None,
+            credential_path: None,
         }
     }
 }
 
 /// Azure Blob Storage options
+///
+/// Requests are signed with `account_key` or authorized by `sas_token`.
 #[derive(Debug, Clone)]
 pub struct AzureOptions {
     pub container: Option<String>,
     pub account_name: Option<String>,
-    // TODO: Add more Azure-specific options as needed
+    pub endpoint: Option<String>,
+    pub account_key: Option<String>,
+    pub sas_token: Option<String>,
 }
 
 impl AzureOptions {
@@ -119,10 +146,23 @@ impl AzureOptions {
     pub fn to_props(&self) -> HashMap<String, String> {
         let mut props = HashMap::new();
         if let Some(container) = &self.container {
-            props.insert("container".to_string(), container.clone());
+            props
+                .insert(storage_option::OPT_CONTAINER.to_string(), container.clone());
         }
         if let Some(account) = &self.account_name {
-            props.insert("account_name".to_string(), account.clone());
+            props.insert(
+                storage_option::OPT_ACCOUNT_NAME.to_string(),
+                account.clone(),
+            );
+        }
+        if let Some(endpoint) = &self.endpoint {
+            props.insert(storage_option::OPT_ENDPOINT.to_string(), endpoint.clone());
+        }
+        if let Some(key) = &self.account_key {
+            props.insert(storage_option::OPT_ACCOUNT_KEY.to_string(), key.clone());
+        }
+        if let Some(token) = &self.sas_token {
+            props.insert(storage_option::OPT_SAS_TOKEN.to_string(), token.clone());
         }
         props
     }
@@ -133,6 +173,9 @@ impl Default for AzureOptions {
         Self {
             container: None,
             account_name: None,
+            endpoint: None,
+            account_key: None,
+            sas_token: None,
         }
     }
 }
@@ -406,12 +449,18 @@ fn parse_gcs_options(opts: &HashMap<&str, String>) -> GcsOptions {
     GcsOptions {
         bucket: opts.get(storage_option::OPT_BUCKET).cloned(),
         project_id: None, // TODO: Add constant when GCS options are defined
+        endpoint: opts.get(storage_option::OPT_ENDPOINT).cloned(),
+        credential: opts.get(storage_option::OPT_CREDENTIAL).cloned(),
+        credential_path: opts.get(storage_option::OPT_CREDENTIAL_PATH).cloned(),
     }
 }
 
-fn parse_azure_options(_opts: &HashMap<&str, String>) -> AzureOptions {
+fn parse_azure_options(opts: &HashMap<&str, String>) -> AzureOptions {
     AzureOptions {
-        container: None, // TODO: Add constant when Azure options are defined
-        account_name: None,
+        container: opts.get(storage_option::OPT_CONTAINER).cloned(),
+        account_name: opts.get(storage_option::OPT_ACCOUNT_NAME).cloned(),
+        endpoint: opts.get(storage_option::OPT_ENDPOINT).cloned(),
+        account_key: opts.get(storage_option::OPT_ACCOUNT_KEY).cloned(),
+        sas_token: opts.get(storage_option::OPT_SAS_TOKEN).cloned(),
     }
 }
@@ -317,6 +317,93 @@ impl PgWrapper {
         }
     }
 
+    /// Wrapper for `pg_sys::LockRelationOid` with error handling.
+    pub fn lock_relation_oid(
+        relid: pg_sys::Oid,
+        lockmode: pg_sys::LOCKMODE,
+    ) -> Result<(), PgWrapperError> {
+        unsafe {
+            PgTryBuilder::new(move || {
+                pg_sys::LockRelationOid(relid, lockmode);
+                Ok(())
+            })
+            .catch_others(|err| {
+                Err(PgWrapperError::PostgresError(format!("{:?}", err)))
+            })
+            .execute()
+        }
+    }
+
+    /// Drop a relation and its dependent objects as `DROP TABLE` would.
+    pub fn drop_relation(
+        relid: pg_sys::Oid,
+        behavior: pg_sys::DropBehavior::Type,
+    ) -> Result<(), PgWrapperError> {
+        unsafe {
+            PgTryBuilder::new(move || {
+                let object = pg_sys::ObjectAddress {
+                    classId: pg_sys::RelationRelationId,
+                    objectId: relid,
+                    objectSubId: 0,
+                };
+                pg_sys::performDeletion(&object, behavior, 0);
+                Ok(())
+            })
+            .catch_others(|err| {
+                Err(PgWrapperError::PostgresError(format!("{:?}", err)))
+            })
+            .execute()
+        }
+    }
+
+    /// Wrapper for `pg_sys::RenameRelationInternal` with error handling.
+    pub fn rename_relation(
+        relid: pg_sys::Oid,
+        new_name: &CStr,
+    ) -> Result<(), PgWrapperError> {
+        let new_name_ptr = new_name.as_ptr();
+        unsafe {
+            PgTryBuilder::new(move || {
+                pg_sys::RenameRelationInternal(relid, new_name_ptr, false, false);
+                Ok(())
+            })
+            .catch_others(|err| {
+                Err(PgWrapperError::PostgresError(format!("{:?}", err)))
+            })
+            .execute()
+        }
+    }
+
+    /// Move a relation to another namespace as `ALTER TABLE ... SET SCHEMA`
+    /// would.
+    ///
+    /// The relation must already be locked in `AccessExclusiveLock` mode.
+    pub fn alter_relation_namespace(
+        relid: pg_sys::Oid,
+        nsp_oid: pg_sys::Oid,
+    ) -> Result<(), PgWrapperError> {
+        unsafe {
+            PgTryBuilder::new(move || {
+                let rel = pg_sys::relation_open(relid, pg_sys::NoLock as _);
+                let old_nsp_oid = (*(*rel).rd_rel).relnamespace;
+                let objs_moved = pg_sys::new_object_addresses();
+                pg_sys::AlterTableNamespaceInternal(
+                    rel,
+                    old_nsp_oid,
+                    nsp_oid,
+                    objs_moved,
+                );
+                pg_sys::free_object_addresses(objs_moved);
+                pg_sys::relation_close(rel, pg_sys::NoLock as _);
+                Ok(())
+            })
+            .catch_others(|err| {
+                Err(PgWrapperError::PostgresError(format!("{:?}", err)))
+            })
+            .execute()
+        }
+    }
+
     /// Rust wrapper for the C macro `ScanKeyInit`.
     ///
     /// Initializes a `ScanKeyData` structure and looks up the function via `fmgr_info`.